@@ -230,7 +230,7 @@ impl FromBytes for bool {
         match u8::read(reader) {
             Ok(0) => Ok(false),
             Ok(1) => Ok(true),
-            Ok(_) => Err(::std::io::ErrorKind::Other.into()),
+            Ok(_) => Err(::std::io::ErrorKind::InvalidData.into()),
             Err(err) => Err(err),
         }
     }
@@ -144,3 +144,18 @@ fn bls12_377_unique() {
     use std::str::FromStr;
     println!("{}", Fq::from_str("155198655607781456406391640216936120121836107652948796323930557600032281009004493664981332883744016074664192874906").unwrap());
 }
+
+#[test]
+fn test_g1_invalid_bytes() {
+    use crate::bytes::FromBytes;
+    use std::io::ErrorKind;
+
+    // y^2 = x^3 + 1 does not hold for (1, 1).
+    let off_curve_bytes = to_bytes![Fq::one(), Fq::one()].unwrap();
+    let error = G1Affine::read(off_curve_bytes.as_slice()).unwrap_err();
+    assert_eq!(error.kind(), ErrorKind::InvalidData);
+
+    let off_curve_bytes = to_bytes![Fq::one(), Fq::one(), Fq::one()].unwrap();
+    let error = G1Projective::read(off_curve_bytes.as_slice()).unwrap_err();
+    assert_eq!(error.kind(), ErrorKind::InvalidData);
+}
@@ -102,3 +102,33 @@ fn test_bytes() {
     let g = JubJubAffine::read(g_bytes.as_slice()).unwrap();
     assert_eq!(g_from_repr, g);
 }
+
+#[test]
+fn test_invalid_bytes() {
+    use crate::fields::{jubjub::fq::Fq, Field};
+    use std::io::ErrorKind;
+
+    // (1, 1) is not on the curve.
+    let off_curve_bytes = to_bytes![Fq::one(), Fq::one()].unwrap();
+    let error = JubJubAffine::read(off_curve_bytes.as_slice()).unwrap_err();
+    assert_eq!(error.kind(), ErrorKind::InvalidData);
+
+    // (0, -1) is on the curve, but has order two.
+    let torsion = JubJubAffine::new(Fq::zero(), -Fq::one());
+    assert!(torsion.is_on_curve());
+    let torsion_bytes = to_bytes![torsion].unwrap();
+    let error = JubJubAffine::read(torsion_bytes.as_slice()).unwrap_err();
+    assert_eq!(error.kind(), ErrorKind::InvalidData);
+    let torsion_bytes = to_bytes![torsion.into_projective()].unwrap();
+    let error = JubJubProjective::read(torsion_bytes.as_slice()).unwrap_err();
+    assert_eq!(error.kind(), ErrorKind::InvalidData);
+
+    // Extended coordinates must satisfy x * y = t * z.
+    let g: JubJubProjective = rand::random();
+    let mut g_bytes = to_bytes![g].unwrap();
+    assert_eq!(JubJubProjective::read(g_bytes.as_slice()).unwrap(), g);
+    let t_offset = 2 * to_bytes![Fq::one()].unwrap().len();
+    g_bytes[t_offset] ^= 1;
+    let error = JubJubProjective::read(g_bytes.as_slice()).unwrap_err();
+    assert_eq!(error.kind(), ErrorKind::InvalidData);
+}
@@ -0,0 +1,125 @@
+use crate::{
+    biginteger::BigInteger320,
+    bytes::ToBytes,
+    curves::{
+        mnt4::{G2Affine, MNT4},
+        models::{ModelParameters, SWModelParameters},
+        short_weierstrass_jacobian::{GroupAffine, GroupProjective},
+        AffineCurve, PairingCurve, PairingEngine,
+    },
+    fields::mnt4::{Fq, Fq2, Fq4, Fr},
+};
+use std::io::{Result as IoResult, Write};
+
+pub type G1Affine = GroupAffine<MNT4G1Parameters>;
+pub type G1Projective = GroupProjective<MNT4G1Parameters>;
+
+impl PairingCurve for G1Affine {
+    type Engine = MNT4;
+    type Prepared = G1Prepared;
+    type PairWith = G2Affine;
+    type PairingResult = Fq4;
+
+    fn prepare(&self) -> Self::Prepared {
+        Self::Prepared::from_affine(self)
+    }
+
+    fn pairing_with(&self, other: &Self::PairWith) -> Self::PairingResult {
+        MNT4::pairing(*self, *other)
+    }
+}
+
+#[derive(Copy, Clone, Default, PartialEq, Eq)]
+pub struct MNT4G1Parameters;
+
+impl ModelParameters for MNT4G1Parameters {
+    type BaseField = Fq;
+    type ScalarField = Fr;
+}
+
+impl SWModelParameters for MNT4G1Parameters {
+    /// COEFF_A = 2
+    const COEFF_A: Fq = Fq::new(BigInteger320([
+        0x318634f6b0c708b8,
+        0xd3bcf42bc76d1bea,
+        0x8bbf0b0e51f55681,
+        0x52308130c8f6a32f,
+        0x382447a6786,
+    ]));
+
+    /// COEFF_B =
+    /// 423894536526684178289416011533888240029318103673896002803341544124054745019340795360841685
+    const COEFF_B: Fq = Fq::new(BigInteger320([
+        0x6cd74067bbddcb31,
+        0x3ffe4a5e33d7477,
+        0x39c29c6219621ca5,
+        0x4c2b62b6cfc1895f,
+        0x169b131a14d,
+    ]));
+
+    /// COFACTOR = 1
+    const COFACTOR: &'static [u64] = &[1];
+
+    /// COFACTOR^(-1) mod r =
+    /// 1
+    const COFACTOR_INV: Fr = Fr::new(BigInteger320([
+        0xc3177aefffbb845c,
+        0x9b80c702f9961788,
+        0xc5df8dcdac70a85a,
+        0x29184098647b5197,
+        0x1c1223d33c3,
+    ]));
+
+    /// AFFINE_GENERATOR_COEFFS = (G1_GENERATOR_X, G1_GENERATOR_Y)
+    const AFFINE_GENERATOR_COEFFS: (Self::BaseField, Self::BaseField) =
+        (G1_GENERATOR_X, G1_GENERATOR_Y);
+}
+
+/// G1_GENERATOR_X =
+/// 60760244141852568949126569781626075788424196370144486719385562369396875346601926534016838
+pub const G1_GENERATOR_X: Fq = Fq::new(BigInteger320([
+    0x53e8c71197d9f8b4,
+    0xd1a0ccc72d575667,
+    0xdaaf7bad5bfe5f43,
+    0x54d91c797e47fb02,
+    0x2c92de78361,
+]));
+
+/// G1_GENERATOR_Y =
+/// 363732850702582978263902770815145784459747722357071843971107674179038674942891694705904306
+pub const G1_GENERATOR_Y: Fq = Fq::new(BigInteger320([
+    0x7a1a14f4dec3207d,
+    0x87975c3ee01d86d3,
+    0xf599a22085a378e8,
+    0xd3ac75497936f0f8,
+    0x37f5ae096e4,
+]));
+
+#[derive(Eq, PartialEq, Copy, Clone, Debug)]
+pub struct G1Prepared {
+    pub x:       Fq,
+    pub y:       Fq,
+    pub x_twist: Fq2,
+    pub y_twist: Fq2,
+}
+
+impl ToBytes for G1Prepared {
+    fn write<W: Write>(&self, mut writer: W) -> IoResult<()> {
+        self.x.write(&mut writer)?;
+        self.y.write(&mut writer)?;
+        self.x_twist.write(&mut writer)?;
+        self.y_twist.write(&mut writer)
+    }
+}
+
+impl G1Prepared {
+    pub fn from_affine(point: &G1Affine) -> Self {
+        MNT4::ate_precompute_g1(&point.into_projective())
+    }
+}
+
+impl Default for G1Prepared {
+    fn default() -> Self {
+        Self::from_affine(&G1Affine::prime_subgroup_generator())
+    }
+}
@@ -0,0 +1,202 @@
+use crate::{
+    biginteger::BigInteger320,
+    bytes::ToBytes,
+    curves::{
+        mnt4::{G1Affine, MNT4, TWIST_COEFF_A},
+        models::{ModelParameters, SWModelParameters},
+        short_weierstrass_jacobian::{GroupAffine, GroupProjective},
+        AffineCurve, PairingCurve, PairingEngine,
+    },
+    fields::mnt4::{Fq, Fq2, Fq4, Fr},
+};
+use std::io::{Result as IoResult, Write};
+
+pub type G2Affine = GroupAffine<MNT4G2Parameters>;
+pub type G2Projective = GroupProjective<MNT4G2Parameters>;
+
+impl PairingCurve for G2Affine {
+    type Engine = MNT4;
+    type Prepared = G2Prepared;
+    type PairWith = G1Affine;
+    type PairingResult = Fq4;
+
+    #[inline(always)]
+    fn prepare(&self) -> Self::Prepared {
+        Self::Prepared::from_affine(self)
+    }
+
+    #[inline(always)]
+    fn pairing_with(&self, other: &Self::PairWith) -> Self::PairingResult {
+        MNT4::pairing(*other, *self)
+    }
+}
+
+#[derive(Copy, Clone, Default, PartialEq, Eq)]
+pub struct MNT4G2Parameters;
+
+impl ModelParameters for MNT4G2Parameters {
+    type BaseField = Fq2;
+    type ScalarField = Fr;
+}
+
+/// MUL_BY_A_C0 = NONRESIDUE * COEFF_A
+pub const MUL_BY_A_C0: Fq = Fq::new(BigInteger320([
+    0x8228f515183d9429,
+    0x3697e4617d5e0773,
+    0x63b1ef20fd2ae0e5,
+    0xf13c6f00850be1ca,
+    0x39408106d24,
+]));
+
+/// MUL_BY_A_C1 = NONRESIDUE * COEFF_A
+pub const MUL_BY_A_C1: Fq = MUL_BY_A_C0;
+
+impl SWModelParameters for MNT4G2Parameters {
+    const COEFF_A: Fq2 = TWIST_COEFF_A;
+    /// COEFF_B = (0, NONRESIDUE * G1::COEFF_B)
+    const COEFF_B: Fq2 = Fq2::new(
+        Fq::new(BigInteger320([0, 0, 0, 0, 0])),
+        Fq::new(BigInteger320([
+            0x83fe40c4d1567e3b,
+            0xba02dace3054677d,
+            0x1453aa2f8110c177,
+            0x407cb271ddb823fa,
+            0x196f5debb6c,
+        ])),
+    );
+
+    /// COFACTOR =
+    /// 475922286169261325753349249653048451545124879932565935237842521413255878328503110407553025
+    const COFACTOR: &'static [u64] = &[
+        15480692783052488705,
+        9802782456999489873,
+        14622846468721090623,
+        11702080941310629006,
+        4110145082483,
+    ];
+
+    /// COFACTOR^(-1) mod r =
+    /// 475922286169261325753349249653048451545124878207887910632124039320641839552134835598065665
+    const COFACTOR_INV: Fr = Fr::new(BigInteger320([
+        0x6fef8b0e4b5e0345,
+        0x689ba5b3b1ea7f10,
+        0xa5e24d5ba466c5d,
+        0x57ed4a84e9933918,
+        0x1b464609c04,
+    ]));
+
+    /// AFFINE_GENERATOR_COEFFS = (G2_GENERATOR_X, G2_GENERATOR_Y)
+    const AFFINE_GENERATOR_COEFFS: (Self::BaseField, Self::BaseField) =
+        (G2_GENERATOR_X, G2_GENERATOR_Y);
+
+    #[inline(always)]
+    fn mul_by_a(elt: &Fq2) -> Fq2 {
+        Fq2::new(MUL_BY_A_C0 * &elt.c0, MUL_BY_A_C1 * &elt.c1)
+    }
+}
+
+const G2_GENERATOR_X: Fq2 = Fq2::new(G2_GENERATOR_X_C0, G2_GENERATOR_X_C1);
+const G2_GENERATOR_Y: Fq2 = Fq2::new(G2_GENERATOR_Y_C0, G2_GENERATOR_Y_C1);
+
+/// G2_GENERATOR_X_C0 =
+/// 438374926219350099854919100077809681842783509163790991847867546339851681564223481322252708
+pub const G2_GENERATOR_X_C0: Fq = Fq::new(BigInteger320([
+    0x4a56b87bf6e3bbf2,
+    0x2540055a02dbe484,
+    0x40fdc053176b14fe,
+    0x909fe4b201a779ae,
+    0x178e1c4e680,
+]));
+
+/// G2_GENERATOR_X_C1 =
+/// 37620953615500480110935514360923278605464476459712393277679280819942849043649216370485641
+pub const G2_GENERATOR_X_C1: Fq = Fq::new(BigInteger320([
+    0x1d9e9b91f772f70,
+    0xd5a6dac8c5ab51c2,
+    0xfd90c8649f6452a5,
+    0x7e38904fbe0dfae2,
+    0x2186470a169,
+]));
+
+/// G2_GENERATOR_Y_C0 =
+/// 37437409008528968268352521034936931842973546441370663118543015118291998305624025037512482
+pub const G2_GENERATOR_Y_C0: Fq = Fq::new(BigInteger320([
+    0xead181e1ccdfa094,
+    0xd837d925c1014f34,
+    0x3728254a46d08bd6,
+    0x7206e4a3c7ca1455,
+    0x26768c82920,
+]));
+
+/// G2_GENERATOR_Y_C1 =
+/// 424621479598893882672393190337420680597584695892317197646113820787463109735345923009077489
+pub const G2_GENERATOR_Y_C1: Fq = Fq::new(BigInteger320([
+    0x1063f70c6460c54,
+    0x783caeb87ed305ec,
+    0xa0a5df3e419c22c6,
+    0xf927cfd064735d73,
+    0x1f8a707e350,
+]));
+
+#[derive(Eq, PartialEq, Clone, Debug)]
+pub struct G2Prepared {
+    pub x:                     Fq2,
+    pub y:                     Fq2,
+    pub x_over_twist:          Fq2,
+    pub y_over_twist:          Fq2,
+    pub double_coefficients:   Vec<AteDoubleCoefficients>,
+    pub addition_coefficients: Vec<AteAdditionCoefficients>,
+}
+
+impl ToBytes for G2Prepared {
+    fn write<W: Write>(&self, mut writer: W) -> IoResult<()> {
+        self.x.write(&mut writer)?;
+        self.y.write(&mut writer)?;
+        self.x_over_twist.write(&mut writer)?;
+        self.y_over_twist.write(&mut writer)?;
+        for coeff in &self.double_coefficients {
+            coeff.c_h.write(&mut writer)?;
+            coeff.c_4c.write(&mut writer)?;
+            coeff.c_j.write(&mut writer)?;
+            coeff.c_l.write(&mut writer)?;
+        }
+        for coeff in &self.addition_coefficients {
+            coeff.c_l1.write(&mut writer)?;
+            coeff.c_rz.write(&mut writer)?;
+        }
+        Ok(())
+    }
+}
+
+impl G2Prepared {
+    pub fn from_affine(point: &G2Affine) -> Self {
+        MNT4::ate_precompute_g2(&point.into_projective())
+    }
+}
+
+impl Default for G2Prepared {
+    fn default() -> Self {
+        Self::from_affine(&G2Affine::prime_subgroup_generator())
+    }
+}
+
+pub(super) struct G2ProjectiveExtended {
+    pub(crate) x: Fq2,
+    pub(crate) y: Fq2,
+    pub(crate) z: Fq2,
+    pub(crate) t: Fq2,
+}
+
+#[derive(Eq, PartialEq, Copy, Clone, Debug)]
+pub struct AteDoubleCoefficients {
+    pub(crate) c_h:  Fq2,
+    pub(crate) c_4c: Fq2,
+    pub(crate) c_j:  Fq2,
+    pub(crate) c_l:  Fq2,
+}
+
+#[derive(Eq, PartialEq, Copy, Clone, Debug)]
+pub struct AteAdditionCoefficients {
+    pub(crate) c_l1: Fq2,
+    pub(crate) c_rz: Fq2,
+}
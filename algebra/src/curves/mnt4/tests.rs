@@ -0,0 +1,75 @@
+use crate::{
+    curves::{
+        mnt4::{G1Affine, G1Projective, G2Affine, G2Projective, MNT4},
+        tests::curve_tests,
+        AffineCurve, PairingEngine,
+    },
+    fields::mnt4::fr::Fr,
+    groups::tests::group_test,
+};
+use rand;
+
+#[test]
+fn test_g1_projective_curve() {
+    curve_tests::<G1Projective>();
+}
+
+#[test]
+fn test_g1_projective_group() {
+    let a: G1Projective = rand::random();
+    let b: G1Projective = rand::random();
+    group_test(a, b);
+}
+
+#[test]
+fn test_g1_generator() {
+    let generator = G1Affine::prime_subgroup_generator();
+    assert!(generator.is_on_curve());
+    assert!(generator.is_in_correct_subgroup_assuming_on_curve());
+}
+
+#[test]
+fn test_g2_projective_curve() {
+    curve_tests::<G2Projective>();
+}
+
+#[test]
+fn test_g2_projective_group() {
+    let a: G2Projective = rand::random();
+    let b: G2Projective = rand::random();
+    group_test(a, b);
+}
+
+#[test]
+fn test_g2_generator() {
+    let generator = G2Affine::prime_subgroup_generator();
+    assert!(generator.is_on_curve());
+    assert!(generator.is_in_correct_subgroup_assuming_on_curve());
+}
+
+#[test]
+fn test_bilinearity() {
+    use crate::fields::{mnt4::fq4::Fq4, Field, PrimeField};
+
+    let a: G1Projective = rand::random();
+    let b: G2Projective = rand::random();
+    let s: Fr = rand::random();
+
+    let sa = a * &s;
+    let sb = b * &s;
+
+    let ans1 = MNT4::pairing(sa, b);
+    let ans2 = MNT4::pairing(a, sb);
+    let ans3 = MNT4::pairing(a, b).pow(s.into_repr());
+
+    assert_eq!(ans1, ans2);
+    assert_eq!(ans2, ans3);
+
+    assert_ne!(ans1, Fq4::one());
+    assert_ne!(ans2, Fq4::one());
+    assert_ne!(ans3, Fq4::one());
+
+    assert_eq!(ans1.pow(Fr::characteristic()), Fq4::one());
+    assert_eq!(ans2.pow(Fr::characteristic()), Fq4::one());
+    assert_eq!(ans3.pow(Fr::characteristic()), Fq4::one());
+}
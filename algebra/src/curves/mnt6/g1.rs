@@ -4,7 +4,7 @@ use crate::{
     curves::{
         mnt6::{G2Affine, MNT6},
         models::{ModelParameters, SWModelParameters},
-        short_weierstrass_projective::{GroupAffine, GroupProjective},
+        short_weierstrass_jacobian::{GroupAffine, GroupProjective},
         AffineCurve, PairingCurve, PairingEngine,
     },
     fields::mnt6::{Fq, Fq3, Fq6, Fr},
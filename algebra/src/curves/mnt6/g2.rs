@@ -4,7 +4,7 @@ use crate::{
     curves::{
         mnt6::{g1::MNT6G1Parameters, G1Affine, MNT6, TWIST_COEFF_A},
         models::{ModelParameters, SWModelParameters},
-        short_weierstrass_projective::{GroupAffine, GroupProjective},
+        short_weierstrass_jacobian::{GroupAffine, GroupProjective},
         AffineCurve, PairingCurve, PairingEngine,
     },
     fields::mnt6::{Fq, Fq3, Fq6, Fr},
@@ -1,10 +1,10 @@
 use crate::{
     biginteger::BigInteger320,
-    curves::{PairingCurve, PairingEngine, ProjectiveCurve},
+    curves::{models::mnt6::MNT6Parameters, PairingCurve, PairingEngine, ProjectiveCurve},
     fields::{
         mnt6::{
             fq::{Fq, FqParameters},
-            Fq3, Fq6, Fr,
+            Fq3, Fq3Parameters, Fq6, Fq6Parameters, Fr,
         },
         BitIterator, Field, FpParameters,
     },
@@ -17,8 +17,8 @@ mod tests;
 
 use self::g2::{AteAdditionCoefficients, AteDoubleCoefficients, G2ProjectiveExtended};
 pub use self::{
-    g1::{G1Affine, G1Prepared, G1Projective},
-    g2::{G2Affine, G2Prepared, G2Projective},
+    g1::{G1Affine, G1Prepared, G1Projective, MNT6G1Parameters},
+    g2::{G2Affine, G2Prepared, G2Projective, MNT6G2Parameters},
 };
 
 pub type GT = Fq6;
@@ -57,6 +57,21 @@ impl PairingEngine for MNT6 {
     }
 }
 
+impl MNT6Parameters for MNT6 {
+    const TWIST: Fq3 = TWIST;
+    const ATE_LOOP_COUNT: &'static [u64] = &ATE_LOOP_COUNT;
+    const ATE_IS_LOOP_COUNT_NEG: bool = ATE_IS_LOOP_COUNT_NEG;
+    const FINAL_EXPONENT_LAST_CHUNK_1: &'static [u64] = &FINAL_EXPONENT_LAST_CHUNK_1.0;
+    const FINAL_EXPONENT_LAST_CHUNK_W0_IS_NEG: bool = FINAL_EXPONENT_LAST_CHUNK_W0_IS_NEG;
+    const FINAL_EXPONENT_LAST_CHUNK_ABS_OF_W0: &'static [u64] =
+        &FINAL_EXPONENT_LAST_CHUNK_ABS_OF_W0.0;
+    type Fp = Fq;
+    type Fp3Params = Fq3Parameters;
+    type Fp6Params = Fq6Parameters;
+    type G1Parameters = MNT6G1Parameters;
+    type G2Parameters = MNT6G2Parameters;
+}
+
 impl MNT6 {
     /// Takes as input a point in G1 in projective coordinates, and outputs a
     /// precomputed version of it for pairing purposes.
@@ -15,6 +15,7 @@ pub mod bls12_381;
 pub mod edwards_bls12;
 pub mod edwards_sw6;
 pub mod jubjub;
+pub mod mnt4;
 pub mod mnt6;
 pub mod models;
 pub mod sw6;
@@ -242,6 +243,13 @@ pub trait AffineCurve:
     /// `Self::ScalarField`.
     #[must_use]
     fn mul_by_cofactor_inv(&self) -> Self;
+
+    /// Returns a point with x-coordinate `x`, choosing the lexicographically
+    /// largest y-coordinate if and only if `greatest` is set, or `None` if
+    /// there is no such point. The point is not guaranteed to be in the prime
+    /// order subgroup.
+    #[must_use]
+    fn from_x_coordinate(x: Self::BaseField, greatest: bool) -> Option<Self>;
 }
 
 pub trait PairingCurve: AffineCurve {
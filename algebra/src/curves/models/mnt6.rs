@@ -0,0 +1,31 @@
+use crate::{
+    curves::models::{ModelParameters, SWModelParameters},
+    fields::{
+        fp3::Fp3Parameters,
+        fp6_2over3::Fp6Parameters,
+        Fp3, PrimeField, SquareRootField,
+    },
+};
+
+/// Parameters of a pairing-friendly curve with embedding degree 6 whose
+/// pairing is an ate pairing over the sextic tower `Fp6 = Fp3[Y]/(Y^2 - X)`,
+/// such as MNT6 and SW6.
+pub trait MNT6Parameters: 'static {
+    /// The twist `(0, 1, 0)` of `G2` over `Fp3`.
+    const TWIST: Fp3<Self::Fp3Params>;
+    const ATE_LOOP_COUNT: &'static [u64];
+    const ATE_IS_LOOP_COUNT_NEG: bool;
+    /// `w1` of the last chunk of the final exponent `w1 * q + w0`.
+    const FINAL_EXPONENT_LAST_CHUNK_1: &'static [u64];
+    const FINAL_EXPONENT_LAST_CHUNK_W0_IS_NEG: bool;
+    /// `|w0|` of the last chunk of the final exponent `w1 * q + w0`.
+    const FINAL_EXPONENT_LAST_CHUNK_ABS_OF_W0: &'static [u64];
+    type Fp: PrimeField + SquareRootField + Into<<Self::Fp as PrimeField>::BigInt>;
+    type Fp3Params: Fp3Parameters<Fp = Self::Fp>;
+    type Fp6Params: Fp6Parameters<Fp3Params = Self::Fp3Params>;
+    type G1Parameters: SWModelParameters<BaseField = Self::Fp>;
+    type G2Parameters: SWModelParameters<
+        BaseField = Fp3<Self::Fp3Params>,
+        ScalarField = <Self::G1Parameters as ModelParameters>::ScalarField,
+    >;
+}
@@ -2,7 +2,7 @@ use crate::curves::models::SWModelParameters as Parameters;
 use rand::{Rand, Rng};
 use std::{
     fmt::{Display, Formatter, Result as FmtResult},
-    io::{Read, Result as IoResult, Write},
+    io::{Error as IoError, ErrorKind, Read, Result as IoResult, Write},
     marker::PhantomData,
 };
 
@@ -71,7 +71,6 @@ impl<P: Parameters> GroupAffine<P> {
     ///
     /// If and only if `greatest` is set will the lexicographically
     /// largest y-coordinate be selected.
-    #[allow(dead_code)]
     pub(crate) fn get_point_from_x(x: P::BaseField, greatest: bool) -> Option<Self> {
         // Compute x^3 + ax + b
         let x3b = P::add_b(&((x.square() * &x) + &P::mul_by_a(&x)));
@@ -139,6 +138,10 @@ impl<P: Parameters> AffineCurve for GroupAffine<P> {
         self.mul(P::COFACTOR_INV).into()
     }
 
+    fn from_x_coordinate(x: Self::BaseField, greatest: bool) -> Option<Self> {
+        Self::get_point_from_x(x, greatest)
+    }
+
     #[inline]
     fn into_projective(&self) -> GroupProjective<P> {
         (*self).into()
@@ -166,13 +169,24 @@ impl<P: Parameters> ToBytes for GroupAffine<P> {
     }
 }
 
+/// Rejects points that are not on the curve. Checking membership of the
+/// prime-order subgroup costs a scalar multiplication, which is too slow for
+/// proving keys, so callers reading untrusted points must do it themselves with
+/// `is_in_correct_subgroup_assuming_on_curve`.
 impl<P: Parameters> FromBytes for GroupAffine<P> {
     #[inline]
     fn read<R: Read>(mut reader: R) -> IoResult<Self> {
         let x = P::BaseField::read(&mut reader)?;
         let y = P::BaseField::read(&mut reader)?;
         let infinity = x.is_zero() && y.is_one();
-        Ok(Self::new(x, y, infinity))
+        let point = Self::new(x, y, infinity);
+        if !point.is_on_curve() {
+            return Err(IoError::new(
+                ErrorKind::InvalidData,
+                "point is not on the curve",
+            ));
+        }
+        Ok(point)
     }
 }
 
@@ -253,7 +267,14 @@ impl<P: Parameters> FromBytes for GroupProjective<P> {
         let x = P::BaseField::read(&mut reader)?;
         let y = P::BaseField::read(&mut reader)?;
         let z = P::BaseField::read(reader)?;
-        Ok(Self::new(x, y, z))
+        let point = Self::new(x, y, z);
+        if !point.into_affine().is_on_curve() {
+            return Err(IoError::new(
+                ErrorKind::InvalidData,
+                "point is not on the curve",
+            ));
+        }
+        Ok(point)
     }
 }
 
@@ -2,7 +2,7 @@ use crate::curves::models::SWModelParameters as Parameters;
 use rand::{Rand, Rng};
 use std::{
     fmt::{Display, Formatter, Result as FmtResult},
-    io::{Read, Result as IoResult, Write},
+    io::{Error as IoError, ErrorKind, Read, Result as IoResult, Write},
     marker::PhantomData,
 };
 
@@ -73,7 +73,6 @@ impl<P: Parameters> GroupAffine<P> {
     ///
     /// If and only if `greatest` is set will the lexicographically
     /// largest y-coordinate be selected.
-    #[allow(dead_code)]
     pub(crate) fn get_point_from_x(x: P::BaseField, greatest: bool) -> Option<Self> {
         // Compute x^3 + ax + b
         let x3b = P::add_b(&((x.square() * &x) + &P::mul_by_a(&x)));
@@ -140,6 +139,10 @@ impl<P: Parameters> AffineCurve for GroupAffine<P> {
         self.mul(P::COFACTOR_INV).into()
     }
 
+    fn from_x_coordinate(x: Self::BaseField, greatest: bool) -> Option<Self> {
+        Self::get_point_from_x(x, greatest)
+    }
+
     fn into_projective(&self) -> GroupProjective<P> {
         (*self).into()
     }
@@ -166,13 +169,31 @@ impl<P: Parameters> ToBytes for GroupAffine<P> {
     }
 }
 
+/// Rejects points that are not on the curve, and encodings of the point at
+/// infinity other than the one written by `ToBytes`. Checking membership of the
+/// prime-order subgroup costs a scalar multiplication, which is too slow for
+/// proving keys, so callers reading untrusted points must do it themselves with
+/// `is_in_correct_subgroup_assuming_on_curve`.
 impl<P: Parameters> FromBytes for GroupAffine<P> {
     #[inline]
     fn read<R: Read>(mut reader: R) -> IoResult<Self> {
         let x = P::BaseField::read(&mut reader)?;
         let y = P::BaseField::read(&mut reader)?;
         let infinity = bool::read(reader)?;
-        Ok(Self::new(x, y, infinity))
+        let point = Self::new(x, y, infinity);
+        if infinity && point != Self::zero() {
+            return Err(IoError::new(
+                ErrorKind::InvalidData,
+                "point at infinity is not canonically encoded",
+            ));
+        }
+        if !point.is_on_curve() {
+            return Err(IoError::new(
+                ErrorKind::InvalidData,
+                "point is not on the curve",
+            ));
+        }
+        Ok(point)
     }
 }
 
@@ -248,7 +269,14 @@ impl<P: Parameters> FromBytes for GroupProjective<P> {
         let x = P::BaseField::read(&mut reader)?;
         let y = P::BaseField::read(&mut reader)?;
         let z = P::BaseField::read(reader)?;
-        Ok(Self::new(x, y, z))
+        let point = Self::new(x, y, z);
+        if !point.into_affine().is_on_curve() {
+            return Err(IoError::new(
+                ErrorKind::InvalidData,
+                "point is not on the curve",
+            ));
+        }
+        Ok(point)
     }
 }
 
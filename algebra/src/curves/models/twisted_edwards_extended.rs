@@ -1,7 +1,7 @@
 use rand::{Rand, Rng};
 use std::{
     fmt::{Display, Formatter, Result as FmtResult},
-    io::{Read, Result as IoResult, Write},
+    io::{Error as IoError, ErrorKind, Read, Result as IoResult, Write},
     marker::PhantomData,
     ops::{Add, AddAssign, Mul, MulAssign, Neg, Sub, SubAssign},
 };
@@ -68,7 +68,6 @@ impl<P: Parameters> GroupAffine<P> {
     ///
     /// If and only if `greatest` is set will the lexicographically
     /// largest y-coordinate be selected.
-    #[allow(dead_code)]
     pub(crate) fn get_point_from_x(x: P::BaseField, greatest: bool) -> Option<Self> {
         let x2 = x.square();
         let one = P::BaseField::one();
@@ -130,6 +129,10 @@ impl<P: Parameters> AffineCurve for GroupAffine<P> {
         self.mul(P::COFACTOR_INV).into()
     }
 
+    fn from_x_coordinate(x: Self::BaseField, greatest: bool) -> Option<Self> {
+        Self::get_point_from_x(x, greatest)
+    }
+
     fn into_projective(&self) -> GroupProjective<P> {
         (*self).into()
     }
@@ -207,12 +210,21 @@ impl<P: Parameters> ToBytes for GroupAffine<P> {
     }
 }
 
+/// Rejects points that are not on the curve or not in the prime-order
+/// subgroup.
 impl<P: Parameters> FromBytes for GroupAffine<P> {
     #[inline]
     fn read<R: Read>(mut reader: R) -> IoResult<Self> {
         let x = P::BaseField::read(&mut reader)?;
         let y = P::BaseField::read(&mut reader)?;
-        Ok(Self::new(x, y))
+        let point = Self::new(x, y);
+        if !point.is_on_curve() || !point.is_in_correct_subgroup_assuming_on_curve() {
+            return Err(IoError::new(
+                ErrorKind::InvalidData,
+                "point is not in the prime-order subgroup",
+            ));
+        }
+        Ok(point)
     }
 }
 
@@ -338,7 +350,23 @@ impl<P: Parameters> FromBytes for GroupProjective<P> {
         let y = P::BaseField::read(&mut reader)?;
         let t = P::BaseField::read(&mut reader)?;
         let z = P::BaseField::read(reader)?;
-        Ok(Self::new(x, y, t, z))
+        // The extended coordinates must also be consistent: z != 0 and
+        // x * y = t * z.
+        if z.is_zero() || x * &y != t * &z {
+            return Err(IoError::new(
+                ErrorKind::InvalidData,
+                "extended coordinates are inconsistent",
+            ));
+        }
+        let point = Self::new(x, y, t, z);
+        let affine = point.into_affine();
+        if !affine.is_on_curve() || !affine.is_in_correct_subgroup_assuming_on_curve() {
+            return Err(IoError::new(
+                ErrorKind::InvalidData,
+                "point is not in the prime-order subgroup",
+            ));
+        }
+        Ok(point)
     }
 }
 
@@ -1,20 +1,20 @@
 use crate::{
     biginteger::BigInteger832,
-    curves::{PairingCurve, PairingEngine},
+    curves::{models::mnt6::MNT6Parameters, PairingCurve, PairingEngine},
     fields::{
         sw6::{
             fq::{Fq, FqParameters},
-            Fq3, Fq6, Fr,
+            Fq3, Fq3Parameters, Fq6, Fq6Parameters, Fr,
         },
         BitIterator, Field, FpParameters,
     },
 };
 
 pub mod g1;
-pub use self::g1::{G1Affine, G1Projective};
+pub use self::g1::{G1Affine, G1Projective, SW6G1Parameters};
 
 pub mod g2;
-pub use self::g2::{G2Affine, G2Projective};
+pub use self::g2::{G2Affine, G2Projective, SW6G2Parameters};
 
 #[cfg(test)]
 mod tests;
@@ -55,6 +55,21 @@ impl PairingEngine for SW6 {
     }
 }
 
+impl MNT6Parameters for SW6 {
+    const TWIST: Fq3 = TWIST;
+    const ATE_LOOP_COUNT: &'static [u64] = &ATE_LOOP_COUNT;
+    const ATE_IS_LOOP_COUNT_NEG: bool = ATE_IS_LOOP_COUNT_NEG;
+    const FINAL_EXPONENT_LAST_CHUNK_1: &'static [u64] = &FINAL_EXPONENT_LAST_CHUNK_W1.0;
+    const FINAL_EXPONENT_LAST_CHUNK_W0_IS_NEG: bool = FINAL_EXPONENT_LAST_CHUNK_W0_IS_NEG;
+    const FINAL_EXPONENT_LAST_CHUNK_ABS_OF_W0: &'static [u64] =
+        &FINAL_EXPONENT_LAST_CHUNK_ABS_OF_W0.0;
+    type Fp = Fq;
+    type Fp3Params = Fq3Parameters;
+    type Fp6Params = Fq6Parameters;
+    type G1Parameters = SW6G1Parameters;
+    type G2Parameters = SW6G2Parameters;
+}
+
 impl SW6 {
     pub fn ate_pairing(p: &G1Affine, q: &G2Affine) -> GT {
         SW6::final_exponentiation(&SW6::ate_miller_loop(p, q))
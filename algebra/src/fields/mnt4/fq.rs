@@ -0,0 +1,2 @@
+//! The base field of MNT4-298 is the scalar field of MNT6-298.
+pub use crate::fields::mnt6::fr::{Fr as Fq, FrParameters as FqParameters};
@@ -0,0 +1,62 @@
+use crate::{
+    biginteger::BigInteger320 as BigInteger,
+    fields::{
+        fp2::{Fp2, Fp2Parameters},
+        mnt4::fq::Fq,
+    },
+};
+
+pub type Fq2 = Fp2<Fq2Parameters>;
+
+pub struct Fq2Parameters;
+
+impl Fp2Parameters for Fq2Parameters {
+    type Fp = Fq;
+
+    /// NONRESIDUE = 17
+    const NONRESIDUE: Fq = Fq::new(BigInteger([
+        0x259ae5b7c4d1ca15,
+        0xbc20e3dfe73f0ac3,
+        0x97505c422d1f08e7,
+        0x49d149cf165e1b2c,
+        0x3a87fe6a0cc,
+    ]));
+
+    /// QUADRATIC_NONRESIDUE = 3 + 2 * U
+    const QUADRATIC_NONRESIDUE: (Fq, Fq) = (
+        Fq::new(BigInteger([
+            0x813c791797c48d13,
+            0x7bf18ae35a039bcc,
+            0x86afc7321ddcd0d8,
+            0xd8e29d2b85c1a038,
+            0x1866efac6d5,
+        ])),
+        Fq::new(BigInteger([
+            0x318634f6b0c708b8,
+            0xd3bcf42bc76d1bea,
+            0x8bbf0b0e51f55681,
+            0x52308130c8f6a32f,
+            0x382447a6786,
+        ])),
+    );
+
+    /// Coefficients for the Frobenius automorphism.
+    const FROBENIUS_COEFF_FP2_C1: [Fq; 2] = [
+        // NONRESIDUE**(((q^0) - 1) / 2)
+        Fq::new(BigInteger([
+            0x18c31a7b5863845c,
+            0xe9de7a15e3b68df5,
+            0xc5df858728faab40,
+            0x29184098647b5197,
+            0x1c1223d33c3,
+        ])),
+        // NONRESIDUE**(((q^1) - 1) / 2)
+        Fq::new(BigInteger([
+            0xb049bbdf19027ba5,
+            0x57cb69486d69801d,
+            0x50f43dc341885a9,
+            0x794de405433502f7,
+            0x1fbd57fa0b0,
+        ])),
+    ];
+}
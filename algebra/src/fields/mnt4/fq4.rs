@@ -0,0 +1,62 @@
+use crate::{
+    biginteger::BigInteger320 as BigInteger,
+    fields::{
+        fp4::{Fp4, Fp4Parameters},
+        mnt4::{
+            fq::Fq,
+            fq2::{Fq2, Fq2Parameters},
+        },
+    },
+};
+
+pub type Fq4 = Fp4<Fq4Parameters>;
+
+pub struct Fq4Parameters;
+
+impl Fp4Parameters for Fq4Parameters {
+    type Fp2Params = Fq2Parameters;
+
+    /// NONRESIDUE = U
+    const NONRESIDUE: Fq2 = Fq2::new(
+        Fq::new(BigInteger([0, 0, 0, 0, 0])),
+        Fq::new(BigInteger([
+            0x18c31a7b5863845c,
+            0xe9de7a15e3b68df5,
+            0xc5df858728faab40,
+            0x29184098647b5197,
+            0x1c1223d33c3,
+        ])),
+    );
+
+    /// Coefficients for the Frobenius automorphism, 17^((q^i - 1) / 4).
+    const FROBENIUS_COEFF_FP4_C1: [Fq; 4] = [
+        Fq::new(BigInteger([
+            0x18c31a7b5863845c,
+            0xe9de7a15e3b68df5,
+            0xc5df858728faab40,
+            0x29184098647b5197,
+            0x1c1223d33c3,
+        ])),
+        Fq::new(BigInteger([
+            0xe426145080bf2ee7,
+            0xcd02cc9816da8a8d,
+            0xe07b85760f50a074,
+            0x3fb62479f705d41e,
+            0x3772430e00d,
+        ])),
+        Fq::new(BigInteger([
+            0xb049bbdf19027ba5,
+            0x57cb69486d69801d,
+            0x50f43dc341885a9,
+            0x794de405433502f7,
+            0x1fbd57fa0b0,
+        ])),
+        Fq::new(BigInteger([
+            0xe4e6c209f0a6d11a,
+            0x74a716c63a458384,
+            0xea7343ed4dc29075,
+            0x62b00023b0aa806f,
+            0x45d38bf466,
+        ])),
+    ];
+}
@@ -0,0 +1,2 @@
+//! The scalar field of MNT4-298 is the base field of MNT6-298.
+pub use crate::fields::mnt6::fq::{Fq as Fr, FqParameters as FrParameters};
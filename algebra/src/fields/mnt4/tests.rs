@@ -0,0 +1,25 @@
+use crate::{
+    fields::tests::{field_test, frobenius_test, sqrt_field_test},
+    Field,
+};
+
+#[test]
+fn test_mnt4_fq2() {
+    use crate::fields::mnt4::{Fq, Fq2};
+
+    let a: Fq2 = rand::random();
+    let b: Fq2 = rand::random();
+    field_test(a, b);
+    sqrt_field_test(a);
+    frobenius_test::<Fq2, _>(Fq::characteristic(), 13);
+}
+
+#[test]
+fn test_mnt4_fq4() {
+    use crate::fields::mnt4::{Fq, Fq4};
+
+    let a: Fq4 = rand::random();
+    let b: Fq4 = rand::random();
+    field_test(a, b);
+    frobenius_test::<Fq4, _>(Fq::characteristic(), 13);
+}
@@ -18,6 +18,7 @@ pub mod bls12_381;
 pub mod edwards_bls12;
 pub mod edwards_sw6;
 pub mod jubjub;
+pub mod mnt4;
 pub mod mnt6;
 pub mod models;
 pub mod sw6;
@@ -0,0 +1,351 @@
+use rand::{Rand, Rng};
+use std::{
+    cmp::Ordering,
+    io::{Read, Result as IoResult, Write},
+    marker::PhantomData,
+    ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign},
+};
+
+use crate::{
+    biginteger::BigInteger,
+    bytes::{FromBytes, ToBytes},
+    fields::{Field, Fp2, Fp2Parameters},
+};
+
+pub trait Fp4Parameters: 'static + Send + Sync {
+    type Fp2Params: Fp2Parameters;
+
+    const NONRESIDUE: Fp2<Self::Fp2Params>;
+
+    /// Coefficients for the Frobenius automorphism.
+    const FROBENIUS_COEFF_FP4_C1: [<Self::Fp2Params as Fp2Parameters>::Fp; 4];
+
+    #[inline(always)]
+    fn mul_fp2_by_nonresidue(fe: &Fp2<Self::Fp2Params>) -> Fp2<Self::Fp2Params> {
+        Self::NONRESIDUE * fe
+    }
+}
+
+#[derive(Derivative)]
+#[derivative(
+    Default(bound = "P: Fp4Parameters"),
+    Hash(bound = "P: Fp4Parameters"),
+    Clone(bound = "P: Fp4Parameters"),
+    Copy(bound = "P: Fp4Parameters"),
+    Debug(bound = "P: Fp4Parameters"),
+    PartialEq(bound = "P: Fp4Parameters"),
+    Eq(bound = "P: Fp4Parameters")
+)]
+pub struct Fp4<P: Fp4Parameters> {
+    pub c0: Fp2<P::Fp2Params>,
+    pub c1: Fp2<P::Fp2Params>,
+    #[derivative(Debug = "ignore")]
+    _parameters: PhantomData<P>,
+}
+
+impl<P: Fp4Parameters> Fp4<P> {
+    pub fn new(c0: Fp2<P::Fp2Params>, c1: Fp2<P::Fp2Params>) -> Self {
+        Fp4 {
+            c0,
+            c1,
+            _parameters: PhantomData,
+        }
+    }
+
+    /// Multiply by quadratic nonresidue u.
+    pub fn mul_by_nonresidue(value: &Fp2<P::Fp2Params>) -> Fp2<P::Fp2Params> {
+        let mut res = *value;
+        res.c0 = value.c1;
+        res.c1 = value.c0;
+        res.c0
+            .mul_assign(&<P::Fp2Params as Fp2Parameters>::NONRESIDUE);
+        res
+    }
+
+    pub fn unitary_inverse(&self) -> Self {
+        Self::new(self.c0, -self.c1)
+    }
+
+    pub fn cyclotomic_exp<B: BigInteger>(&self, exponent: &B) -> Self {
+        let mut res = Self::one();
+        let self_inverse = self.unitary_inverse();
+
+        let mut found_nonzero = false;
+        let naf = exponent.find_wnaf();
+
+        for &value in naf.iter().rev() {
+            if found_nonzero {
+                res = res.square();
+            }
+
+            if value != 0 {
+                found_nonzero = true;
+
+                if value > 0 {
+                    res = res * self;
+                } else {
+                    res = res * &self_inverse;
+                }
+            }
+        }
+
+        res
+    }
+}
+
+impl<P: Fp4Parameters> Field for Fp4<P> {
+    fn zero() -> Self {
+        Fp4 {
+            c0:          Fp2::zero(),
+            c1:          Fp2::zero(),
+            _parameters: PhantomData,
+        }
+    }
+
+    fn is_zero(&self) -> bool {
+        self.c0.is_zero() && self.c1.is_zero()
+    }
+
+    fn one() -> Self {
+        Fp4 {
+            c0:          Fp2::one(),
+            c1:          Fp2::zero(),
+            _parameters: PhantomData,
+        }
+    }
+
+    fn is_one(&self) -> bool {
+        self.c0.is_one() && self.c1.is_zero()
+    }
+
+    #[inline]
+    fn characteristic<'a>() -> &'a [u64] {
+        Fp2::<P::Fp2Params>::characteristic()
+    }
+
+    fn double(&self) -> Self {
+        let mut result = *self;
+        result.double_in_place();
+        result
+    }
+
+    fn double_in_place(&mut self) -> &mut Self {
+        self.c0.double_in_place();
+        self.c1.double_in_place();
+        self
+    }
+
+    fn square(&self) -> Self {
+        let mut result = *self;
+        result.square_in_place();
+        result
+    }
+
+    fn square_in_place(&mut self) -> &mut Self {
+        // Devegili OhEig Scott Dahab --- Multiplication and Squaring on
+        // Pairing-Friendly
+        // Fields.pdf; Section 3 (Complex)
+        let a = self.c0;
+        let mut b = self.c1;
+        let ab_add = a + &b;
+        let mut ab_mul = a * &b;
+
+        let c0 = ab_add * &(a + &Self::mul_by_nonresidue(&mut b))
+            - &ab_mul
+            - &Self::mul_by_nonresidue(&mut ab_mul);
+        let c1 = ab_mul.double();
+
+        self.c0 = c0;
+        self.c1 = c1;
+        self
+    }
+
+    fn inverse(&self) -> Option<Self> {
+        if self.is_zero() {
+            None
+        } else {
+            // From "High-Speed Software Implementation of the Optimal Ate Pairing over
+            // Barreto-Naehrig
+            // Curves"; Algorithm 8
+            let a = self.c0;
+            let b = self.c1;
+
+            let mut t1 = b.square();
+            let t0 = a.square() - &Self::mul_by_nonresidue(&mut t1);
+            let t2 = t0.inverse().unwrap();
+
+            let c0 = a * &t2;
+            let c1 = (b * &t2).neg();
+
+            Some(Self::new(c0, c1))
+        }
+    }
+
+    fn inverse_in_place(&mut self) -> Option<&mut Self> {
+        if let Some(inverse) = self.inverse() {
+            *self = inverse;
+            Some(self)
+        } else {
+            None
+        }
+    }
+
+    fn frobenius_map(&mut self, power: usize) {
+        self.c0.frobenius_map(power);
+        self.c1.frobenius_map(power);
+        self.c1.mul_by_fp(&P::FROBENIUS_COEFF_FP4_C1[power % 4]);
+    }
+}
+
+/// `Fp4` elements are ordered lexicographically.
+impl<P: Fp4Parameters> Ord for Fp4<P> {
+    #[inline(always)]
+    fn cmp(&self, other: &Self) -> Ordering {
+        let c1_cmp = self.c1.cmp(&other.c1);
+        if c1_cmp == Ordering::Equal {
+            self.c0.cmp(&other.c0)
+        } else {
+            c1_cmp
+        }
+    }
+}
+
+impl<P: Fp4Parameters> PartialOrd for Fp4<P> {
+    #[inline(always)]
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<P: Fp4Parameters> ToBytes for Fp4<P> {
+    #[inline]
+    fn write<W: Write>(&self, mut writer: W) -> IoResult<()> {
+        self.c0.write(&mut writer)?;
+        self.c1.write(&mut writer)
+    }
+}
+
+impl<P: Fp4Parameters> FromBytes for Fp4<P> {
+    #[inline]
+    fn read<R: Read>(mut reader: R) -> IoResult<Self> {
+        let c0 = Fp2::read(&mut reader)?;
+        let c1 = Fp2::read(&mut reader)?;
+        Ok(Fp4::new(c0, c1))
+    }
+}
+
+impl<P: Fp4Parameters> Neg for Fp4<P> {
+    type Output = Self;
+    #[inline]
+    fn neg(mut self) -> Self {
+        self.c0 = self.c0.neg();
+        self.c1 = self.c1.neg();
+        self
+    }
+}
+
+impl<P: Fp4Parameters> Rand for Fp4<P> {
+    fn rand<R: Rng>(rng: &mut R) -> Self {
+        Fp4 {
+            c0:          rng.gen(),
+            c1:          rng.gen(),
+            _parameters: PhantomData,
+        }
+    }
+}
+
+impl<'a, P: Fp4Parameters> Add<&'a Fp4<P>> for Fp4<P> {
+    type Output = Self;
+
+    #[inline]
+    fn add(self, other: &Self) -> Self {
+        let mut result = self;
+        result.add_assign(&other);
+        result
+    }
+}
+
+impl<'a, P: Fp4Parameters> Sub<&'a Fp4<P>> for Fp4<P> {
+    type Output = Self;
+
+    #[inline]
+    fn sub(self, other: &Self) -> Self {
+        let mut result = self;
+        result.sub_assign(&other);
+        result
+    }
+}
+
+impl<'a, P: Fp4Parameters> Mul<&'a Fp4<P>> for Fp4<P> {
+    type Output = Self;
+
+    #[inline]
+    fn mul(self, other: &Self) -> Self {
+        let mut result = self;
+        result.mul_assign(&other);
+        result
+    }
+}
+
+impl<'a, P: Fp4Parameters> Div<&'a Fp4<P>> for Fp4<P> {
+    type Output = Self;
+
+    #[inline]
+    fn div(self, other: &Self) -> Self {
+        let mut result = self;
+        result.mul_assign(&other.inverse().unwrap());
+        result
+    }
+}
+
+impl<'a, P: Fp4Parameters> AddAssign<&'a Self> for Fp4<P> {
+    #[inline]
+    fn add_assign(&mut self, other: &Self) {
+        self.c0.add_assign(&other.c0);
+        self.c1.add_assign(&other.c1);
+    }
+}
+
+impl<'a, P: Fp4Parameters> SubAssign<&'a Self> for Fp4<P> {
+    #[inline]
+    fn sub_assign(&mut self, other: &Self) {
+        self.c0.sub_assign(&other.c0);
+        self.c1.sub_assign(&other.c1);
+    }
+}
+
+impl<'a, P: Fp4Parameters> MulAssign<&'a Self> for Fp4<P> {
+    #[inline]
+    fn mul_assign(&mut self, other: &Self) {
+        // Devegili OhEig Scott Dahab --- Multiplication and Squaring on
+        // Pairing-Friendly
+        // Fields.pdf; Section 3 (Karatsuba)
+        let a0 = self.c0;
+        let b0 = self.c1;
+        let a1 = other.c0;
+        let b1 = other.c1;
+
+        let a0a1 = a0 * &a1;
+        let mut b0b1 = b0 * &b1;
+        let beta_b0b1 = Self::mul_by_nonresidue(&mut b0b1);
+
+        let c0 = a0a1 + &beta_b0b1;
+        let c1 = (a0 + &b0) * &(a1 + &b1) - &a0a1 - &b0b1;
+
+        self.c0 = c0;
+        self.c1 = c1;
+    }
+}
+
+impl<'a, P: Fp4Parameters> DivAssign<&'a Self> for Fp4<P> {
+    #[inline]
+    fn div_assign(&mut self, other: &Self) {
+        self.mul_assign(&other.inverse().unwrap());
+    }
+}
+
+impl<P: Fp4Parameters> ::std::fmt::Display for Fp4<P> {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+        write!(f, "Fp4({}, {})", self.c0, self.c1)
+    }
+}
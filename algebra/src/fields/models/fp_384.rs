@@ -2,7 +2,7 @@ use rand::Rand;
 use std::{
     cmp::{Ord, Ordering, PartialOrd},
     fmt::{Display, Formatter, Result as FmtResult},
-    io::{Read, Result as IoResult, Write},
+    io::{Error as IoError, ErrorKind, Read, Result as IoResult, Write},
     marker::PhantomData,
     ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign},
     str::FromStr,
@@ -449,7 +449,14 @@ impl<P: Fp384Parameters> ToBytes for Fp384<P> {
 impl<P: Fp384Parameters> FromBytes for Fp384<P> {
     #[inline]
     fn read<R: Read>(reader: R) -> IoResult<Self> {
-        BigInteger::read(reader).map(Fp384::from_repr)
+        let repr = BigInteger::read(reader)?;
+        if repr >= P::MODULUS {
+            return Err(IoError::new(
+                ErrorKind::InvalidData,
+                "field element is not canonically encoded",
+            ));
+        }
+        Ok(Fp384::from_repr(repr))
     }
 }
 
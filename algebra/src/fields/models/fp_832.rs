@@ -7,7 +7,7 @@ use rand::Rand;
 use std::{
     cmp::{Ord, Ordering, PartialOrd},
     fmt::{Display, Formatter, Result as FmtResult},
-    io::{Read, Result as IoResult, Write},
+    io::{Error as IoError, ErrorKind, Read, Result as IoResult, Write},
     marker::PhantomData,
     ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign},
     str::FromStr,
@@ -748,7 +748,14 @@ impl<P: Fp832Parameters> ToBytes for Fp832<P> {
 impl<P: Fp832Parameters> FromBytes for Fp832<P> {
     #[inline]
     fn read<R: Read>(reader: R) -> IoResult<Self> {
-        BigInteger::read(reader).map(Fp832::from_repr)
+        let repr = BigInteger::read(reader)?;
+        if repr >= P::MODULUS {
+            return Err(IoError::new(
+                ErrorKind::InvalidData,
+                "field element is not canonically encoded",
+            ));
+        }
+        Ok(Fp832::from_repr(repr))
     }
 }
 
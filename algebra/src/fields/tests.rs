@@ -1,5 +1,9 @@
-use crate::fields::{Field, LegendreSymbol, PrimeField, SquareRootField};
+use crate::{
+    bytes::{FromBytes, ToBytes},
+    fields::{Field, FpParameters, LegendreSymbol, PrimeField, SquareRootField},
+};
 use rand::{Rng, SeedableRng, XorShiftRng};
+use std::io::ErrorKind;
 
 pub const ITERATIONS: u32 = 40;
 
@@ -318,6 +322,11 @@ pub fn primefield_test<F: PrimeField>() {
     let one = F::one();
     assert_eq!(F::from_repr(one.into_repr()), one);
     assert_eq!(F::from_str("1").ok().unwrap(), one);
+
+    // `from_repr` maps the modulus to zero, but decoding rejects it.
+    let modulus_bytes = to_bytes![F::Params::MODULUS].unwrap();
+    let error = F::read(modulus_bytes.as_slice()).unwrap_err();
+    assert_eq!(error.kind(), ErrorKind::InvalidData);
 }
 
 pub fn sqrt_field_test<F: SquareRootField>(elem: F) {
@@ -1,4 +1,4 @@
-use crate::{BigInteger, FpParameters, PairingEngine, PrimeField, ProjectiveCurve};
+use crate::{BigInteger, FpParameters, Group, PairingEngine, PrimeField, ProjectiveCurve};
 use rayon::prelude::*;
 
 pub struct FixedBaseMSM;
@@ -12,11 +12,7 @@ impl FixedBaseMSM {
         }
     }
 
-    pub fn get_window_table<T: ProjectiveCurve>(
-        scalar_size: usize,
-        window: usize,
-        g: T,
-    ) -> Vec<Vec<T>> {
+    pub fn get_window_table<T: Group>(scalar_size: usize, window: usize, g: T) -> Vec<Vec<T>> {
         let in_window = 1 << window;
         let outerc = (scalar_size + window - 1) / window;
         let last_in_window = 1 << (scalar_size - (outerc - 1) * window);
@@ -1,16 +1,13 @@
-use crate::{
-    AffineCurve, BigInteger, Field, FpParameters, PairingCurve, PairingEngine, PrimeField,
-    ProjectiveCurve,
-};
+use crate::{AffineCurve, BigInteger, Field, FpParameters, PrimeField, ProjectiveCurve};
 use rayon::prelude::*;
 
 pub struct VariableBaseMSM;
 
 impl VariableBaseMSM {
-    fn msm_inner<G: PairingCurve>(
+    fn msm_inner<G: AffineCurve>(
         bases: &[G],
-        scalars: &[<<G::Engine as PairingEngine>::Fr as PrimeField>::BigInt],
-    ) -> <G as AffineCurve>::Projective {
+        scalars: &[<G::ScalarField as PrimeField>::BigInt],
+    ) -> G::Projective {
         assert_eq!(bases.len(), scalars.len());
         let c = if scalars.len() < 32 {
             3
@@ -18,9 +15,8 @@ impl VariableBaseMSM {
             (f64::from(scalars.len() as u32)).ln().ceil() as usize
         };
 
-        let num_bits =
-            <<G::Engine as PairingEngine>::Fr as PrimeField>::Params::MODULUS_BITS as usize;
-        let fr_one = <G::Engine as PairingEngine>::Fr::one().into_repr();
+        let num_bits = <G::ScalarField as PrimeField>::Params::MODULUS_BITS as usize;
+        let fr_one = G::ScalarField::one().into_repr();
 
         let zero = G::zero().into_projective();
         let window_starts: Vec<_> = (0..num_bits).step_by(c).collect();
@@ -88,10 +84,10 @@ impl VariableBaseMSM {
         total + &lowest
     }
 
-    pub fn multi_scalar_mul<G: PairingCurve>(
+    pub fn multi_scalar_mul<G: AffineCurve>(
         bases: &[G],
-        scalars: &[<<G::Engine as PairingEngine>::Fr as PrimeField>::BigInt],
-    ) -> <G as AffineCurve>::Projective {
+        scalars: &[<G::ScalarField as PrimeField>::BigInt],
+    ) -> G::Projective {
         Self::msm_inner(bases, scalars)
     }
 }
@@ -99,7 +95,7 @@ impl VariableBaseMSM {
 #[cfg(test)]
 mod test {
     use super::*;
-    use crate::curves::bls12_381::Bls12_381;
+    use crate::{curves::bls12_381::Bls12_381, PairingEngine};
     use rand::{self, Rand, SeedableRng, XorShiftRng};
 
     fn naive_var_base_msm<G: AffineCurve>(
@@ -5,7 +5,7 @@ extern crate criterion;
 
 use algebra::curves::edwards_bls12::EdwardsAffine as Edwards;
 use criterion::Criterion;
-use dpc::crypto_primitives::crh::{pedersen::*, FixedLengthCRH};
+use dpc::crypto_primitives::crh::{bowe_hopwood::*, pedersen::*, FixedLengthCRH};
 
 #[derive(Clone, PartialEq, Eq, Hash)]
 pub struct HashWindow;
@@ -15,6 +15,14 @@ impl PedersenWindow for HashWindow {
     const NUM_WINDOWS: usize = 8;
 }
 
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct BoweHopwoodHashWindow;
+
+impl PedersenWindow for BoweHopwoodHashWindow {
+    const WINDOW_SIZE: usize = 50;
+    const NUM_WINDOWS: usize = 8;
+}
+
 fn pedersen_crh_setup(c: &mut Criterion) {
     c.bench_function("Pedersen CRH Setup", move |b| {
         b.iter(|| {
@@ -35,16 +43,49 @@ fn pedersen_crh_eval(c: &mut Criterion) {
     });
 }
 
+fn pedersen_crh_precompute_tables(c: &mut Criterion) {
+    let mut rng = rand::OsRng::new().unwrap();
+    let parameters = PedersenCRH::<Edwards, HashWindow>::setup(&mut rng).unwrap();
+    c.bench_function("Pedersen CRH Precompute Tables", move |b| {
+        b.iter(|| PedersenParameters::new(parameters.generators.clone()))
+    });
+}
+
+fn pedersen_crh_eval_without_tables(c: &mut Criterion) {
+    let mut rng = rand::OsRng::new().unwrap();
+    let mut parameters = PedersenCRH::<Edwards, HashWindow>::setup(&mut rng).unwrap();
+    parameters.tables.clear();
+    let input = vec![5u8; 128];
+    c.bench_function("Pedersen CRH Eval without tables", move |b| {
+        b.iter(|| {
+            PedersenCRH::<Edwards, HashWindow>::evaluate(&parameters, &input).unwrap();
+        })
+    });
+}
+
+fn bowe_hopwood_crh_eval(c: &mut Criterion) {
+    let mut rng = rand::OsRng::new().unwrap();
+    let parameters =
+        BoweHopwoodPedersenCRH::<Edwards, BoweHopwoodHashWindow>::setup(&mut rng).unwrap();
+    let input = vec![5u8; 128];
+    c.bench_function("Bowe-Hopwood Pedersen CRH Eval", move |b| {
+        b.iter(|| {
+            BoweHopwoodPedersenCRH::<Edwards, BoweHopwoodHashWindow>::evaluate(&parameters, &input)
+                .unwrap();
+        })
+    });
+}
+
 criterion_group! {
     name = crh_setup;
     config = Criterion::default().sample_size(5);
-    targets = pedersen_crh_setup
+    targets = pedersen_crh_setup, pedersen_crh_precompute_tables
 }
 
 criterion_group! {
     name = crh_eval;
     config = Criterion::default().sample_size(10);
-    targets = pedersen_crh_eval
+    targets = pedersen_crh_eval, pedersen_crh_eval_without_tables, bowe_hopwood_crh_eval
 }
 
 criterion_main!(crh_setup, crh_eval);
@@ -1 +1,2 @@
 pub const MAX_MERKLE_TREE_HEIGHT: u8 = 32;
+pub const SPARSE_MERKLE_TREE_DEPTH: usize = 64;
@@ -1,7 +1,7 @@
 use rand::{Rand, Rng};
 use std::{fmt::Debug, hash::Hash};
 
-use algebra::bytes::ToBytes;
+use algebra::bytes::{FromBytes, ToBytes};
 
 pub mod blake2s;
 pub mod injective_map;
@@ -10,9 +10,9 @@ pub mod pedersen;
 use failure::Error;
 
 pub trait CommitmentScheme {
-    type Output: ToBytes + Clone + Default + Eq + Hash + Debug;
-    type Parameters: Clone;
-    type Randomness: Clone + ToBytes + Default + Eq + Rand + Debug;
+    type Output: ToBytes + FromBytes + Clone + Default + Eq + Hash + Debug;
+    type Parameters: ToBytes + FromBytes + Clone;
+    type Randomness: Clone + ToBytes + FromBytes + Default + Eq + Rand + Debug;
 
     fn setup<R: Rng>(r: &mut R) -> Result<Self::Parameters, Error>;
     fn commit(
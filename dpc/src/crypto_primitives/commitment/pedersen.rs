@@ -3,19 +3,61 @@ use rand::{Rand, Rng};
 use std::marker::PhantomData;
 
 use super::CommitmentScheme;
-use algebra::{bytes::ToBytes, groups::Group, BitIterator, FpParameters, PrimeField};
-use std::io::{Result as IoResult, Write};
+use algebra::{
+    bytes::{FromBytes, ToBytes},
+    groups::Group,
+    BitIterator, FpParameters, PrimeField,
+};
+use std::io::{Read, Result as IoResult, Write};
 
 pub use crate::crypto_primitives::crh::pedersen::PedersenWindow;
-use crate::crypto_primitives::crh::{
-    pedersen::{PedersenCRH, PedersenParameters as PedersenCRHParameters},
-    FixedLengthCRH,
+use crate::crypto_primitives::crh::pedersen::{
+    bytes_to_bits, read_powers, sum_of_powers, window_tables, write_powers, PedersenCRH,
 };
 
 #[derive(Clone)]
 pub struct PedersenParameters<G: Group> {
     pub randomness_generator: Vec<G>,
     pub generators:           Vec<Vec<G>>,
+    // The tables of `crh::pedersen::PedersenParameters` for `generators`,
+    // precomputed once so that `commit` does not have to.
+    pub tables:               Vec<Vec<Vec<G>>>,
+}
+
+impl<G: Group> PedersenParameters<G> {
+    /// Returns parameters with the given generator powers, and the tables
+    /// that `PedersenCommitment::commit` uses to hash its input.
+    pub fn new(randomness_generator: Vec<G>, generators: Vec<Vec<G>>) -> Self {
+        let tables = window_tables(&generators);
+        Self {
+            randomness_generator,
+            generators,
+            tables,
+        }
+    }
+}
+
+impl<G: Group> ToBytes for PedersenParameters<G> {
+    fn write<W: Write>(&self, mut writer: W) -> IoResult<()> {
+        write_powers(&self.randomness_generator, &mut writer)?;
+        (self.generators.len() as u64).write(&mut writer)?;
+        for powers in &self.generators {
+            write_powers(powers, &mut writer)?;
+        }
+        Ok(())
+    }
+}
+
+impl<G: Group> FromBytes for PedersenParameters<G> {
+    fn read<R: Read>(mut reader: R) -> IoResult<Self> {
+        let randomness_generator = read_powers(&mut reader)?;
+        let num_generators = u64::read(&mut reader)?;
+        let mut generators = Vec::new();
+        for _ in 0..num_generators {
+            generators.push(read_powers(&mut reader)?);
+        }
+        Ok(Self::new(randomness_generator, generators))
+    }
 }
 
 pub struct PedersenCommitment<G: Group, W: PedersenWindow> {
@@ -46,6 +88,13 @@ impl<G: Group> ToBytes for PedersenRandomness<G> {
     }
 }
 
+impl<G: Group> FromBytes for PedersenRandomness<G> {
+    #[inline]
+    fn read<R: Read>(reader: R) -> IoResult<Self> {
+        Ok(PedersenRandomness(FromBytes::read(reader)?))
+    }
+}
+
 impl<G: Group, W: PedersenWindow> CommitmentScheme for PedersenCommitment<G, W> {
     type Parameters = PedersenParameters<G>;
     type Randomness = PedersenRandomness<G>;
@@ -63,10 +112,7 @@ impl<G: Group, W: PedersenWindow> CommitmentScheme for PedersenCommitment<G, W>
         let generators = PedersenCRH::<_, W>::create_generators(rng);
         timer_end!(time);
 
-        Ok(Self::Parameters {
-            randomness_generator,
-            generators,
-        })
+        Ok(Self::Parameters::new(randomness_generator, generators))
     }
 
     fn commit(
@@ -76,7 +122,7 @@ impl<G: Group, W: PedersenWindow> CommitmentScheme for PedersenCommitment<G, W>
     ) -> Result<Self::Output, Error> {
         let commit_time = timer_start!(|| "PedersenCOMM::Commit");
         // If the input is too long, return an error.
-        if input.len() > W::WINDOW_SIZE * W::NUM_WINDOWS {
+        if (input.len() * 8) > W::WINDOW_SIZE * W::NUM_WINDOWS {
             panic!("incorrect input length: {:?}", input.len());
         }
         // Pad the input to the necessary length.
@@ -92,12 +138,13 @@ impl<G: Group, W: PedersenWindow> CommitmentScheme for PedersenCommitment<G, W>
         }
         assert_eq!(parameters.generators.len(), W::NUM_WINDOWS);
 
-        // Invoke Pedersen CRH here, to prevent code duplication.
-
-        let crh_parameters = PedersenCRHParameters {
-            generators: parameters.generators.clone(),
-        };
-        let mut result = PedersenCRH::<_, W>::evaluate(&crh_parameters, &input)?;
+        // Hash the input as the Pedersen CRH does.
+        let mut result = sum_of_powers(
+            &parameters.generators,
+            &parameters.tables,
+            W::WINDOW_SIZE,
+            &bytes_to_bits(input),
+        );
         let randomize_time = timer_start!(|| "Randomize");
 
         // Compute h^r.
@@ -0,0 +1,179 @@
+use failure::{format_err, Error};
+use rand::Rng;
+use rayon::prelude::*;
+use std::{
+    fmt::{Debug, Formatter, Result as FmtResult},
+    io::{Read, Result as IoResult, Write},
+    marker::PhantomData,
+};
+
+use super::{
+    pedersen::{bytes_to_bits, read_powers, write_powers, PedersenWindow},
+    FixedLengthCRH,
+};
+use algebra::{
+    biginteger::BigInteger,
+    bytes::{FromBytes, ToBytes},
+    fields::PrimeField,
+    groups::Group,
+};
+
+/// The number of input bits that make up one signed digit.
+pub const CHUNK_SIZE: usize = 3;
+
+#[derive(Clone, Default)]
+pub struct BoweHopwoodPedersenParameters<G: Group> {
+    pub generators: Vec<Vec<G>>,
+}
+
+/// The Pedersen hash of Bowe and Hopwood, which splits its input into
+/// `W::NUM_WINDOWS` segments of `W::WINDOW_SIZE` chunks of `CHUNK_SIZE` bits.
+/// The `j`-th chunk `(s0, s1, s2)` of a segment encodes the signed digit
+/// `d_j = (1 + s0 + 2 * s1) * (1 - 2 * s2)`, and the segment hashes to
+/// `Σ_j d_j * 16^j * g` for the generator `g` of the segment.
+///
+/// As digits are never zero, the sums of the digits in a segment never hit
+/// the exceptional cases of incomplete addition laws, which lets gadgets add
+/// them in Montgomery form.
+pub struct BoweHopwoodPedersenCRH<G: Group, W: PedersenWindow> {
+    group:  PhantomData<G>,
+    window: PhantomData<W>,
+}
+
+impl<G: Group, W: PedersenWindow> BoweHopwoodPedersenCRH<G, W> {
+    /// Returns the largest number of chunks in a segment for which every
+    /// partial sum of the segment is distinct from, and not the negation of,
+    /// the next term: that is, the largest `c` with `16^c <= (r - 1) / 2`.
+    pub fn max_window_size() -> usize {
+        let bound = G::ScalarField::modulus_minus_one_div_two().num_bits() as usize;
+        (bound - 1) / 4
+    }
+
+    pub fn create_generators<R: Rng>(rng: &mut R) -> Vec<Vec<G>> {
+        let mut generators = Vec::new();
+        for _ in 0..W::NUM_WINDOWS {
+            let mut generators_for_segment = Vec::with_capacity(W::WINDOW_SIZE);
+            let mut base = G::rand(rng);
+            for _ in 0..W::WINDOW_SIZE {
+                generators_for_segment.push(base);
+                for _ in 0..4 {
+                    base.double_in_place();
+                }
+            }
+            generators.push(generators_for_segment);
+        }
+        generators
+    }
+}
+
+impl<G: Group, W: PedersenWindow> FixedLengthCRH for BoweHopwoodPedersenCRH<G, W> {
+    const INPUT_SIZE_BITS: usize = CHUNK_SIZE * W::WINDOW_SIZE * W::NUM_WINDOWS;
+    type Output = G;
+    type Parameters = BoweHopwoodPedersenParameters<G>;
+
+    fn setup<R: Rng>(rng: &mut R) -> Result<Self::Parameters, Error> {
+        let max_window_size = Self::max_window_size();
+        if W::WINDOW_SIZE > max_window_size {
+            return Err(format_err!(
+                "segments of {} chunks exceed the maximum of {}",
+                W::WINDOW_SIZE,
+                max_window_size
+            ));
+        }
+
+        let time = timer_start!(|| format!(
+            "BoweHopwoodPedersenCRH::Setup: {} segments of {} {}-bit chunks; {{0,1}}^{{{}}} -> G",
+            W::NUM_WINDOWS,
+            W::WINDOW_SIZE,
+            CHUNK_SIZE,
+            Self::INPUT_SIZE_BITS
+        ));
+        let generators = Self::create_generators(rng);
+        timer_end!(time);
+        Ok(BoweHopwoodPedersenParameters { generators })
+    }
+
+    fn evaluate(parameters: &Self::Parameters, input: &[u8]) -> Result<Self::Output, Error> {
+        let eval_time = timer_start!(|| "BoweHopwoodPedersenCRH::Eval");
+
+        if (input.len() * 8) > Self::INPUT_SIZE_BITS {
+            panic!(
+                "incorrect input length {:?} for window params {:?}x{:?}x{}",
+                input.len(),
+                W::WINDOW_SIZE,
+                W::NUM_WINDOWS,
+                CHUNK_SIZE
+            );
+        }
+        assert_eq!(
+            parameters.generators.len(),
+            W::NUM_WINDOWS,
+            "Incorrect pp of size {:?} for window params {:?}x{:?}x{}",
+            parameters.generators.len(),
+            W::WINDOW_SIZE,
+            W::NUM_WINDOWS,
+            CHUNK_SIZE
+        );
+
+        // Pad the input with zero bits to the full length.
+        let mut bits = bytes_to_bits(input);
+        bits.resize(Self::INPUT_SIZE_BITS, false);
+
+        // Compute Σ_i Σ_j d_ij * 16^j * g_i.
+        let result = bits
+            .par_chunks(W::WINDOW_SIZE * CHUNK_SIZE)
+            .zip(&parameters.generators)
+            .map(|(segment, generators)| {
+                let mut encoded = G::zero();
+                for (chunk, base) in segment.chunks(CHUNK_SIZE).zip(generators) {
+                    let mut digit = *base;
+                    if chunk[0] {
+                        digit += base;
+                    }
+                    if chunk[1] {
+                        digit += &base.double();
+                    }
+                    if chunk[2] {
+                        digit = -digit;
+                    }
+                    encoded += &digit;
+                }
+                encoded
+            })
+            .reduce(|| G::zero(), |a, b| a + &b);
+        timer_end!(eval_time);
+
+        Ok(result)
+    }
+}
+
+impl<G: Group> ToBytes for BoweHopwoodPedersenParameters<G> {
+    fn write<W: Write>(&self, mut writer: W) -> IoResult<()> {
+        (self.generators.len() as u64).write(&mut writer)?;
+        for powers in &self.generators {
+            write_powers(powers, &mut writer)?;
+        }
+        Ok(())
+    }
+}
+
+impl<G: Group> FromBytes for BoweHopwoodPedersenParameters<G> {
+    fn read<R: Read>(mut reader: R) -> IoResult<Self> {
+        let num_generators = u64::read(&mut reader)?;
+        let mut generators = Vec::new();
+        for _ in 0..num_generators {
+            generators.push(read_powers(&mut reader)?);
+        }
+        Ok(Self { generators })
+    }
+}
+
+impl<G: Group> Debug for BoweHopwoodPedersenParameters<G> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        write!(f, "Bowe-Hopwood Pedersen Hash Parameters {{\n")?;
+        for (i, g) in self.generators.iter().enumerate() {
+            write!(f, "\t  Generator {}: {:?}\n", i, g)?;
+        }
+        write!(f, "}}\n")
+    }
+}
@@ -1,10 +1,11 @@
 use crate::crypto_primitives::CryptoError;
-use algebra::bytes::ToBytes;
+use algebra::bytes::{FromBytes, ToBytes};
 use failure::Error;
 use rand::Rng;
 use std::{fmt::Debug, hash::Hash, marker::PhantomData};
 
 use super::{
+    bowe_hopwood::{BoweHopwoodPedersenCRH, BoweHopwoodPedersenParameters},
     pedersen::{PedersenCRH, PedersenParameters, PedersenWindow},
     FixedLengthCRH,
 };
@@ -18,7 +19,7 @@ use algebra::{
 };
 
 pub trait InjectiveMap<G: Group> {
-    type Output: ToBytes + Clone + Eq + Hash + Default + Debug;
+    type Output: ToBytes + FromBytes + Clone + Eq + Hash + Default + Debug;
     fn injective_map(ge: &G) -> Result<Self::Output, CryptoError>;
 }
 
@@ -70,3 +71,33 @@ impl<G: Group, I: InjectiveMap<G>, W: PedersenWindow> FixedLengthCRH
         Ok(result)
     }
 }
+
+pub struct BoweHopwoodPedersenCRHCompressor<G: Group, I: InjectiveMap<G>, W: PedersenWindow> {
+    _group:      PhantomData<G>,
+    _compressor: PhantomData<I>,
+    _crh:        BoweHopwoodPedersenCRH<G, W>,
+}
+
+impl<G: Group, I: InjectiveMap<G>, W: PedersenWindow> FixedLengthCRH
+    for BoweHopwoodPedersenCRHCompressor<G, I, W>
+{
+    const INPUT_SIZE_BITS: usize = BoweHopwoodPedersenCRH::<G, W>::INPUT_SIZE_BITS;
+    type Output = I::Output;
+    type Parameters = BoweHopwoodPedersenParameters<G>;
+
+    fn setup<R: Rng>(rng: &mut R) -> Result<Self::Parameters, Error> {
+        let time = timer_start!(|| format!("BoweHopwoodPedersenCRHCompressor::Setup"));
+        let params = BoweHopwoodPedersenCRH::<G, W>::setup(rng);
+        timer_end!(time);
+        params
+    }
+
+    fn evaluate(parameters: &Self::Parameters, input: &[u8]) -> Result<Self::Output, Error> {
+        let eval_time = timer_start!(|| "BoweHopwoodPedersenCRHCompressor::Eval");
+        let result = I::injective_map(&BoweHopwoodPedersenCRH::<G, W>::evaluate(
+            parameters, input,
+        )?)?;
+        timer_end!(eval_time);
+        Ok(result)
+    }
+}
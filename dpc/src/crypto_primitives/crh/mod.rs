@@ -1,7 +1,8 @@
-use algebra::bytes::ToBytes;
+use algebra::bytes::{FromBytes, ToBytes};
 use rand::Rng;
 use std::hash::Hash;
 
+pub mod bowe_hopwood;
 pub mod injective_map;
 pub mod pedersen;
 
@@ -9,8 +10,8 @@ use failure::Error;
 
 pub trait FixedLengthCRH {
     const INPUT_SIZE_BITS: usize;
-    type Output: ToBytes + Clone + Eq + Hash + Default;
-    type Parameters: Clone + Default;
+    type Output: ToBytes + FromBytes + Clone + Eq + Hash + Default;
+    type Parameters: ToBytes + FromBytes + Clone + Default;
 
     fn setup<R: Rng>(r: &mut R) -> Result<Self::Parameters, Error>;
     fn evaluate(parameters: &Self::Parameters, input: &[u8]) -> Result<Self::Output, Error>;
@@ -19,16 +20,18 @@ pub trait FixedLengthCRH {
 #[cfg(test)]
 mod test {
     use super::{
-        pedersen::{PedersenCRH, PedersenWindow},
+        bowe_hopwood::BoweHopwoodPedersenCRH,
+        pedersen::{bytes_to_bits, PedersenCRH, PedersenParameters, PedersenWindow},
         FixedLengthCRH,
     };
     use algebra::{
-        bytes::ToBytes,
+        bytes::{FromBytes, ToBytes},
         curves::jubjub::JubJubAffine as JubJub,
-        fields::{jubjub::fr::Fr, Field},
+        fields::{jubjub::fr::Fr, Field, PrimeField},
+        groups::Group,
         to_bytes,
     };
-    use rand::thread_rng;
+    use rand::{thread_rng, Rng};
 
     fn crh_test<C: FixedLengthCRH>(input: &[u8]) {
         let rng = &mut thread_rng();
@@ -54,6 +57,91 @@ mod test {
         crh_test::<PedersenCRH<JubJub, Window>>(&input);
     }
 
+    #[test]
+    fn pedersen_crh_table_test() {
+        #[derive(Clone)]
+        pub(super) struct Window;
+
+        // Not a multiple of the table window size, so that the last chunk of
+        // each window is partial.
+        impl PedersenWindow for Window {
+            const WINDOW_SIZE: usize = 10;
+            const NUM_WINDOWS: usize = 8;
+        }
+
+        let rng = &mut thread_rng();
+        let parameters = PedersenCRH::<JubJub, Window>::setup(rng).unwrap();
+        assert_eq!(parameters.tables.len(), Window::NUM_WINDOWS);
+        let mut bitwise_parameters = parameters.clone();
+        bitwise_parameters.tables.clear();
+        for _ in 0..10 {
+            let input: Vec<u8> = (0..10).map(|_| rng.gen()).collect();
+            assert_eq!(
+                PedersenCRH::<JubJub, Window>::evaluate(&parameters, &input).unwrap(),
+                PedersenCRH::<JubJub, Window>::evaluate(&bitwise_parameters, &input).unwrap()
+            );
+        }
+
+        // Tables are rebuilt when the parameters are read back.
+        let parameters_bytes = to_bytes![parameters].unwrap();
+        let read_parameters = PedersenParameters::<JubJub>::read(&parameters_bytes[..]).unwrap();
+        assert_eq!(read_parameters.tables, parameters.tables);
+    }
+
+    #[test]
+    fn bowe_hopwood_crh_test() {
+        #[derive(Clone)]
+        pub(super) struct Window;
+
+        impl PedersenWindow for Window {
+            const WINDOW_SIZE: usize = 10;
+            const NUM_WINDOWS: usize = 4;
+        }
+
+        #[derive(Clone)]
+        pub(super) struct LargeWindow;
+
+        impl PedersenWindow for LargeWindow {
+            const WINDOW_SIZE: usize = 63;
+            const NUM_WINDOWS: usize = 1;
+        }
+
+        type TestCRH = BoweHopwoodPedersenCRH<JubJub, Window>;
+
+        let rng = &mut thread_rng();
+        assert_eq!(
+            BoweHopwoodPedersenCRH::<JubJub, LargeWindow>::max_window_size(),
+            62
+        );
+        assert!(BoweHopwoodPedersenCRH::<JubJub, LargeWindow>::setup(rng).is_err());
+
+        // Compare against one scalar multiplication of the first generator of
+        // each segment.
+        let parameters = TestCRH::setup(rng).unwrap();
+        let input: Vec<u8> = (0..10).map(|_| rng.gen()).collect();
+        let mut bits = bytes_to_bits(&input);
+        bits.resize(TestCRH::INPUT_SIZE_BITS, false);
+        let sixteen = Fr::from_repr(16u64.into());
+        let mut expected = JubJub::zero();
+        for (segment, generators) in bits
+            .chunks(3 * Window::WINDOW_SIZE)
+            .zip(&parameters.generators)
+        {
+            let mut scalar = Fr::zero();
+            let mut power = Fr::one();
+            for chunk in segment.chunks(3) {
+                let mut digit = Fr::from_repr((1 + chunk[0] as u64 + 2 * chunk[1] as u64).into());
+                if chunk[2] {
+                    digit = -digit;
+                }
+                scalar += &(digit * &power);
+                power *= &sixteen;
+            }
+            expected += &generators[0].mul(&scalar);
+        }
+        assert_eq!(TestCRH::evaluate(&parameters, &input).unwrap(), expected);
+    }
+
     #[test]
     fn pedersen_crh_benchmark() {
         use std::time::Instant;
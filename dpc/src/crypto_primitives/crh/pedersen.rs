@@ -3,20 +3,57 @@ use rand::Rng;
 use rayon::prelude::*;
 use std::{
     fmt::{Debug, Formatter, Result as FmtResult},
+    io::{Read, Result as IoResult, Write},
     marker::PhantomData,
 };
 
 use super::FixedLengthCRH;
-use algebra::groups::Group;
+use algebra::{
+    bytes::{FromBytes, ToBytes},
+    groups::Group,
+    msm::FixedBaseMSM,
+};
 
 pub trait PedersenWindow: Clone {
     const WINDOW_SIZE: usize;
     const NUM_WINDOWS: usize;
 }
 
+/// The number of input bits that `evaluate` handles with a single lookup in
+/// the precomputed tables.
+pub const TABLE_WINDOW_SIZE: usize = 4;
+
 #[derive(Clone, Default)]
 pub struct PedersenParameters<G: Group> {
     pub generators: Vec<Vec<G>>,
+    // For each generator, the multiples of its powers for each
+    // `TABLE_WINDOW_SIZE`-bit chunk of a window, as computed by
+    // `FixedBaseMSM::get_window_table`. Empty if not precomputed.
+    pub tables:     Vec<Vec<Vec<G>>>,
+}
+
+impl<G: Group> PedersenParameters<G> {
+    /// Returns parameters with the given generator powers, and the tables that
+    /// make `PedersenCRH::evaluate` add a table entry for every
+    /// `TABLE_WINDOW_SIZE` bits of input instead of a power for every bit.
+    pub fn new(generators: Vec<Vec<G>>) -> Self {
+        let tables = window_tables(&generators);
+        Self { generators, tables }
+    }
+}
+
+/// Returns the `tables` of `PedersenParameters` for `generators`.
+pub(crate) fn window_tables<G: Group>(generators: &[Vec<G>]) -> Vec<Vec<Vec<G>>> {
+    let table_time = timer_start!(|| "PedersenCRH::PrecomputeTables");
+    let tables = generators
+        .par_iter()
+        .map(|powers| match powers.first() {
+            Some(base) => FixedBaseMSM::get_window_table(powers.len(), TABLE_WINDOW_SIZE, *base),
+            None => Vec::new(),
+        })
+        .collect();
+    timer_end!(table_time);
+    tables
 }
 
 pub struct PedersenCRH<G: Group, W: PedersenWindow> {
@@ -58,7 +95,7 @@ impl<G: Group, W: PedersenWindow> FixedLengthCRH for PedersenCRH<G, W> {
         ));
         let generators = Self::create_generators(rng);
         timer_end!(time);
-        Ok(Self::Parameters { generators })
+        Ok(Self::Parameters::new(generators))
     }
 
     fn evaluate(parameters: &Self::Parameters, input: &[u8]) -> Result<Self::Output, Error> {
@@ -95,10 +132,45 @@ impl<G: Group, W: PedersenWindow> FixedLengthCRH for PedersenCRH<G, W> {
             W::NUM_WINDOWS
         );
 
-        // Compute sum of h_i^{m_i} for all i.
-        let result = bytes_to_bits(input)
-            .par_chunks(W::WINDOW_SIZE)
-            .zip(&parameters.generators)
+        let result = sum_of_powers(
+            &parameters.generators,
+            &parameters.tables,
+            W::WINDOW_SIZE,
+            &bytes_to_bits(input),
+        );
+        timer_end!(eval_time);
+
+        Ok(result)
+    }
+}
+
+/// Computes the sum of `h_i^{m_i}` over the `window_size`-bit windows `m_i` of
+/// `bits`, where `h_i` are the powers in `generators[i]`. Uses `tables` if
+/// they have been precomputed for `generators`.
+pub(crate) fn sum_of_powers<G: Group>(
+    generators: &[Vec<G>],
+    tables: &[Vec<Vec<G>>],
+    window_size: usize,
+    bits: &[bool],
+) -> G {
+    if tables.len() == generators.len() {
+        bits.par_chunks(window_size)
+            .zip(tables)
+            .map(|(bits, table)| {
+                let mut encoded = G::zero();
+                for (chunk, multiples) in bits.chunks(TABLE_WINDOW_SIZE).zip(table) {
+                    let index = chunk
+                        .iter()
+                        .rev()
+                        .fold(0, |index, bit| (index << 1) | (*bit as usize));
+                    encoded += &multiples[index];
+                }
+                encoded
+            })
+            .reduce(|| G::zero(), |a, b| a + &b)
+    } else {
+        bits.par_chunks(window_size)
+            .zip(generators)
             .map(|(bits, generator_powers)| {
                 let mut encoded = G::zero();
                 for (bit, base) in bits.iter().zip(generator_powers.iter()) {
@@ -108,10 +180,7 @@ impl<G: Group, W: PedersenWindow> FixedLengthCRH for PedersenCRH<G, W> {
                 }
                 encoded
             })
-            .reduce(|| G::zero(), |a, b| a + &b);
-        timer_end!(eval_time);
-
-        Ok(result)
+            .reduce(|| G::zero(), |a, b| a + &b)
     }
 }
 
@@ -126,6 +195,45 @@ pub fn bytes_to_bits(bytes: &[u8]) -> Vec<bool> {
     bits
 }
 
+impl<G: Group> ToBytes for PedersenParameters<G> {
+    fn write<W: Write>(&self, mut writer: W) -> IoResult<()> {
+        (self.generators.len() as u64).write(&mut writer)?;
+        for powers in &self.generators {
+            write_powers(powers, &mut writer)?;
+        }
+        Ok(())
+    }
+}
+
+impl<G: Group> FromBytes for PedersenParameters<G> {
+    fn read<R: Read>(mut reader: R) -> IoResult<Self> {
+        let num_generators = u64::read(&mut reader)?;
+        let mut generators = Vec::new();
+        for _ in 0..num_generators {
+            generators.push(read_powers(&mut reader)?);
+        }
+        Ok(Self::new(generators))
+    }
+}
+
+/// Writes the powers of a generator, prefixed with their number.
+pub(crate) fn write_powers<G: Group, W: Write>(powers: &[G], mut writer: W) -> IoResult<()> {
+    (powers.len() as u64).write(&mut writer)?;
+    for power in powers {
+        power.write(&mut writer)?;
+    }
+    Ok(())
+}
+
+pub(crate) fn read_powers<G: Group, R: Read>(mut reader: R) -> IoResult<Vec<G>> {
+    let num_powers = u64::read(&mut reader)?;
+    let mut powers = Vec::new();
+    for _ in 0..num_powers {
+        powers.push(G::read(&mut reader)?);
+    }
+    Ok(powers)
+}
+
 impl<G: Group> Debug for PedersenParameters<G> {
     fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
         write!(f, "Pedersen Hash Parameters {{\n")?;
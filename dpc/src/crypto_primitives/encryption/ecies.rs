@@ -0,0 +1,345 @@
+use crate::crypto_primitives::{CryptoError, EncryptionScheme};
+use algebra::{
+    bytes::{FromBytes, ToBytes},
+    fields::{FpParameters, PrimeField},
+    groups::Group,
+    to_bytes, BitIterator,
+};
+use digest::Digest;
+use failure::Error;
+use rand::{Rand, Rng};
+use std::{
+    io::{Error as IoError, ErrorKind, Read, Result as IoResult, Write},
+    marker::PhantomData,
+};
+
+/// Elliptic-curve integrated encryption: an ephemeral Diffie-Hellman key
+/// agreement over `G`, followed by a `D`-based keystream and MAC keyed with the
+/// shared point.
+pub struct ECIES<G: Group, D: Digest> {
+    _group: PhantomData<G>,
+    _hash:  PhantomData<D>,
+}
+
+#[derive(Derivative)]
+#[derivative(Clone(bound = "G: Group, D: Digest"))]
+pub struct ECIESParameters<G: Group, D: Digest> {
+    _hash:         PhantomData<D>,
+    pub generator: G,
+    pub salt:      [u8; 32],
+}
+
+impl<G: Group, D: Digest> ToBytes for ECIESParameters<G, D> {
+    #[inline]
+    fn write<W: Write>(&self, mut writer: W) -> IoResult<()> {
+        self.generator.write(&mut writer)?;
+        self.salt.write(&mut writer)
+    }
+}
+
+impl<G: Group, D: Digest> FromBytes for ECIESParameters<G, D> {
+    #[inline]
+    fn read<R: Read>(mut reader: R) -> IoResult<Self> {
+        let generator = G::read(&mut reader)?;
+        let salt = <[u8; 32]>::read(&mut reader)?;
+        Ok(Self {
+            _hash: PhantomData,
+            generator,
+            salt,
+        })
+    }
+}
+
+#[derive(Derivative)]
+#[derivative(
+    Clone(bound = "G: Group"),
+    Default(bound = "G: Group"),
+    Debug(bound = "G: Group")
+)]
+pub struct ECIESSecretKey<G: Group>(pub G::ScalarField);
+
+impl<G: Group> ToBytes for ECIESSecretKey<G> {
+    #[inline]
+    fn write<W: Write>(&self, writer: W) -> IoResult<()> {
+        self.0.write(writer)
+    }
+}
+
+impl<G: Group> FromBytes for ECIESSecretKey<G> {
+    #[inline]
+    fn read<R: Read>(reader: R) -> IoResult<Self> {
+        Ok(ECIESSecretKey(G::ScalarField::read(reader)?))
+    }
+}
+
+#[derive(Derivative)]
+#[derivative(
+    Clone(bound = "G: Group"),
+    Default(bound = "G: Group"),
+    Debug(bound = "G: Group"),
+    PartialEq(bound = "G: Group"),
+    Eq(bound = "G: Group")
+)]
+pub struct ECIESCiphertext<G: Group> {
+    pub ephemeral_key: G,
+    pub body:          Vec<u8>,
+    pub tag:           Vec<u8>,
+}
+
+impl<G: Group> ToBytes for ECIESCiphertext<G> {
+    #[inline]
+    fn write<W: Write>(&self, mut writer: W) -> IoResult<()> {
+        self.ephemeral_key.write(&mut writer)?;
+        (self.body.len() as u64).write(&mut writer)?;
+        self.body.write(&mut writer)?;
+        (self.tag.len() as u64).write(&mut writer)?;
+        self.tag.write(&mut writer)
+    }
+}
+
+impl<G: Group> FromBytes for ECIESCiphertext<G> {
+    #[inline]
+    fn read<R: Read>(mut reader: R) -> IoResult<Self> {
+        let ephemeral_key = G::read(&mut reader)?;
+        if !is_in_prime_order_subgroup(&ephemeral_key) {
+            return Err(IoError::new(
+                ErrorKind::InvalidData,
+                "ephemeral key is not in the prime-order subgroup",
+            ));
+        }
+        let body = read_bytes(&mut reader)?;
+        let tag = read_bytes(&mut reader)?;
+        Ok(ECIESCiphertext {
+            ephemeral_key,
+            body,
+            tag,
+        })
+    }
+}
+
+/// Reads a byte string prefixed with its length. The length comes from
+/// untrusted input, so the buffer only grows as bytes are actually read.
+fn read_bytes<R: Read>(mut reader: R) -> IoResult<Vec<u8>> {
+    let len = u64::read(&mut reader)?;
+    let mut bytes = Vec::new();
+    reader.take(len).read_to_end(&mut bytes)?;
+    if bytes.len() as u64 != len {
+        return Err(IoError::new(
+            ErrorKind::UnexpectedEof,
+            "ciphertext is truncated",
+        ));
+    }
+    Ok(bytes)
+}
+
+/// Returns whether `point` is a nonzero element of the subgroup of order `r`,
+/// the modulus of the scalar field of `G`, by checking that `r * point` is zero.
+fn is_in_prime_order_subgroup<G: Group>(point: &G) -> bool {
+    let mut result = G::zero();
+    for bit in BitIterator::new(<G::ScalarField as PrimeField>::Params::MODULUS) {
+        result.double_in_place();
+        if bit {
+            result += point;
+        }
+    }
+    !point.is_zero() && result.is_zero()
+}
+
+impl<G: Group, D: Digest> ECIES<G, D> {
+    const KEYSTREAM_DOMAIN: &'static [u8] = b"ECIES keystream";
+    const MAC_DOMAIN: &'static [u8] = b"ECIES mac";
+
+    /// XORs `message` with the keystream derived from the shared point.
+    fn apply_keystream(
+        parameters: &ECIESParameters<G, D>,
+        shared_key: &[u8],
+        message: &[u8],
+    ) -> Result<Vec<u8>, Error> {
+        let mut output = Vec::with_capacity(message.len());
+        let mut counter = 0u64;
+        while output.len() < message.len() {
+            let block = D::digest(&to_bytes![
+                parameters.salt,
+                Self::KEYSTREAM_DOMAIN,
+                shared_key,
+                counter
+            ]?);
+            let offset = output.len();
+            output.extend(
+                message[offset..]
+                    .iter()
+                    .zip(block.iter())
+                    .map(|(m, k)| m ^ k),
+            );
+            counter += 1;
+        }
+        Ok(output)
+    }
+
+    fn mac(
+        parameters: &ECIESParameters<G, D>,
+        shared_key: &[u8],
+        body: &[u8],
+    ) -> Result<Vec<u8>, Error> {
+        let tag = D::digest(&to_bytes![
+            parameters.salt,
+            Self::MAC_DOMAIN,
+            shared_key,
+            body
+        ]?);
+        Ok(tag.to_vec())
+    }
+}
+
+impl<G: Group, D: Digest + Send + Sync> EncryptionScheme for ECIES<G, D> {
+    type Parameters = ECIESParameters<G, D>;
+    type PublicKey = G;
+    type SecretKey = ECIESSecretKey<G>;
+    type Ciphertext = ECIESCiphertext<G>;
+
+    fn setup<R: Rng>(rng: &mut R) -> Result<Self::Parameters, Error> {
+        let setup_time = timer_start!(|| "ECIES::Setup");
+
+        let mut salt = [0u8; 32];
+        rng.fill_bytes(&mut salt);
+        let generator = G::rand(rng);
+
+        timer_end!(setup_time);
+        Ok(ECIESParameters {
+            _hash: PhantomData,
+            generator,
+            salt,
+        })
+    }
+
+    fn keygen<R: Rng>(
+        parameters: &Self::Parameters,
+        rng: &mut R,
+    ) -> Result<(Self::PublicKey, Self::SecretKey), Error> {
+        let keygen_time = timer_start!(|| "ECIES::KeyGen");
+
+        let secret_key = G::ScalarField::rand(rng);
+        let public_key = parameters.generator.mul(&secret_key);
+
+        timer_end!(keygen_time);
+        Ok((public_key, ECIESSecretKey(secret_key)))
+    }
+
+    fn encrypt<R: Rng>(
+        parameters: &Self::Parameters,
+        pk: &Self::PublicKey,
+        message: &[u8],
+        rng: &mut R,
+    ) -> Result<Self::Ciphertext, Error> {
+        let encrypt_time = timer_start!(|| "ECIES::Encrypt");
+        // Encrypting to the identity would make the shared key public, and to
+        // a point of small order would leave only a few possible shared keys.
+        if pk.is_zero() {
+            return Err(CryptoError::InvalidPublicKey.into());
+        }
+        if !is_in_prime_order_subgroup(pk) {
+            return Err(CryptoError::NotPrimeOrder.into());
+        }
+
+        let ephemeral_secret = G::ScalarField::rand(rng);
+        let ephemeral_key = parameters.generator.mul(&ephemeral_secret);
+        let shared_key = to_bytes![pk.mul(&ephemeral_secret), ephemeral_key]?;
+
+        let body = Self::apply_keystream(parameters, &shared_key, message)?;
+        let tag = Self::mac(parameters, &shared_key, &body)?;
+
+        timer_end!(encrypt_time);
+        Ok(ECIESCiphertext {
+            ephemeral_key,
+            body,
+            tag,
+        })
+    }
+
+    fn decrypt(
+        parameters: &Self::Parameters,
+        sk: &Self::SecretKey,
+        ciphertext: &Self::Ciphertext,
+    ) -> Result<Option<Vec<u8>>, Error> {
+        let decrypt_time = timer_start!(|| "ECIES::Decrypt");
+
+        // An ephemeral key outside the prime-order subgroup would let the
+        // sender learn the secret key modulo the cofactor from which tags we
+        // accept, so such ciphertexts are treated as tampered with.
+        if !is_in_prime_order_subgroup(&ciphertext.ephemeral_key) {
+            timer_end!(decrypt_time);
+            return Ok(None);
+        }
+
+        let shared_key = to_bytes![
+            ciphertext.ephemeral_key.mul(&sk.0),
+            ciphertext.ephemeral_key
+        ]?;
+
+        // Compare the whole tag so that the time taken does not depend on the
+        // position of the first mismatch.
+        let expected_tag = Self::mac(parameters, &shared_key, &ciphertext.body)?;
+        let tag_matches = expected_tag.len() == ciphertext.tag.len()
+            && expected_tag
+                .iter()
+                .zip(&ciphertext.tag)
+                .fold(0u8, |acc, (a, b)| acc | (a ^ b))
+                == 0;
+        if !tag_matches {
+            timer_end!(decrypt_time);
+            return Ok(None);
+        }
+
+        let message = Self::apply_keystream(parameters, &shared_key, &ciphertext.body)?;
+        timer_end!(decrypt_time);
+        Ok(Some(message))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use algebra::{
+        curves::edwards_bls12::EdwardsAffine,
+        fields::{edwards_bls12::fq::Fq, Field},
+    };
+    use blake2::Blake2s;
+    use rand::thread_rng;
+
+    type TestECIES = ECIES<EdwardsAffine, Blake2s>;
+
+    #[test]
+    fn small_order_key_test() {
+        let rng = &mut thread_rng();
+        let message = b"a record for somebody else";
+        let parameters = TestECIES::setup(rng).unwrap();
+        let (pk, sk) = TestECIES::keygen(&parameters, rng).unwrap();
+
+        // (0, -1) has order two on every twisted Edwards curve.
+        let torsion = EdwardsAffine::new(Fq::zero(), -Fq::one());
+        assert!(TestECIES::encrypt(&parameters, &torsion, message, rng).is_err());
+        assert!(TestECIES::encrypt(&parameters, &(pk + &torsion), message, rng).is_err());
+
+        // A sender who guesses the secret key modulo two can produce a valid
+        // tag under an ephemeral key of order two.
+        let shared_key = to_bytes![torsion.mul(&sk.0), torsion].unwrap();
+        let body = TestECIES::apply_keystream(&parameters, &shared_key, message).unwrap();
+        let tag = TestECIES::mac(&parameters, &shared_key, &body).unwrap();
+        let ciphertext = ECIESCiphertext {
+            ephemeral_key: torsion,
+            body,
+            tag,
+        };
+        assert!(TestECIES::decrypt(&parameters, &sk, &ciphertext)
+            .unwrap()
+            .is_none());
+
+        // Neither such keys nor such ciphertexts can be decoded.
+        let torsion_bytes = to_bytes![torsion].unwrap();
+        let error = EdwardsAffine::read(&torsion_bytes[..]).unwrap_err();
+        assert_eq!(error.kind(), ErrorKind::InvalidData);
+        let ciphertext_bytes = to_bytes![ciphertext].unwrap();
+        let error = ECIESCiphertext::<EdwardsAffine>::read(&ciphertext_bytes[..]).unwrap_err();
+        assert_eq!(error.kind(), ErrorKind::InvalidData);
+    }
+}
@@ -0,0 +1,99 @@
+use algebra::bytes::{FromBytes, ToBytes};
+use failure::Error;
+use rand::Rng;
+use std::fmt::Debug;
+
+pub mod ecies;
+
+pub trait EncryptionScheme {
+    type Parameters: ToBytes + FromBytes + Clone;
+    type PublicKey: ToBytes + FromBytes + Clone + Default + Eq + Debug;
+    type SecretKey: ToBytes + FromBytes + Clone + Default + Debug;
+    type Ciphertext: ToBytes + FromBytes + Clone + Default + Eq + Debug;
+
+    fn setup<R: Rng>(rng: &mut R) -> Result<Self::Parameters, Error>;
+
+    fn keygen<R: Rng>(
+        pp: &Self::Parameters,
+        rng: &mut R,
+    ) -> Result<(Self::PublicKey, Self::SecretKey), Error>;
+
+    fn encrypt<R: Rng>(
+        pp: &Self::Parameters,
+        pk: &Self::PublicKey,
+        message: &[u8],
+        rng: &mut R,
+    ) -> Result<Self::Ciphertext, Error>;
+
+    /// Returns `None` if `ciphertext` was not produced under the public key
+    /// matching `sk`, or if it has been tampered with.
+    fn decrypt(
+        pp: &Self::Parameters,
+        sk: &Self::SecretKey,
+        ciphertext: &Self::Ciphertext,
+    ) -> Result<Option<Vec<u8>>, Error>;
+}
+
+#[cfg(test)]
+mod test {
+    use crate::crypto_primitives::{encryption::ecies::ECIES, EncryptionScheme};
+    use algebra::curves::{
+        edwards_bls12::EdwardsProjective as EdwardsBls, edwards_sw6::EdwardsAffine as EdwardsSW,
+    };
+    use blake2::Blake2s;
+    use rand::{thread_rng, Rng};
+
+    fn encrypt_and_decrypt<S: EncryptionScheme>(message: &[u8]) {
+        let rng = &mut thread_rng();
+        let parameters = S::setup(rng).unwrap();
+        let (pk, sk) = S::keygen(&parameters, rng).unwrap();
+        let ciphertext = S::encrypt(&parameters, &pk, message, rng).unwrap();
+        let decrypted = S::decrypt(&parameters, &sk, &ciphertext).unwrap();
+        assert_eq!(decrypted.as_ref().map(Vec::as_slice), Some(message));
+    }
+
+    fn failed_decryption<S: EncryptionScheme>(message: &[u8]) {
+        let rng = &mut thread_rng();
+        let parameters = S::setup(rng).unwrap();
+        let (pk, _) = S::keygen(&parameters, rng).unwrap();
+        let (_, other_sk) = S::keygen(&parameters, rng).unwrap();
+        let ciphertext = S::encrypt(&parameters, &pk, message, rng).unwrap();
+        assert!(S::decrypt(&parameters, &other_sk, &ciphertext)
+            .unwrap()
+            .is_none());
+    }
+
+    #[test]
+    fn ecies_encryption_test() {
+        let rng = &mut thread_rng();
+        let message: Vec<u8> = (0..200).map(|_| rng.gen()).collect();
+        encrypt_and_decrypt::<ECIES<EdwardsBls, Blake2s>>(&message);
+        encrypt_and_decrypt::<ECIES<EdwardsSW, Blake2s>>(&message);
+        encrypt_and_decrypt::<ECIES<EdwardsBls, Blake2s>>(&[]);
+        failed_decryption::<ECIES<EdwardsBls, Blake2s>>(&message);
+        failed_decryption::<ECIES<EdwardsSW, Blake2s>>(&message);
+    }
+
+    #[test]
+    fn ecies_tampered_ciphertext_test() {
+        type TestECIES = ECIES<EdwardsBls, Blake2s>;
+
+        let rng = &mut thread_rng();
+        let message = b"a record for somebody else";
+        let parameters = TestECIES::setup(rng).unwrap();
+        let (pk, sk) = TestECIES::keygen(&parameters, rng).unwrap();
+        let ciphertext = TestECIES::encrypt(&parameters, &pk, message, rng).unwrap();
+
+        let mut tampered = ciphertext.clone();
+        tampered.body[3] ^= 1;
+        assert!(TestECIES::decrypt(&parameters, &sk, &tampered)
+            .unwrap()
+            .is_none());
+
+        let mut tampered = ciphertext;
+        tampered.tag[0] ^= 1;
+        assert!(TestECIES::decrypt(&parameters, &sk, &tampered)
+            .unwrap()
+            .is_none());
+    }
+}
@@ -3,6 +3,9 @@ use algebra::bytes::ToBytes;
 use failure::{format_err, Error};
 use std::{fmt, marker::PhantomData, rc::Rc};
 
+pub mod sparse;
+pub use self::sparse::*;
+
 /// Returns the log2 value of the given number.
 #[inline]
 fn log2(number: usize) -> usize {
@@ -180,6 +183,7 @@ pub struct MerkleHashTree<H: FixedLengthCRH, L: ToBytes + Eq> {
     parameters:   Rc<H::Parameters>,
     _leaf:        PhantomData<L>,
     root:         Option<H::Output>,
+    num_leaves:   usize,
 }
 
 impl<H: FixedLengthCRH, L: ToBytes + Eq + Clone> MerkleHashTree<H, L> {
@@ -192,13 +196,77 @@ impl<H: FixedLengthCRH, L: ToBytes + Eq + Clone> MerkleHashTree<H, L> {
             root: None,
             _leaf: PhantomData,
             parameters,
+            num_leaves: 0,
         }
     }
 
     pub fn new(parameters: Rc<H::Parameters>, leaves: &[L]) -> Result<Self, Error> {
         let new_time = timer_start!(|| "MHT::New");
 
-        let last_level_size = leaves.len().next_power_of_two();
+        let mut buffer = [0u8; 128];
+        let mut leaf_hashes = Vec::with_capacity(leaves.len());
+        for leaf in leaves {
+            leaf_hashes.push(hash_leaf::<H, L>(&parameters, leaf, &mut buffer)?);
+        }
+        let tree = Self::from_leaf_hashes(parameters, &leaf_hashes)?;
+
+        timer_end!(new_time);
+        Ok(tree)
+    }
+
+    /// Appends `leaves` after the existing leaves. Only the paths from the
+    /// new leaves to the root are rehashed, unless the last level is full, in
+    /// which case the tree is rebuilt with room for twice as many leaves. The
+    /// result is the same as calling `new` on all the leaves.
+    pub fn append(&mut self, leaves: &[L]) -> Result<(), Error> {
+        let append_time = timer_start!(|| "MHT::Append");
+
+        let mut buffer = [0u8; 128];
+        let mut leaf_hashes = Vec::with_capacity(leaves.len());
+        for leaf in leaves {
+            leaf_hashes.push(hash_leaf::<H, L>(&self.parameters, leaf, &mut buffer)?);
+        }
+
+        let capacity = (self.tree.len() + 1) / 2;
+        let first_leaf_index = capacity.saturating_sub(1);
+        let num_leaves = self.num_leaves + leaf_hashes.len();
+        if self.root.is_none() || num_leaves > capacity {
+            let mut all_leaf_hashes =
+                self.tree[first_leaf_index..first_leaf_index + self.num_leaves].to_vec();
+            all_leaf_hashes.extend(leaf_hashes);
+            *self = Self::from_leaf_hashes(self.parameters.clone(), &all_leaf_hashes)?;
+        } else {
+            let mut start = first_leaf_index + self.num_leaves;
+            let mut end = first_leaf_index + num_leaves;
+            for (i, leaf_hash) in leaf_hashes.into_iter().enumerate() {
+                self.tree[start + i] = leaf_hash;
+            }
+            // Rehash the ancestors of the new leaves, one level at a time.
+            while start < end && !is_root(start) {
+                start = parent(start).unwrap();
+                end = parent(end - 1).unwrap() + 1;
+                for index in start..end {
+                    self.tree[index] = hash_inner_node::<H>(
+                        &self.parameters,
+                        &self.tree[left_child(index)],
+                        &self.tree[right_child(index)],
+                        &mut buffer,
+                    )?;
+                }
+            }
+            self.num_leaves = num_leaves;
+            self.hash_padding_and_root()?;
+        }
+
+        timer_end!(append_time);
+        Ok(())
+    }
+
+    fn from_leaf_hashes(
+        parameters: Rc<H::Parameters>,
+        leaf_hashes: &[H::Output],
+    ) -> Result<Self, Error> {
+        let last_level_size = leaf_hashes.len().next_power_of_two();
         let tree_size = 2 * last_level_size - 1;
         let tree_height = tree_height(tree_size);
         assert!(tree_height as u8 <= Self::MAX_HEIGHT);
@@ -218,11 +286,10 @@ impl<H: FixedLengthCRH, L: ToBytes + Eq + Clone> MerkleHashTree<H, L> {
             index = left_child(index);
         }
 
-        // Compute and store the hash values for each leaf.
+        // Store the hash values for each leaf.
         let last_level_index = level_indices.pop().unwrap();
-        let mut buffer = [0u8; 128];
-        for (i, leaf) in leaves.iter().enumerate() {
-            tree[last_level_index + i] = hash_leaf::<H, L>(&parameters, leaf, &mut buffer)?;
+        for (i, leaf_hash) in leaf_hashes.iter().enumerate() {
+            tree[last_level_index + i] = leaf_hash.clone();
         }
 
         // Compute the hash values for every node in the tree.
@@ -245,28 +312,38 @@ impl<H: FixedLengthCRH, L: ToBytes + Eq + Clone> MerkleHashTree<H, L> {
             }
             upper_bound = start_index;
         }
-        // Finished computing actual tree.
-        // Now, we compute the dummy nodes until we hit our MAX_HEIGHT goal.
-        let mut cur_height = tree_height;
-        let mut padding_tree = Vec::new();
-        let mut cur_hash = tree[0].clone();
-        while cur_height < (Self::MAX_HEIGHT - 1) as usize {
-            cur_hash = hash_inner_node::<H>(&parameters, &cur_hash, &empty_hash, &mut buffer)?;
-            padding_tree.push((cur_hash.clone(), empty_hash.clone()));
-            cur_height += 1;
-        }
-
-        let root_hash = hash_inner_node::<H>(&parameters, &cur_hash, &empty_hash, &mut buffer)?;
 
-        timer_end!(new_time);
-
-        Ok(MerkleHashTree {
+        let mut merkle_tree = MerkleHashTree {
             tree,
-            padding_tree,
+            padding_tree: Vec::new(),
             parameters,
             _leaf: PhantomData,
-            root: Some(root_hash),
-        })
+            root: None,
+            num_leaves: leaf_hashes.len(),
+        };
+        merkle_tree.hash_padding_and_root()?;
+        Ok(merkle_tree)
+    }
+
+    /// Computes the dummy nodes above the actual tree until we hit our
+    /// MAX_HEIGHT goal, and the root.
+    fn hash_padding_and_root(&mut self) -> Result<(), Error> {
+        let empty_hash = hash_empty::<H>(&self.parameters)?;
+        let mut buffer = [0u8; 128];
+        let mut cur_height = tree_height(self.tree.len());
+        let mut cur_hash = self.tree[0].clone();
+        self.padding_tree.clear();
+        while cur_height < (Self::MAX_HEIGHT - 1) as usize {
+            cur_hash = hash_inner_node::<H>(&self.parameters, &cur_hash, &empty_hash, &mut buffer)?;
+            self.padding_tree
+                .push((cur_hash.clone(), empty_hash.clone()));
+            cur_height += 1;
+        }
+
+        let root_hash =
+            hash_inner_node::<H>(&self.parameters, &cur_hash, &empty_hash, &mut buffer)?;
+        self.root = Some(root_hash);
+        Ok(())
     }
 
     #[inline]
@@ -379,6 +456,32 @@ mod test {
         generate_merkle_tree(&leaves);
     }
 
+    #[test]
+    fn mht_append_test() {
+        let seed: [u32; 8] = [
+            2053759276, 152413135, 1690980041, 4293109333, 2390175708, 686052238, 1844363894,
+            1379683288,
+        ];
+        let mut rng = ChaChaRng::from_seed(&seed);
+        let crh_parameters = Rc::new(H::setup(&mut rng).unwrap());
+
+        let leaves = (0..13u8).map(|i| [i; 8]).collect::<Vec<_>>();
+        let mut tree = JubJubMHT::blank(crh_parameters.clone());
+        let mut len = 0;
+        // Appending in batches of different sizes, including empty ones,
+        // must give the same tree as building it from scratch.
+        for batch_size in &[0, 1, 1, 2, 0, 3, 6] {
+            tree.append(&leaves[len..len + batch_size]).unwrap();
+            len += batch_size;
+            let expected = JubJubMHT::new(crh_parameters.clone(), &leaves[..len]).unwrap();
+            assert_eq!(tree.root(), expected.root());
+            for (i, leaf) in leaves[..len].iter().enumerate() {
+                let proof = tree.generate_proof(i, leaf).unwrap();
+                assert!(proof.verify(&crh_parameters, &tree.root(), leaf).unwrap());
+            }
+        }
+    }
+
     fn bad_merkle_tree_verify<L: ToBytes + Clone + Eq>(leaves: &[L]) -> () {
         use algebra::groups::Group;
         let seed: [u32; 8] = [
@@ -0,0 +1,304 @@
+use crate::{config::SPARSE_MERKLE_TREE_DEPTH, crypto_primitives::crh::FixedLengthCRH};
+use algebra::{bytes::ToBytes, to_bytes};
+use failure::{format_err, Error};
+use std::{collections::HashMap, fmt, marker::PhantomData, rc::Rc};
+
+use super::{hash_empty, hash_inner_node, hash_leaf};
+
+/// Returns the position of a leaf with hash `leaf_hash`, which is given by the
+/// first `SPARSE_MERKLE_TREE_DEPTH` bits of `leaf_hash`.
+fn leaf_position<H: FixedLengthCRH>(leaf_hash: &H::Output) -> Result<u64, Error> {
+    let bytes = to_bytes![leaf_hash]?;
+    if bytes.len() * 8 < SPARSE_MERKLE_TREE_DEPTH {
+        return Err(format_err!("leaf hash is too short for the tree depth."));
+    }
+    let mut position = 0u64;
+    for i in 0..SPARSE_MERKLE_TREE_DEPTH {
+        let bit = (bytes[i / 8] >> (i % 8)) & 1;
+        position |= u64::from(bit) << i;
+    }
+    Ok(position)
+}
+
+/// Stores the siblings of the nodes on the path from a leaf position to the
+/// root, starting at the leaf level. The same proof shows that a leaf is in
+/// the tree, or that its position is empty.
+#[derive(Derivative)]
+#[derivative(
+    Clone(bound = "H: FixedLengthCRH, L: ToBytes"),
+    Debug(bound = "H: FixedLengthCRH, L: ToBytes, H::Output: fmt::Debug")
+)]
+pub struct SparseMerkleProof<H: FixedLengthCRH, L: ToBytes> {
+    pub(crate) siblings: Vec<H::Output>,
+    _leaf:               PhantomData<L>,
+}
+
+impl<H: FixedLengthCRH, L: ToBytes> Default for SparseMerkleProof<H, L> {
+    fn default() -> Self {
+        Self {
+            siblings: vec![H::Output::default(); SPARSE_MERKLE_TREE_DEPTH],
+            _leaf:    PhantomData,
+        }
+    }
+}
+
+impl<H: FixedLengthCRH, L: ToBytes> SparseMerkleProof<H, L> {
+    /// Checks that `leaf` is in the tree with root `root_hash`.
+    pub fn verify_membership(
+        &self,
+        parameters: &H::Parameters,
+        root_hash: &H::Output,
+        leaf: &L,
+    ) -> Result<bool, Error> {
+        let mut buffer = [0u8; 128];
+        let leaf_hash = hash_leaf::<H, L>(parameters, leaf, &mut buffer)?;
+        let position = leaf_position::<H>(&leaf_hash)?;
+        Ok(&self.root(parameters, position, leaf_hash)? == root_hash)
+    }
+
+    /// Checks that `leaf` is not in the tree with root `root_hash`.
+    pub fn verify_non_membership(
+        &self,
+        parameters: &H::Parameters,
+        root_hash: &H::Output,
+        leaf: &L,
+    ) -> Result<bool, Error> {
+        let mut buffer = [0u8; 128];
+        let leaf_hash = hash_leaf::<H, L>(parameters, leaf, &mut buffer)?;
+        let position = leaf_position::<H>(&leaf_hash)?;
+        let empty_hash = hash_empty::<H>(parameters)?;
+        Ok(&self.root(parameters, position, empty_hash)? == root_hash)
+    }
+
+    /// Returns the root of the tree in which the leaf at `position` has hash
+    /// `leaf_hash` and the path has siblings `self.siblings`.
+    fn root(
+        &self,
+        parameters: &H::Parameters,
+        position: u64,
+        leaf_hash: H::Output,
+    ) -> Result<H::Output, Error> {
+        if self.siblings.len() != SPARSE_MERKLE_TREE_DEPTH {
+            return Err(format_err!("incorrect path length."));
+        }
+        let mut buffer = [0u8; 128];
+        let mut current = leaf_hash;
+        for (height, sibling) in self.siblings.iter().enumerate() {
+            current = if (position >> height) & 1 == 0 {
+                hash_inner_node::<H>(parameters, &current, sibling, &mut buffer)?
+            } else {
+                hash_inner_node::<H>(parameters, sibling, &current, &mut buffer)?
+            };
+        }
+        Ok(current)
+    }
+}
+
+/// A Merkle tree of depth `SPARSE_MERKLE_TREE_DEPTH` in which each leaf is
+/// stored at the position given by its hash, so that the absence of a leaf
+/// can be proven by showing that its position is empty. Only the nodes above
+/// non-empty leaves are stored.
+///
+/// Two leaves whose hashes share the same position cannot both be inserted.
+pub struct SparseMerkleTree<H: FixedLengthCRH, L: ToBytes> {
+    parameters:   Rc<H::Parameters>,
+    // The non-empty nodes, keyed by their height above the leaves and their
+    // index within that level.
+    nodes:        HashMap<(usize, u64), H::Output>,
+    // The hash of an empty subtree of each height.
+    empty_hashes: Vec<H::Output>,
+    _leaf:        PhantomData<L>,
+}
+
+impl<H: FixedLengthCRH, L: ToBytes> SparseMerkleTree<H, L> {
+    pub const DEPTH: usize = SPARSE_MERKLE_TREE_DEPTH;
+
+    /// Returns an empty tree.
+    pub fn new(parameters: Rc<H::Parameters>) -> Result<Self, Error> {
+        assert!(Self::DEPTH <= 64);
+        let mut buffer = [0u8; 128];
+        let mut empty_hashes = Vec::with_capacity(Self::DEPTH + 1);
+        empty_hashes.push(hash_empty::<H>(&parameters)?);
+        for height in 0..Self::DEPTH {
+            let empty_hash = &empty_hashes[height];
+            let parent = hash_inner_node::<H>(&parameters, empty_hash, empty_hash, &mut buffer)?;
+            empty_hashes.push(parent);
+        }
+
+        Ok(Self {
+            parameters,
+            nodes: HashMap::new(),
+            empty_hashes,
+            _leaf: PhantomData,
+        })
+    }
+
+    #[inline]
+    pub fn root(&self) -> H::Output {
+        self.node(Self::DEPTH, 0)
+    }
+
+    /// Returns the position of `leaf` in the tree.
+    pub fn position(&self, leaf: &L) -> Result<u64, Error> {
+        let mut buffer = [0u8; 128];
+        leaf_position::<H>(&hash_leaf::<H, L>(&self.parameters, leaf, &mut buffer)?)
+    }
+
+    /// Returns true iff a leaf is stored at `position`.
+    pub fn is_occupied(&self, position: u64) -> bool {
+        self.nodes.contains_key(&(0, position))
+    }
+
+    /// Returns true iff `leaf` is in the tree.
+    pub fn contains(&self, leaf: &L) -> Result<bool, Error> {
+        let mut buffer = [0u8; 128];
+        let leaf_hash = hash_leaf::<H, L>(&self.parameters, leaf, &mut buffer)?;
+        let position = leaf_position::<H>(&leaf_hash)?;
+        Ok(self.nodes.get(&(0, position)) == Some(&leaf_hash))
+    }
+
+    /// Inserts `leaf`, failing if its position is already occupied.
+    pub fn insert(&mut self, leaf: &L) -> Result<(), Error> {
+        let mut buffer = [0u8; 128];
+        let leaf_hash = hash_leaf::<H, L>(&self.parameters, leaf, &mut buffer)?;
+        let position = leaf_position::<H>(&leaf_hash)?;
+        if self.is_occupied(position) {
+            return Err(format_err!("leaf position is already occupied."));
+        }
+        if leaf_hash == self.empty_hashes[0] {
+            return Err(format_err!("leaf hashes to the empty leaf."));
+        }
+        self.update(position, leaf_hash)
+    }
+
+    /// Removes `leaf`, failing if it is not in the tree.
+    pub fn remove(&mut self, leaf: &L) -> Result<(), Error> {
+        if !self.contains(leaf)? {
+            return Err(format_err!("leaf is not in the tree."));
+        }
+        let position = self.position(leaf)?;
+        let empty_hash = self.empty_hashes[0].clone();
+        self.update(position, empty_hash)
+    }
+
+    /// Returns a proof of membership of `leaf` if it is in the tree, and a
+    /// proof of non-membership otherwise.
+    pub fn generate_proof(&self, leaf: &L) -> Result<SparseMerkleProof<H, L>, Error> {
+        let prove_time = timer_start!(|| "SparseMHT::GenProof");
+        let position = self.position(leaf)?;
+        let siblings = (0..Self::DEPTH)
+            .map(|height| self.node(height, (position >> height) ^ 1))
+            .collect();
+        timer_end!(prove_time);
+        Ok(SparseMerkleProof {
+            siblings,
+            _leaf: PhantomData,
+        })
+    }
+
+    fn node(&self, height: usize, index: u64) -> H::Output {
+        self.nodes
+            .get(&(height, index))
+            .unwrap_or(&self.empty_hashes[height])
+            .clone()
+    }
+
+    fn set_node(&mut self, height: usize, index: u64, hash: H::Output) {
+        if hash == self.empty_hashes[height] {
+            self.nodes.remove(&(height, index));
+        } else {
+            self.nodes.insert((height, index), hash);
+        }
+    }
+
+    /// Sets the leaf at `position` to `leaf_hash` and recomputes the nodes on
+    /// its path to the root.
+    fn update(&mut self, position: u64, leaf_hash: H::Output) -> Result<(), Error> {
+        let mut buffer = [0u8; 128];
+        let mut current = leaf_hash;
+        for height in 0..Self::DEPTH {
+            let index = position >> height;
+            self.set_node(height, index, current.clone());
+            let sibling = self.node(height, index ^ 1);
+            current = if index & 1 == 0 {
+                hash_inner_node::<H>(&self.parameters, &current, &sibling, &mut buffer)?
+            } else {
+                hash_inner_node::<H>(&self.parameters, &sibling, &current, &mut buffer)?
+            };
+        }
+        self.set_node(Self::DEPTH, 0, current);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::crypto_primitives::{
+        crh::{pedersen::*, *},
+        mht::*,
+    };
+    use algebra::curves::jubjub::JubJubAffine as JubJub;
+    use rand::{ChaChaRng, SeedableRng};
+    use std::rc::Rc;
+
+    #[derive(Clone)]
+    pub(super) struct Window4x256;
+    impl PedersenWindow for Window4x256 {
+        const WINDOW_SIZE: usize = 4;
+        const NUM_WINDOWS: usize = 256;
+    }
+
+    type H = PedersenCRH<JubJub, Window4x256>;
+    type JubJubSparseMHT = SparseMerkleTree<H, [u8; 32]>;
+
+    #[test]
+    fn sparse_merkle_tree_test() {
+        let seed: [u32; 8] = [
+            2053759276, 152413135, 1690980041, 4293109333, 2390175708, 686052238, 1844363894,
+            1379683288,
+        ];
+        let mut rng = ChaChaRng::from_seed(&seed);
+        let crh_parameters = Rc::new(H::setup(&mut rng).unwrap());
+
+        let mut tree = JubJubSparseMHT::new(crh_parameters.clone()).unwrap();
+        let empty_root = tree.root();
+        let leaves = (1..5u8).map(|i| [i; 32]).collect::<Vec<_>>();
+        for leaf in &leaves {
+            tree.insert(leaf).unwrap();
+        }
+        assert!(tree.insert(&leaves[0]).is_err());
+
+        let root = tree.root();
+        for leaf in &leaves {
+            assert!(tree.contains(leaf).unwrap());
+            let proof = tree.generate_proof(leaf).unwrap();
+            assert!(proof
+                .verify_membership(&crh_parameters, &root, leaf)
+                .unwrap());
+            assert!(!proof
+                .verify_non_membership(&crh_parameters, &root, leaf)
+                .unwrap());
+            assert!(!proof
+                .verify_membership(&crh_parameters, &empty_root, leaf)
+                .unwrap());
+        }
+
+        let absent = [42u8; 32];
+        assert!(!tree.contains(&absent).unwrap());
+        let proof = tree.generate_proof(&absent).unwrap();
+        assert!(proof
+            .verify_non_membership(&crh_parameters, &root, &absent)
+            .unwrap());
+        assert!(!proof
+            .verify_membership(&crh_parameters, &root, &absent)
+            .unwrap());
+
+        // Removing every leaf gives back the empty tree.
+        for leaf in &leaves {
+            tree.remove(leaf).unwrap();
+        }
+        assert!(tree.remove(&leaves[0]).is_err());
+        assert!(tree.root() == empty_root);
+        assert!(tree.nodes.is_empty());
+    }
+}
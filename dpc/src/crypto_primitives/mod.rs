@@ -1,5 +1,6 @@
 pub mod commitment;
 pub mod crh;
+pub mod encryption;
 pub mod mht;
 pub mod nizk;
 pub mod prf;
@@ -8,7 +9,8 @@ pub mod signature;
 pub use self::{
     commitment::CommitmentScheme,
     crh::FixedLengthCRH,
-    mht::{HashMembershipProof, MerkleHashTree},
+    encryption::EncryptionScheme,
+    mht::{HashMembershipProof, MerkleHashTree, SparseMerkleProof, SparseMerkleTree},
     nizk::NIZK,
     prf::PRF,
     signature::SignatureScheme,
@@ -20,4 +22,8 @@ pub enum CryptoError {
     IncorrectInputLength(usize),
     #[fail(display = "Element is not prime order")]
     NotPrimeOrder,
+    #[fail(display = "Public key is the identity")]
+    InvalidPublicKey,
+    #[fail(display = "signature {} is invalid", _0)]
+    InvalidSignature(usize),
 }
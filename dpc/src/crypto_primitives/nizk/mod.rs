@@ -1,4 +1,4 @@
-use algebra::bytes::ToBytes;
+use algebra::bytes::{FromBytes, ToBytes};
 use rand::Rng;
 
 pub mod gm17;
@@ -10,10 +10,14 @@ pub trait NIZK {
     type Circuit;
     type AssignedCircuit;
     type VerifierInput: ?Sized;
-    type ProvingParameters: Clone;
-    type VerificationParameters: Clone + Default + From<Self::PreparedVerificationParameters>;
+    type ProvingParameters: ToBytes + FromBytes + Clone;
+    type VerificationParameters: ToBytes
+        + FromBytes
+        + Clone
+        + Default
+        + From<Self::PreparedVerificationParameters>;
     type PreparedVerificationParameters: Clone + Default + From<Self::VerificationParameters>;
-    type Proof: ToBytes + Clone + Default;
+    type Proof: ToBytes + FromBytes + Clone + Default;
 
     fn setup<R: Rng>(
         circuit: Self::Circuit,
@@ -8,7 +8,7 @@ pub use self::blake2s::*;
 
 pub trait PRF {
     type Input: FromBytes + Default;
-    type Output: ToBytes + Eq + Clone + Default + Hash;
+    type Output: ToBytes + FromBytes + Eq + Clone + Default + Hash;
     type Seed: FromBytes + ToBytes + Clone + Default + Debug;
 
     fn evaluate(seed: &Self::Seed, input: &Self::Input) -> Result<Self::Output, CryptoError>;
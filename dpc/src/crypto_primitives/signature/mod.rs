@@ -1,15 +1,16 @@
-use algebra::bytes::ToBytes;
+use algebra::bytes::{FromBytes, ToBytes};
 use failure::Error;
 use rand::Rng;
 use std::hash::Hash;
 
+pub mod reddsa;
 pub mod schnorr;
 
 pub trait SignatureScheme {
-    type Parameters: Clone + Send + Sync;
-    type PublicKey: ToBytes + Hash + Eq + Clone + Default + Send + Sync;
-    type SecretKey: ToBytes + Clone + Default;
-    type Signature: Clone + Default + Send + Sync;
+    type Parameters: ToBytes + FromBytes + Clone + Send + Sync;
+    type PublicKey: ToBytes + FromBytes + Hash + Eq + Clone + Default + Send + Sync;
+    type SecretKey: ToBytes + FromBytes + Clone + Default;
+    type Signature: ToBytes + FromBytes + Clone + Default + Send + Sync;
 
     fn setup<R: Rng>(rng: &mut R) -> Result<Self::Parameters, Error>;
 
@@ -47,9 +48,18 @@ pub trait SignatureScheme {
 
 #[cfg(test)]
 mod test {
-    use crate::crypto_primitives::{signature::schnorr::SchnorrSignature, SignatureScheme};
+    use crate::crypto_primitives::{
+        signature::{reddsa::RedDSASignature, schnorr::SchnorrSignature},
+        SignatureScheme,
+    };
     use algebra::{
-        curves::edwards_sw6::EdwardsAffine as Edwards, groups::Group, to_bytes, ToBytes,
+        curves::{
+            edwards_bls12::{EdwardsParameters, EdwardsProjective},
+            edwards_sw6::EdwardsAffine as Edwards,
+            jubjub::JubJubParameters,
+        },
+        groups::Group,
+        to_bytes, ToBytes,
     };
     use blake2::Blake2s;
     use rand::{thread_rng, Rand};
@@ -96,4 +106,105 @@ mod test {
             &random_scalar.as_slice(),
         );
     }
+
+    #[test]
+    fn reddsa_signature_test() {
+        type JubJubSig = RedDSASignature<JubJubParameters, Blake2s>;
+        let message = "Hi, I am a RedDSA signature!";
+        let rng = &mut thread_rng();
+        sign_and_verify::<JubJubSig>(message.as_bytes());
+        sign_and_verify::<RedDSASignature<EdwardsParameters, Blake2s>>(message.as_bytes());
+        failed_verification::<JubJubSig>(message.as_bytes(), "Bad message".as_bytes());
+        let random_scalar = to_bytes!(<Edwards as Group>::ScalarField::rand(rng)).unwrap();
+
+        // Signing is deterministic, the challenge binds the public key, and
+        // signing with the randomized secret key gives a signature under the
+        // randomized public key.
+        let parameters = JubJubSig::setup(rng).unwrap();
+        let (pk, sk) = JubJubSig::keygen(&parameters, rng).unwrap();
+        let sig = JubJubSig::sign(&parameters, &sk, message.as_bytes(), rng).unwrap();
+        let sig_again = JubJubSig::sign(&parameters, &sk, message.as_bytes(), rng).unwrap();
+        assert_eq!(to_bytes![sig].unwrap(), to_bytes![sig_again].unwrap());
+        assert!(JubJubSig::randomize_signature(&parameters, &sig, &random_scalar).is_err());
+        let randomized_pk =
+            JubJubSig::randomize_public_key(&parameters, &pk, &random_scalar).unwrap();
+        let randomized_sk = JubJubSig::randomize_secret_key(&sk, &random_scalar);
+        let sig = JubJubSig::sign(&parameters, &randomized_sk, message.as_bytes(), rng).unwrap();
+        assert!(JubJubSig::verify(&parameters, &randomized_pk, message.as_bytes(), &sig).unwrap());
+    }
+
+    #[test]
+    fn reddsa_batch_verification_test() {
+        type JubJubSig = RedDSASignature<JubJubParameters, Blake2s>;
+        let rng = &mut thread_rng();
+        let parameters = JubJubSig::setup(rng).unwrap();
+        let messages = ["first", "second", "third"];
+        let messages = messages.iter().map(|m| m.as_bytes()).collect::<Vec<_>>();
+        let mut public_keys = Vec::new();
+        let mut signatures = Vec::new();
+        for message in &messages {
+            let (pk, sk) = JubJubSig::keygen(&parameters, rng).unwrap();
+            public_keys.push(pk);
+            signatures.push(JubJubSig::sign(&parameters, &sk, message, rng).unwrap());
+        }
+        assert!(
+            JubJubSig::batch_verify(&parameters, &public_keys, &messages, &signatures, rng)
+                .unwrap()
+        );
+
+        public_keys.swap(0, 1);
+        assert!(
+            !JubJubSig::batch_verify(&parameters, &public_keys, &messages, &signatures, rng)
+                .unwrap()
+        );
+        assert!(JubJubSig::batch_verify(
+            &parameters,
+            &public_keys[1..],
+            &messages,
+            &signatures,
+            rng
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn schnorr_batch_verification_test() {
+        type EdwardsSig = SchnorrSignature<EdwardsProjective, Blake2s>;
+        let rng = &mut thread_rng();
+        let parameters = EdwardsSig::setup(rng).unwrap();
+        let messages = ["first", "second", "third"];
+        let messages = messages.iter().map(|m| m.as_bytes()).collect::<Vec<_>>();
+        let mut public_keys = Vec::new();
+        let mut signatures = Vec::new();
+        for message in &messages {
+            let (pk, sk) = EdwardsSig::keygen(&parameters, rng).unwrap();
+            public_keys.push(pk);
+            signatures.push(EdwardsSig::sign(&parameters, &sk, message, rng).unwrap());
+        }
+        EdwardsSig::batch_verify(&parameters, &public_keys, &messages, &signatures, rng).unwrap();
+
+        public_keys.swap(1, 2);
+        let error =
+            EdwardsSig::batch_verify(&parameters, &public_keys, &messages, &signatures, rng)
+                .unwrap_err()
+                .to_string();
+        assert_eq!(error, "signature 1 is invalid");
+        public_keys.swap(1, 2);
+
+        let offset: <EdwardsProjective as Group>::ScalarField = Rand::rand(rng);
+        signatures[2].prover_response += &offset;
+        let error =
+            EdwardsSig::batch_verify(&parameters, &public_keys, &messages, &signatures, rng)
+                .unwrap_err()
+                .to_string();
+        assert_eq!(error, "signature 2 is invalid");
+        assert!(EdwardsSig::batch_verify(
+            &parameters,
+            &public_keys[1..],
+            &messages,
+            &signatures,
+            rng
+        )
+        .is_err());
+    }
 }
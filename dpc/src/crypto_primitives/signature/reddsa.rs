@@ -0,0 +1,335 @@
+use crate::crypto_primitives::SignatureScheme;
+use algebra::{
+    bytes::{FromBytes, ToBytes},
+    curves::{
+        models::TEModelParameters, twisted_edwards_extended::GroupAffine as TEAffine, AffineCurve,
+        ProjectiveCurve,
+    },
+    fields::{Field, PrimeField},
+    msm::VariableBaseMSM,
+    to_bytes,
+};
+use digest::Digest;
+use failure::{format_err, Error};
+use rand::{Rand, Rng};
+use std::{
+    io::{Read, Result as IoResult, Write},
+    marker::PhantomData,
+};
+
+/// A RedDSA-style Schnorr signature over a twisted Edwards curve.
+///
+/// Nonces are derived deterministically from the secret key and the message,
+/// and verification multiplies by the cofactor, so that a batch of
+/// signatures is accepted exactly when each of them is. As in RedDSA, the
+/// challenge binds the public key, so a signature cannot be randomized after
+/// the fact: to sign under a randomized public key, sign with the secret key
+/// that `randomize_secret_key` returns.
+pub struct RedDSASignature<P: TEModelParameters, D: Digest> {
+    _curve: PhantomData<P>,
+    _hash:  PhantomData<D>,
+}
+
+#[derive(Derivative)]
+#[derivative(Clone(bound = "P: TEModelParameters, D: Digest"))]
+pub struct RedDSAParameters<P: TEModelParameters, D: Digest> {
+    _hash:         PhantomData<D>,
+    pub generator: TEAffine<P>,
+    pub salt:      [u8; 32],
+}
+
+impl<P: TEModelParameters, D: Digest> ToBytes for RedDSAParameters<P, D> {
+    #[inline]
+    fn write<W: Write>(&self, mut writer: W) -> IoResult<()> {
+        self.generator.write(&mut writer)?;
+        self.salt.write(&mut writer)
+    }
+}
+
+impl<P: TEModelParameters, D: Digest> FromBytes for RedDSAParameters<P, D> {
+    #[inline]
+    fn read<R: Read>(mut reader: R) -> IoResult<Self> {
+        let generator = TEAffine::<P>::read(&mut reader)?;
+        let salt = <[u8; 32]>::read(&mut reader)?;
+        Ok(Self {
+            _hash: PhantomData,
+            generator,
+            salt,
+        })
+    }
+}
+
+pub type RedDSAPublicKey<P> = TEAffine<P>;
+
+#[derive(Derivative)]
+#[derivative(
+    Clone(bound = "P: TEModelParameters"),
+    Default(bound = "P: TEModelParameters")
+)]
+pub struct RedDSASecretKey<P: TEModelParameters>(pub P::ScalarField);
+
+impl<P: TEModelParameters> ToBytes for RedDSASecretKey<P> {
+    #[inline]
+    fn write<W: Write>(&self, writer: W) -> IoResult<()> {
+        self.0.write(writer)
+    }
+}
+
+impl<P: TEModelParameters> FromBytes for RedDSASecretKey<P> {
+    #[inline]
+    fn read<R: Read>(reader: R) -> IoResult<Self> {
+        Ok(RedDSASecretKey(P::ScalarField::read(reader)?))
+    }
+}
+
+#[derive(Derivative)]
+#[derivative(
+    Clone(bound = "P: TEModelParameters"),
+    Default(bound = "P: TEModelParameters"),
+    Debug(bound = "P: TEModelParameters")
+)]
+pub struct RedDSASig<P: TEModelParameters> {
+    pub prover_commitment:  TEAffine<P>,
+    pub verifier_challenge: P::ScalarField,
+    pub prover_response:    P::ScalarField,
+}
+
+impl<P: TEModelParameters> ToBytes for RedDSASig<P> {
+    #[inline]
+    fn write<W: Write>(&self, mut writer: W) -> IoResult<()> {
+        self.prover_commitment.write(&mut writer)?;
+        self.verifier_challenge.write(&mut writer)?;
+        self.prover_response.write(&mut writer)
+    }
+}
+
+impl<P: TEModelParameters> FromBytes for RedDSASig<P> {
+    #[inline]
+    fn read<R: Read>(mut reader: R) -> IoResult<Self> {
+        let prover_commitment = TEAffine::<P>::read(&mut reader)?;
+        let verifier_challenge = P::ScalarField::read(&mut reader)?;
+        let prover_response = P::ScalarField::read(&mut reader)?;
+        Ok(RedDSASig {
+            prover_commitment,
+            verifier_challenge,
+            prover_response,
+        })
+    }
+}
+
+impl<P: TEModelParameters, D: Digest + Send + Sync> RedDSASignature<P, D> {
+    /// Returns the challenge `c := H(salt || pk || R || msg)`, read as a
+    /// little-endian integer.
+    fn challenge(
+        parameters: &RedDSAParameters<P, D>,
+        public_key: &RedDSAPublicKey<P>,
+        prover_commitment: &TEAffine<P>,
+        message: &[u8],
+    ) -> Result<P::ScalarField, Error> {
+        let mut hash_input = Vec::new();
+        hash_input.extend_from_slice(&parameters.salt);
+        hash_input.extend_from_slice(&to_bytes![public_key]?);
+        hash_input.extend_from_slice(&to_bytes![prover_commitment]?);
+        hash_input.extend_from_slice(message);
+        Ok(scalar_from_le_bytes(&D::digest(&hash_input)))
+    }
+
+    /// Returns the nonce `r := H(salt || 0 || sk || msg) || H(salt || 1 || sk
+    /// || msg)`, which is twice as long as a scalar so that its reduction is
+    /// close to uniform.
+    fn nonce(
+        parameters: &RedDSAParameters<P, D>,
+        sk: &RedDSASecretKey<P>,
+        message: &[u8],
+    ) -> Result<P::ScalarField, Error> {
+        let sk_bytes = to_bytes![sk]?;
+        let mut nonce_bytes = Vec::new();
+        for domain in 0..2u8 {
+            let mut hash_input = Vec::new();
+            hash_input.extend_from_slice(&parameters.salt);
+            hash_input.push(domain);
+            hash_input.extend_from_slice(&sk_bytes);
+            hash_input.extend_from_slice(message);
+            nonce_bytes.extend_from_slice(&D::digest(&hash_input));
+        }
+        Ok(scalar_from_le_bytes(&nonce_bytes))
+    }
+
+    /// Returns the secret key of the public key that `randomize_public_key`
+    /// returns for the same `randomness`.
+    pub fn randomize_secret_key(
+        secret_key: &RedDSASecretKey<P>,
+        randomness: &[u8],
+    ) -> RedDSASecretKey<P> {
+        RedDSASecretKey(secret_key.0 + &scalar_from_le_bytes(randomness))
+    }
+
+    /// Checks all `signatures` at once, with a single multi-scalar
+    /// multiplication. Except with negligible probability, accepts iff
+    /// `verify` accepts each of them.
+    pub fn batch_verify<R: Rng>(
+        parameters: &RedDSAParameters<P, D>,
+        public_keys: &[RedDSAPublicKey<P>],
+        messages: &[&[u8]],
+        signatures: &[RedDSASig<P>],
+        rng: &mut R,
+    ) -> Result<bool, Error> {
+        let verify_time = timer_start!(|| "RedDSASig::BatchVerify");
+        if public_keys.len() != signatures.len() || messages.len() != signatures.len() {
+            return Err(format_err!(
+                "expected as many public keys and messages as signatures"
+            ));
+        }
+
+        // [h] Σ z_i (R_i + c_i · pk_i - s_i · g) = 0 for random 128-bit z_i.
+        let mut bases = Vec::with_capacity(2 * signatures.len() + 1);
+        let mut scalars = Vec::with_capacity(2 * signatures.len() + 1);
+        let mut response_sum = P::ScalarField::zero();
+        for ((pk, message), signature) in public_keys.iter().zip(messages).zip(signatures) {
+            let challenge = Self::challenge(parameters, pk, &signature.prover_commitment, message)?;
+            if challenge != signature.verifier_challenge {
+                return Ok(false);
+            }
+            let mut z_bytes = [0u8; 16];
+            rng.fill_bytes(&mut z_bytes);
+            let z = scalar_from_le_bytes::<P::ScalarField>(&z_bytes);
+
+            response_sum += &(z * &signature.prover_response);
+            bases.push(signature.prover_commitment);
+            scalars.push(z.into_repr());
+            bases.push(*pk);
+            scalars.push((z * &challenge).into_repr());
+        }
+        bases.push(parameters.generator);
+        scalars.push((-response_sum).into_repr());
+        let sum = VariableBaseMSM::multi_scalar_mul(&bases, &scalars).into_affine();
+        let result = sum.mul_by_cofactor().is_zero();
+
+        timer_end!(verify_time);
+        Ok(result)
+    }
+}
+
+impl<P: TEModelParameters, D: Digest + Send + Sync> SignatureScheme for RedDSASignature<P, D> {
+    type Parameters = RedDSAParameters<P, D>;
+    type PublicKey = RedDSAPublicKey<P>;
+    type SecretKey = RedDSASecretKey<P>;
+    type Signature = RedDSASig<P>;
+
+    fn setup<R: Rng>(rng: &mut R) -> Result<Self::Parameters, Error> {
+        let setup_time = timer_start!(|| "RedDSASig::Setup");
+
+        let mut salt = [0u8; 32];
+        rng.fill_bytes(&mut salt);
+        let generator = TEAffine::<P>::rand(rng);
+
+        timer_end!(setup_time);
+        Ok(RedDSAParameters {
+            _hash: PhantomData,
+            generator,
+            salt,
+        })
+    }
+
+    fn keygen<R: Rng>(
+        parameters: &Self::Parameters,
+        rng: &mut R,
+    ) -> Result<(Self::PublicKey, Self::SecretKey), Error> {
+        let keygen_time = timer_start!(|| "RedDSASig::KeyGen");
+
+        let secret_key = P::ScalarField::rand(rng);
+        let public_key = parameters.generator.mul(secret_key).into_affine();
+
+        timer_end!(keygen_time);
+        Ok((public_key, RedDSASecretKey(secret_key)))
+    }
+
+    fn sign<R: Rng>(
+        parameters: &Self::Parameters,
+        sk: &Self::SecretKey,
+        message: &[u8],
+        _rng: &mut R,
+    ) -> Result<Self::Signature, Error> {
+        let sign_time = timer_start!(|| "RedDSASig::Sign");
+
+        // R := r · g, c := H(salt || pk || R || msg), s := r + c · sk.
+        let nonce = Self::nonce(parameters, sk, message)?;
+        let public_key = parameters.generator.mul(sk.0).into_affine();
+        let prover_commitment = parameters.generator.mul(nonce).into_affine();
+        let verifier_challenge =
+            Self::challenge(parameters, &public_key, &prover_commitment, message)?;
+        let prover_response = nonce + &(verifier_challenge * &sk.0);
+        let signature = RedDSASig {
+            prover_commitment,
+            verifier_challenge,
+            prover_response,
+        };
+
+        timer_end!(sign_time);
+        Ok(signature)
+    }
+
+    fn verify(
+        parameters: &Self::Parameters,
+        pk: &Self::PublicKey,
+        message: &[u8],
+        signature: &Self::Signature,
+    ) -> Result<bool, Error> {
+        let verify_time = timer_start!(|| "RedDSASig::Verify");
+
+        let challenge = Self::challenge(parameters, pk, &signature.prover_commitment, message)?;
+        if challenge != signature.verifier_challenge {
+            return Ok(false);
+        }
+        // [h](R + c · pk - s · g) = 0
+        let mut claimed_zero = pk.mul(challenge);
+        claimed_zero.add_assign_mixed(&signature.prover_commitment);
+        claimed_zero -= &parameters.generator.mul(signature.prover_response);
+        let result = claimed_zero.into_affine().mul_by_cofactor().is_zero();
+
+        timer_end!(verify_time);
+        Ok(result)
+    }
+
+    fn randomize_public_key(
+        parameters: &Self::Parameters,
+        public_key: &Self::PublicKey,
+        randomness: &[u8],
+    ) -> Result<Self::PublicKey, Error> {
+        let rand_pk_time = timer_start!(|| "RedDSASig::RandomizePubKey");
+
+        let mut randomized_pk = parameters
+            .generator
+            .mul(scalar_from_le_bytes::<P::ScalarField>(randomness));
+        randomized_pk.add_assign_mixed(public_key);
+
+        timer_end!(rand_pk_time);
+        Ok(randomized_pk.into_affine())
+    }
+
+    /// Always fails: the challenge binds the public key, so signatures under
+    /// a randomized public key have to be made with `randomize_secret_key`.
+    fn randomize_signature(
+        _parameters: &Self::Parameters,
+        _signature: &Self::Signature,
+        _randomness: &[u8],
+    ) -> Result<Self::Signature, Error> {
+        Err(format_err!(
+            "RedDSA signatures cannot be randomized; sign with the randomized secret key"
+        ))
+    }
+}
+
+/// Reads `bytes` as a little-endian integer, reduced modulo the field order.
+fn scalar_from_le_bytes<F: PrimeField>(bytes: &[u8]) -> F {
+    let mut result = F::zero();
+    for byte in bytes.iter().rev() {
+        for i in (0..8).rev() {
+            result.double_in_place();
+            if (byte >> i) & 1 == 1 {
+                result += &F::one();
+            }
+        }
+    }
+    result
+}
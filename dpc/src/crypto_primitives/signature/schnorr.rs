@@ -1,16 +1,18 @@
-use crate::crypto_primitives::SignatureScheme;
+use crate::crypto_primitives::{CryptoError, SignatureScheme};
 use algebra::{
-    bytes::ToBytes,
+    bytes::{FromBytes, ToBytes},
     fields::{Field, PrimeField},
     groups::Group,
-    to_bytes,
+    msm::VariableBaseMSM,
+    to_bytes, ProjectiveCurve,
 };
 use digest::Digest;
 use failure::Error;
 use rand::{Rand, Rng};
+use rayon::prelude::*;
 use std::{
     hash::Hash,
-    io::{Result as IoResult, Write},
+    io::{Read, Result as IoResult, Write},
     marker::PhantomData,
     ops::Sub,
 };
@@ -28,6 +30,27 @@ pub struct SchnorrSigParameters<G: Group, H: Digest> {
     pub salt:      [u8; 32],
 }
 
+impl<G: Group, H: Digest> ToBytes for SchnorrSigParameters<G, H> {
+    #[inline]
+    fn write<W: Write>(&self, mut writer: W) -> IoResult<()> {
+        self.generator.write(&mut writer)?;
+        self.salt.write(&mut writer)
+    }
+}
+
+impl<G: Group, H: Digest> FromBytes for SchnorrSigParameters<G, H> {
+    #[inline]
+    fn read<R: Read>(mut reader: R) -> IoResult<Self> {
+        let generator = G::read(&mut reader)?;
+        let salt = <[u8; 32]>::read(&mut reader)?;
+        Ok(Self {
+            _hash: PhantomData,
+            generator,
+            salt,
+        })
+    }
+}
+
 pub type SchnorrPublicKey<G> = G;
 
 #[derive(Derivative)]
@@ -41,13 +64,145 @@ impl<G: Group> ToBytes for SchnorrSecretKey<G> {
     }
 }
 
+impl<G: Group> FromBytes for SchnorrSecretKey<G> {
+    #[inline]
+    fn read<R: Read>(reader: R) -> IoResult<Self> {
+        Ok(SchnorrSecretKey(G::ScalarField::read(reader)?))
+    }
+}
+
 #[derive(Derivative)]
 #[derivative(Clone(bound = "G: Group"), Default(bound = "G: Group"))]
 pub struct SchnorrSig<G: Group> {
+    pub prover_commitment:  G,
     pub prover_response:    G::ScalarField,
     pub verifier_challenge: G::ScalarField,
 }
 
+impl<G: Group> ToBytes for SchnorrSig<G> {
+    #[inline]
+    fn write<W: Write>(&self, mut writer: W) -> IoResult<()> {
+        self.prover_commitment.write(&mut writer)?;
+        self.prover_response.write(&mut writer)?;
+        self.verifier_challenge.write(&mut writer)
+    }
+}
+
+impl<G: Group> FromBytes for SchnorrSig<G> {
+    #[inline]
+    fn read<R: Read>(mut reader: R) -> IoResult<Self> {
+        let prover_commitment = G::read(&mut reader)?;
+        let prover_response = G::ScalarField::read(&mut reader)?;
+        let verifier_challenge = G::ScalarField::read(&mut reader)?;
+        Ok(SchnorrSig {
+            prover_commitment,
+            prover_response,
+            verifier_challenge,
+        })
+    }
+}
+
+impl<G: Group + Hash, D: Digest + Send + Sync> SchnorrSignature<G, D>
+where
+    G::ScalarField: PrimeField,
+{
+    /// Returns the challenge `e := H(salt || r || msg)`, or `None` if the hash
+    /// is not a valid scalar.
+    fn challenge(
+        parameters: &SchnorrSigParameters<G, D>,
+        prover_commitment: &G,
+        message: &[u8],
+    ) -> Result<Option<G::ScalarField>, Error> {
+        let mut hash_input = Vec::new();
+        hash_input.extend_from_slice(&parameters.salt);
+        hash_input.extend_from_slice(&to_bytes![prover_commitment]?);
+        hash_input.extend_from_slice(message);
+        Ok(G::ScalarField::from_random_bytes(&D::digest(&hash_input)))
+    }
+}
+
+impl<G, D> SchnorrSignature<G, D>
+where
+    G: Group + ProjectiveCurve<ScalarField = <G as Group>::ScalarField>,
+    D: Digest + Send + Sync,
+{
+    /// Checks `signatures[i]` on `messages[i]` under `public_keys[i]` for all
+    /// `i`, and fails with `CryptoError::InvalidSignature(i)` for the first
+    /// invalid one.
+    ///
+    /// The group equations are checked at once, as a random linear
+    /// combination `Σ z_i (s_i · g + e_i · pk_i - r_i) = 0` computed with a
+    /// single multi-scalar multiplication; signatures are only checked one by
+    /// one to find the offending index. Points are assumed to lie in the
+    /// prime-order subgroup, as `FromBytes` ensures for twisted Edwards
+    /// curves.
+    pub fn batch_verify<R: Rng>(
+        parameters: &SchnorrSigParameters<G, D>,
+        public_keys: &[G],
+        messages: &[&[u8]],
+        signatures: &[SchnorrSig<G>],
+        rng: &mut R,
+    ) -> Result<(), Error> {
+        let verify_time = timer_start!(|| "SchnorrSig::BatchVerify");
+        if public_keys.len() != signatures.len() {
+            Err(CryptoError::IncorrectInputLength(public_keys.len()))?;
+        }
+        if messages.len() != signatures.len() {
+            Err(CryptoError::IncorrectInputLength(messages.len()))?;
+        }
+
+        let challenges_match = messages
+            .par_iter()
+            .zip(signatures)
+            .map(|(message, signature)| {
+                let challenge = Self::challenge(parameters, &signature.prover_commitment, message)?;
+                Ok(challenge == Some(signature.verifier_challenge))
+            })
+            .collect::<Result<Vec<_>, Error>>()?;
+        if let Some(index) = challenges_match.iter().position(|is_valid| !is_valid) {
+            Err(CryptoError::InvalidSignature(index))?;
+        }
+
+        let mut bases = Vec::with_capacity(2 * signatures.len() + 1);
+        let mut scalars = Vec::with_capacity(2 * signatures.len() + 1);
+        let mut response_sum = <G as Group>::ScalarField::zero();
+        for (pk, signature) in public_keys.iter().zip(signatures) {
+            let z = <G as Group>::ScalarField::rand(rng);
+            response_sum += &(z * &signature.prover_response);
+            bases.push(signature.prover_commitment);
+            scalars.push((-z).into_repr());
+            bases.push(*pk);
+            scalars.push((z * &signature.verifier_challenge).into_repr());
+        }
+        bases.push(parameters.generator);
+        scalars.push(response_sum.into_repr());
+
+        <G as ProjectiveCurve>::batch_normalization(&mut bases);
+        let bases = bases
+            .iter()
+            .map(|base| base.into_affine())
+            .collect::<Vec<_>>();
+        let is_valid =
+            ProjectiveCurve::is_zero(&VariableBaseMSM::multi_scalar_mul(&bases, &scalars));
+
+        if !is_valid {
+            let results = public_keys
+                .par_iter()
+                .zip(messages)
+                .zip(signatures)
+                .map(|((pk, message), signature)| {
+                    <Self as SignatureScheme>::verify(parameters, pk, message, signature)
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+            if let Some(index) = results.iter().position(|is_valid| !is_valid) {
+                Err(CryptoError::InvalidSignature(index))?;
+            }
+        }
+        timer_end!(verify_time);
+        Ok(())
+    }
+}
+
 impl<G: Group + Hash, D: Digest + Send + Sync> SignatureScheme for SchnorrSignature<G, D>
 where
     G::ScalarField: PrimeField,
@@ -92,31 +247,26 @@ where
         rng: &mut R,
     ) -> Result<Self::Signature, Error> {
         let sign_time = timer_start!(|| "SchnorrSig::Sign");
-        // (k, e);
-        let (random_scalar, verifier_challenge) = loop {
+        // (k, r, e);
+        let (random_scalar, prover_commitment, verifier_challenge) = loop {
             // Sample a random scalar `k` from the prime scalar field.
             let random_scalar: G::ScalarField = G::ScalarField::rand(rng);
             // Commit to the random scalar via r := k · g.
             // This is the prover's first msg in the Sigma protocol.
             let prover_commitment: G = parameters.generator.mul(&random_scalar);
 
-            // Hash everything to get verifier challenge.
-            let mut hash_input = Vec::new();
-            hash_input.extend_from_slice(&parameters.salt);
-            hash_input.extend_from_slice(&to_bytes![prover_commitment]?);
-            hash_input.extend_from_slice(message);
-
             // Compute the supposed verifier response: e := H(salt || r || msg);
             if let Some(verifier_challenge) =
-                G::ScalarField::from_random_bytes(&D::digest(&hash_input))
+                Self::challenge(parameters, &prover_commitment, message)?
             {
-                break (random_scalar, verifier_challenge);
+                break (random_scalar, prover_commitment, verifier_challenge);
             };
         };
 
         // k - xe;
         let prover_response = random_scalar - &(verifier_challenge * &sk.0);
         let signature = SchnorrSig {
+            prover_commitment,
             prover_response,
             verifier_challenge,
         };
@@ -134,27 +284,21 @@ where
         let verify_time = timer_start!(|| "SchnorrSig::Verify");
 
         let &SchnorrSig {
+            ref prover_commitment,
             ref prover_response,
             ref verifier_challenge,
         } = signature;
+        let obtained_verifier_challenge = Self::challenge(parameters, prover_commitment, message)?;
+        if obtained_verifier_challenge != Some(*verifier_challenge) {
+            return Ok(false);
+        }
+
+        // s · g + e · pk = r
         let mut claimed_prover_commitment = parameters.generator.mul(prover_response);
         let public_key_times_verifier_challenge = pk.mul(verifier_challenge);
         claimed_prover_commitment += &public_key_times_verifier_challenge;
-
-        let mut hash_input = Vec::new();
-        hash_input.extend_from_slice(&parameters.salt);
-        hash_input.extend_from_slice(&to_bytes![claimed_prover_commitment]?);
-        hash_input.extend_from_slice(&message);
-
-        let obtained_verifier_challenge = if let Some(obtained_verifier_challenge) =
-            G::ScalarField::from_random_bytes(&D::digest(&hash_input))
-        {
-            obtained_verifier_challenge
-        } else {
-            return Ok(false);
-        };
         timer_end!(verify_time);
-        Ok(verifier_challenge == &obtained_verifier_challenge)
+        Ok(&claimed_prover_commitment == prover_commitment)
     }
 
     fn randomize_public_key(
@@ -187,6 +331,7 @@ where
     ) -> Result<Self::Signature, Error> {
         let rand_signature_time = timer_start!(|| "SchnorrSig::RandomizeSig");
         let &SchnorrSig {
+            ref prover_commitment,
             ref prover_response,
             ref verifier_challenge,
         } = signature;
@@ -200,6 +345,7 @@ where
         }
 
         let new_sig = SchnorrSig {
+            prover_commitment:  *prover_commitment,
             prover_response:    prover_response.sub(&(*verifier_challenge * &multiplier)),
             verifier_challenge: *verifier_challenge,
         };
@@ -1,9 +1,13 @@
 use crate::{
     crypto_primitives::{CommitmentScheme, SignatureScheme, PRF},
-    dpc::{delegable_dpc::DelegableDPCComponents, AddressKeyPair},
+    dpc::{
+        delegable_dpc::DelegableDPCComponents,
+        serialization::{read_version, write_version},
+        AddressKeyPair,
+    },
 };
-use algebra::bytes::ToBytes;
-use std::io::{Result as IoResult, Write};
+use algebra::bytes::{FromBytes, ToBytes};
+use std::io::{Read, Result as IoResult, Write};
 
 #[derive(Derivative)]
 #[derivative(
@@ -15,8 +19,17 @@ pub struct AddressPublicKey<C: DelegableDPCComponents> {
 }
 
 impl<C: DelegableDPCComponents> ToBytes for AddressPublicKey<C> {
-    fn write<W: Write>(&self, writer: W) -> IoResult<()> {
-        self.public_key.write(writer)
+    fn write<W: Write>(&self, mut writer: W) -> IoResult<()> {
+        write_version(&mut writer)?;
+        self.public_key.write(&mut writer)
+    }
+}
+
+impl<C: DelegableDPCComponents> FromBytes for AddressPublicKey<C> {
+    fn read<R: Read>(mut reader: R) -> IoResult<Self> {
+        read_version(&mut reader)?;
+        let public_key = FromBytes::read(&mut reader)?;
+        Ok(Self { public_key })
     }
 }
 
@@ -33,6 +46,78 @@ pub struct AddressSecretKey<C: DelegableDPCComponents> {
     pub r_pk:     <C::AddrC as CommitmentScheme>::Randomness,
 }
 
+impl<C: DelegableDPCComponents> ToBytes for AddressSecretKey<C> {
+    fn write<W: Write>(&self, mut writer: W) -> IoResult<()> {
+        write_version(&mut writer)?;
+        self.pk_sig.write(&mut writer)?;
+        self.sk_sig.write(&mut writer)?;
+        self.sk_prf.write(&mut writer)?;
+        self.metadata.write(&mut writer)?;
+        self.r_pk.write(&mut writer)
+    }
+}
+
+impl<C: DelegableDPCComponents> FromBytes for AddressSecretKey<C> {
+    fn read<R: Read>(mut reader: R) -> IoResult<Self> {
+        read_version(&mut reader)?;
+        let pk_sig = FromBytes::read(&mut reader)?;
+        let sk_sig = FromBytes::read(&mut reader)?;
+        let sk_prf = FromBytes::read(&mut reader)?;
+        let metadata = FromBytes::read(&mut reader)?;
+        let r_pk = FromBytes::read(&mut reader)?;
+        Ok(Self {
+            pk_sig,
+            sk_sig,
+            sk_prf,
+            metadata,
+            r_pk,
+        })
+    }
+}
+
+impl<C: DelegableDPCComponents> AddressSecretKey<C> {
+    /// Returns the part of `self` that a prover needs to create transactions
+    /// on behalf of the owner of `self`.
+    pub fn proving_key(&self) -> AddressProvingKey<C> {
+        AddressProvingKey {
+            pk_sig:   self.pk_sig.clone(),
+            sk_prf:   self.sk_prf.clone(),
+            metadata: self.metadata,
+            r_pk:     self.r_pk.clone(),
+        }
+    }
+}
+
+/// An `AddressSecretKey` without `sk_sig`. A prover holding it can prove
+/// statements about records of the address, but cannot sign the transactions
+/// that spend them. It still reveals `sk_prf`, and hence which serial
+/// numbers belong to the address.
+#[derive(Derivative)]
+#[derivative(
+    Default(bound = "C: DelegableDPCComponents"),
+    Clone(bound = "C: DelegableDPCComponents")
+)]
+pub struct AddressProvingKey<C: DelegableDPCComponents> {
+    pub pk_sig:   <C::S as SignatureScheme>::PublicKey,
+    pub sk_prf:   <C::P as PRF>::Seed,
+    pub metadata: [u8; 32],
+    pub r_pk:     <C::AddrC as CommitmentScheme>::Randomness,
+}
+
+impl<C: DelegableDPCComponents> AddressProvingKey<C> {
+    /// Returns an `AddressSecretKey` with a default `sk_sig`, for the
+    /// circuits, which never use `sk_sig`.
+    pub(crate) fn to_secret_key(&self) -> AddressSecretKey<C> {
+        AddressSecretKey {
+            pk_sig:   self.pk_sig.clone(),
+            sk_sig:   Default::default(),
+            sk_prf:   self.sk_prf.clone(),
+            metadata: self.metadata,
+            r_pk:     self.r_pk.clone(),
+        }
+    }
+}
+
 #[derive(Derivative)]
 #[derivative(Clone(bound = "C: DelegableDPCComponents"))]
 pub struct AddressPair<C: DelegableDPCComponents> {
@@ -8,8 +8,11 @@ use crate::{
 };
 
 use crate::{
-    dpc::delegable_dpc::{
-        AddressSecretKey, CommCRHSigPublicParameters, DPCRecord, DelegableDPCComponents,
+    dpc::{
+        delegable_dpc::{
+            AddressSecretKey, CommCRHSigPublicParameters, DPCRecord, DelegableDPCComponents,
+        },
+        Arity,
     },
     gadgets::dpc::delegable_dpc::execute_core_checks_gadget,
     ledger::LedgerDigest,
@@ -125,9 +128,10 @@ impl<C: DelegableDPCComponents> CoreChecksCircuit<C> {
     pub fn blank(
         comm_crh_sig_parameters: &CommCRHSigPublicParameters<C>,
         ledger_parameters: &<C::D as LedgerDigest>::Parameters,
+        arity: Arity,
     ) -> Self {
-        let num_input_records = C::NUM_INPUT_RECORDS;
-        let num_output_records = C::NUM_OUTPUT_RECORDS;
+        let num_input_records = arity.num_input_records;
+        let num_output_records = arity.num_output_records;
         let digest = C::D::default();
 
         let old_sn = vec![<C::S as SignatureScheme>::PublicKey::default(); num_input_records];
@@ -206,15 +210,13 @@ impl<C: DelegableDPCComponents> CoreChecksCircuit<C> {
         memo: &[u8; 32],
         auxiliary: &[u8; 32],
     ) -> Self {
-        let num_input_records = C::NUM_INPUT_RECORDS;
-        let num_output_records = C::NUM_OUTPUT_RECORDS;
+        let num_input_records = old_records.len();
+        let num_output_records = new_records.len();
 
-        assert_eq!(num_input_records, old_records.len());
         assert_eq!(num_input_records, old_witnesses.len());
         assert_eq!(num_input_records, old_address_secret_keys.len());
         assert_eq!(num_input_records, old_serial_numbers.len());
 
-        assert_eq!(num_output_records, new_records.len());
         assert_eq!(num_output_records, new_sn_nonce_randomness.len());
         assert_eq!(num_output_records, new_commitments.len());
 
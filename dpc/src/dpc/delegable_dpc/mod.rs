@@ -6,7 +6,7 @@ use std::marker::PhantomData;
 
 use crate::{
     crypto_primitives::{CommitmentScheme, FixedLengthCRH, SignatureScheme, NIZK, PRF},
-    dpc::{AddressKeyPair, DPCScheme, Predicate, Record, Transaction},
+    dpc::{AddressKeyPair, Arity, DPCScheme, Predicate, Record, Transaction},
     gadgets::{
         CommitmentGadget, FixedLengthCRHGadget, LCWGadget, NIZKVerifierGadget, PRFGadget,
         SigRandomizePkGadget,
@@ -48,8 +48,9 @@ use self::parameters::*;
 /// scheme. Simplifies the interface of `DelegableDPC` by wrapping all these
 /// into one.
 pub trait DelegableDPCComponents: 'static + Sized {
-    const NUM_INPUT_RECORDS: usize;
-    const NUM_OUTPUT_RECORDS: usize;
+    // Shapes of the transactions that can be created and verified. Each arity
+    // gets its own core check and proof check circuits.
+    const ARITIES: &'static [Arity];
 
     type E: PairingEngine<Fq = <Self::ProofCheckE as PairingEngine>::Fr>;
     type ProofCheckE: PairingEngine;
@@ -164,22 +165,6 @@ pub(crate) struct ExecuteContext<'a, Components: DelegableDPCComponents> {
     local_data_rand: <Components::LocalDataComm as CommitmentScheme>::Randomness,
 }
 
-impl<Components: DelegableDPCComponents> ExecuteContext<'_, Components> {
-    fn into_local_data(&self) -> LocalData<Components> {
-        LocalData {
-            comm_crh_sig_pp: self.comm_crh_sig_pp.clone(),
-
-            old_records:        self.old_records.to_vec(),
-            old_serial_numbers: self.old_serial_numbers.to_vec(),
-
-            new_records: self.new_records.to_vec(),
-
-            local_data_comm: self.local_data_comm.clone(),
-            local_data_rand: self.local_data_rand.clone(),
-        }
-    }
-}
-
 /// Stores local data required to produce predicate proofs.
 pub struct LocalData<Components: DelegableDPCComponents> {
     pub comm_crh_sig_pp: CommCRHSigPublicParameters<Components>,
@@ -196,6 +181,60 @@ pub struct LocalData<Components: DelegableDPCComponents> {
     pub local_data_rand: <Components::LocalDataComm as CommitmentScheme>::Randomness,
 }
 
+/// A transaction that the owners of its old records have authorized, but
+/// whose proofs are not yet computed. Returned by `DPC::authorize` and turned
+/// into a transaction by `DPC::execute_authorized`: it contains everything
+/// the proofs depend on, but no `sk_sig`.
+pub struct ExecuteAuthorization<Components: DelegableDPCComponents> {
+    arity:              Arity,
+    num_input_records:  usize,
+    num_output_records: usize,
+    ledger_digest:      Components::D,
+
+    // Old record stuff
+    old_records:        Vec<DPCRecord<Components>>,
+    old_witnesses:      Vec<Components::LCW>,
+    old_proving_keys:   Vec<AddressProvingKey<Components>>,
+    old_serial_numbers: Vec<<Components::S as SignatureScheme>::PublicKey>,
+
+    // New record stuff
+    new_records:             Vec<DPCRecord<Components>>,
+    new_sn_nonce_randomness: Vec<[u8; 32]>,
+    new_commitments:         Vec<<Components::RecC as CommitmentScheme>::Output>,
+
+    // Predicate and local data commitment and randomness
+    predicate_comm: <Components::PredVkComm as CommitmentScheme>::Output,
+    predicate_rand: <Components::PredVkComm as CommitmentScheme>::Randomness,
+
+    local_data_comm: <Components::LocalDataComm as CommitmentScheme>::Output,
+    local_data_rand: <Components::LocalDataComm as CommitmentScheme>::Randomness,
+
+    memorandum: [u8; 32],
+    auxiliary:  [u8; 32],
+
+    // Signatures of the owners of the old records, which become the
+    // transaction signatures.
+    signatures: Vec<<Components::S as SignatureScheme>::Signature>,
+}
+
+impl<Components: DelegableDPCComponents> ExecuteAuthorization<Components> {
+    /// Returns the serial numbers of the old records.
+    pub fn old_serial_numbers(&self) -> &[<Components::S as SignatureScheme>::PublicKey] {
+        &self.old_serial_numbers
+    }
+
+    /// Returns the new records, including the dummy records that pad the
+    /// transaction to a supported arity.
+    pub fn new_records(&self) -> &[DPCRecord<Components>] {
+        &self.new_records
+    }
+
+    /// Returns the proving keys of the owners of the old records.
+    pub fn old_proving_keys(&self) -> &[AddressProvingKey<Components>] {
+        &self.old_proving_keys
+    }
+}
+
 ///////////////////////////////////////////////////////////////////////////////
 
 impl<Components: DelegableDPCComponents> DPC<Components> {
@@ -394,21 +433,17 @@ impl<Components: DelegableDPCComponents> DPC<Components> {
         <L as Ledger>::SnWitness: LedgerWitness<Components::D>,
         <L as Ledger>::MemoWitness: LedgerWitness<Components::D>,
     {
-        assert_eq!(Components::NUM_INPUT_RECORDS, old_records.len());
-        assert_eq!(Components::NUM_INPUT_RECORDS, old_address_secret_keys.len());
+        assert_eq!(old_records.len(), old_address_secret_keys.len());
 
-        assert_eq!(
-            Components::NUM_OUTPUT_RECORDS,
-            new_address_public_keys.len()
-        );
-        assert_eq!(Components::NUM_OUTPUT_RECORDS, new_is_dummy_flags.len());
-        assert_eq!(Components::NUM_OUTPUT_RECORDS, new_payloads.len());
-        assert_eq!(Components::NUM_OUTPUT_RECORDS, new_birth_predicates.len());
-        assert_eq!(Components::NUM_OUTPUT_RECORDS, new_death_predicates.len());
-
-        let mut old_witnesses = Vec::with_capacity(Components::NUM_INPUT_RECORDS);
-        let mut old_serial_numbers = Vec::with_capacity(Components::NUM_INPUT_RECORDS);
-        let mut old_randomizers = Vec::with_capacity(Components::NUM_INPUT_RECORDS);
+        let num_output_records = new_address_public_keys.len();
+        assert_eq!(num_output_records, new_is_dummy_flags.len());
+        assert_eq!(num_output_records, new_payloads.len());
+        assert_eq!(num_output_records, new_birth_predicates.len());
+        assert_eq!(num_output_records, new_death_predicates.len());
+
+        let mut old_witnesses = Vec::with_capacity(old_records.len());
+        let mut old_serial_numbers = Vec::with_capacity(old_records.len());
+        let mut old_randomizers = Vec::with_capacity(old_records.len());
         let mut joint_serial_numbers = Vec::new();
         let mut old_death_pred_hashes = Vec::new();
 
@@ -434,13 +469,13 @@ impl<Components: DelegableDPCComponents> DPC<Components> {
             timer_end!(input_record_time);
         }
 
-        let mut new_records = Vec::with_capacity(Components::NUM_OUTPUT_RECORDS);
-        let mut new_commitments = Vec::with_capacity(Components::NUM_OUTPUT_RECORDS);
-        let mut new_sn_nonce_randomness = Vec::with_capacity(Components::NUM_OUTPUT_RECORDS);
+        let mut new_records = Vec::with_capacity(num_output_records);
+        let mut new_commitments = Vec::with_capacity(num_output_records);
+        let mut new_sn_nonce_randomness = Vec::with_capacity(num_output_records);
         let mut new_birth_pred_hashes = Vec::new();
 
         // Generate new records and commitments for them.
-        for j in 0..Components::NUM_OUTPUT_RECORDS {
+        for j in 0..num_output_records {
             let output_record_time = timer_start!(|| format!("Process output record {}", j));
             let sn_nonce_time = timer_start!(|| "Generate serial number nonce");
 
@@ -473,25 +508,23 @@ impl<Components: DelegableDPCComponents> DPC<Components> {
 
         let local_data_comm_timer = timer_start!(|| "Compute predicate input commitment");
         let mut predicate_input = Vec::new();
-        for i in 0..Components::NUM_INPUT_RECORDS {
-            let record = &old_records[i];
+        for (record, sn) in old_records.iter().zip(&old_serial_numbers) {
             let bytes = to_bytes![
                 record.commitment(),
-                record.address_public_key(),
+                record.address_public_key().public_key,
                 record.is_dummy(),
                 record.payload(),
                 record.birth_predicate_repr(),
                 record.death_predicate_repr(),
-                old_serial_numbers[i]
+                sn
             ]?;
             predicate_input.extend_from_slice(&bytes);
         }
 
-        for j in 0..Components::NUM_OUTPUT_RECORDS {
-            let record = &new_records[j];
+        for record in &new_records {
             let bytes = to_bytes![
                 record.commitment(),
-                record.address_public_key(),
+                record.address_public_key().public_key,
                 record.is_dummy(),
                 record.payload(),
                 record.birth_predicate_repr(),
@@ -573,6 +606,7 @@ where
     type Metadata = [u8; 32];
     type Payload = <Self::Record as Record>::Payload;
     type Parameters = PublicParameters<Components>;
+    type VerifierParameters = VerifierParameters<Components>;
     type Predicate = DPCPredicate<Components>;
     type PrivatePredInput = PrivatePredInput<Components>;
     type Record = DPCRecord<Components>;
@@ -592,25 +626,35 @@ where
             proof: pred_nizk_pp.proof.clone(),
         };
 
-        let nizk_setup_time = timer_start!(|| "Execute Tx Core Checks NIZK Setup");
-        let core_nizk_pp = Components::MainNIZK::setup(
-            CoreChecksCircuit::blank(&comm_crh_sig_pp, ledger_pp),
-            rng,
-        )?;
-        timer_end!(nizk_setup_time);
+        let mut arity_pp = Vec::with_capacity(Components::ARITIES.len());
+        for &arity in Components::ARITIES {
+            let nizk_setup_time =
+                timer_start!(|| format!("Execute Tx Core Checks NIZK Setup for {:?}", arity));
+            let core_nizk_pp = Components::MainNIZK::setup(
+                CoreChecksCircuit::blank(&comm_crh_sig_pp, ledger_pp, arity),
+                rng,
+            )?;
+            timer_end!(nizk_setup_time);
 
-        let nizk_setup_time = timer_start!(|| "Execute Tx Proof Checks NIZK Setup");
-        let proof_check_nizk_pp = Components::ProofCheckNIZK::setup(
-            ProofCheckCircuit::blank(&comm_crh_sig_pp, &private_pred_input),
-            rng,
-        )?;
-        timer_end!(nizk_setup_time);
+            let nizk_setup_time =
+                timer_start!(|| format!("Execute Tx Proof Checks NIZK Setup for {:?}", arity));
+            let proof_check_nizk_pp = Components::ProofCheckNIZK::setup(
+                ProofCheckCircuit::blank(&comm_crh_sig_pp, &private_pred_input, arity),
+                rng,
+            )?;
+            timer_end!(nizk_setup_time);
+
+            arity_pp.push(ArityParameters {
+                arity,
+                proof_check_nizk_pp,
+                core_nizk_pp,
+            });
+        }
         timer_end!(setup_time);
         Ok(PublicParameters {
             comm_crh_sig_pp,
             pred_nizk_pp,
-            core_nizk_pp,
-            proof_check_nizk_pp,
+            arity_pp,
         })
     }
 
@@ -630,14 +674,14 @@ where
 
         old_records: &[Self::Record],
         old_address_secret_keys: &[<Self::AddressKeyPair as AddressKeyPair>::AddressSecretKey],
-        mut old_death_pred_proof_generator: impl FnMut(&Self::LocalData) -> Vec<Self::PrivatePredInput>,
+        old_death_pred_proof_generator: impl FnMut(&Self::LocalData) -> Vec<Self::PrivatePredInput>,
 
         new_address_public_keys: &[<Self::AddressKeyPair as AddressKeyPair>::AddressPublicKey],
         new_is_dummy_flags: &[bool],
         new_payloads: &[Self::Payload],
         new_birth_predicates: &[Self::Predicate],
         new_death_predicates: &[Self::Predicate],
-        mut new_birth_pred_proof_generator: impl FnMut(&Self::LocalData) -> Vec<Self::PrivatePredInput>,
+        new_birth_pred_proof_generator: impl FnMut(&Self::LocalData) -> Vec<Self::PrivatePredInput>,
 
         auxiliary: &Self::Auxiliary,
         memorandum: &<Self::Transaction as Transaction>::Memorandum,
@@ -645,8 +689,8 @@ where
         rng: &mut R,
     ) -> Result<(Vec<Self::Record>, Self::Transaction), Error> {
         let exec_time = timer_start!(|| "DelegableDPC::Exec");
-        let context = Self::execute_helper(
-            &parameters.comm_crh_sig_pp,
+        let authorization = Self::authorize(
+            parameters,
             old_records,
             old_address_secret_keys,
             new_address_public_keys,
@@ -654,124 +698,42 @@ where
             new_payloads,
             new_birth_predicates,
             new_death_predicates,
-            memorandum,
             auxiliary,
+            memorandum,
+            ledger,
+            rng,
+        )?;
+        let result = Self::execute_authorized(
+            parameters,
+            authorization,
+            old_death_pred_proof_generator,
+            new_birth_pred_proof_generator,
             ledger,
             rng,
         )?;
-
-        let local_data = context.into_local_data();
-        let old_death_pred_vk_and_proofs = old_death_pred_proof_generator(&local_data);
-        let new_birth_pred_vk_and_proofs = new_birth_pred_proof_generator(&local_data);
-
-        let ExecuteContext {
-            comm_crh_sig_pp,
-            ledger_digest,
-
-            old_records,
-            old_witnesses,
-            old_address_secret_keys,
-            old_serial_numbers,
-            old_randomizers,
-
-            new_records,
-            new_sn_nonce_randomness,
-            new_commitments,
-
-            predicate_comm,
-            predicate_rand,
-
-            local_data_comm,
-            local_data_rand,
-        } = context;
-
-        let core_proof = {
-            let circuit = CoreChecksCircuit::new(
-                &parameters.comm_crh_sig_pp,
-                ledger.parameters(),
-                &ledger_digest,
-                old_records,
-                &old_witnesses,
-                old_address_secret_keys,
-                &old_serial_numbers,
-                &new_records,
-                &new_sn_nonce_randomness,
-                &new_commitments,
-                &predicate_comm,
-                &predicate_rand,
-                &local_data_comm,
-                &local_data_rand,
-                memorandum,
-                auxiliary,
-            );
-
-            Components::MainNIZK::prove(&parameters.core_nizk_pp.0, circuit, rng)?
-        };
-
-        let proof_checks_proof = {
-            let circuit = ProofCheckCircuit::new(
-                &parameters.comm_crh_sig_pp,
-                old_death_pred_vk_and_proofs.as_slice(),
-                new_birth_pred_vk_and_proofs.as_slice(),
-                &predicate_comm,
-                &predicate_rand,
-                &local_data_comm,
-            );
-
-            Components::ProofCheckNIZK::prove(&parameters.proof_check_nizk_pp.0, circuit, rng)?
-        };
-
-        let signature_message = to_bytes![
-            old_serial_numbers,
-            new_commitments,
-            memorandum,
-            ledger_digest,
-            core_proof,
-            proof_checks_proof
-        ]?;
-
-        let mut signatures = Vec::with_capacity(Components::NUM_INPUT_RECORDS);
-        for i in 0..Components::NUM_INPUT_RECORDS {
-            let sig_time = timer_start!(|| format!("Sign and randomize Tx contents {}", i));
-
-            let sk_sig = &old_address_secret_keys[i].sk_sig;
-            let randomizer = &old_randomizers[i];
-            // Sign transaction message
-            let signature =
-                Components::S::sign(&comm_crh_sig_pp.sig_pp, sk_sig, &signature_message, rng)?;
-            let randomized_signature = Components::S::randomize_signature(
-                &comm_crh_sig_pp.sig_pp,
-                &signature,
-                randomizer,
-            )?;
-            signatures.push(randomized_signature);
-
-            timer_end!(sig_time);
-        }
-
-        let transaction = Self::Transaction::new(
-            old_serial_numbers,
-            new_commitments,
-            memorandum.clone(),
-            ledger_digest,
-            core_proof,
-            proof_checks_proof,
-            predicate_comm,
-            local_data_comm,
-            signatures,
-        );
-
         timer_end!(exec_time);
-        Ok((new_records, transaction))
+        Ok(result)
     }
 
     fn verify(
-        parameters: &Self::Parameters,
+        parameters: &Self::VerifierParameters,
         transaction: &Self::Transaction,
         ledger: &L,
     ) -> Result<bool, Error> {
         let mut result = true;
         let verify_time = timer_start!(|| "DelegableDPC::Verify");
+        let arity = Arity::new(
+            transaction.old_serial_numbers().len(),
+            transaction.new_commitments().len(),
+        );
+        let arity_vk = match parameters.arity_vk(arity) {
+            Some(arity_vk) => arity_vk,
+            None => {
+                eprintln!("Transaction arity is not supported.");
+                return Ok(false);
+            },
+        };
+
         let ledger_time = timer_start!(|| "Ledger checks");
         for sn in transaction.old_serial_numbers() {
             if ledger.contains_sn(sn) {
@@ -808,7 +770,7 @@ where
         };
 
         if !Components::MainNIZK::verify(
-            &parameters.core_nizk_pp.1,
+            &arity_vk.core_nizk_vk,
             &input,
             &transaction.stuff.core_proof,
         )? {
@@ -821,21 +783,21 @@ where
             local_data_comm: transaction.stuff.local_data_comm.clone(),
         };
         if !Components::ProofCheckNIZK::verify(
-            &parameters.proof_check_nizk_pp.1,
+            &arity_vk.proof_check_nizk_vk,
             &input,
             &transaction.stuff.predicate_proof,
         )? {
             eprintln!("Transaction proof is invalid.");
             result &= false;
         }
-        let signature_message = &to_bytes![
+        let signature_message = &Self::signature_message(
             transaction.old_serial_numbers(),
             transaction.new_commitments(),
             transaction.memorandum(),
-            transaction.stuff.digest,
-            transaction.stuff.core_proof,
-            transaction.stuff.predicate_proof
-        ]?;
+            &transaction.stuff.digest,
+            &transaction.stuff.predicate_comm,
+            &transaction.stuff.local_data_comm,
+        )?;
 
         let sig_time = timer_start!(|| "Signature verification (in parallel)");
         let sig_pp = &parameters.comm_crh_sig_pp.sig_pp;
@@ -855,3 +817,400 @@ where
         Ok(result)
     }
 }
+
+impl<Components: DelegableDPCComponents> DPC<Components> {
+    /// Returns the message that the owners of the old records sign. It covers
+    /// every public input of the transaction proofs, but not the proofs
+    /// themselves, so that it can be signed before the proofs exist.
+    fn signature_message(
+        old_serial_numbers: &[<Components::S as SignatureScheme>::PublicKey],
+        new_commitments: &[<Components::RecC as CommitmentScheme>::Output],
+        memorandum: &[u8; 32],
+        ledger_digest: &Components::D,
+        predicate_comm: &<Components::PredVkComm as CommitmentScheme>::Output,
+        local_data_comm: &<Components::LocalDataComm as CommitmentScheme>::Output,
+    ) -> Result<Vec<u8>, Error> {
+        Ok(to_bytes![
+            old_serial_numbers,
+            new_commitments,
+            memorandum,
+            ledger_digest,
+            predicate_comm,
+            local_data_comm
+        ]?)
+    }
+
+    /// Creates the old serial numbers and the new records of a transaction of
+    /// the smallest supported arity with room for the given records, and signs
+    /// them with the `sk_sig` of each old record's owner. The result can be
+    /// handed to an untrusted prover, which finishes the transaction with
+    /// `execute_authorized` but cannot change what it spends or creates.
+    pub fn authorize<L, R: Rng>(
+        parameters: &PublicParameters<Components>,
+
+        old_records: &[DPCRecord<Components>],
+        old_address_secret_keys: &[AddressSecretKey<Components>],
+
+        new_address_public_keys: &[AddressPublicKey<Components>],
+        new_is_dummy_flags: &[bool],
+        new_payloads: &[[u8; 32]],
+        new_birth_predicates: &[DPCPredicate<Components>],
+        new_death_predicates: &[DPCPredicate<Components>],
+
+        auxiliary: &[u8; 32],
+        memorandum: &[u8; 32],
+        ledger: &L,
+        rng: &mut R,
+    ) -> Result<ExecuteAuthorization<Components>, Error>
+    where
+        L: Ledger<
+            Parameters = <Components::D as LedgerDigest>::Parameters,
+            Commitment = <Components::RecC as CommitmentScheme>::Output,
+            SerialNumber = <Components::S as SignatureScheme>::PublicKey,
+            LedgerStateDigest = Components::D,
+            CommWitness = Components::LCW,
+        >,
+        <L as Ledger>::SnWitness: LedgerWitness<Components::D>,
+        <L as Ledger>::MemoWitness: LedgerWitness<Components::D>,
+    {
+        let authorize_time = timer_start!(|| "DelegableDPC::Authorize");
+        let num_input_records = old_records.len();
+        let num_output_records = new_address_public_keys.len();
+        let arity = Arity::select(Components::ARITIES, num_input_records, num_output_records)
+            .ok_or_else(|| {
+                format_err!(
+                    "no supported arity fits {} old and {} new records",
+                    num_input_records,
+                    num_output_records
+                )
+            })?;
+        if parameters.arity_pp(arity).is_none() {
+            return Err(format_err!("missing parameters for arity {:?}", arity));
+        }
+
+        // Pad the transaction to `arity` with dummy records that belong to a
+        // fresh address and are guarded by the empty predicate.
+        let mut old_records = old_records.to_vec();
+        let mut old_address_secret_keys = old_address_secret_keys.to_vec();
+        let mut new_address_public_keys = new_address_public_keys.to_vec();
+        let mut new_is_dummy_flags = new_is_dummy_flags.to_vec();
+        let mut new_payloads = new_payloads.to_vec();
+        let mut new_birth_predicates = new_birth_predicates.to_vec();
+        let mut new_death_predicates = new_death_predicates.to_vec();
+        if arity != Arity::new(num_input_records, num_output_records) {
+            let padding_time = timer_start!(|| "Pad transaction");
+            let comm_crh_sig_pp = &parameters.comm_crh_sig_pp;
+            let empty_predicate_hash = Components::PredVkH::evaluate(
+                &comm_crh_sig_pp.pred_vk_crh_pp,
+                &to_bytes![parameters.pred_nizk_pp.vk]?,
+            )?;
+            let empty_predicate = DPCPredicate::new(to_bytes![empty_predicate_hash]?);
+            let metadata: [u8; 32] = rng.gen();
+            let padding_address = Self::create_address_helper(comm_crh_sig_pp, &metadata, rng)?;
+
+            arity.pad_old(&mut old_records, |_| {
+                // Dummy records are never on the ledger, so a random nonce only
+                // needs to keep their serial numbers distinct.
+                let sn_nonce_input: [u8; 32] = rng.gen();
+                let sn_nonce = Components::SnNonceH::evaluate(
+                    &comm_crh_sig_pp.sn_nonce_crh_pp,
+                    &sn_nonce_input,
+                )?;
+                Self::generate_record(
+                    comm_crh_sig_pp,
+                    &sn_nonce,
+                    &padding_address.public_key,
+                    true,
+                    &[0u8; 32],
+                    &empty_predicate,
+                    &empty_predicate,
+                    rng,
+                )
+            })?;
+            arity.pad_old(&mut old_address_secret_keys, |_| {
+                Ok(padding_address.secret_key.clone())
+            })?;
+            arity.pad_new(&mut new_address_public_keys, |_| {
+                Ok(padding_address.public_key.clone())
+            })?;
+            arity.pad_new(&mut new_is_dummy_flags, |_| Ok(true))?;
+            arity.pad_new(&mut new_payloads, |_| Ok([0u8; 32]))?;
+            arity.pad_new(&mut new_birth_predicates, |_| Ok(empty_predicate.clone()))?;
+            arity.pad_new(&mut new_death_predicates, |_| Ok(empty_predicate.clone()))?;
+            timer_end!(padding_time);
+        }
+
+        let context = Self::execute_helper(
+            &parameters.comm_crh_sig_pp,
+            &old_records,
+            &old_address_secret_keys,
+            &new_address_public_keys,
+            &new_is_dummy_flags,
+            &new_payloads,
+            &new_birth_predicates,
+            &new_death_predicates,
+            memorandum,
+            auxiliary,
+            ledger,
+            rng,
+        )?;
+
+        let ExecuteContext {
+            comm_crh_sig_pp,
+            ledger_digest,
+
+            old_records,
+            old_witnesses,
+            old_address_secret_keys,
+            old_serial_numbers,
+            old_randomizers,
+
+            new_records,
+            new_sn_nonce_randomness,
+            new_commitments,
+
+            predicate_comm,
+            predicate_rand,
+
+            local_data_comm,
+            local_data_rand,
+        } = context;
+
+        let signature_message = Self::signature_message(
+            &old_serial_numbers,
+            &new_commitments,
+            memorandum,
+            &ledger_digest,
+            &predicate_comm,
+            &local_data_comm,
+        )?;
+
+        let sig_pp = &comm_crh_sig_pp.sig_pp;
+        let mut signatures = Vec::with_capacity(old_records.len());
+        for (i, (secret_key, randomizer)) in old_address_secret_keys
+            .iter()
+            .zip(&old_randomizers)
+            .enumerate()
+        {
+            let sig_time = timer_start!(|| format!("Sign and randomize Tx contents {}", i));
+
+            // Sign transaction message
+            let signature =
+                Components::S::sign(sig_pp, &secret_key.sk_sig, &signature_message, rng)?;
+            let randomized_signature =
+                Components::S::randomize_signature(sig_pp, &signature, randomizer)?;
+            signatures.push(randomized_signature);
+
+            timer_end!(sig_time);
+        }
+
+        // The prover needs every part of the secret keys but `sk_sig`.
+        let old_proving_keys = old_address_secret_keys
+            .iter()
+            .map(AddressSecretKey::proving_key)
+            .collect();
+
+        timer_end!(authorize_time);
+        Ok(ExecuteAuthorization {
+            arity,
+            num_input_records,
+            num_output_records,
+            ledger_digest,
+
+            old_records: old_records.to_vec(),
+            old_witnesses,
+            old_proving_keys,
+            old_serial_numbers,
+
+            new_records,
+            new_sn_nonce_randomness,
+            new_commitments,
+
+            predicate_comm,
+            predicate_rand,
+            local_data_comm,
+            local_data_rand,
+
+            memorandum: *memorandum,
+            auxiliary: *auxiliary,
+            signatures,
+        })
+    }
+
+    /// Computes the proofs of an authorized transaction, and returns the new
+    /// records and the transaction. Needs no secret key beyond those in
+    /// `authorization`, so it can be run by an untrusted prover.
+    pub fn execute_authorized<L, R: Rng>(
+        parameters: &PublicParameters<Components>,
+        authorization: ExecuteAuthorization<Components>,
+        mut old_death_pred_proof_generator: impl FnMut(
+            &LocalData<Components>,
+        ) -> Vec<PrivatePredInput<Components>>,
+        mut new_birth_pred_proof_generator: impl FnMut(
+            &LocalData<Components>,
+        ) -> Vec<PrivatePredInput<Components>>,
+        ledger: &L,
+        rng: &mut R,
+    ) -> Result<(Vec<DPCRecord<Components>>, DPCTransaction<Components>), Error>
+    where
+        L: Ledger<
+            Parameters = <Components::D as LedgerDigest>::Parameters,
+            Commitment = <Components::RecC as CommitmentScheme>::Output,
+            SerialNumber = <Components::S as SignatureScheme>::PublicKey,
+            LedgerStateDigest = Components::D,
+            CommWitness = Components::LCW,
+        >,
+        <L as Ledger>::SnWitness: LedgerWitness<Components::D>,
+        <L as Ledger>::MemoWitness: LedgerWitness<Components::D>,
+    {
+        let prove_time = timer_start!(|| "DelegableDPC::ExecuteAuthorized");
+        let ExecuteAuthorization {
+            arity,
+            num_input_records,
+            num_output_records,
+            ledger_digest,
+
+            old_records,
+            old_witnesses,
+            old_proving_keys,
+            old_serial_numbers,
+
+            new_records,
+            new_sn_nonce_randomness,
+            new_commitments,
+
+            predicate_comm,
+            predicate_rand,
+            local_data_comm,
+            local_data_rand,
+
+            memorandum,
+            auxiliary,
+            signatures,
+        } = authorization;
+        let arity_pp = parameters
+            .arity_pp(arity)
+            .ok_or_else(|| format_err!("missing parameters for arity {:?}", arity))?;
+
+        // Check the signatures before spending time on the proofs.
+        let signature_message = Self::signature_message(
+            &old_serial_numbers,
+            &new_commitments,
+            &memorandum,
+            &ledger_digest,
+            &predicate_comm,
+            &local_data_comm,
+        )?;
+        let sig_pp = &parameters.comm_crh_sig_pp.sig_pp;
+        if signatures.len() != old_serial_numbers.len() {
+            return Err(format_err!(
+                "expected {} signatures",
+                old_serial_numbers.len()
+            ));
+        }
+        for (sn, signature) in old_serial_numbers.iter().zip(&signatures) {
+            if !Components::S::verify(sig_pp, sn, &signature_message, signature)? {
+                return Err(format_err!("invalid authorization signature"));
+            }
+        }
+
+        let local_data = LocalData {
+            comm_crh_sig_pp: parameters.comm_crh_sig_pp.clone(),
+
+            old_records:        old_records.clone(),
+            old_serial_numbers: old_serial_numbers.clone(),
+
+            new_records: new_records.clone(),
+
+            local_data_comm: local_data_comm.clone(),
+            local_data_rand: local_data_rand.clone(),
+        };
+        let mut old_death_pred_vk_and_proofs = old_death_pred_proof_generator(&local_data);
+        let mut new_birth_pred_vk_and_proofs = new_birth_pred_proof_generator(&local_data);
+        if old_death_pred_vk_and_proofs.len() != num_input_records
+            || new_birth_pred_vk_and_proofs.len() != num_output_records
+        {
+            return Err(format_err!(
+                "expected predicate proofs for {} old and {} new records",
+                num_input_records,
+                num_output_records
+            ));
+        }
+        let mut prove_empty_predicate = |position: usize| -> Result<_, Error> {
+            let circuit = EmptyPredicateCircuit::new(
+                &local_data.comm_crh_sig_pp,
+                &local_data.local_data_comm,
+                position as u8,
+            );
+            let pred_nizk_pp = &parameters.pred_nizk_pp;
+            Ok(PrivatePredInput {
+                vk:    pred_nizk_pp.vk.clone(),
+                proof: Components::PredicateNIZK::prove(&pred_nizk_pp.pk, circuit, rng)?,
+            })
+        };
+        arity.pad_old(
+            &mut old_death_pred_vk_and_proofs,
+            &mut prove_empty_predicate,
+        )?;
+        arity.pad_new(
+            &mut new_birth_pred_vk_and_proofs,
+            &mut prove_empty_predicate,
+        )?;
+
+        let core_proof = {
+            // The circuit does not use `sk_sig`.
+            let old_address_secret_keys = old_proving_keys
+                .iter()
+                .map(AddressProvingKey::to_secret_key)
+                .collect::<Vec<_>>();
+            let circuit = CoreChecksCircuit::new(
+                &parameters.comm_crh_sig_pp,
+                ledger.parameters(),
+                &ledger_digest,
+                &old_records,
+                &old_witnesses,
+                &old_address_secret_keys,
+                &old_serial_numbers,
+                &new_records,
+                &new_sn_nonce_randomness,
+                &new_commitments,
+                &predicate_comm,
+                &predicate_rand,
+                &local_data_comm,
+                &local_data_rand,
+                &memorandum,
+                &auxiliary,
+            );
+
+            Components::MainNIZK::prove(&arity_pp.core_nizk_pp.0, circuit, rng)?
+        };
+
+        let proof_checks_proof = {
+            let circuit = ProofCheckCircuit::new(
+                &parameters.comm_crh_sig_pp,
+                old_death_pred_vk_and_proofs.as_slice(),
+                new_birth_pred_vk_and_proofs.as_slice(),
+                &predicate_comm,
+                &predicate_rand,
+                &local_data_comm,
+            );
+
+            Components::ProofCheckNIZK::prove(&arity_pp.proof_check_nizk_pp.0, circuit, rng)?
+        };
+
+        let transaction = DPCTransaction::new(
+            old_serial_numbers,
+            new_commitments,
+            memorandum,
+            ledger_digest,
+            core_proof,
+            proof_checks_proof,
+            predicate_comm,
+            local_data_comm,
+            signatures,
+        );
+
+        timer_end!(prove_time);
+        Ok((new_records, transaction))
+    }
+}
@@ -1,7 +1,7 @@
 use crate::{
     crypto_primitives::CommitmentScheme,
     delegable_dpc::*,
-    dpc::{delegable_dpc::DPCRecord, Record},
+    dpc::{delegable_dpc::DPCRecord, Arity, Record},
     gadgets::Assignment,
 };
 use snark_gadgets::{uint8::UInt8, utils::AllocGadget};
@@ -34,44 +34,46 @@ pub struct PredicateHashInput<C: DelegableDPCComponents> {
     pub auxiliary: [u8; 32],
 }
 
-impl<C: DelegableDPCComponents> Default for PredicateHashInput<C> {
-    fn default() -> Self {
+impl<C: DelegableDPCComponents> PredicateHashInput<C> {
+    pub fn blank(arity: Arity) -> Self {
+        let num_input_records = arity.num_input_records;
+        let num_output_records = arity.num_output_records;
         Self {
             old_rec_comms:      vec![
                 <C::RecC as CommitmentScheme>::Output::default();
-                C::NUM_INPUT_RECORDS
+                num_input_records
             ],
             old_apks:           vec![
                 <C::AddrC as CommitmentScheme>::Output::default();
-                C::NUM_INPUT_RECORDS
+                num_input_records
             ],
-            old_dummy_flags:    vec![false; C::NUM_INPUT_RECORDS],
+            old_dummy_flags:    vec![false; num_input_records],
             old_payloads:       vec![
                 <DPCRecord<C> as Record>::Payload::default();
-                C::NUM_INPUT_RECORDS
+                num_input_records
             ],
-            old_death_pred_ids: vec![vec![0u8; 48]; C::NUM_INPUT_RECORDS],
-            old_birth_pred_ids: vec![vec![0u8; 48]; C::NUM_INPUT_RECORDS],
+            old_death_pred_ids: vec![vec![0u8; 48]; num_input_records],
+            old_birth_pred_ids: vec![vec![0u8; 48]; num_input_records],
             old_serial_numbers: vec![
                 <C::S as SignatureScheme>::PublicKey::default();
-                C::NUM_INPUT_RECORDS
+                num_input_records
             ],
 
             new_rec_comms:      vec![
                 <C::RecC as CommitmentScheme>::Output::default();
-                C::NUM_OUTPUT_RECORDS
+                num_output_records
             ],
             new_apks:           vec![
                 <C::AddrC as CommitmentScheme>::Output::default();
-                C::NUM_OUTPUT_RECORDS
+                num_output_records
             ],
-            new_dummy_flags:    vec![false; C::NUM_OUTPUT_RECORDS],
+            new_dummy_flags:    vec![false; num_output_records],
             new_payloads:       vec![
                 <DPCRecord<C> as Record>::Payload::default();
-                C::NUM_OUTPUT_RECORDS
+                num_output_records
             ],
-            new_death_pred_ids: vec![vec![0u8; 48]; C::NUM_OUTPUT_RECORDS],
-            new_birth_pred_ids: vec![vec![0u8; 48]; C::NUM_OUTPUT_RECORDS],
+            new_death_pred_ids: vec![vec![0u8; 48]; num_output_records],
+            new_birth_pred_ids: vec![vec![0u8; 48]; num_output_records],
 
             memo:      [0u8; 32],
             auxiliary: [0u8; 32],
@@ -81,7 +83,7 @@ impl<C: DelegableDPCComponents> Default for PredicateHashInput<C> {
 
 impl<C: DelegableDPCComponents> ToBytes for PredicateHashInput<C> {
     fn write<W: Write>(&self, mut writer: W) -> IoResult<()> {
-        for i in 0..C::NUM_INPUT_RECORDS {
+        for i in 0..self.old_rec_comms.len() {
             self.old_rec_comms[i].write(&mut writer)?;
             self.old_apks[i].write(&mut writer)?;
             self.old_dummy_flags[i].write(&mut writer)?;
@@ -91,7 +93,7 @@ impl<C: DelegableDPCComponents> ToBytes for PredicateHashInput<C> {
             self.old_serial_numbers[i].write(&mut writer)?;
         }
 
-        for i in 0..C::NUM_OUTPUT_RECORDS {
+        for i in 0..self.new_rec_comms.len() {
             self.new_rec_comms[i].write(&mut writer)?;
             self.new_apks[i].write(&mut writer)?;
             self.new_dummy_flags[i].write(&mut writer)?;
@@ -8,7 +8,10 @@ use crate::{
 };
 
 use crate::{
-    dpc::delegable_dpc::{CommCRHSigPublicParameters, DelegableDPCComponents, PrivatePredInput},
+    dpc::{
+        delegable_dpc::{CommCRHSigPublicParameters, DelegableDPCComponents, PrivatePredInput},
+        Arity,
+    },
     gadgets::dpc::delegable_dpc::execute_proof_check_gadget,
 };
 
@@ -78,9 +81,10 @@ impl<C: DelegableDPCComponents> ProofCheckCircuit<C> {
     pub fn blank(
         comm_crh_sig_parameters: &CommCRHSigPublicParameters<C>,
         predicate_nizk_vk_and_proof: &PrivatePredInput<C>,
+        arity: Arity,
     ) -> Self {
-        let num_input_records = C::NUM_INPUT_RECORDS;
-        let num_output_records = C::NUM_OUTPUT_RECORDS;
+        let num_input_records = arity.num_input_records;
+        let num_output_records = arity.num_output_records;
 
         let old_private_pred_inputs =
             Some(vec![predicate_nizk_vk_and_proof.clone(); num_input_records]);
@@ -119,13 +123,6 @@ impl<C: DelegableDPCComponents> ProofCheckCircuit<C> {
         predicate_rand: &<C::PredVkComm as CommitmentScheme>::Randomness,
         local_data_comm: &<C::LocalDataComm as CommitmentScheme>::Output,
     ) -> Self {
-        let num_input_records = C::NUM_INPUT_RECORDS;
-        let num_output_records = C::NUM_OUTPUT_RECORDS;
-
-        assert_eq!(num_input_records, old_private_pred_inputs.len());
-
-        assert_eq!(num_output_records, new_private_pred_inputs.len());
-
         Self {
             comm_crh_sig_parameters: Some(comm_crh_sig_parameters.clone()),
 
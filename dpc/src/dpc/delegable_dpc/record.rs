@@ -2,11 +2,18 @@ use crate::{
     crypto_primitives::{CommitmentScheme, FixedLengthCRH, SignatureScheme},
     dpc::{
         delegable_dpc::{AddressPublicKey, DPCPredicate, DelegableDPCComponents},
+        serialization::{read_version, write_version},
         Record,
     },
 };
-use algebra::to_bytes;
-use std::marker::PhantomData;
+use algebra::{
+    bytes::{FromBytes, ToBytes},
+    to_bytes,
+};
+use std::{
+    io::{Read, Result as IoResult, Write},
+    marker::PhantomData,
+};
 
 #[derive(Derivative)]
 #[derivative(
@@ -33,10 +40,52 @@ pub struct DPCRecord<C: DelegableDPCComponents> {
 }
 
 fn default_predicate_hash<C: FixedLengthCRH>() -> Vec<u8> {
-    use algebra::bytes::ToBytes;
     to_bytes![C::Output::default()].unwrap()
 }
 
+impl<C: DelegableDPCComponents> ToBytes for DPCRecord<C> {
+    fn write<W: Write>(&self, mut writer: W) -> IoResult<()> {
+        write_version(&mut writer)?;
+        self.address_public_key.write(&mut writer)?;
+        self.is_dummy.write(&mut writer)?;
+        self.payload.write(&mut writer)?;
+        self.birth_predicate_repr.write(&mut writer)?;
+        self.death_predicate_repr.write(&mut writer)?;
+        self.serial_number_nonce.write(&mut writer)?;
+        self.commitment.write(&mut writer)?;
+        self.commitment_randomness.write(&mut writer)
+    }
+}
+
+impl<C: DelegableDPCComponents> FromBytes for DPCRecord<C> {
+    fn read<R: Read>(mut reader: R) -> IoResult<Self> {
+        read_version(&mut reader)?;
+        let address_public_key = FromBytes::read(&mut reader)?;
+        let is_dummy = FromBytes::read(&mut reader)?;
+        let payload = FromBytes::read(&mut reader)?;
+        // Predicate representations are hashes of verification keys, so they
+        // are read as such to check their length.
+        let birth_predicate_repr: <C::PredVkH as FixedLengthCRH>::Output =
+            FromBytes::read(&mut reader)?;
+        let death_predicate_repr: <C::PredVkH as FixedLengthCRH>::Output =
+            FromBytes::read(&mut reader)?;
+        let serial_number_nonce = FromBytes::read(&mut reader)?;
+        let commitment = FromBytes::read(&mut reader)?;
+        let commitment_randomness = FromBytes::read(&mut reader)?;
+        Ok(Self {
+            address_public_key,
+            is_dummy,
+            payload,
+            birth_predicate_repr: to_bytes![birth_predicate_repr]?,
+            death_predicate_repr: to_bytes![death_predicate_repr]?,
+            serial_number_nonce,
+            commitment,
+            commitment_randomness,
+            _components: PhantomData,
+        })
+    }
+}
+
 impl<C: DelegableDPCComponents> Record for DPCRecord<C> {
     type AddressPublicKey = AddressPublicKey<C>;
     type Commitment = <C::RecC as CommitmentScheme>::Output;
@@ -42,7 +42,7 @@ use crate::gadgets::prf::blake2s::Blake2sGadget;
 
 use crate::gadgets::signature::schnorr::SchnorrRandomizePkGadget;
 
-use crate::dpc::{DPCScheme, Record};
+use crate::dpc::{Arity, DPCScheme, Record, Transaction};
 
 use crate::dpc::delegable_dpc::DPC;
 use crate::dpc::delegable_dpc::ExecuteContext;
@@ -53,6 +53,10 @@ use crate::dpc::delegable_dpc::predicate_circuit::*;
 use crate::dpc::delegable_dpc::predicate::PrivatePredInput;
 use crate::dpc::delegable_dpc::predicate::DPCPredicate;
 use crate::dpc::delegable_dpc::transaction::DPCTransaction;
+use crate::dpc::delegable_dpc::record::DPCRecord;
+use crate::dpc::delegable_dpc::address::{AddressPublicKey, AddressSecretKey};
+use crate::dpc::delegable_dpc::parameters::{PublicParameters, VerifierParameters};
+use crate::dpc::serialization::from_bytes;
 
 use crate::ledger::{CommPath, Digest, IdealLedger, Ledger};
 
@@ -111,8 +115,10 @@ impl PedersenWindow for AddressWindow {
 struct Components;
 
 impl DelegableDPCComponents for Components {
-    const NUM_INPUT_RECORDS: usize = NUM_INPUT_RECORDS;
-    const NUM_OUTPUT_RECORDS: usize = NUM_OUTPUT_RECORDS;
+    const ARITIES: &'static [Arity] = &[
+        Arity::new(1, 1),
+        Arity::new(NUM_INPUT_RECORDS, NUM_OUTPUT_RECORDS),
+    ];
     type E = CoreEngine;
     type ProofCheckE = ProofCheckEngine;
 
@@ -479,6 +485,12 @@ fn execution() {
     let ledger_parameters = MerkleTreeIdealLedger::setup(rng).expect("Ledger setup failed");
     let parameters = <InstantiatedDPC as DPCScheme<MerkleTreeIdealLedger>>::setup(&ledger_parameters, rng).expect("DPC setup failed");
 
+    // Store the parameters and continue with the ones loaded back.
+    let mut parameters_bytes = vec![];
+    parameters.store(&mut parameters_bytes).unwrap();
+    let parameters = PublicParameters::<Components>::load(&parameters_bytes[..]).unwrap();
+    let verifier_parameters = VerifierParameters::<Components>::load(&parameters_bytes[..]).unwrap();
+    assert!(verifier_parameters.pred_nizk_vk == parameters.pred_nizk_pp.vk);
 
     // Create genesis record
     let genesis_metadata = [1u8; 32];
@@ -541,7 +553,7 @@ fn execution() {
     let new_private_predicate_input = vec![test_predicate_private_input.clone(); NUM_OUTPUT_RECORDS];
     let auxiliary1 = [1u8; 32];
 
-    let (_records1, transaction1) = InstantiatedDPC::execute(
+    let (records1, transaction1) = InstantiatedDPC::execute(
         &parameters,
         &old_records1,
         &old_asks1,
@@ -558,9 +570,168 @@ fn execution() {
         rng,
     ).unwrap();
 
-    let _is_valid1 = InstantiatedDPC::verify(&parameters, &transaction1, &ledger).unwrap();
+    let _is_valid1 = InstantiatedDPC::verify(&verifier_parameters, &transaction1, &ledger).unwrap();
+
+    // Round-trip the transaction, a new record and the address through their encodings.
+    let transaction_bytes = to_bytes![transaction1].unwrap();
+    let decoded_transaction: Tx = from_bytes(&transaction_bytes).unwrap();
+    assert_eq!(to_bytes![decoded_transaction].unwrap(), transaction_bytes);
+    assert!(from_bytes::<Tx>(&transaction_bytes[..transaction_bytes.len() - 1]).is_err());
+
+    let record_bytes = to_bytes![records1[0]].unwrap();
+    let decoded_record: DPCRecord<Components> = from_bytes(&record_bytes).unwrap();
+    assert_eq!(decoded_record.commitment(), records1[0].commitment());
+    assert_eq!(to_bytes![decoded_record].unwrap(), record_bytes);
+
+    let public_key_bytes = to_bytes![address1.public_key].unwrap();
+    let decoded_public_key: AddressPublicKey<Components> = from_bytes(&public_key_bytes).unwrap();
+    assert_eq!(to_bytes![decoded_public_key].unwrap(), public_key_bytes);
+    let secret_key_bytes = to_bytes![address1.secret_key].unwrap();
+    let decoded_secret_key: AddressSecretKey<Components> = from_bytes(&secret_key_bytes).unwrap();
+    assert_eq!(to_bytes![decoded_secret_key].unwrap(), secret_key_bytes);
 
     ledger.push(transaction1).unwrap();
 
     assert_eq!(ledger.len(), 1);
+
+    // One old and one new record are spent in the smallest arity. With a
+    // second new record only the larger arity fits, and the transaction is
+    // padded with a dummy old record.
+    for num_output_records in 1..=NUM_OUTPUT_RECORDS {
+        let i = num_output_records - 1;
+        let sn_nonce = SnNonceCRH::evaluate(&parameters.sn_nonce_crh_pp(), &[1u8 + i as u8; 1]).unwrap();
+        let dummy_record = InstantiatedDPC::generate_record(
+            &parameters.comm_crh_sig_pp,
+            &sn_nonce,
+            &genesis_address.public_key,
+            true,
+            &[0u8; 32],
+            &test_predicate,
+            &test_predicate,
+            rng,
+        ).unwrap();
+        let (records, transaction) = InstantiatedDPC::execute(
+            &parameters,
+            &[dummy_record],
+            &[genesis_address.secret_key.clone()],
+            |_| vec![test_predicate_private_input.clone()],
+            &vec![address1.public_key.clone(); num_output_records],
+            &vec![false; num_output_records],
+            &vec![test_payload; num_output_records],
+            &vec![test_predicate.clone(); num_output_records],
+            &vec![test_predicate.clone(); num_output_records],
+            |_| vec![test_predicate_private_input.clone(); num_output_records],
+            &auxiliary1,
+            &[0u8; 32],
+            &ledger,
+            rng,
+        ).unwrap();
+        let arity = Arity::select(Components::ARITIES, 1, num_output_records).unwrap();
+        assert_eq!(arity, Components::ARITIES[i]);
+        assert_eq!(records.len(), arity.num_output_records);
+        assert_eq!(transaction.old_serial_numbers().len(), arity.num_input_records);
+        assert!(InstantiatedDPC::verify(&verifier_parameters, &transaction, &ledger).unwrap());
+    }
+}
+
+#[test]
+fn delegated_execution() {
+    let rng = &mut thread_rng();
+
+    let ledger_parameters = MerkleTreeIdealLedger::setup(rng).expect("Ledger setup failed");
+    let parameters = <InstantiatedDPC as DPCScheme<MerkleTreeIdealLedger>>::setup(&ledger_parameters, rng).expect("DPC setup failed");
+    let verifier_parameters = parameters.verifier_parameters();
+
+    let genesis_metadata = [1u8; 32];
+    let genesis_address =
+        <InstantiatedDPC as DPCScheme<MerkleTreeIdealLedger>>::create_address(&parameters, &genesis_metadata, rng)
+            .unwrap();
+    let genesis_sn_nonce = SnNonceCRH::evaluate(&parameters.sn_nonce_crh_pp(), &[0u8; 1]).unwrap();
+    let pred_vk_bytes = to_bytes![PredVkCRH::evaluate(
+        &parameters.comm_crh_sig_pp.pred_vk_crh_pp,
+        &to_bytes![parameters.pred_nizk_pp.vk].unwrap()
+    ).unwrap()].unwrap();
+    let predicate = Predicate::new(pred_vk_bytes);
+    let genesis_record = InstantiatedDPC::generate_record(
+        &parameters.comm_crh_sig_pp,
+        &genesis_sn_nonce,
+        &genesis_address.public_key,
+        true,
+        &[0u8; 32],
+        &predicate,
+        &predicate,
+        rng,
+    ).unwrap();
+    let (genesis_sn, _) = InstantiatedDPC::generate_sn(
+        &parameters.comm_crh_sig_pp,
+        &genesis_record,
+        &genesis_address.secret_key
+    ).unwrap();
+    let ledger = MerkleTreeIdealLedger::new(
+        ledger_parameters,
+        genesis_record.commitment(),
+        genesis_sn,
+        [0u8; 32],
+    );
+
+    let address = <InstantiatedDPC as DPCScheme<MerkleTreeIdealLedger>>::create_address(&parameters, &[2u8; 32], rng).unwrap();
+    let private_predicate_input = PrivatePredInput {
+        vk: parameters.pred_nizk_pp.vk.clone(),
+        proof: parameters.pred_nizk_pp.proof.clone(),
+    };
+
+    // The owner of the old records authorizes the transaction...
+    let authorize = |rng: &mut _| InstantiatedDPC::authorize(
+        &parameters,
+        &vec![genesis_record.clone(); NUM_INPUT_RECORDS],
+        &vec![genesis_address.secret_key.clone(); NUM_INPUT_RECORDS],
+        &vec![address.public_key.clone(); NUM_OUTPUT_RECORDS],
+        &vec![false; NUM_OUTPUT_RECORDS],
+        &vec![[1u8; 32]; NUM_OUTPUT_RECORDS],
+        &vec![predicate.clone(); NUM_OUTPUT_RECORDS],
+        &vec![predicate.clone(); NUM_OUTPUT_RECORDS],
+        &[1u8; 32],
+        &[0u8; 32],
+        &ledger,
+        rng,
+    ).unwrap();
+    let authorization = authorize(rng);
+    for proving_key in authorization.old_proving_keys() {
+        assert!(proving_key.pk_sig == genesis_address.secret_key.pk_sig);
+    }
+
+    // ... and the prover computes the proofs without `sk_sig`.
+    let (records, transaction) = InstantiatedDPC::execute_authorized(
+        &parameters,
+        authorization,
+        |_| vec![private_predicate_input.clone(); NUM_INPUT_RECORDS],
+        |_| vec![private_predicate_input.clone(); NUM_OUTPUT_RECORDS],
+        &ledger,
+        rng,
+    ).unwrap();
+    assert_eq!(records.len(), NUM_OUTPUT_RECORDS);
+    assert!(InstantiatedDPC::verify(&verifier_parameters, &transaction, &ledger).unwrap());
+
+    // The prover cannot change what the owner authorized.
+    let mut authorization = authorize(rng);
+    authorization.memorandum = [2u8; 32];
+    assert!(InstantiatedDPC::execute_authorized(
+        &parameters,
+        authorization,
+        |_| vec![private_predicate_input.clone(); NUM_INPUT_RECORDS],
+        |_| vec![private_predicate_input.clone(); NUM_OUTPUT_RECORDS],
+        &ledger,
+        rng,
+    ).is_err());
+
+    let mut authorization = authorize(rng);
+    authorization.signatures.swap(0, 1);
+    assert!(InstantiatedDPC::execute_authorized(
+        &parameters,
+        authorization,
+        |_| vec![private_predicate_input.clone(); NUM_INPUT_RECORDS],
+        |_| vec![private_predicate_input.clone(); NUM_OUTPUT_RECORDS],
+        &ledger,
+        rng,
+    ).is_err());
 }
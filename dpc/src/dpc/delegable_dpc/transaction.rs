@@ -1,7 +1,13 @@
 use crate::{
     crypto_primitives::{CommitmentScheme, SignatureScheme, NIZK},
-    dpc::{delegable_dpc::DelegableDPCComponents, Transaction},
+    dpc::{
+        delegable_dpc::DelegableDPCComponents,
+        serialization::{invalid_data, read_vec, read_version, write_vec, write_version},
+        Arity, Transaction,
+    },
 };
+use algebra::bytes::{FromBytes, ToBytes};
+use std::io::{Read, Result as IoResult, Write};
 
 #[derive(Derivative)]
 #[derivative(
@@ -73,6 +79,76 @@ impl<C: DelegableDPCComponents> DPCTransaction<C> {
     }
 }
 
+impl<C: DelegableDPCComponents> ToBytes for DPCTransaction<C> {
+    fn write<W: Write>(&self, mut writer: W) -> IoResult<()> {
+        write_version(&mut writer)?;
+        write_vec(&self.old_serial_numbers, &mut writer)?;
+        write_vec(&self.new_commitments, &mut writer)?;
+        self.memorandum.write(&mut writer)?;
+        self.stuff.write(&mut writer)
+    }
+}
+
+impl<C: DelegableDPCComponents> FromBytes for DPCTransaction<C> {
+    fn read<R: Read>(mut reader: R) -> IoResult<Self> {
+        read_version(&mut reader)?;
+        let max_arity = Arity::max_of(C::ARITIES);
+        let old_serial_numbers: Vec<_> = read_vec(&mut reader, max_arity.num_input_records)?;
+        let new_commitments: Vec<_> = read_vec(&mut reader, max_arity.num_output_records)?;
+        let arity = Arity::new(old_serial_numbers.len(), new_commitments.len());
+        if !C::ARITIES.contains(&arity) {
+            return Err(invalid_data("transaction has an unsupported arity"));
+        }
+        let memorandum = FromBytes::read(&mut reader)?;
+        let stuff: DPCStuff<C> = FromBytes::read(&mut reader)?;
+        // Each old record is spent with a signature under its serial number.
+        if stuff.signatures.len() != old_serial_numbers.len() {
+            return Err(invalid_data(
+                "transaction has a different number of signatures and serial numbers",
+            ));
+        }
+        Ok(Self {
+            old_serial_numbers,
+            new_commitments,
+            memorandum,
+            stuff,
+        })
+    }
+}
+
+impl<C: DelegableDPCComponents> ToBytes for DPCStuff<C> {
+    fn write<W: Write>(&self, mut writer: W) -> IoResult<()> {
+        write_version(&mut writer)?;
+        self.digest.write(&mut writer)?;
+        self.core_proof.write(&mut writer)?;
+        self.predicate_proof.write(&mut writer)?;
+        self.predicate_comm.write(&mut writer)?;
+        self.local_data_comm.write(&mut writer)?;
+        write_vec(&self.signatures, &mut writer)
+    }
+}
+
+impl<C: DelegableDPCComponents> FromBytes for DPCStuff<C> {
+    fn read<R: Read>(mut reader: R) -> IoResult<Self> {
+        read_version(&mut reader)?;
+        let max_num_input_records = Arity::max_of(C::ARITIES).num_input_records;
+        let digest = FromBytes::read(&mut reader)?;
+        let core_proof = FromBytes::read(&mut reader)?;
+        let predicate_proof = FromBytes::read(&mut reader)?;
+        let predicate_comm = FromBytes::read(&mut reader)?;
+        let local_data_comm = FromBytes::read(&mut reader)?;
+        let signatures = read_vec(&mut reader, max_num_input_records)?;
+        Ok(Self {
+            digest,
+            core_proof,
+            predicate_proof,
+            predicate_comm,
+            local_data_comm,
+            signatures,
+        })
+    }
+}
+
 impl<C: DelegableDPCComponents> Transaction for DPCTransaction<C> {
     type Stuff = DPCStuff<C>;
     type SerialNumber = <C::S as SignatureScheme>::PublicKey;
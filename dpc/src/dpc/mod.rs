@@ -4,9 +4,94 @@ use std::hash::Hash;
 
 pub mod delegable_dpc;
 pub mod plain_dpc;
+pub mod serialization;
 
 use crate::ledger::Ledger;
 
+/// The number of old and new records of a transaction.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Arity {
+    pub num_input_records:  usize,
+    pub num_output_records: usize,
+}
+
+impl Arity {
+    pub const fn new(num_input_records: usize, num_output_records: usize) -> Self {
+        Self {
+            num_input_records,
+            num_output_records,
+        }
+    }
+
+    /// Returns whether a transaction of this arity has room for
+    /// `num_input_records` old records and `num_output_records` new records.
+    pub fn fits(&self, num_input_records: usize, num_output_records: usize) -> bool {
+        num_input_records <= self.num_input_records && num_output_records <= self.num_output_records
+    }
+
+    /// Returns the smallest of `arities` with room for `num_input_records` old
+    /// records and `num_output_records` new records.
+    pub fn select(
+        arities: &[Arity],
+        num_input_records: usize,
+        num_output_records: usize,
+    ) -> Option<Self> {
+        arities
+            .iter()
+            .filter(|arity| arity.fits(num_input_records, num_output_records))
+            .min_by_key(|arity| arity.num_input_records + arity.num_output_records)
+            .cloned()
+    }
+
+    /// Pads `old`, which holds an entry for each of the first old records of a
+    /// transaction, with `dummy(i)` for every remaining position `i` of this
+    /// arity.
+    pub(crate) fn pad_old<T>(
+        &self,
+        old: &mut Vec<T>,
+        dummy: impl FnMut(usize) -> Result<T, Error>,
+    ) -> Result<(), Error> {
+        pad(old, self.num_input_records, dummy)
+    }
+
+    /// Like `pad_old`, but for the new records of a transaction.
+    pub(crate) fn pad_new<T>(
+        &self,
+        new: &mut Vec<T>,
+        dummy: impl FnMut(usize) -> Result<T, Error>,
+    ) -> Result<(), Error> {
+        pad(new, self.num_output_records, dummy)
+    }
+
+    /// Returns the largest numbers of old and new records allowed by any of
+    /// `arities`.
+    pub fn max_of(arities: &[Arity]) -> Self {
+        Self {
+            num_input_records:  arities
+                .iter()
+                .map(|arity| arity.num_input_records)
+                .max()
+                .unwrap_or(0),
+            num_output_records: arities
+                .iter()
+                .map(|arity| arity.num_output_records)
+                .max()
+                .unwrap_or(0),
+        }
+    }
+}
+
+fn pad<T>(
+    items: &mut Vec<T>,
+    len: usize,
+    mut dummy: impl FnMut(usize) -> Result<T, Error>,
+) -> Result<(), Error> {
+    for i in items.len()..len {
+        items.push(dummy(i)?);
+    }
+    Ok(())
+}
+
 pub trait AddressKeyPair {
     type AddressPublicKey: Default;
     type AddressSecretKey: Default;
@@ -81,6 +166,7 @@ pub trait DPCScheme<L: Ledger> {
     type Metadata: ?Sized;
     type Payload;
     type Parameters;
+    type VerifierParameters;
     type Predicate: Predicate<PrivateWitness = Self::PrivatePredInput>;
     type PrivatePredInput;
     type Record: Record<
@@ -105,7 +191,11 @@ pub trait DPCScheme<L: Ledger> {
     ) -> Result<Self::AddressKeyPair, Error>;
 
     /// Returns new records and a transaction based on the authorized
-    /// consumption of old records.
+    /// consumption of old records. The transaction has the smallest supported
+    /// arity with room for the given records, and it is an error if there is
+    /// none; the remaining positions are filled with dummy records guarded by
+    /// the empty predicate. The proof generators get the local data of the
+    /// padded transaction, and only prove the predicates of the given records.
     fn execute<R: Rng>(
         parameters: &Self::Parameters,
 
@@ -127,8 +217,9 @@ pub trait DPCScheme<L: Ledger> {
     ) -> Result<(Vec<Self::Record>, Self::Transaction), Error>;
 
     /// Returns true iff the transaction is valid according to the ledger.
+    /// Verification only needs the verifier part of the public parameters.
     fn verify(
-        parameters: &Self::Parameters,
+        parameters: &Self::VerifierParameters,
         transaction: &Self::Transaction,
         ledger: &L,
     ) -> Result<bool, Error>;
@@ -1,9 +1,13 @@
 use crate::{
-    crypto_primitives::{CommitmentScheme, PRF},
-    dpc::{plain_dpc::PlainDPCComponents, AddressKeyPair},
+    crypto_primitives::{CommitmentScheme, EncryptionScheme, PRF},
+    dpc::{
+        plain_dpc::PlainDPCComponents,
+        serialization::{read_version, write_version},
+        AddressKeyPair,
+    },
 };
-use algebra::bytes::ToBytes;
-use std::io::{Result as IoResult, Write};
+use algebra::bytes::{FromBytes, ToBytes};
+use std::io::{Read, Result as IoResult, Write};
 
 #[derive(Derivative)]
 #[derivative(
@@ -12,12 +16,27 @@ use std::io::{Result as IoResult, Write};
     Debug(bound = "C: PlainDPCComponents")
 )]
 pub struct AddressPublicKey<C: PlainDPCComponents> {
-    pub public_key: <C::AddrC as CommitmentScheme>::Output,
+    pub public_key:     <C::AddrC as CommitmentScheme>::Output,
+    pub encryption_key: <C::RecEnc as EncryptionScheme>::PublicKey,
 }
 
 impl<C: PlainDPCComponents> ToBytes for AddressPublicKey<C> {
-    fn write<W: Write>(&self, writer: W) -> IoResult<()> {
-        self.public_key.write(writer)
+    fn write<W: Write>(&self, mut writer: W) -> IoResult<()> {
+        write_version(&mut writer)?;
+        self.public_key.write(&mut writer)?;
+        self.encryption_key.write(&mut writer)
+    }
+}
+
+impl<C: PlainDPCComponents> FromBytes for AddressPublicKey<C> {
+    fn read<R: Read>(mut reader: R) -> IoResult<Self> {
+        read_version(&mut reader)?;
+        let public_key = FromBytes::read(&mut reader)?;
+        let encryption_key = FromBytes::read(&mut reader)?;
+        Ok(Self {
+            public_key,
+            encryption_key,
+        })
     }
 }
 
@@ -28,9 +47,74 @@ impl<C: PlainDPCComponents> ToBytes for AddressPublicKey<C> {
     Debug(bound = "C: PlainDPCComponents")
 )]
 pub struct AddressSecretKey<C: PlainDPCComponents> {
-    pub sk_prf:   <C::P as PRF>::Seed,
-    pub metadata: [u8; 32],
-    pub r_pk:     <C::AddrC as CommitmentScheme>::Randomness,
+    pub sk_prf:         <C::P as PRF>::Seed,
+    pub metadata:       [u8; 32],
+    pub r_pk:           <C::AddrC as CommitmentScheme>::Randomness,
+    pub decryption_key: <C::RecEnc as EncryptionScheme>::SecretKey,
+}
+
+impl<C: PlainDPCComponents> ToBytes for AddressSecretKey<C> {
+    fn write<W: Write>(&self, mut writer: W) -> IoResult<()> {
+        write_version(&mut writer)?;
+        self.sk_prf.write(&mut writer)?;
+        self.metadata.write(&mut writer)?;
+        self.r_pk.write(&mut writer)?;
+        self.decryption_key.write(&mut writer)
+    }
+}
+
+impl<C: PlainDPCComponents> FromBytes for AddressSecretKey<C> {
+    fn read<R: Read>(mut reader: R) -> IoResult<Self> {
+        read_version(&mut reader)?;
+        let sk_prf = FromBytes::read(&mut reader)?;
+        let metadata = FromBytes::read(&mut reader)?;
+        let r_pk = FromBytes::read(&mut reader)?;
+        let decryption_key = FromBytes::read(&mut reader)?;
+        Ok(Self {
+            sk_prf,
+            metadata,
+            r_pk,
+            decryption_key,
+        })
+    }
+}
+
+/// Read-only key for an address. It suffices to decrypt and recognise incoming
+/// records and to compute their serial numbers, but not to spend them: proving
+/// ownership of the address also requires opening the address commitment with
+/// `r_pk`, which the viewing key omits.
+#[derive(Derivative)]
+#[derivative(
+    Clone(bound = "C: PlainDPCComponents"),
+    Debug(bound = "C: PlainDPCComponents")
+)]
+pub struct AddressViewingKey<C: PlainDPCComponents> {
+    pub public_key:     AddressPublicKey<C>,
+    pub sk_prf:         <C::P as PRF>::Seed,
+    pub decryption_key: <C::RecEnc as EncryptionScheme>::SecretKey,
+}
+
+impl<C: PlainDPCComponents> ToBytes for AddressViewingKey<C> {
+    fn write<W: Write>(&self, mut writer: W) -> IoResult<()> {
+        write_version(&mut writer)?;
+        self.public_key.write(&mut writer)?;
+        self.sk_prf.write(&mut writer)?;
+        self.decryption_key.write(&mut writer)
+    }
+}
+
+impl<C: PlainDPCComponents> FromBytes for AddressViewingKey<C> {
+    fn read<R: Read>(mut reader: R) -> IoResult<Self> {
+        read_version(&mut reader)?;
+        let public_key = FromBytes::read(&mut reader)?;
+        let sk_prf = FromBytes::read(&mut reader)?;
+        let decryption_key = FromBytes::read(&mut reader)?;
+        Ok(Self {
+            public_key,
+            sk_prf,
+            decryption_key,
+        })
+    }
 }
 
 #[derive(Derivative)]
@@ -40,6 +124,17 @@ pub struct AddressPair<C: PlainDPCComponents> {
     pub secret_key: AddressSecretKey<C>,
 }
 
+impl<C: PlainDPCComponents> AddressPair<C> {
+    /// Derives the viewing key of this address.
+    pub fn viewing_key(&self) -> AddressViewingKey<C> {
+        AddressViewingKey {
+            public_key:     self.public_key.clone(),
+            sk_prf:         self.secret_key.sk_prf.clone(),
+            decryption_key: self.secret_key.decryption_key.clone(),
+        }
+    }
+}
+
 impl<C: PlainDPCComponents> AddressKeyPair for AddressPair<C> {
     type AddressSecretKey = AddressSecretKey<C>;
     type AddressPublicKey = AddressPublicKey<C>;
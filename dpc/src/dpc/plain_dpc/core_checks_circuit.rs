@@ -8,7 +8,10 @@ use crate::{
 };
 
 use crate::{
-    dpc::plain_dpc::{AddressSecretKey, CommAndCRHPublicParameters, DPCRecord, PlainDPCComponents},
+    dpc::{
+        plain_dpc::{AddressSecretKey, CommAndCRHPublicParameters, DPCRecord, PlainDPCComponents},
+        Arity,
+    },
     gadgets::dpc::plain_dpc::execute_core_checks_gadget,
     ledger::LedgerDigest,
 };
@@ -32,7 +35,10 @@ pub struct CoreChecksVerifierInput<C: PlainDPCComponents> {
     // Predicate input commitment and memo
     pub predicate_comm:  <C::PredVkComm as CommitmentScheme>::Output,
     pub local_data_comm: <C::LocalDataComm as CommitmentScheme>::Output,
-    pub memo:            [u8; 32],
+    pub memo:            Vec<u8>,
+
+    // Hash of the new records encrypted to their owners
+    pub encrypted_records_digest: [u8; 32],
 }
 
 impl<C: PlainDPCComponents> ToEngineFr<C::E> for CoreChecksVerifierInput<C>
@@ -78,7 +84,10 @@ where
         }
 
         v.extend_from_slice(&self.predicate_comm.to_engine_fr()?);
-        v.extend_from_slice(&ToEngineFr::<C::E>::to_engine_fr(self.memo.as_ref())?);
+        v.extend_from_slice(&ToEngineFr::<C::E>::to_engine_fr(self.memo.as_slice())?);
+        v.extend_from_slice(&ToEngineFr::<C::E>::to_engine_fr(
+            &self.encrypted_records_digest[..],
+        )?);
         v.extend_from_slice(&self.local_data_comm.to_engine_fr()?);
 
         Ok(v)
@@ -112,17 +121,20 @@ pub struct CoreChecksCircuit<C: PlainDPCComponents> {
     local_data_comm: Option<<C::LocalDataComm as CommitmentScheme>::Output>,
     local_data_rand: Option<<C::LocalDataComm as CommitmentScheme>::Randomness>,
 
-    memo:      Option<[u8; 32]>,
+    memo:      Option<Vec<u8>>,
     auxiliary: Option<[u8; 32]>,
+
+    encrypted_records_digest: Option<[u8; 32]>,
 }
 
 impl<C: PlainDPCComponents> CoreChecksCircuit<C> {
     pub fn blank(
         comm_and_crh_parameters: &CommAndCRHPublicParameters<C>,
         ledger_parameters: &<C::D as LedgerDigest>::Parameters,
+        arity: Arity,
     ) -> Self {
-        let num_input_records = C::NUM_INPUT_RECORDS;
-        let num_output_records = C::NUM_OUTPUT_RECORDS;
+        let num_input_records = arity.num_input_records;
+        let num_output_records = arity.num_output_records;
         let digest = C::D::default();
 
         let old_sn = vec![<C::P as PRF>::Output::default(); num_input_records];
@@ -135,7 +147,8 @@ impl<C: PlainDPCComponents> CoreChecksCircuit<C> {
         let new_records = vec![DPCRecord::default(); num_output_records];
 
         let auxiliary = [1u8; 32];
-        let memo = [0u8; 32];
+        let memo = vec![0u8; C::MEMO_SIZE];
+        let encrypted_records_digest = [0u8; 32];
 
         let predicate_comm = <C::PredVkComm as CommitmentScheme>::Output::default();
         let predicate_rand = <C::PredVkComm as CommitmentScheme>::Randomness::default();
@@ -169,6 +182,8 @@ impl<C: PlainDPCComponents> CoreChecksCircuit<C> {
             local_data_rand: Some(local_data_rand),
             memo:            Some(memo),
             auxiliary:       Some(auxiliary),
+
+            encrypted_records_digest: Some(encrypted_records_digest),
         }
     }
 
@@ -198,18 +213,18 @@ impl<C: PlainDPCComponents> CoreChecksCircuit<C> {
         local_data_comm: &<C::LocalDataComm as CommitmentScheme>::Output,
         local_data_rand: &<C::LocalDataComm as CommitmentScheme>::Randomness,
 
-        memo: &[u8; 32],
+        memo: &[u8],
         auxiliary: &[u8; 32],
+
+        encrypted_records_digest: &[u8; 32],
     ) -> Self {
-        let num_input_records = C::NUM_INPUT_RECORDS;
-        let num_output_records = C::NUM_OUTPUT_RECORDS;
+        let num_input_records = old_records.len();
+        let num_output_records = new_records.len();
 
-        assert_eq!(num_input_records, old_records.len());
         assert_eq!(num_input_records, old_witnesses.len());
         assert_eq!(num_input_records, old_address_secret_keys.len());
         assert_eq!(num_input_records, old_serial_numbers.len());
 
-        assert_eq!(num_output_records, new_records.len());
         assert_eq!(num_output_records, new_sn_nonce_randomness.len());
         assert_eq!(num_output_records, new_commitments.len());
 
@@ -239,8 +254,10 @@ impl<C: PlainDPCComponents> CoreChecksCircuit<C> {
             local_data_comm: Some(local_data_comm.clone()),
             local_data_rand: Some(local_data_rand.clone()),
 
-            memo:      Some(memo.clone()),
+            memo:      Some(memo.to_vec()),
             auxiliary: Some(auxiliary.clone()),
+
+            encrypted_records_digest: Some(*encrypted_records_digest),
         }
     }
 }
@@ -270,6 +287,7 @@ impl<C: PlainDPCComponents> Circuit<C::E> for CoreChecksCircuit<C> {
             self.local_data_rand.get()?,
             self.memo.get()?,
             self.auxiliary.get()?,
+            self.encrypted_records_digest.get()?,
         )?;
         Ok(())
     }
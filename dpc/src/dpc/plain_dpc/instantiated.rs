@@ -1,14 +1,18 @@
 use algebra::curves::{
-    bls12_377::Bls12_377, edwards_bls12::EdwardsProjective as EdwardsBls,
-    edwards_sw6::EdwardsProjective as EdwardsSW, sw6::SW6,
+    bls12_377::Bls12_377,
+    edwards_bls12::{EdwardsAffine as EdwardsBlsAffine, EdwardsProjective as EdwardsBls},
+    edwards_sw6::EdwardsProjective as EdwardsSW,
+    sw6::SW6,
 };
+use blake2::Blake2s as Blake2sHash;
 
 use crate::crypto_primitives::{
     commitment::{blake2s::Blake2sCommitment, injective_map::PedersenCommCompressor},
     crh::{
-        injective_map::{PedersenCRHCompressor, TECompressor},
+        injective_map::{BoweHopwoodPedersenCRHCompressor, PedersenCRHCompressor, TECompressor},
         pedersen::PedersenWindow,
     },
+    encryption::ecies::ECIES,
     nizk::Gm17,
     prf::blake2s::Blake2s,
     CommitmentScheme,
@@ -18,7 +22,9 @@ use crate::gadgets::{
     commitment::{
         blake2s::Blake2sCommitmentGadget, injective_map::PedersenCommitmentCompressorGadget,
     },
-    crh::injective_map::{PedersenCRHCompressorGadget, TECompressorGadget},
+    crh::injective_map::{
+        BoweHopwoodPedersenCRHCompressorGadget, PedersenCRHCompressorGadget, TECompressorGadget,
+    },
     mht::IdealLedgerGadget,
     prf::blake2s::Blake2sGadget,
     verifier::gm17::Gm17VerifierGadget,
@@ -30,21 +36,38 @@ use snark_gadgets::{
     pairing::bls12_377::PairingGadget,
 };
 
-use crate::dpc::plain_dpc::{
-    core_checks_circuit::*, predicate::DPCPredicate, predicate_circuit::*, proof_check_circuit::*,
-    transaction::DPCTransaction, LocalData as DPCLocalData, PlainDPCComponents, DPC,
+use crate::dpc::{
+    plain_dpc::{
+        core_checks_circuit::*, predicate::DPCPredicate, predicate_circuit::*,
+        proof_check_circuit::*, transaction::DPCTransaction, LocalData as DPCLocalData,
+        PlainDPCComponents, DPC,
+    },
+    Arity,
+};
+
+use crate::{
+    ledger::{CommPath, Digest, IdealLedger},
+    predicates::plain_dpc::predicate_circuit::ConserveCircuit,
 };
 
-use crate::ledger::{CommPath, Digest, IdealLedger};
+// Transactions with 1, 2 or 4 old and new records are supported.
+pub const ARITIES: [Arity; 3] = [Arity::new(1, 1), Arity::new(2, 2), Arity::new(4, 4)];
+const MAX_NUM_INPUT_RECORDS: usize = 4;
+const MAX_NUM_OUTPUT_RECORDS: usize = 4;
+
+// Records carry 64-byte payloads, and transactions 64-byte memoranda.
+pub const PAYLOAD_SIZE: usize = 64;
+pub const MEMO_SIZE: usize = 64;
 
+// Shape of the example transactions in tests and benchmarks.
 pub const NUM_INPUT_RECORDS: usize = 2;
 pub const NUM_OUTPUT_RECORDS: usize = 2;
 
 #[derive(Clone, PartialEq, Eq, Hash)]
 pub struct SnNonceWindow;
 
-// `WINDOW_SIZE * NUM_WINDOWS` = 2 * 256 + 8 + 256 bits
-const SN_NONCE_SIZE_BITS: usize = NUM_INPUT_RECORDS * 2 * 256 + 8 + 256;
+// `WINDOW_SIZE * NUM_WINDOWS` = 4 * 256 + 8 + 256 bits
+const SN_NONCE_SIZE_BITS: usize = MAX_NUM_INPUT_RECORDS * 256 + 8 + 256;
 impl PedersenWindow for SnNonceWindow {
     const WINDOW_SIZE: usize = SN_NONCE_SIZE_BITS / 8;
     const NUM_WINDOWS: usize = 8;
@@ -61,38 +84,56 @@ impl PedersenWindow for PredVkHashWindow {
 #[derive(Clone, PartialEq, Eq, Hash)]
 pub struct LocalDataWindow;
 
+// The local data of a transaction with 4 old and 4 new records. Each old record
+// takes 193 + PAYLOAD_SIZE bytes, each new record 161 + PAYLOAD_SIZE bytes, and
+// the memorandum and auxiliary input MEMO_SIZE + 32 bytes.
+const LOCAL_DATA_SIZE_BITS: usize = 8
+    * (MAX_NUM_INPUT_RECORDS * (193 + PAYLOAD_SIZE)
+        + MAX_NUM_OUTPUT_RECORDS * (161 + PAYLOAD_SIZE)
+        + MEMO_SIZE
+        + 32);
 impl PedersenWindow for LocalDataWindow {
     const WINDOW_SIZE: usize = 248;
-    const NUM_WINDOWS: usize = 30;
+    const NUM_WINDOWS: usize = (LOCAL_DATA_SIZE_BITS + 247) / 248;
 }
 
 #[derive(Clone, PartialEq, Eq, Hash)]
 pub struct TwoToOneWindow;
-// `WINDOW_SIZE * NUM_WINDOWS` = 2 * 256 bits
+// The Merkle tree is hashed with the Bowe-Hopwood hash, whose windows are
+// segments of 3-bit chunks: `3 * WINDOW_SIZE * NUM_WINDOWS` = 513 >= 2 * 256
+// bits.
 impl PedersenWindow for TwoToOneWindow {
-    const WINDOW_SIZE: usize = 128;
-    const NUM_WINDOWS: usize = 4;
+    const WINDOW_SIZE: usize = 57;
+    const NUM_WINDOWS: usize = 3;
 }
 
 #[derive(Clone, PartialEq, Eq, Hash)]
 pub struct RecordWindow;
+
+// `WINDOW_SIZE * NUM_WINDOWS` = 161 + PAYLOAD_SIZE bytes, the contents of a
+// record that are committed to.
+const RECORD_SIZE_BITS: usize = 8 * (161 + PAYLOAD_SIZE);
 impl PedersenWindow for RecordWindow {
-    const WINDOW_SIZE: usize = 225;
+    const WINDOW_SIZE: usize = RECORD_SIZE_BITS / 8;
     const NUM_WINDOWS: usize = 8;
 }
 
 #[derive(Clone, PartialEq, Eq, Hash)]
 pub struct AddressWindow;
+// `WINDOW_SIZE * NUM_WINDOWS` = 32 + 32 + 64 bytes: the PRF seed, the metadata
+// and the affine encryption key of an address.
 impl PedersenWindow for AddressWindow {
     const WINDOW_SIZE: usize = 128;
-    const NUM_WINDOWS: usize = 4;
+    const NUM_WINDOWS: usize = 8;
 }
 
 pub struct Components;
 
 impl PlainDPCComponents for Components {
-    const NUM_INPUT_RECORDS: usize = NUM_INPUT_RECORDS;
-    const NUM_OUTPUT_RECORDS: usize = NUM_OUTPUT_RECORDS;
+    const ARITIES: &'static [Arity] = &ARITIES;
+    const PAYLOAD_SIZE: usize = PAYLOAD_SIZE;
+    const MEMO_SIZE: usize = MEMO_SIZE;
+
     type E = CoreEngine;
     type ProofCheckE = ProofCheckEngine;
 
@@ -102,6 +143,8 @@ impl PlainDPCComponents for Components {
     type AddrCGadget = AddressCommGadget;
     type RecCGadget = RecordCommGadget;
 
+    type RecEnc = RecordEncryption;
+
     type D = MerkleTreeDigest;
     type SnNonceH = SnNonceCRH;
     type SnNonceHGadget = SnNonceCRHGadget;
@@ -133,7 +176,11 @@ pub type RecordComm = PedersenCommCompressor<EdwardsBls, EdwardsCompressor, Reco
 pub type PredicateComm = Blake2sCommitment;
 pub type LocalDataComm = PedersenCommCompressor<EdwardsBls, EdwardsCompressor, LocalDataWindow>;
 
-pub type MerkleTreeCRH = PedersenCRHCompressor<EdwardsBls, EdwardsCompressor, TwoToOneWindow>;
+// Affine points have a unique encoding, which the address commitment relies on.
+pub type RecordEncryption = ECIES<EdwardsBlsAffine, Blake2sHash>;
+
+pub type MerkleTreeCRH =
+    BoweHopwoodPedersenCRHCompressor<EdwardsBls, EdwardsCompressor, TwoToOneWindow>;
 pub type SnNonceCRH = PedersenCRHCompressor<EdwardsBls, EdwardsCompressor, SnNonceWindow>;
 pub type PredVkCRH = PedersenCRHCompressor<EdwardsSW, EdwardsCompressor, PredVkHashWindow>;
 
@@ -143,6 +190,7 @@ pub type CoreCheckNIZK =
 pub type ProofCheckNIZK =
     Gm17<ProofCheckEngine, ProofCheckCircuit<Components>, ProofCheckVerifierInput<Components>>;
 pub type PredicateNIZK<C> = Gm17<CoreEngine, EmptyPredicateCircuit<C>, PredicateLocalData<C>>;
+pub type ConservePredicateNIZK<C> = Gm17<CoreEngine, ConserveCircuit<C>, PredicateLocalData<C>>;
 pub type PRF = Blake2s;
 
 pub type MerkleTreeDigest = Digest<MerkleTreeCRH>;
@@ -182,7 +230,7 @@ pub type SnNonceCRHGadget = PedersenCRHCompressorGadget<
     EdwardsBlsGadget,
     EdwardsCompressorGadget,
 >;
-pub type MerkleTreeCRHGadget = PedersenCRHCompressorGadget<
+pub type MerkleTreeCRHGadget = BoweHopwoodPedersenCRHCompressorGadget<
     EdwardsBls,
     EdwardsCompressor,
     CoreEngine,
@@ -0,0 +1,174 @@
+use failure::Error;
+use std::{collections::HashSet, marker::PhantomData};
+
+use crate::{
+    crypto_primitives::{CommitmentScheme, PRF},
+    dpc::{
+        plain_dpc::{
+            parameters::VerifierParameters, transaction::DPCTransaction, PlainDPCComponents, DPC,
+        },
+        DPCScheme, Transaction,
+    },
+    ledger::{Ledger, LedgerDigest, LedgerWitness},
+};
+
+#[derive(Debug, Fail, PartialEq, Eq)]
+pub enum MempoolError {
+    #[fail(display = "serial number is already on the ledger")]
+    SpentSn,
+    #[fail(display = "serial number is spent by a pending transaction")]
+    ConflictingSn,
+    #[fail(display = "commitment is already on the ledger")]
+    DuplicateCm,
+    #[fail(display = "commitment is created by a pending transaction")]
+    ConflictingCm,
+    #[fail(display = "memorandum is already on the ledger or in a pending transaction")]
+    DuplicateMemo,
+    #[fail(display = "ledger digest is not a valid past digest of the ledger")]
+    StaleDigest,
+    #[fail(display = "transaction failed verification")]
+    InvalidTransaction,
+}
+
+/// Verified transactions waiting to be appended to a ledger. Pending
+/// transactions never conflict with each other, so any subset of them can be
+/// appended together.
+#[derive(Derivative)]
+#[derivative(Default(bound = "C: PlainDPCComponents, L: Ledger"))]
+pub struct Mempool<C: PlainDPCComponents, L: Ledger> {
+    transactions:   Vec<DPCTransaction<C>>,
+    serial_numbers: HashSet<<C::P as PRF>::Output>,
+    commitments:    HashSet<<C::RecC as CommitmentScheme>::Output>,
+    memos:          HashSet<Vec<u8>>,
+    _ledger:        PhantomData<L>,
+}
+
+impl<C: PlainDPCComponents, L> Mempool<C, L>
+where
+    L: Ledger<
+        Parameters = <C::D as LedgerDigest>::Parameters,
+        Commitment = <C::RecC as CommitmentScheme>::Output,
+        SerialNumber = <C::P as PRF>::Output,
+        Memo = Vec<u8>,
+        LedgerStateDigest = C::D,
+        CommWitness = C::LCW,
+        Transaction = DPCTransaction<C>,
+    >,
+    <L as Ledger>::SnWitness: LedgerWitness<C::D>,
+    <L as Ledger>::MemoWitness: LedgerWitness<C::D>,
+{
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn len(&self) -> usize {
+        self.transactions.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.transactions.is_empty()
+    }
+
+    /// Returns the pending transactions, in the order they were inserted.
+    pub fn transactions(&self) -> &[DPCTransaction<C>] {
+        &self.transactions
+    }
+
+    /// Returns whether a pending transaction spends `sn`.
+    pub fn contains_sn(&self, sn: &<C::P as PRF>::Output) -> bool {
+        self.serial_numbers.contains(sn)
+    }
+
+    /// Verifies `transaction` against `ledger` and adds it to the pool, unless
+    /// it conflicts with the ledger or with a pending transaction.
+    pub fn insert(
+        &mut self,
+        parameters: &VerifierParameters<C>,
+        transaction: DPCTransaction<C>,
+        ledger: &L,
+    ) -> Result<(), Error> {
+        let insert_time = timer_start!(|| "Mempool::Insert");
+        Self::check_against_ledger(&transaction, ledger)?;
+        for sn in transaction.old_serial_numbers() {
+            if self.serial_numbers.contains(sn) {
+                Err(MempoolError::ConflictingSn)?;
+            }
+        }
+        for cm in transaction.new_commitments() {
+            if self.commitments.contains(cm) {
+                Err(MempoolError::ConflictingCm)?;
+            }
+        }
+        if self.memos.contains(transaction.memorandum()) {
+            Err(MempoolError::DuplicateMemo)?;
+        }
+        // Only verify the proofs once the cheap checks have passed.
+        if !<DPC<C> as DPCScheme<L>>::verify(parameters, &transaction, ledger)? {
+            Err(MempoolError::InvalidTransaction)?;
+        }
+
+        self.serial_numbers
+            .extend(transaction.old_serial_numbers().iter().cloned());
+        self.commitments
+            .extend(transaction.new_commitments().iter().cloned());
+        self.memos.insert(transaction.memorandum().clone());
+        self.transactions.push(transaction);
+        timer_end!(insert_time);
+        Ok(())
+    }
+
+    /// Removes the pending transactions that can no longer be appended to
+    /// `ledger`, such as those already included in it or those whose ledger
+    /// digest it no longer recognizes, and returns them.
+    pub fn prune(&mut self, ledger: &L) -> Vec<DPCTransaction<C>> {
+        let (pending, removed): (Vec<_>, Vec<_>) = self
+            .transactions
+            .drain(..)
+            .partition(|transaction| Self::check_against_ledger(transaction, ledger).is_ok());
+
+        self.serial_numbers = pending
+            .iter()
+            .flat_map(|transaction| transaction.old_serial_numbers().iter().cloned())
+            .collect();
+        self.commitments = pending
+            .iter()
+            .flat_map(|transaction| transaction.new_commitments().iter().cloned())
+            .collect();
+        self.memos = pending
+            .iter()
+            .map(|transaction| transaction.memorandum().clone())
+            .collect();
+        self.transactions = pending;
+        removed
+    }
+
+    /// Returns up to `max_num_transactions` pending transactions to append to
+    /// the ledger together, oldest first.
+    pub fn candidate_block(&self, max_num_transactions: usize) -> Vec<DPCTransaction<C>> {
+        self.transactions
+            .iter()
+            .take(max_num_transactions)
+            .cloned()
+            .collect()
+    }
+
+    fn check_against_ledger(transaction: &DPCTransaction<C>, ledger: &L) -> Result<(), Error> {
+        if !ledger.validate_digest(&transaction.stuff.digest) {
+            Err(MempoolError::StaleDigest)?;
+        }
+        for sn in transaction.old_serial_numbers() {
+            if ledger.contains_sn(sn) {
+                Err(MempoolError::SpentSn)?;
+            }
+        }
+        for cm in transaction.new_commitments() {
+            if ledger.contains_cm(cm) {
+                Err(MempoolError::DuplicateCm)?;
+            }
+        }
+        if ledger.contains_memo(transaction.memorandum()) {
+            Err(MempoolError::DuplicateMemo)?;
+        }
+        Ok(())
+    }
+}
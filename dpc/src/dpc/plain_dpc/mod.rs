@@ -1,11 +1,13 @@
 use algebra::{to_bytes, PairingEngine};
+use blake2::Blake2s as b2s;
+use digest::Digest;
 use failure::{format_err, Error};
 use rand::{Rand, Rng};
-use std::marker::PhantomData;
+use std::{io::Read, marker::PhantomData};
 
 use crate::{
-    crypto_primitives::{CommitmentScheme, FixedLengthCRH, NIZK, PRF},
-    dpc::{AddressKeyPair, DPCScheme, Predicate, Record, Transaction},
+    crypto_primitives::{CommitmentScheme, EncryptionScheme, FixedLengthCRH, NIZK, PRF},
+    dpc::{AddressKeyPair, Arity, DPCScheme, Predicate, Record, Transaction},
     gadgets::{CommitmentGadget, FixedLengthCRHGadget, LCWGadget, NIZKVerifierGadget, PRFGadget},
     ledger::{Ledger, LedgerDigest, LedgerWitness},
 };
@@ -23,6 +25,8 @@ use self::record::*;
 pub mod transaction;
 use self::transaction::*;
 
+pub mod payload;
+
 pub mod core_checks_circuit;
 use self::core_checks_circuit::*;
 
@@ -35,6 +39,10 @@ use self::predicate_circuit::*;
 pub mod parameters;
 use self::parameters::*;
 
+pub mod wallet;
+
+pub mod mempool;
+
 pub mod instantiated;
 
 #[cfg(test)]
@@ -46,8 +54,16 @@ mod test;
 /// scheme. Simplifies the interface of Plain DPC by wrapping all these into
 /// one.
 pub trait PlainDPCComponents: 'static + Sized {
-    const NUM_INPUT_RECORDS: usize;
-    const NUM_OUTPUT_RECORDS: usize;
+    // Shapes of the transactions that can be created and verified. Each arity
+    // gets its own core check and proof check circuits.
+    const ARITIES: &'static [Arity];
+
+    // Sizes in bytes of the payload of a record and of the memorandum of a
+    // transaction. Both are committed to in the local data commitment, and
+    // the payload also in the record commitment, so the input sizes of
+    // `Self::RecC` and `Self::LocalDataComm` must account for them.
+    const PAYLOAD_SIZE: usize;
+    const MEMO_SIZE: usize;
 
     type E: PairingEngine<Fq = <Self::ProofCheckE as PairingEngine>::Fr>;
     type ProofCheckE: PairingEngine;
@@ -60,6 +76,10 @@ pub trait PlainDPCComponents: 'static + Sized {
     type RecC: CommitmentScheme;
     type RecCGadget: CommitmentGadget<Self::RecC, Self::E>;
 
+    // Encryption scheme used to send record contents to their owners. Only
+    // invoked natively.
+    type RecEnc: EncryptionScheme;
+
     // Ledger digest type.
     type D: LedgerDigest + Clone;
 
@@ -99,7 +119,9 @@ pub trait PlainDPCComponents: 'static + Sized {
         VerifierInput = ProofCheckVerifierInput<Self>,
     >;
 
-    // NIZK for a "dummy predicate" that does nothing with its input.
+    // NIZK for a "dummy predicate" that does nothing with its input. Its
+    // verification keys and proofs also fix the format of those of user-defined
+    // predicates; see `RegisteredPredicate`.
     type PredicateNIZK: NIZK<
         Circuit = EmptyPredicateCircuit<Self>,
         AssignedCircuit = EmptyPredicateCircuit<Self>,
@@ -107,7 +129,7 @@ pub trait PlainDPCComponents: 'static + Sized {
     >;
 
     // NIZK Verifier gadget for the "dummy predicate" that does nothing with its
-    // input.
+    // input, and for every user-defined predicate.
     type PredicateNIZKGadget: NIZKVerifierGadget<Self::PredicateNIZK, Self::ProofCheckE>;
 
     type LCW: LedgerWitness<Self::D> + Clone;
@@ -155,10 +177,14 @@ pub(crate) struct ExecuteContext<'a, Components: PlainDPCComponents> {
 
     local_data_comm: <Components::LocalDataComm as CommitmentScheme>::Output,
     local_data_rand: <Components::LocalDataComm as CommitmentScheme>::Randomness,
+
+    // Memorandum and auxiliary input
+    memo:      Vec<u8>,
+    auxiliary: [u8; 32],
 }
 
 impl<Components: PlainDPCComponents> ExecuteContext<'_, Components> {
-    fn into_local_data(&self) -> LocalData<Components> {
+    pub(crate) fn into_local_data(&self) -> LocalData<Components> {
         LocalData {
             comm_and_crh_pp: self.comm_and_crh_pp.clone(),
 
@@ -169,6 +195,9 @@ impl<Components: PlainDPCComponents> ExecuteContext<'_, Components> {
 
             local_data_comm: self.local_data_comm.clone(),
             local_data_rand: self.local_data_rand.clone(),
+
+            memo:      self.memo.clone(),
+            auxiliary: self.auxiliary,
         }
     }
 }
@@ -187,6 +216,10 @@ pub struct LocalData<Components: PlainDPCComponents> {
     // Commitment to the above information.
     pub local_data_comm: <Components::LocalDataComm as CommitmentScheme>::Output,
     pub local_data_rand: <Components::LocalDataComm as CommitmentScheme>::Randomness,
+
+    // Memorandum and auxiliary input, also committed to in `local_data_comm`.
+    pub memo:      Vec<u8>,
+    pub auxiliary: [u8; 32],
 }
 
 ///////////////////////////////////////////////////////////////////////////////
@@ -203,6 +236,10 @@ impl<Components: PlainDPCComponents> DPC<Components> {
         let rec_comm_pp = Components::RecC::setup(rng)?;
         timer_end!(time);
 
+        let time = timer_start!(|| "Record encryption scheme setup");
+        let rec_enc_pp = Components::RecEnc::setup(rng)?;
+        timer_end!(time);
+
         let time = timer_start!(|| "Verification Key Commitment setup");
         let pred_vk_comm_pp = Components::PredVkComm::setup(rng)?;
         timer_end!(time);
@@ -225,6 +262,8 @@ impl<Components: PlainDPCComponents> DPC<Components> {
             pred_vk_comm_pp,
             local_data_comm_pp,
 
+            rec_enc_pp,
+
             sn_nonce_crh_pp,
             pred_vk_crh_pp,
         };
@@ -254,9 +293,24 @@ impl<Components: PlainDPCComponents> DPC<Components> {
     pub fn generate_sn(
         record: &DPCRecord<Components>,
         address_secret_key: &AddressSecretKey<Components>,
+    ) -> Result<<Components::P as PRF>::Output, Error> {
+        Self::compute_sn(record, &address_secret_key.sk_prf)
+    }
+
+    /// Computes the serial number of `record` from a viewing key, so that its
+    /// holder can tell when the record is spent.
+    pub fn generate_sn_with_viewing_key(
+        record: &DPCRecord<Components>,
+        viewing_key: &AddressViewingKey<Components>,
+    ) -> Result<<Components::P as PRF>::Output, Error> {
+        Self::compute_sn(record, &viewing_key.sk_prf)
+    }
+
+    fn compute_sn(
+        record: &DPCRecord<Components>,
+        sk_prf: &<Components::P as PRF>::Seed,
     ) -> Result<<Components::P as PRF>::Output, Error> {
         let sn_time = timer_start!(|| "Generate serial number");
-        let sk_prf = &address_secret_key.sk_prf;
         let sn_nonce = to_bytes!(record.serial_number_nonce())?;
         // Compute the serial number.
         let prf_input = FromBytes::read(sn_nonce.as_slice())?;
@@ -271,11 +325,18 @@ impl<Components: PlainDPCComponents> DPC<Components> {
         sn_nonce: &<Components::SnNonceH as FixedLengthCRH>::Output,
         address_public_key: &AddressPublicKey<Components>,
         is_dummy: bool,
-        payload: &[u8; 32],
+        payload: &[u8],
         birth_predicate: &DPCPredicate<Components>,
         death_predicate: &DPCPredicate<Components>,
         rng: &mut R,
     ) -> Result<DPCRecord<Components>, Error> {
+        if payload.len() != Components::PAYLOAD_SIZE {
+            return Err(format_err!(
+                "expected a payload of {} bytes, got {} bytes",
+                Components::PAYLOAD_SIZE,
+                payload.len()
+            ));
+        }
         let record_time = timer_start!(|| "Generate record");
         // Sample new commitment randomness.
         let commitment_randomness = <Components::RecC as CommitmentScheme>::Randomness::rand(rng);
@@ -283,11 +344,11 @@ impl<Components: PlainDPCComponents> DPC<Components> {
         // Construct a record commitment.
         let birth_predicate_repr = birth_predicate.into_compact_repr();
         let death_predicate_repr = death_predicate.into_compact_repr();
-        // Total = 32 + 1 + 32 + 32 + 32 + 32 = 161 bytes
+        // Total = 32 + 1 + PAYLOAD_SIZE + 32 + 32 + 32 bytes
         let commitment_input = to_bytes![
             address_public_key.public_key, // 256 bits = 32 bytes
             is_dummy,                      // 1 bit = 1 byte
-            payload,                       // 256 bits = 32 bytes
+            payload,                       // PAYLOAD_SIZE bytes
             birth_predicate_repr,          // 256 bits = 32 bytes
             death_predicate_repr,          // 256 bits = 32 bytes
             sn_nonce                       // 256 bits = 32 bytes
@@ -302,7 +363,7 @@ impl<Components: PlainDPCComponents> DPC<Components> {
         let record = DPCRecord {
             address_public_key: address_public_key.clone(),
             is_dummy,
-            payload: *payload,
+            payload: payload.to_vec(),
             birth_predicate_repr,
             death_predicate_repr,
             serial_number_nonce: sn_nonce.clone(),
@@ -314,6 +375,153 @@ impl<Components: PlainDPCComponents> DPC<Components> {
         Ok(record)
     }
 
+    /// Encrypts the contents of `record` to the encryption key of its owner,
+    /// so that they can later recover it with `decrypt_record`.
+    pub fn encrypt_record<R: Rng>(
+        parameters: &CommAndCRHPublicParameters<Components>,
+        record: &DPCRecord<Components>,
+        rng: &mut R,
+    ) -> Result<<Components::RecEnc as EncryptionScheme>::Ciphertext, Error> {
+        let plaintext = to_bytes![
+            record.is_dummy(),
+            record.payload(),
+            record.birth_predicate_repr(),
+            record.death_predicate_repr(),
+            record.serial_number_nonce(),
+            record.commitment_randomness()
+        ]?;
+        Components::RecEnc::encrypt(
+            &parameters.rec_enc_pp,
+            &record.address_public_key().encryption_key,
+            &plaintext,
+            rng,
+        )
+    }
+
+    /// Trial-decrypts `ciphertext` with `viewing_key`. Returns `None` if the
+    /// ciphertext is not addressed to the viewed address, or if its contents do
+    /// not open `commitment`.
+    pub fn decrypt_record(
+        parameters: &CommAndCRHPublicParameters<Components>,
+        viewing_key: &AddressViewingKey<Components>,
+        commitment: &<Components::RecC as CommitmentScheme>::Output,
+        ciphertext: &<Components::RecEnc as EncryptionScheme>::Ciphertext,
+    ) -> Result<Option<DPCRecord<Components>>, Error> {
+        let plaintext = match Components::RecEnc::decrypt(
+            &parameters.rec_enc_pp,
+            &viewing_key.decryption_key,
+            ciphertext,
+        )? {
+            Some(plaintext) => plaintext,
+            None => return Ok(None),
+        };
+
+        // A sender can encrypt arbitrary bytes to us, so malformed plaintexts
+        // are treated as not ours rather than as errors.
+        let mut reader = plaintext.as_slice();
+        let decoded = (|| -> Result<_, Error> {
+            let is_dummy: bool = FromBytes::read(&mut reader)?;
+            let mut payload = vec![0u8; Components::PAYLOAD_SIZE];
+            reader.read_exact(&mut payload)?;
+            let birth_predicate_repr: <Components::PredVkH as FixedLengthCRH>::Output =
+                FromBytes::read(&mut reader)?;
+            let death_predicate_repr: <Components::PredVkH as FixedLengthCRH>::Output =
+                FromBytes::read(&mut reader)?;
+            let serial_number_nonce: <Components::SnNonceH as FixedLengthCRH>::Output =
+                FromBytes::read(&mut reader)?;
+            let commitment_randomness: <Components::RecC as CommitmentScheme>::Randomness =
+                FromBytes::read(&mut reader)?;
+            Ok((
+                is_dummy,
+                payload,
+                to_bytes![birth_predicate_repr]?,
+                to_bytes![death_predicate_repr]?,
+                serial_number_nonce,
+                commitment_randomness,
+            ))
+        })();
+        let (
+            is_dummy,
+            payload,
+            birth_predicate_repr,
+            death_predicate_repr,
+            serial_number_nonce,
+            commitment_randomness,
+        ) = match decoded {
+            Ok(decoded) => decoded,
+            Err(_) => return Ok(None),
+        };
+
+        // Check that the decrypted contents open the commitment on the ledger.
+        let commitment_input = to_bytes![
+            viewing_key.public_key.public_key,
+            is_dummy,
+            payload,
+            birth_predicate_repr,
+            death_predicate_repr,
+            serial_number_nonce
+        ]?;
+        let expected_commitment = Components::RecC::commit(
+            &parameters.rec_comm_pp,
+            &commitment_input,
+            &commitment_randomness,
+        )?;
+        if &expected_commitment != commitment {
+            return Ok(None);
+        }
+
+        Ok(Some(DPCRecord {
+            address_public_key: viewing_key.public_key.clone(),
+            is_dummy,
+            payload,
+            birth_predicate_repr,
+            death_predicate_repr,
+            serial_number_nonce,
+            commitment: expected_commitment,
+            commitment_randomness,
+            _components: PhantomData,
+        }))
+    }
+
+    /// Hashes the encrypted new records of a transaction. The core checks
+    /// proof takes this digest as a public input, so the ciphertexts cannot be
+    /// swapped out without invalidating the proof.
+    pub fn encrypted_records_digest(
+        encrypted_records: &[<Components::RecEnc as EncryptionScheme>::Ciphertext],
+    ) -> Result<[u8; 32], Error> {
+        let mut digest = [0u8; 32];
+        digest.copy_from_slice(&b2s::digest(&to_bytes![encrypted_records]?));
+        Ok(digest)
+    }
+
+    /// Trial-decrypts the new records of every transaction in `transactions`,
+    /// returning the non-dummy records owned by the address of `viewing_key`.
+    pub fn scan_transactions<'a>(
+        parameters: &CommAndCRHPublicParameters<Components>,
+        viewing_key: &AddressViewingKey<Components>,
+        transactions: impl IntoIterator<Item = &'a DPCTransaction<Components>>,
+    ) -> Result<Vec<DPCRecord<Components>>, Error> {
+        let scan_time = timer_start!(|| "PlainDPC::ScanTransactions");
+        let mut records = Vec::new();
+        for transaction in transactions {
+            let new_records = transaction
+                .new_commitments()
+                .iter()
+                .zip(transaction.encrypted_records());
+            for (commitment, ciphertext) in new_records {
+                if let Some(record) =
+                    Self::decrypt_record(parameters, viewing_key, commitment, ciphertext)?
+                {
+                    if !record.is_dummy() {
+                        records.push(record);
+                    }
+                }
+            }
+        }
+        timer_end!(scan_time);
+        Ok(records)
+    }
+
     pub fn create_address_helper<R: Rng>(
         parameters: &CommAndCRHPublicParameters<Components>,
         metadata: &[u8; 32],
@@ -326,16 +534,27 @@ impl<Components: PlainDPCComponents> DPC<Components> {
         // Sample randomness rpk for the commitment scheme.
         let r_pk = <Components::AddrC as CommitmentScheme>::Randomness::rand(rng);
 
-        // Construct the address public key.
-        let commit_input = to_bytes![sk_prf, metadata]?;
+        // Sample the key pair used to receive encrypted records.
+        let (encryption_key, decryption_key) =
+            Components::RecEnc::keygen(&parameters.rec_enc_pp, rng)?;
+
+        // Construct the address public key. It commits to the encryption key
+        // too, so that the key cannot be swapped for another one without
+        // changing the address.
+        let commit_input = to_bytes![sk_prf, metadata, encryption_key]?;
         let public_key = Components::AddrC::commit(&parameters.addr_comm_pp, &commit_input, &r_pk)?;
-        let public_key = AddressPublicKey { public_key };
+
+        let public_key = AddressPublicKey {
+            public_key,
+            encryption_key,
+        };
 
         // Construct the address secret key.
         let secret_key = AddressSecretKey {
             sk_prf,
             metadata: *metadata,
             r_pk,
+            decryption_key,
         };
 
         Ok(AddressPair {
@@ -356,7 +575,7 @@ impl<Components: PlainDPCComponents> DPC<Components> {
         new_birth_predicates: &[<Self as DPCScheme<L>>::Predicate],
         new_death_predicates: &[<Self as DPCScheme<L>>::Predicate],
 
-        memo: &[u8; 32],
+        memo: &[u8],
         auxiliary: &[u8; 32],
 
         ledger: &L,
@@ -373,20 +592,16 @@ impl<Components: PlainDPCComponents> DPC<Components> {
         <L as Ledger>::SnWitness: LedgerWitness<Components::D>,
         <L as Ledger>::MemoWitness: LedgerWitness<Components::D>,
     {
-        assert_eq!(Components::NUM_INPUT_RECORDS, old_records.len());
-        assert_eq!(Components::NUM_INPUT_RECORDS, old_address_secret_keys.len());
+        assert_eq!(old_records.len(), old_address_secret_keys.len());
 
-        assert_eq!(
-            Components::NUM_OUTPUT_RECORDS,
-            new_address_public_keys.len()
-        );
-        assert_eq!(Components::NUM_OUTPUT_RECORDS, new_is_dummy_flags.len());
-        assert_eq!(Components::NUM_OUTPUT_RECORDS, new_payloads.len());
-        assert_eq!(Components::NUM_OUTPUT_RECORDS, new_birth_predicates.len());
-        assert_eq!(Components::NUM_OUTPUT_RECORDS, new_death_predicates.len());
+        let num_output_records = new_address_public_keys.len();
+        assert_eq!(num_output_records, new_is_dummy_flags.len());
+        assert_eq!(num_output_records, new_payloads.len());
+        assert_eq!(num_output_records, new_birth_predicates.len());
+        assert_eq!(num_output_records, new_death_predicates.len());
 
-        let mut old_witnesses = Vec::with_capacity(Components::NUM_INPUT_RECORDS);
-        let mut old_serial_numbers = Vec::with_capacity(Components::NUM_INPUT_RECORDS);
+        let mut old_witnesses = Vec::with_capacity(old_records.len());
+        let mut old_serial_numbers = Vec::with_capacity(old_records.len());
         let mut joint_serial_numbers = Vec::new();
         let mut old_death_pred_hashes = Vec::new();
 
@@ -410,13 +625,13 @@ impl<Components: PlainDPCComponents> DPC<Components> {
             timer_end!(input_record_time);
         }
 
-        let mut new_records = Vec::with_capacity(Components::NUM_OUTPUT_RECORDS);
-        let mut new_commitments = Vec::with_capacity(Components::NUM_OUTPUT_RECORDS);
-        let mut new_sn_nonce_randomness = Vec::with_capacity(Components::NUM_OUTPUT_RECORDS);
+        let mut new_records = Vec::with_capacity(num_output_records);
+        let mut new_commitments = Vec::with_capacity(num_output_records);
+        let mut new_sn_nonce_randomness = Vec::with_capacity(num_output_records);
         let mut new_birth_pred_hashes = Vec::new();
 
         // Generate new records and commitments for them.
-        for j in 0..Components::NUM_OUTPUT_RECORDS {
+        for j in 0..num_output_records {
             let output_record_time = timer_start!(|| format!("Process output record {}", j));
             let sn_nonce_time = timer_start!(|| "Generate serial number nonce");
 
@@ -449,25 +664,23 @@ impl<Components: PlainDPCComponents> DPC<Components> {
 
         let local_data_comm_timer = timer_start!(|| "Compute predicate input commitment");
         let mut predicate_input = Vec::new();
-        for i in 0..Components::NUM_INPUT_RECORDS {
-            let record = &old_records[i];
+        for (record, sn) in old_records.iter().zip(&old_serial_numbers) {
             let bytes = to_bytes![
                 record.commitment(),
-                record.address_public_key(),
+                record.address_public_key().public_key,
                 record.is_dummy(),
                 record.payload(),
                 record.birth_predicate_repr(),
                 record.death_predicate_repr(),
-                old_serial_numbers[i]
+                sn
             ]?;
             predicate_input.extend_from_slice(&bytes);
         }
 
-        for j in 0..Components::NUM_OUTPUT_RECORDS {
-            let record = &new_records[j];
+        for record in &new_records {
             let bytes = to_bytes![
                 record.commitment(),
-                record.address_public_key(),
+                record.address_public_key().public_key,
                 record.is_dummy(),
                 record.payload(),
                 record.birth_predicate_repr(),
@@ -527,6 +740,9 @@ impl<Components: PlainDPCComponents> DPC<Components> {
             predicate_rand,
             local_data_comm,
             local_data_rand,
+
+            memo: memo.to_vec(),
+            auxiliary: *auxiliary,
         };
         Ok(context)
     }
@@ -549,6 +765,7 @@ where
     type Metadata = [u8; 32];
     type Payload = <Self::Record as Record>::Payload;
     type Parameters = PublicParameters<Components>;
+    type VerifierParameters = VerifierParameters<Components>;
     type Predicate = DPCPredicate<Components>;
     type PrivatePredInput = PrivatePredInput<Components>;
     type Record = DPCRecord<Components>;
@@ -568,25 +785,35 @@ where
             proof: pred_nizk_pp.proof.clone(),
         };
 
-        let nizk_setup_time = timer_start!(|| "Execute Tx Core Checks NIZK Setup");
-        let core_nizk_pp = Components::MainNIZK::setup(
-            CoreChecksCircuit::blank(&comm_and_crh_pp, ledger_pp),
-            rng,
-        )?;
-        timer_end!(nizk_setup_time);
+        let mut arity_pp = Vec::with_capacity(Components::ARITIES.len());
+        for &arity in Components::ARITIES {
+            let nizk_setup_time =
+                timer_start!(|| format!("Execute Tx Core Checks NIZK Setup for {:?}", arity));
+            let core_nizk_pp = Components::MainNIZK::setup(
+                CoreChecksCircuit::blank(&comm_and_crh_pp, ledger_pp, arity),
+                rng,
+            )?;
+            timer_end!(nizk_setup_time);
 
-        let nizk_setup_time = timer_start!(|| "Execute Tx Proof Checks NIZK Setup");
-        let proof_check_nizk_pp = Components::ProofCheckNIZK::setup(
-            ProofCheckCircuit::blank(&comm_and_crh_pp, &private_pred_input),
-            rng,
-        )?;
-        timer_end!(nizk_setup_time);
+            let nizk_setup_time =
+                timer_start!(|| format!("Execute Tx Proof Checks NIZK Setup for {:?}", arity));
+            let proof_check_nizk_pp = Components::ProofCheckNIZK::setup(
+                ProofCheckCircuit::blank(&comm_and_crh_pp, &private_pred_input, arity),
+                rng,
+            )?;
+            timer_end!(nizk_setup_time);
+
+            arity_pp.push(ArityParameters {
+                arity,
+                proof_check_nizk_pp,
+                core_nizk_pp,
+            });
+        }
         timer_end!(setup_time);
         Ok(PublicParameters {
             comm_and_crh_pp,
             pred_nizk_pp,
-            core_nizk_pp,
-            proof_check_nizk_pp,
+            arity_pp,
         })
     }
 
@@ -603,6 +830,7 @@ where
 
     fn execute<R: Rng>(
         parameters: &Self::Parameters,
+
         old_records: &[Self::Record],
         old_address_secret_keys: &[<Self::AddressKeyPair as AddressKeyPair>::AddressSecretKey],
         mut old_death_pred_proof_generator: impl FnMut(&Self::LocalData) -> Vec<Self::PrivatePredInput>,
@@ -620,15 +848,88 @@ where
         rng: &mut R,
     ) -> Result<(Vec<Self::Record>, Self::Transaction), Error> {
         let exec_time = timer_start!(|| "PlainDPC::Exec");
+        if memorandum.len() != Components::MEMO_SIZE {
+            return Err(format_err!(
+                "expected a memorandum of {} bytes, got {} bytes",
+                Components::MEMO_SIZE,
+                memorandum.len()
+            ));
+        }
+        let num_input_records = old_records.len();
+        let num_output_records = new_address_public_keys.len();
+        let arity = Arity::select(Components::ARITIES, num_input_records, num_output_records)
+            .ok_or_else(|| {
+                format_err!(
+                    "no supported arity fits {} old and {} new records",
+                    num_input_records,
+                    num_output_records
+                )
+            })?;
+        let arity_pp = parameters
+            .arity_pp(arity)
+            .ok_or_else(|| format_err!("missing parameters for arity {:?}", arity))?;
+
+        // Pad the transaction to `arity` with dummy records that belong to a
+        // fresh address and are guarded by the empty predicate.
+        let mut old_records = old_records.to_vec();
+        let mut old_address_secret_keys = old_address_secret_keys.to_vec();
+        let mut new_address_public_keys = new_address_public_keys.to_vec();
+        let mut new_is_dummy_flags = new_is_dummy_flags.to_vec();
+        let mut new_payloads = new_payloads.to_vec();
+        let mut new_birth_predicates = new_birth_predicates.to_vec();
+        let mut new_death_predicates = new_death_predicates.to_vec();
+        if arity != Arity::new(num_input_records, num_output_records) {
+            let padding_time = timer_start!(|| "Pad transaction");
+            let comm_and_crh_pp = &parameters.comm_and_crh_pp;
+            let empty_predicate = DPCPredicate::from_verification_key(
+                &comm_and_crh_pp.pred_vk_crh_pp,
+                &parameters.pred_nizk_pp.vk,
+            )?;
+            let metadata: [u8; 32] = rng.gen();
+            let padding_address = Self::create_address_helper(comm_and_crh_pp, &metadata, rng)?;
+            let zero_payload = vec![0u8; Components::PAYLOAD_SIZE];
+
+            arity.pad_old(&mut old_records, |_| {
+                // Dummy records are never on the ledger, so a random nonce only
+                // needs to keep their serial numbers distinct.
+                let sn_nonce_input: [u8; 32] = rng.gen();
+                let sn_nonce = Components::SnNonceH::evaluate(
+                    &comm_and_crh_pp.sn_nonce_crh_pp,
+                    &sn_nonce_input,
+                )?;
+                Self::generate_record(
+                    comm_and_crh_pp,
+                    &sn_nonce,
+                    &padding_address.public_key,
+                    true,
+                    &zero_payload,
+                    &empty_predicate,
+                    &empty_predicate,
+                    rng,
+                )
+            })?;
+            arity.pad_old(&mut old_address_secret_keys, |_| {
+                Ok(padding_address.secret_key.clone())
+            })?;
+            arity.pad_new(&mut new_address_public_keys, |_| {
+                Ok(padding_address.public_key.clone())
+            })?;
+            arity.pad_new(&mut new_is_dummy_flags, |_| Ok(true))?;
+            arity.pad_new(&mut new_payloads, |_| Ok(zero_payload.clone()))?;
+            arity.pad_new(&mut new_birth_predicates, |_| Ok(empty_predicate.clone()))?;
+            arity.pad_new(&mut new_death_predicates, |_| Ok(empty_predicate.clone()))?;
+            timer_end!(padding_time);
+        }
+
         let context = Self::execute_helper(
             &parameters.comm_and_crh_pp,
-            old_records,
-            old_address_secret_keys,
-            new_address_public_keys,
-            new_is_dummy_flags,
-            new_payloads,
-            new_birth_predicates,
-            new_death_predicates,
+            &old_records,
+            &old_address_secret_keys,
+            &new_address_public_keys,
+            &new_is_dummy_flags,
+            &new_payloads,
+            &new_birth_predicates,
+            &new_death_predicates,
             memorandum,
             auxiliary,
             ledger,
@@ -636,8 +937,33 @@ where
         )?;
 
         let local_data = context.into_local_data();
-        let old_death_pred_vk_and_proofs = old_death_pred_proof_generator(&local_data);
-        let new_birth_pred_vk_and_proofs = new_birth_pred_proof_generator(&local_data);
+        let mut old_death_pred_vk_and_proofs = old_death_pred_proof_generator(&local_data);
+        let mut new_birth_pred_vk_and_proofs = new_birth_pred_proof_generator(&local_data);
+        if old_death_pred_vk_and_proofs.len() != num_input_records
+            || new_birth_pred_vk_and_proofs.len() != num_output_records
+        {
+            return Err(format_err!(
+                "expected predicate proofs for {} old and {} new records",
+                num_input_records,
+                num_output_records
+            ));
+        }
+        let mut prove_empty_predicate = |position: usize| -> Result<_, Error> {
+            let circuit = EmptyPredicateCircuit::from_local_data(&local_data, position as u8);
+            let pred_nizk_pp = &parameters.pred_nizk_pp;
+            Ok(PrivatePredInput {
+                vk:    pred_nizk_pp.vk.clone(),
+                proof: Components::PredicateNIZK::prove(&pred_nizk_pp.pk, circuit, rng)?,
+            })
+        };
+        arity.pad_old(
+            &mut old_death_pred_vk_and_proofs,
+            &mut prove_empty_predicate,
+        )?;
+        arity.pad_new(
+            &mut new_birth_pred_vk_and_proofs,
+            &mut prove_empty_predicate,
+        )?;
 
         let ExecuteContext {
             comm_and_crh_pp: _comm_and_crh_pp,
@@ -657,7 +983,16 @@ where
 
             local_data_comm,
             local_data_rand,
+
+            memo: _memo,
+            auxiliary: _auxiliary,
         } = context;
+        let encrypted_records = new_records
+            .iter()
+            .map(|record| Self::encrypt_record(&parameters.comm_and_crh_pp, record, rng))
+            .collect::<Result<Vec<_>, _>>()?;
+        let encrypted_records_digest = Self::encrypted_records_digest(&encrypted_records)?;
+
         let core_proof = {
             let circuit = CoreChecksCircuit::new(
                 &parameters.comm_and_crh_pp,
@@ -676,9 +1011,10 @@ where
                 &local_data_rand,
                 memorandum,
                 auxiliary,
+                &encrypted_records_digest,
             );
 
-            Components::MainNIZK::prove(&parameters.core_nizk_pp.0, circuit, rng)?
+            Components::MainNIZK::prove(&arity_pp.core_nizk_pp.0, circuit, rng)?
         };
 
         let proof_checks_proof = {
@@ -691,13 +1027,14 @@ where
                 &local_data_comm,
             );
 
-            Components::ProofCheckNIZK::prove(&parameters.proof_check_nizk_pp.0, circuit, rng)?
+            Components::ProofCheckNIZK::prove(&arity_pp.proof_check_nizk_pp.0, circuit, rng)?
         };
 
         let transaction = Self::Transaction::new(
             old_serial_numbers,
             new_commitments,
             memorandum.clone(),
+            encrypted_records,
             ledger_digest,
             core_proof,
             proof_checks_proof,
@@ -710,11 +1047,23 @@ where
     }
 
     fn verify(
-        parameters: &Self::Parameters,
+        parameters: &Self::VerifierParameters,
         transaction: &Self::Transaction,
         ledger: &L,
     ) -> Result<bool, Error> {
         let verify_time = timer_start!(|| "PlainDPC::Verify");
+        let arity = Arity::new(
+            transaction.old_serial_numbers().len(),
+            transaction.new_commitments().len(),
+        );
+        let arity_vk = match parameters.arity_vk(arity) {
+            Some(arity_vk) => arity_vk,
+            None => {
+                eprintln!("Transaction arity is not supported.");
+                return Ok(false);
+            },
+        };
+
         let ledger_time = timer_start!(|| "Ledger checks");
         for sn in transaction.old_serial_numbers() {
             if ledger.contains_sn(sn) {
@@ -733,6 +1082,17 @@ where
             }
         }
 
+        if transaction.memorandum().len() != Components::MEMO_SIZE {
+            eprintln!("Transaction memorandum has the wrong size.");
+            return Ok(false);
+        }
+
+        // Every new record must be sent to its owner.
+        if transaction.encrypted_records().len() != transaction.new_commitments().len() {
+            eprintln!("Transaction does not contain an encryption of every new record");
+            return Ok(false);
+        }
+
         // Check that the record commitment digest is valid.
         if !ledger.validate_digest(&transaction.stuff.digest) {
             eprintln!("Ledger digest is invalid.");
@@ -749,9 +1109,13 @@ where
             memo:               transaction.memorandum().clone(),
             predicate_comm:     transaction.stuff.predicate_comm.clone(),
             local_data_comm:    transaction.stuff.local_data_comm.clone(),
+
+            encrypted_records_digest: Self::encrypted_records_digest(
+                transaction.encrypted_records(),
+            )?,
         };
         if !Components::MainNIZK::verify(
-            &parameters.core_nizk_pp.1,
+            &arity_vk.core_nizk_vk,
             &input,
             &transaction.stuff.core_proof,
         )? {
@@ -766,7 +1130,7 @@ where
         };
 
         if !Components::ProofCheckNIZK::verify(
-            &parameters.proof_check_nizk_pp.1,
+            &arity_vk.proof_check_nizk_vk,
             &input,
             &transaction.stuff.predicate_proof,
         )? {
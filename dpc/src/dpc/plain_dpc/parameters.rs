@@ -1,6 +1,14 @@
+use algebra::bytes::{FromBytes, ToBytes};
+use failure::{format_err, Error};
+use std::io::{Read, Result as IoResult, Write};
+
 use crate::{
-    crypto_primitives::{CommitmentScheme, FixedLengthCRH, NIZK},
-    dpc::plain_dpc::PlainDPCComponents,
+    crypto_primitives::{CommitmentScheme, EncryptionScheme, FixedLengthCRH, NIZK},
+    dpc::{
+        plain_dpc::PlainDPCComponents,
+        serialization::{read_component, read_version, write_component, write_version},
+        Arity,
+    },
 };
 
 #[derive(Derivative)]
@@ -11,10 +19,38 @@ pub struct CommAndCRHPublicParameters<C: PlainDPCComponents> {
     pub pred_vk_comm_pp:    <C::PredVkComm as CommitmentScheme>::Parameters,
     pub local_data_comm_pp: <C::LocalDataComm as CommitmentScheme>::Parameters,
 
+    pub rec_enc_pp: <C::RecEnc as EncryptionScheme>::Parameters,
+
     pub sn_nonce_crh_pp: <C::SnNonceH as FixedLengthCRH>::Parameters,
     pub pred_vk_crh_pp:  <C::PredVkH as FixedLengthCRH>::Parameters,
 }
 
+impl<C: PlainDPCComponents> ToBytes for CommAndCRHPublicParameters<C> {
+    fn write<W: Write>(&self, mut writer: W) -> IoResult<()> {
+        self.addr_comm_pp.write(&mut writer)?;
+        self.rec_comm_pp.write(&mut writer)?;
+        self.pred_vk_comm_pp.write(&mut writer)?;
+        self.local_data_comm_pp.write(&mut writer)?;
+        self.rec_enc_pp.write(&mut writer)?;
+        self.sn_nonce_crh_pp.write(&mut writer)?;
+        self.pred_vk_crh_pp.write(&mut writer)
+    }
+}
+
+impl<C: PlainDPCComponents> FromBytes for CommAndCRHPublicParameters<C> {
+    fn read<R: Read>(mut reader: R) -> IoResult<Self> {
+        Ok(Self {
+            addr_comm_pp:       FromBytes::read(&mut reader)?,
+            rec_comm_pp:        FromBytes::read(&mut reader)?,
+            pred_vk_comm_pp:    FromBytes::read(&mut reader)?,
+            local_data_comm_pp: FromBytes::read(&mut reader)?,
+            rec_enc_pp:         FromBytes::read(&mut reader)?,
+            sn_nonce_crh_pp:    FromBytes::read(&mut reader)?,
+            pred_vk_crh_pp:     FromBytes::read(&mut reader)?,
+        })
+    }
+}
+
 #[derive(Derivative)]
 #[derivative(Clone(bound = "C: PlainDPCComponents"))]
 pub struct PredNIZKParameters<C: PlainDPCComponents> {
@@ -23,9 +59,10 @@ pub struct PredNIZKParameters<C: PlainDPCComponents> {
     pub proof: <C::PredicateNIZK as NIZK>::Proof,
 }
 
-pub struct PublicParameters<C: PlainDPCComponents> {
-    pub comm_and_crh_pp:     CommAndCRHPublicParameters<C>,
-    pub pred_nizk_pp:        PredNIZKParameters<C>,
+/// Parameters of the core check and proof check circuits for transactions of
+/// one arity.
+pub struct ArityParameters<C: PlainDPCComponents> {
+    pub arity:               Arity,
     pub proof_check_nizk_pp: (
         <C::ProofCheckNIZK as NIZK>::ProvingParameters,
         <C::ProofCheckNIZK as NIZK>::PreparedVerificationParameters,
@@ -36,23 +73,184 @@ pub struct PublicParameters<C: PlainDPCComponents> {
     ),
 }
 
+pub struct PublicParameters<C: PlainDPCComponents> {
+    pub comm_and_crh_pp: CommAndCRHPublicParameters<C>,
+    pub pred_nizk_pp:    PredNIZKParameters<C>,
+    // One entry for each of `C::ARITIES`.
+    pub arity_pp: Vec<ArityParameters<C>>,
+}
+
+/// Verifying keys of the core check and proof check circuits for transactions
+/// of one arity.
+#[derive(Derivative)]
+#[derivative(Clone(bound = "C: PlainDPCComponents"))]
+pub struct ArityVerifierParameters<C: PlainDPCComponents> {
+    pub arity:               Arity,
+    pub proof_check_nizk_vk: <C::ProofCheckNIZK as NIZK>::PreparedVerificationParameters,
+    pub core_nizk_vk:        <C::MainNIZK as NIZK>::PreparedVerificationParameters,
+}
+
+/// The part of `PublicParameters` needed to verify transactions, without the
+/// proving keys.
+#[derive(Derivative)]
+#[derivative(Clone(bound = "C: PlainDPCComponents"))]
+pub struct VerifierParameters<C: PlainDPCComponents> {
+    pub comm_and_crh_pp: CommAndCRHPublicParameters<C>,
+    pub pred_nizk_vk:    <C::PredicateNIZK as NIZK>::VerificationParameters,
+    // One entry for each of `C::ARITIES`.
+    pub arity_vk: Vec<ArityVerifierParameters<C>>,
+}
+
+impl<C: PlainDPCComponents> VerifierParameters<C> {
+    /// Returns the verifying keys for transactions of `arity`, if it is
+    /// supported.
+    pub fn arity_vk(&self, arity: Arity) -> Option<&ArityVerifierParameters<C>> {
+        self.arity_vk.iter().find(|vk| vk.arity == arity)
+    }
+
+    /// Reads the verifier parameters from parameters written by
+    /// `PublicParameters::store`, without reading the proving keys that
+    /// follow them.
+    pub fn load<R: Read>(mut reader: R) -> Result<Self, Error> {
+        read_version(&mut reader)?;
+        let comm_and_crh_pp = read_component(&mut reader, "commitment and CRH parameters")?;
+        let pred_nizk_vk = read_component(&mut reader, "predicate verifying key")?;
+
+        let num_arities = u64::read(&mut reader)?;
+        if num_arities != C::ARITIES.len() as u64 {
+            return Err(format_err!(
+                "expected parameters for {} arities, got {}",
+                C::ARITIES.len(),
+                num_arities
+            ));
+        }
+        let mut arity_vk = Vec::with_capacity(C::ARITIES.len());
+        for &arity in C::ARITIES {
+            let num_input_records = u64::read(&mut reader)?;
+            let num_output_records = u64::read(&mut reader)?;
+            if Arity::new(num_input_records as usize, num_output_records as usize) != arity {
+                return Err(format_err!(
+                    "expected parameters for arity {:?}, got {} inputs and {} outputs",
+                    arity,
+                    num_input_records,
+                    num_output_records
+                ));
+            }
+            let proof_check_nizk_vk: <C::ProofCheckNIZK as NIZK>::VerificationParameters =
+                read_component(&mut reader, "proof check verifying key")?;
+            let core_nizk_vk: <C::MainNIZK as NIZK>::VerificationParameters =
+                read_component(&mut reader, "core check verifying key")?;
+            arity_vk.push(ArityVerifierParameters {
+                arity,
+                proof_check_nizk_vk: proof_check_nizk_vk.into(),
+                core_nizk_vk: core_nizk_vk.into(),
+            });
+        }
+        Ok(Self {
+            comm_and_crh_pp,
+            pred_nizk_vk,
+            arity_vk,
+        })
+    }
+}
+
 impl<C: PlainDPCComponents> PublicParameters<C> {
+    /// Returns a copy of the parameters needed to verify transactions.
+    pub fn verifier_parameters(&self) -> VerifierParameters<C> {
+        VerifierParameters {
+            comm_and_crh_pp: self.comm_and_crh_pp.clone(),
+            pred_nizk_vk:    self.pred_nizk_pp.vk.clone(),
+            arity_vk:        self
+                .arity_pp
+                .iter()
+                .map(|pp| ArityVerifierParameters {
+                    arity:               pp.arity,
+                    proof_check_nizk_vk: pp.proof_check_nizk_pp.1.clone(),
+                    core_nizk_vk:        pp.core_nizk_pp.1.clone(),
+                })
+                .collect(),
+        }
+    }
+
+    /// Writes the parameters in a versioned format in which every component is
+    /// followed by a hash of its encoding. The proving keys come last, so that
+    /// `VerifierParameters::load` can stop before them.
+    pub fn store<W: Write>(&self, mut writer: W) -> Result<(), Error> {
+        write_version(&mut writer)?;
+        write_component(&self.comm_and_crh_pp, &mut writer)?;
+        write_component(&self.pred_nizk_pp.vk, &mut writer)?;
+
+        (self.arity_pp.len() as u64).write(&mut writer)?;
+        for pp in &self.arity_pp {
+            (pp.arity.num_input_records as u64).write(&mut writer)?;
+            (pp.arity.num_output_records as u64).write(&mut writer)?;
+            let proof_check_nizk_vk: <C::ProofCheckNIZK as NIZK>::VerificationParameters =
+                pp.proof_check_nizk_pp.1.clone().into();
+            let core_nizk_vk: <C::MainNIZK as NIZK>::VerificationParameters =
+                pp.core_nizk_pp.1.clone().into();
+            write_component(&proof_check_nizk_vk, &mut writer)?;
+            write_component(&core_nizk_vk, &mut writer)?;
+        }
+
+        write_component(&self.pred_nizk_pp.pk, &mut writer)?;
+        write_component(&self.pred_nizk_pp.proof, &mut writer)?;
+        for pp in &self.arity_pp {
+            write_component(&pp.proof_check_nizk_pp.0, &mut writer)?;
+            write_component(&pp.core_nizk_pp.0, &mut writer)?;
+        }
+        Ok(())
+    }
+
+    /// Reads parameters written by `store`, checking the hash of every
+    /// component.
+    pub fn load<R: Read>(mut reader: R) -> Result<Self, Error> {
+        let verifier_parameters = VerifierParameters::<C>::load(&mut reader)?;
+        let pred_nizk_pp = PredNIZKParameters {
+            pk:    read_component(&mut reader, "predicate proving key")?,
+            vk:    verifier_parameters.pred_nizk_vk,
+            proof: read_component(&mut reader, "predicate proof")?,
+        };
+        let mut arity_pp = Vec::with_capacity(verifier_parameters.arity_vk.len());
+        for vk in verifier_parameters.arity_vk {
+            let proof_check_nizk_pk = read_component(&mut reader, "proof check proving key")?;
+            let core_nizk_pk = read_component(&mut reader, "core check proving key")?;
+            arity_pp.push(ArityParameters {
+                arity:               vk.arity,
+                proof_check_nizk_pp: (proof_check_nizk_pk, vk.proof_check_nizk_vk),
+                core_nizk_pp:        (core_nizk_pk, vk.core_nizk_vk),
+            });
+        }
+        Ok(Self {
+            comm_and_crh_pp: verifier_parameters.comm_and_crh_pp,
+            pred_nizk_pp,
+            arity_pp,
+        })
+    }
+
+    /// Returns the circuit parameters for transactions of `arity`, if it is
+    /// supported.
+    pub fn arity_pp(&self, arity: Arity) -> Option<&ArityParameters<C>> {
+        self.arity_pp.iter().find(|pp| pp.arity == arity)
+    }
+
     pub fn core_check_nizk_pp(
         &self,
-    ) -> &(
+        arity: Arity,
+    ) -> Option<&(
         <C::MainNIZK as NIZK>::ProvingParameters,
         <C::MainNIZK as NIZK>::PreparedVerificationParameters,
-    ) {
-        &self.core_nizk_pp
+    )> {
+        self.arity_pp(arity).map(|pp| &pp.core_nizk_pp)
     }
 
     pub fn proof_check_nizk_pp(
         &self,
-    ) -> &(
+        arity: Arity,
+    ) -> Option<&(
         <C::ProofCheckNIZK as NIZK>::ProvingParameters,
         <C::ProofCheckNIZK as NIZK>::PreparedVerificationParameters,
-    ) {
-        &self.proof_check_nizk_pp
+    )> {
+        self.arity_pp(arity).map(|pp| &pp.proof_check_nizk_pp)
     }
 
     pub fn pred_nizk_pp(&self) -> &PredNIZKParameters<C> {
@@ -83,3 +281,22 @@ impl<C: PlainDPCComponents> PublicParameters<C> {
         &self.comm_and_crh_pp.pred_vk_comm_pp
     }
 }
+
+impl<C: PlainDPCComponents> From<PublicParameters<C>> for VerifierParameters<C> {
+    /// Drops the proving keys of `parameters`.
+    fn from(parameters: PublicParameters<C>) -> Self {
+        Self {
+            comm_and_crh_pp: parameters.comm_and_crh_pp,
+            pred_nizk_vk:    parameters.pred_nizk_pp.vk,
+            arity_vk:        parameters
+                .arity_pp
+                .into_iter()
+                .map(|pp| ArityVerifierParameters {
+                    arity:               pp.arity,
+                    proof_check_nizk_vk: pp.proof_check_nizk_pp.1,
+                    core_nizk_vk:        pp.core_nizk_pp.1,
+                })
+                .collect(),
+        }
+    }
+}
@@ -0,0 +1,35 @@
+use algebra::{
+    bytes::{FromBytes, ToBytes},
+    to_bytes,
+};
+use failure::{format_err, Error};
+
+use crate::dpc::plain_dpc::PlainDPCComponents;
+
+/// Encodes `value` as a record payload of `C::PAYLOAD_SIZE` bytes: the byte
+/// representation of `value`, followed by zeros.
+pub fn encode_payload<C: PlainDPCComponents, T: ToBytes>(value: &T) -> Result<Vec<u8>, Error> {
+    let mut payload = to_bytes![value]?;
+    if payload.len() > C::PAYLOAD_SIZE {
+        return Err(format_err!(
+            "value of {} bytes does not fit in a payload of {} bytes",
+            payload.len(),
+            C::PAYLOAD_SIZE
+        ));
+    }
+    payload.resize(C::PAYLOAD_SIZE, 0u8);
+    Ok(payload)
+}
+
+/// Decodes a value of type `T` from the start of `payload`, the inverse of
+/// `encode_payload`. The bytes of `payload` that follow the value are ignored.
+pub fn decode_payload<C: PlainDPCComponents, T: FromBytes>(payload: &[u8]) -> Result<T, Error> {
+    if payload.len() != C::PAYLOAD_SIZE {
+        return Err(format_err!(
+            "expected a payload of {} bytes, got {} bytes",
+            C::PAYLOAD_SIZE,
+            payload.len()
+        ));
+    }
+    Ok(T::read(payload)?)
+}
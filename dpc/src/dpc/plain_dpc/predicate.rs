@@ -1,6 +1,16 @@
+use algebra::{bytes::ToBytes, to_bytes};
+use failure::Error;
+use rand::Rng;
+
 use crate::{
-    crypto_primitives::NIZK,
-    dpc::{plain_dpc::PlainDPCComponents, Predicate},
+    crypto_primitives::{FixedLengthCRH, NIZK},
+    dpc::{
+        plain_dpc::{
+            predicate_circuit::{PredicateCircuit, PredicateLocalData},
+            CommAndCRHPublicParameters, LocalData, PlainDPCComponents,
+        },
+        Arity, Predicate,
+    },
 };
 use std::marker::PhantomData;
 
@@ -45,17 +55,98 @@ impl<C: PlainDPCComponents> DPCPredicate<C> {
             _components: PhantomData,
         }
     }
+
+    /// Returns the predicate identified by the `PredVkH` hash of `vk`.
+    pub fn from_verification_key(
+        pred_vk_crh_pp: &<C::PredVkH as FixedLengthCRH>::Parameters,
+        vk: &<C::PredicateNIZK as NIZK>::VerificationParameters,
+    ) -> Result<Self, Error> {
+        let vk_hash = C::PredVkH::evaluate(pred_vk_crh_pp, &to_bytes![vk]?)?;
+        Ok(Self::new(to_bytes![vk_hash]?))
+    }
+}
+
+/// Public input for evaluating a `DPCPredicate` outside of the proof check
+/// circuit.
+#[derive(Derivative)]
+#[derivative(Clone(bound = "C: PlainDPCComponents"))]
+pub struct PredicatePublicInput<C: PlainDPCComponents> {
+    pub pred_vk_crh_pp: <C::PredVkH as FixedLengthCRH>::Parameters,
+    pub local_data:     PredicateLocalData<C>,
 }
 
 impl<C: PlainDPCComponents> Predicate for DPCPredicate<C> {
-    type PublicInput = ();
+    type PublicInput = PredicatePublicInput<C>;
     type PrivateWitness = PrivatePredInput<C>;
 
-    fn evaluate(&self, _p: &Self::PublicInput, _w: &Self::PrivateWitness) -> bool {
-        unimplemented!()
+    /// Checks that the verification key in `w` hashes to the identity of this
+    /// predicate, and that the proof in `w` verifies against it.
+    fn evaluate(&self, p: &Self::PublicInput, w: &Self::PrivateWitness) -> bool {
+        match Self::from_verification_key(&p.pred_vk_crh_pp, &w.vk) {
+            Ok(predicate) if predicate.identity == self.identity => {}
+            _ => return false,
+        }
+        let pvk: <C::PredicateNIZK as NIZK>::PreparedVerificationParameters = w.vk.clone().into();
+        C::PredicateNIZK::verify(&pvk, &p.local_data, &w.proof).unwrap_or(false)
     }
 
     fn into_compact_repr(&self) -> Vec<u8> {
         self.identity.clone()
     }
 }
+
+/// A user-defined predicate, together with the parameters needed to prove it.
+/// `N` can be any NIZK whose verification keys and proofs have the same format
+/// as those of `C::PredicateNIZK`, so that records guarded by different
+/// predicate circuits can be consumed and created in one transaction.
+pub struct RegisteredPredicate<C: PlainDPCComponents, N: NIZK> {
+    pub predicate: DPCPredicate<C>,
+    pub pk:        N::ProvingParameters,
+    pub vk:        <C::PredicateNIZK as NIZK>::VerificationParameters,
+}
+
+impl<C, N> RegisteredPredicate<C, N>
+where
+    C: PlainDPCComponents,
+    N: NIZK<
+        VerificationParameters = <C::PredicateNIZK as NIZK>::VerificationParameters,
+        Proof = <C::PredicateNIZK as NIZK>::Proof,
+        VerifierInput = PredicateLocalData<C>,
+    >,
+    N::Circuit: PredicateCircuit<C>,
+    N::AssignedCircuit: PredicateCircuit<C>,
+{
+    /// Generates parameters for `N::Circuit` over transactions of `arity`, and
+    /// derives the identity of the predicate from the resulting verification
+    /// key.
+    pub fn setup<R: Rng>(
+        comm_and_crh_pp: &CommAndCRHPublicParameters<C>,
+        arity: Arity,
+        rng: &mut R,
+    ) -> Result<Self, Error> {
+        let (pk, pvk) = N::setup(
+            <N::Circuit as PredicateCircuit<C>>::blank(comm_and_crh_pp, arity),
+            rng,
+        )?;
+        let vk: N::VerificationParameters = pvk.into();
+        let predicate = DPCPredicate::from_verification_key(&comm_and_crh_pp.pred_vk_crh_pp, &vk)?;
+        Ok(Self { predicate, pk, vk })
+    }
+
+    /// Proves that the record at `position` in the transaction described by
+    /// `local_data` satisfies this predicate.
+    pub fn prove<R: Rng>(
+        &self,
+        local_data: &LocalData<C>,
+        position: u8,
+        rng: &mut R,
+    ) -> Result<PrivatePredInput<C>, Error> {
+        let circuit =
+            <N::AssignedCircuit as PredicateCircuit<C>>::from_local_data(local_data, position);
+        let proof = N::prove(&self.pk, circuit, rng)?;
+        Ok(PrivatePredInput {
+            vk: self.vk.clone(),
+            proof,
+        })
+    }
+}
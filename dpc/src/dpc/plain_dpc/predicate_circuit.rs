@@ -1,7 +1,6 @@
 use crate::{
     crypto_primitives::{CommitmentScheme, PRF},
     dpc::{plain_dpc::DPCRecord, Record},
-    gadgets::Assignment,
     plain_dpc::*,
 };
 use snark_gadgets::{uint8::UInt8, utils::AllocGadget};
@@ -29,47 +28,43 @@ pub struct PredicateHashInput<C: PlainDPCComponents> {
     pub new_death_pred_ids: Vec<Vec<u8>>,
     pub new_birth_pred_ids: Vec<Vec<u8>>,
 
-    pub memo:      [u8; 32],
+    pub memo:      Vec<u8>,
     pub auxiliary: [u8; 32],
 }
 
-impl<C: PlainDPCComponents> Default for PredicateHashInput<C> {
-    fn default() -> Self {
+impl<C: PlainDPCComponents> PredicateHashInput<C> {
+    pub fn blank(arity: Arity) -> Self {
+        let num_input_records = arity.num_input_records;
+        let num_output_records = arity.num_output_records;
         Self {
             old_rec_comms:      vec![
                 <C::RecC as CommitmentScheme>::Output::default();
-                C::NUM_INPUT_RECORDS
+                num_input_records
             ],
             old_apks:           vec![
                 <C::AddrC as CommitmentScheme>::Output::default();
-                C::NUM_INPUT_RECORDS
+                num_input_records
             ],
-            old_dummy_flags:    vec![false; C::NUM_INPUT_RECORDS],
-            old_payloads:       vec![
-                <DPCRecord<C> as Record>::Payload::default();
-                C::NUM_INPUT_RECORDS
-            ],
-            old_death_pred_ids: vec![vec![0u8; 48]; C::NUM_INPUT_RECORDS],
-            old_birth_pred_ids: vec![vec![0u8; 48]; C::NUM_INPUT_RECORDS],
-            old_serial_numbers: vec![<C::P as PRF>::Output::default(); C::NUM_INPUT_RECORDS],
+            old_dummy_flags:    vec![false; num_input_records],
+            old_payloads:       vec![vec![0u8; C::PAYLOAD_SIZE]; num_input_records],
+            old_death_pred_ids: vec![vec![0u8; 48]; num_input_records],
+            old_birth_pred_ids: vec![vec![0u8; 48]; num_input_records],
+            old_serial_numbers: vec![<C::P as PRF>::Output::default(); num_input_records],
 
             new_rec_comms:      vec![
                 <C::RecC as CommitmentScheme>::Output::default();
-                C::NUM_OUTPUT_RECORDS
+                num_output_records
             ],
             new_apks:           vec![
                 <C::AddrC as CommitmentScheme>::Output::default();
-                C::NUM_OUTPUT_RECORDS
-            ],
-            new_dummy_flags:    vec![false; C::NUM_OUTPUT_RECORDS],
-            new_payloads:       vec![
-                <DPCRecord<C> as Record>::Payload::default();
-                C::NUM_OUTPUT_RECORDS
+                num_output_records
             ],
-            new_death_pred_ids: vec![vec![0u8; 48]; C::NUM_OUTPUT_RECORDS],
-            new_birth_pred_ids: vec![vec![0u8; 48]; C::NUM_OUTPUT_RECORDS],
+            new_dummy_flags:    vec![false; num_output_records],
+            new_payloads:       vec![vec![0u8; C::PAYLOAD_SIZE]; num_output_records],
+            new_death_pred_ids: vec![vec![0u8; 48]; num_output_records],
+            new_birth_pred_ids: vec![vec![0u8; 48]; num_output_records],
 
-            memo:      [0u8; 32],
+            memo:      vec![0u8; C::MEMO_SIZE],
             auxiliary: [0u8; 32],
         }
     }
@@ -77,7 +72,7 @@ impl<C: PlainDPCComponents> Default for PredicateHashInput<C> {
 
 impl<C: PlainDPCComponents> ToBytes for PredicateHashInput<C> {
     fn write<W: Write>(&self, mut writer: W) -> IoResult<()> {
-        for i in 0..C::NUM_INPUT_RECORDS {
+        for i in 0..self.old_rec_comms.len() {
             self.old_rec_comms[i].write(&mut writer)?;
             self.old_apks[i].write(&mut writer)?;
             self.old_dummy_flags[i].write(&mut writer)?;
@@ -87,7 +82,7 @@ impl<C: PlainDPCComponents> ToBytes for PredicateHashInput<C> {
             self.old_serial_numbers[i].write(&mut writer)?;
         }
 
-        for i in 0..C::NUM_OUTPUT_RECORDS {
+        for i in 0..self.new_rec_comms.len() {
             self.new_rec_comms[i].write(&mut writer)?;
             self.new_apks[i].write(&mut writer)?;
             self.new_dummy_flags[i].write(&mut writer)?;
@@ -101,6 +96,8 @@ impl<C: PlainDPCComponents> ToBytes for PredicateHashInput<C> {
     }
 }
 
+#[derive(Derivative)]
+#[derivative(Clone(bound = "C: PlainDPCComponents"))]
 pub struct PredicateLocalData<C: PlainDPCComponents> {
     pub local_data_comm_pp: <C::LocalDataComm as CommitmentScheme>::Parameters,
     pub local_data_comm:    <C::LocalDataComm as CommitmentScheme>::Output,
@@ -157,26 +154,87 @@ impl<C: PlainDPCComponents> EmptyPredicateCircuit<C> {
     }
 }
 
+impl<C: PlainDPCComponents> PredicateCircuit<C> for EmptyPredicateCircuit<C> {
+    fn blank(comm_and_crh_parameters: &CommAndCRHPublicParameters<C>, _arity: Arity) -> Self {
+        Self::blank(comm_and_crh_parameters)
+    }
+
+    fn from_local_data(local_data: &LocalData<C>, position: u8) -> Self {
+        Self::new(
+            &local_data.comm_and_crh_pp,
+            &local_data.local_data_comm,
+            position,
+        )
+    }
+}
+
 impl<C: PlainDPCComponents> Circuit<C::E> for EmptyPredicateCircuit<C> {
     fn synthesize<CS: ConstraintSystem<C::E>>(self, cs: &mut CS) -> Result<(), SynthesisError> {
-        let _position = UInt8::alloc_input_vec(cs.ns(|| "Alloc position"), &[self.position])?;
-
-        let _local_data_comm_pp =
-            <C::LocalDataCommGadget as CommitmentGadget<_, _>>::ParametersGadget::alloc_input(
-                &mut cs.ns(|| "Declare Pred Input Comm parameters"),
-                || {
-                    self.comm_and_crh_parameters
-                        .get()
-                        .map(|pp| &pp.local_data_comm_pp)
-                },
-            )?;
-
-        let _local_data_comm =
-            <C::LocalDataCommGadget as CommitmentGadget<_, _>>::OutputGadget::alloc_input(
-                cs.ns(|| "Allocate predicate commitment"),
-                || self.local_data_comm.get(),
-            )?;
+        let _local_data = alloc_predicate_local_data(
+            cs.ns(|| "Declare predicate local data"),
+            self.comm_and_crh_parameters.as_ref(),
+            self.local_data_comm.as_ref(),
+            self.position,
+        )?;
 
         Ok(())
     }
 }
+
+/// A circuit for a birth or death predicate. The circuit must declare exactly
+/// the `PredicateLocalData` as its public input, via
+/// `alloc_predicate_local_data`, so that its verification key has the same
+/// shape as that of `C::PredicateNIZK` and its proofs can be checked inside
+/// the proof check circuit.
+pub trait PredicateCircuit<C: PlainDPCComponents>: Circuit<C::E> {
+    /// Returns an instance of the circuit with default assignments for
+    /// transactions of `arity`, used to generate parameters. Circuits that
+    /// read the records of a transaction can only prove transactions of the
+    /// arity they were set up for.
+    fn blank(comm_and_crh_parameters: &CommAndCRHPublicParameters<C>, arity: Arity) -> Self;
+
+    /// Returns an instance of the circuit for the record at `position` in the
+    /// transaction described by `local_data`.
+    fn from_local_data(local_data: &LocalData<C>, position: u8) -> Self;
+}
+
+/// Declares the public input of a predicate circuit: the position of the
+/// record, the local data commitment parameters, and the local data
+/// commitment.
+pub fn alloc_predicate_local_data<C, CS>(
+    mut cs: CS,
+    comm_and_crh_parameters: Option<&CommAndCRHPublicParameters<C>>,
+    local_data_comm: Option<&<C::LocalDataComm as CommitmentScheme>::Output>,
+    position: u8,
+) -> Result<
+    (
+        Vec<UInt8>,
+        <C::LocalDataCommGadget as CommitmentGadget<C::LocalDataComm, C::E>>::ParametersGadget,
+        <C::LocalDataCommGadget as CommitmentGadget<C::LocalDataComm, C::E>>::OutputGadget,
+    ),
+    SynthesisError,
+>
+where
+    C: PlainDPCComponents,
+    CS: ConstraintSystem<C::E>,
+{
+    let position = UInt8::alloc_input_vec(cs.ns(|| "Alloc position"), &[position])?;
+
+    let local_data_comm_pp =
+        <C::LocalDataCommGadget as CommitmentGadget<_, _>>::ParametersGadget::alloc_input(
+            &mut cs.ns(|| "Declare Pred Input Comm parameters"),
+            || {
+                comm_and_crh_parameters
+                    .map(|pp| &pp.local_data_comm_pp)
+                    .ok_or(SynthesisError::AssignmentMissing)
+            },
+        )?;
+
+    let local_data_comm =
+        <C::LocalDataCommGadget as CommitmentGadget<_, _>>::OutputGadget::alloc_input(
+            cs.ns(|| "Allocate predicate commitment"),
+            || local_data_comm.ok_or(SynthesisError::AssignmentMissing),
+        )?;
+
+    Ok((position, local_data_comm_pp, local_data_comm))
+}
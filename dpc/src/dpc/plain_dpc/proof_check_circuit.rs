@@ -8,7 +8,10 @@ use crate::{
 };
 
 use crate::{
-    dpc::plain_dpc::{CommAndCRHPublicParameters, PlainDPCComponents, PrivatePredInput},
+    dpc::{
+        plain_dpc::{CommAndCRHPublicParameters, PlainDPCComponents, PrivatePredInput},
+        Arity,
+    },
     gadgets::dpc::plain_dpc::execute_proof_check_gadget,
 };
 
@@ -82,9 +85,10 @@ impl<C: PlainDPCComponents> ProofCheckCircuit<C> {
     pub fn blank(
         comm_and_crh_parameters: &CommAndCRHPublicParameters<C>,
         predicate_nizk_vk_and_proof: &PrivatePredInput<C>,
+        arity: Arity,
     ) -> Self {
-        let num_input_records = C::NUM_INPUT_RECORDS;
-        let num_output_records = C::NUM_OUTPUT_RECORDS;
+        let num_input_records = arity.num_input_records;
+        let num_output_records = arity.num_output_records;
 
         let old_private_pred_inputs =
             Some(vec![predicate_nizk_vk_and_proof.clone(); num_input_records]);
@@ -123,13 +127,6 @@ impl<C: PlainDPCComponents> ProofCheckCircuit<C> {
         predicate_rand: &<C::PredVkComm as CommitmentScheme>::Randomness,
         local_data_comm: &<C::LocalDataComm as CommitmentScheme>::Output,
     ) -> Self {
-        let num_input_records = C::NUM_INPUT_RECORDS;
-        let num_output_records = C::NUM_OUTPUT_RECORDS;
-
-        assert_eq!(num_input_records, old_private_pred_inputs.len());
-
-        assert_eq!(num_output_records, new_private_pred_inputs.len());
-
         Self {
             comm_and_crh_parameters: Some(comm_and_crh_parameters.clone()),
 
@@ -2,11 +2,18 @@ use crate::{
     crypto_primitives::{CommitmentScheme, FixedLengthCRH, PRF},
     dpc::{
         plain_dpc::{AddressPublicKey, DPCPredicate, PlainDPCComponents},
+        serialization::{read_fixed_bytes, read_version, write_version},
         Record,
     },
 };
-use algebra::to_bytes;
-use std::marker::PhantomData;
+use algebra::{
+    bytes::{FromBytes, ToBytes},
+    to_bytes,
+};
+use std::{
+    io::{Read, Result as IoResult, Write},
+    marker::PhantomData,
+};
 
 #[derive(Derivative)]
 #[derivative(
@@ -17,7 +24,8 @@ pub struct DPCRecord<C: PlainDPCComponents> {
     pub(super) address_public_key: AddressPublicKey<C>,
 
     pub(super) is_dummy: bool,
-    pub(super) payload:  [u8; 32],
+    #[derivative(Default(value = "vec![0u8; C::PAYLOAD_SIZE]"))]
+    pub(super) payload: Vec<u8>,
 
     #[derivative(Default(value = "default_predicate_hash::<C::PredVkH>()"))]
     pub(super) birth_predicate_repr: Vec<u8>,
@@ -33,16 +41,58 @@ pub struct DPCRecord<C: PlainDPCComponents> {
 }
 
 fn default_predicate_hash<C: FixedLengthCRH>() -> Vec<u8> {
-    use algebra::bytes::ToBytes;
     to_bytes![C::Output::default()].unwrap()
 }
 
+impl<C: PlainDPCComponents> ToBytes for DPCRecord<C> {
+    fn write<W: Write>(&self, mut writer: W) -> IoResult<()> {
+        write_version(&mut writer)?;
+        self.address_public_key.write(&mut writer)?;
+        self.is_dummy.write(&mut writer)?;
+        self.payload.write(&mut writer)?;
+        self.birth_predicate_repr.write(&mut writer)?;
+        self.death_predicate_repr.write(&mut writer)?;
+        self.serial_number_nonce.write(&mut writer)?;
+        self.commitment.write(&mut writer)?;
+        self.commitment_randomness.write(&mut writer)
+    }
+}
+
+impl<C: PlainDPCComponents> FromBytes for DPCRecord<C> {
+    fn read<R: Read>(mut reader: R) -> IoResult<Self> {
+        read_version(&mut reader)?;
+        let address_public_key = FromBytes::read(&mut reader)?;
+        let is_dummy = FromBytes::read(&mut reader)?;
+        let payload = read_fixed_bytes(&mut reader, C::PAYLOAD_SIZE)?;
+        // Predicate representations are hashes of verification keys, so they
+        // are read as such to check their length.
+        let birth_predicate_repr: <C::PredVkH as FixedLengthCRH>::Output =
+            FromBytes::read(&mut reader)?;
+        let death_predicate_repr: <C::PredVkH as FixedLengthCRH>::Output =
+            FromBytes::read(&mut reader)?;
+        let serial_number_nonce = FromBytes::read(&mut reader)?;
+        let commitment = FromBytes::read(&mut reader)?;
+        let commitment_randomness = FromBytes::read(&mut reader)?;
+        Ok(Self {
+            address_public_key,
+            is_dummy,
+            payload,
+            birth_predicate_repr: to_bytes![birth_predicate_repr]?,
+            death_predicate_repr: to_bytes![death_predicate_repr]?,
+            serial_number_nonce,
+            commitment,
+            commitment_randomness,
+            _components: PhantomData,
+        })
+    }
+}
+
 impl<C: PlainDPCComponents> Record for DPCRecord<C> {
     type AddressPublicKey = AddressPublicKey<C>;
     type Commitment = <C::RecC as CommitmentScheme>::Output;
     type CommitmentRandomness = <C::RecC as CommitmentScheme>::Randomness;
 
-    type Payload = [u8; 32];
+    type Payload = Vec<u8>;
     type Predicate = DPCPredicate<C>;
     type SerialNumberNonce = <C::SnNonceH as FixedLengthCRH>::Output;
     type SerialNumber = <C::P as PRF>::Output;
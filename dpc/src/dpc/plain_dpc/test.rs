@@ -1,22 +1,28 @@
 use super::instantiated::*;
 use algebra::{
     curves::{bls12_377::Bls12_377, sw6::SW6},
-    to_bytes, ToBytes,
+    fields::bls12_377::Fr,
+    to_bytes, Field, ToBytes,
 };
 use rand::{SeedableRng, XorShiftRng};
 #[cfg(debug_assertions)]
 use snark::gm17::PreparedVerifyingKey;
 
-use crate::crypto_primitives::FixedLengthCRH;
+use crate::crypto_primitives::{nizk::Gm17, CommitmentScheme, FixedLengthCRH};
 
-use snark::ConstraintSystem;
+use snark::{Circuit, ConstraintSystem, SynthesisError};
 
 use crate::gadgets::dpc::plain_dpc::{execute_core_checks_gadget, execute_proof_check_gadget};
 use snark_gadgets::test_constraint_system::TestConstraintSystem;
 
 use crate::dpc::{
-    plain_dpc::{predicate::PrivatePredInput, predicate_circuit::*, ExecuteContext, DPC},
-    Record,
+    plain_dpc::{
+        payload::{decode_payload, encode_payload},
+        predicate::{PredicatePublicInput, PrivatePredInput, RegisteredPredicate},
+        predicate_circuit::*,
+        CommAndCRHPublicParameters, ExecuteContext, LocalData, PlainDPCComponents, DPC,
+    },
+    Arity, Predicate as _, Record,
 };
 
 use crate::ledger::Ledger;
@@ -51,7 +57,7 @@ fn test_execute_constraint_systems() {
         &genesis_sn_nonce,
         &genesis_address.public_key,
         true,
-        &[0u8; 32],
+        &[0u8; PAYLOAD_SIZE],
         &Predicate::new(pred_nizk_vk_bytes.clone()),
         &Predicate::new(pred_nizk_vk_bytes.clone()),
         &mut rng,
@@ -60,7 +66,7 @@ fn test_execute_constraint_systems() {
 
     // Generate serial number for the genesis record.
     let genesis_sn = DPC::generate_sn(&genesis_record, &genesis_address.secret_key).unwrap();
-    let genesis_memo = [0u8; 32];
+    let genesis_memo = vec![0u8; MEMO_SIZE];
 
     // Use genesis record, serial number, and memo to initialize the ledger.
     let ledger = MerkleTreeIdealLedger::new(
@@ -82,7 +88,7 @@ fn test_execute_constraint_systems() {
         DPC::create_address_helper(&comm_and_crh_pp, &new_metadata, &mut rng).unwrap();
 
     // Create a payload.
-    let new_payload = [1u8; 32];
+    let new_payload = vec![1u8; PAYLOAD_SIZE];
     // Set the new record's predicate to be the "always-accept" predicate.
     let new_predicate = Predicate::new(pred_nizk_vk_bytes.clone());
 
@@ -92,7 +98,7 @@ fn test_execute_constraint_systems() {
     let new_death_predicates = vec![new_predicate.clone(); NUM_OUTPUT_RECORDS];
     let new_dummy_flags = vec![false; NUM_OUTPUT_RECORDS];
     let auxiliary = [122u8; 32];
-    let memo = [238u8; 32];
+    let memo = [238u8; MEMO_SIZE];
 
     let context = DPC::execute_helper(
         &comm_and_crh_pp,
@@ -128,6 +134,9 @@ fn test_execute_constraint_systems() {
 
         local_data_comm,
         local_data_rand,
+
+        memo: _memo,
+        auxiliary: _auxiliary,
     } = context;
 
     //////////////////////////////////////////////////////////////////////////
@@ -152,6 +161,7 @@ fn test_execute_constraint_systems() {
         &local_data_rand,
         &memo,
         &auxiliary,
+        &[7u8; 32],
     )
     .unwrap();
 
@@ -246,3 +256,403 @@ fn test_execute_constraint_systems() {
 
     assert!(pf_check_cs.is_satisfied());
 }
+
+// A predicate that, besides declaring the local data, proves knowledge of a
+// square root of four. It has a different verification key than
+// `EmptyPredicateCircuit`.
+struct SquareRootPredicateCircuit {
+    comm_and_crh_parameters: Option<CommAndCRHPublicParameters<Components>>,
+    local_data_comm:         Option<<LocalDataComm as CommitmentScheme>::Output>,
+    position:                u8,
+    root:                    Option<Fr>,
+}
+
+impl PredicateCircuit<Components> for SquareRootPredicateCircuit {
+    fn blank(comm_and_crh_parameters: &CommAndCRHPublicParameters<Components>, _: Arity) -> Self {
+        Self {
+            comm_and_crh_parameters: Some(comm_and_crh_parameters.clone()),
+            local_data_comm:         Some(<LocalDataComm as CommitmentScheme>::Output::default()),
+            position:                0u8,
+            root:                    Some(Fr::zero()),
+        }
+    }
+
+    fn from_local_data(local_data: &LocalData<Components>, position: u8) -> Self {
+        Self {
+            comm_and_crh_parameters: Some(local_data.comm_and_crh_pp.clone()),
+            local_data_comm:         Some(local_data.local_data_comm.clone()),
+            position,
+            root:                    Some(Fr::one().double()),
+        }
+    }
+}
+
+impl Circuit<CoreEngine> for SquareRootPredicateCircuit {
+    fn synthesize<CS: ConstraintSystem<CoreEngine>>(
+        self,
+        cs: &mut CS,
+    ) -> Result<(), SynthesisError> {
+        let _local_data = alloc_predicate_local_data(
+            cs.ns(|| "Declare predicate local data"),
+            self.comm_and_crh_parameters.as_ref(),
+            self.local_data_comm.as_ref(),
+            self.position,
+        )?;
+
+        let root = cs.alloc(
+            || "Alloc root",
+            || self.root.ok_or(SynthesisError::AssignmentMissing),
+        )?;
+        let four = Fr::one().double().double();
+        cs.enforce(
+            || "root * root = 4",
+            |lc| lc + root,
+            |lc| lc + root,
+            |lc| lc + (four, CS::one()),
+        );
+        Ok(())
+    }
+}
+
+type SquareRootPredicateNIZK =
+    Gm17<CoreEngine, SquareRootPredicateCircuit, PredicateLocalData<Components>>;
+
+#[test]
+fn test_execute_with_user_defined_predicates() {
+    let mut rng = XorShiftRng::from_seed([0x5dbe6259, 0x8d313d76, 0x3237db17, 0xe5bc0654]);
+    let ledger_parameters = MerkleTreeIdealLedger::setup(&mut rng).expect("Ledger setup failed");
+    let comm_and_crh_pp = InstantiatedDPC::generate_comm_and_crh_parameters(&mut rng).unwrap();
+
+    // Register the "always-accept" predicate and the square root predicate.
+    let arity = Arity::new(NUM_INPUT_RECORDS, NUM_OUTPUT_RECORDS);
+    let empty_predicate = RegisteredPredicate::<Components, PredicateNIZK<Components>>::setup(
+        &comm_and_crh_pp,
+        arity,
+        &mut rng,
+    )
+    .unwrap();
+    let square_root_predicate = RegisteredPredicate::<Components, SquareRootPredicateNIZK>::setup(
+        &comm_and_crh_pp,
+        arity,
+        &mut rng,
+    )
+    .unwrap();
+    assert_ne!(
+        empty_predicate.predicate.into_compact_repr(),
+        square_root_predicate.predicate.into_compact_repr()
+    );
+
+    let genesis_address =
+        DPC::create_address_helper(&comm_and_crh_pp, &[1u8; 32], &mut rng).unwrap();
+    let genesis_sn_nonce =
+        SnNonceCRH::evaluate(&comm_and_crh_pp.sn_nonce_crh_pp, &[0u8; 1]).unwrap();
+    let genesis_record = DPC::generate_record(
+        &comm_and_crh_pp,
+        &genesis_sn_nonce,
+        &genesis_address.public_key,
+        true,
+        &[0u8; PAYLOAD_SIZE],
+        &empty_predicate.predicate,
+        &empty_predicate.predicate,
+        &mut rng,
+    )
+    .unwrap();
+    let genesis_sn = DPC::generate_sn(&genesis_record, &genesis_address.secret_key).unwrap();
+    let ledger = MerkleTreeIdealLedger::new(
+        ledger_parameters,
+        genesis_record.commitment(),
+        genesis_sn,
+        vec![0u8; MEMO_SIZE],
+    );
+
+    // The input records alternate between the two death predicates, and the
+    // output records between the two birth predicates.
+    let predicates = [&empty_predicate.predicate, &square_root_predicate.predicate];
+    let mut old_records = vec![];
+    for i in 0..NUM_INPUT_RECORDS {
+        let sn_nonce =
+            SnNonceCRH::evaluate(&comm_and_crh_pp.sn_nonce_crh_pp, &[64u8 + (i as u8); 1]).unwrap();
+        let record = DPC::generate_record(
+            &comm_and_crh_pp,
+            &sn_nonce,
+            &genesis_address.public_key,
+            true,
+            &[2u8; PAYLOAD_SIZE],
+            &empty_predicate.predicate,
+            predicates[i % 2],
+            &mut rng,
+        )
+        .unwrap();
+        old_records.push(record);
+    }
+    let old_asks = vec![genesis_address.secret_key.clone(); NUM_INPUT_RECORDS];
+
+    let new_address = DPC::create_address_helper(&comm_and_crh_pp, &[2u8; 32], &mut rng).unwrap();
+    let new_apks = vec![new_address.public_key.clone(); NUM_OUTPUT_RECORDS];
+    let new_payloads = vec![vec![1u8; PAYLOAD_SIZE]; NUM_OUTPUT_RECORDS];
+    let new_birth_predicates = (0..NUM_OUTPUT_RECORDS)
+        .map(|j| predicates[(j + 1) % 2].clone())
+        .collect::<Vec<_>>();
+    let new_death_predicates = vec![empty_predicate.predicate.clone(); NUM_OUTPUT_RECORDS];
+    let new_dummy_flags = vec![false; NUM_OUTPUT_RECORDS];
+    let auxiliary = [122u8; 32];
+    let memo = [238u8; MEMO_SIZE];
+
+    let context = DPC::execute_helper(
+        &comm_and_crh_pp,
+        &old_records,
+        &old_asks,
+        &new_apks,
+        &new_dummy_flags,
+        &new_payloads,
+        &new_birth_predicates,
+        &new_death_predicates,
+        &memo,
+        &auxiliary,
+        &ledger,
+        &mut rng,
+    )
+    .unwrap();
+    let local_data = context.into_local_data();
+
+    let prove = |i: usize, pred: &Predicate, rng: &mut XorShiftRng| {
+        if pred.into_compact_repr() == empty_predicate.predicate.into_compact_repr() {
+            empty_predicate.prove(&local_data, i as u8, rng).unwrap()
+        } else {
+            square_root_predicate
+                .prove(&local_data, i as u8, rng)
+                .unwrap()
+        }
+    };
+    let old_proof_and_vk = (0..NUM_INPUT_RECORDS)
+        .map(|i| prove(i, predicates[i % 2], &mut rng))
+        .collect::<Vec<_>>();
+    let new_proof_and_vk = (0..NUM_OUTPUT_RECORDS)
+        .map(|j| prove(j, &new_birth_predicates[j], &mut rng))
+        .collect::<Vec<_>>();
+
+    // Each predicate accepts its own proofs, and rejects proofs made for a
+    // different predicate.
+    for (i, private_input) in old_proof_and_vk.iter().enumerate() {
+        let public_input = PredicatePublicInput {
+            pred_vk_crh_pp: comm_and_crh_pp.pred_vk_crh_pp.clone(),
+            local_data:     PredicateLocalData {
+                local_data_comm_pp: comm_and_crh_pp.local_data_comm_pp.clone(),
+                local_data_comm:    local_data.local_data_comm.clone(),
+                position:           i as u8,
+            },
+        };
+        assert!(predicates[i % 2].evaluate(&public_input, private_input));
+        assert!(!predicates[(i + 1) % 2].evaluate(&public_input, private_input));
+    }
+
+    let mut pf_check_cs = TestConstraintSystem::<SW6>::new();
+    execute_proof_check_gadget::<_, _>(
+        &mut pf_check_cs.ns(|| "Check predicate proofs"),
+        &comm_and_crh_pp,
+        &old_proof_and_vk,
+        &new_proof_and_vk,
+        &context.predicate_comm,
+        &context.predicate_rand,
+        &context.local_data_comm,
+    )
+    .unwrap();
+
+    if !pf_check_cs.is_satisfied() {
+        println!("=========================================================");
+        println!("Unsatisfied constraints:");
+        println!("{}", pf_check_cs.which_is_unsatisfied().unwrap());
+        println!("=========================================================");
+    }
+    assert!(pf_check_cs.is_satisfied());
+}
+
+#[test]
+fn test_record_encryption() {
+    let mut rng = XorShiftRng::from_seed([0x5dbe6259, 0x8d313d76, 0x3237db17, 0xe5bc0654]);
+    let comm_and_crh_pp = InstantiatedDPC::generate_comm_and_crh_parameters(&mut rng).unwrap();
+    let predicate_repr =
+        to_bytes![PredVkCRH::evaluate(&comm_and_crh_pp.pred_vk_crh_pp, &[7u8; 32]).unwrap()]
+            .unwrap();
+    let predicate = Predicate::new(predicate_repr);
+
+    let owner = DPC::create_address_helper(&comm_and_crh_pp, &[1u8; 32], &mut rng).unwrap();
+    let other = DPC::create_address_helper(&comm_and_crh_pp, &[2u8; 32], &mut rng).unwrap();
+
+    let mut records = vec![];
+    for i in 0..2u8 {
+        let sn_nonce = SnNonceCRH::evaluate(&comm_and_crh_pp.sn_nonce_crh_pp, &[i; 1]).unwrap();
+        let record = DPC::generate_record(
+            &comm_and_crh_pp,
+            &sn_nonce,
+            &owner.public_key,
+            false,
+            &[i + 3; PAYLOAD_SIZE],
+            &predicate,
+            &predicate,
+            &mut rng,
+        )
+        .unwrap();
+        records.push(record);
+    }
+    let ciphertext = DPC::encrypt_record(&comm_and_crh_pp, &records[0], &mut rng).unwrap();
+
+    // The owner recovers the full record.
+    let decrypted = DPC::decrypt_record(
+        &comm_and_crh_pp,
+        &owner.viewing_key(),
+        &records[0].commitment(),
+        &ciphertext,
+    )
+    .unwrap()
+    .expect("owner should be able to decrypt the record");
+    assert_eq!(decrypted.commitment(), records[0].commitment());
+    assert_eq!(decrypted.payload(), records[0].payload());
+    assert_eq!(
+        decrypted.birth_predicate_repr(),
+        records[0].birth_predicate_repr()
+    );
+    assert_eq!(
+        decrypted.serial_number_nonce(),
+        records[0].serial_number_nonce()
+    );
+    assert_eq!(
+        decrypted.commitment_randomness(),
+        records[0].commitment_randomness()
+    );
+
+    // Viewing keys compute the same serial numbers as spending keys.
+    assert_eq!(
+        DPC::generate_sn_with_viewing_key(&decrypted, &owner.viewing_key()).unwrap(),
+        DPC::generate_sn(&records[0], &owner.secret_key).unwrap()
+    );
+
+    // Nobody else can decrypt it, and the contents must open the claimed commitment.
+    assert!(DPC::decrypt_record(
+        &comm_and_crh_pp,
+        &other.viewing_key(),
+        &records[0].commitment(),
+        &ciphertext
+    )
+    .unwrap()
+    .is_none());
+    assert!(DPC::decrypt_record(
+        &comm_and_crh_pp,
+        &owner.viewing_key(),
+        &records[1].commitment(),
+        &ciphertext
+    )
+    .unwrap()
+    .is_none());
+}
+
+#[test]
+fn test_select_arity() {
+    let select = |num_input_records, num_output_records| {
+        Arity::select(Components::ARITIES, num_input_records, num_output_records)
+    };
+    assert_eq!(select(0, 0), Some(Arity::new(1, 1)));
+    assert_eq!(select(1, 1), Some(Arity::new(1, 1)));
+    assert_eq!(select(1, 2), Some(Arity::new(2, 2)));
+    assert_eq!(select(3, 1), Some(Arity::new(4, 4)));
+    assert_eq!(select(5, 1), None);
+}
+
+#[test]
+fn test_payload_encoding() {
+    let payload = encode_payload::<Components, _>(&42u64).unwrap();
+    assert_eq!(payload.len(), PAYLOAD_SIZE);
+    assert!(payload[8..].iter().all(|byte| *byte == 0));
+    assert_eq!(decode_payload::<Components, u64>(&payload).unwrap(), 42);
+
+    assert!(encode_payload::<Components, _>(&vec![0u8; PAYLOAD_SIZE + 1]).is_err());
+    assert!(decode_payload::<Components, u64>(&payload[1..]).is_err());
+
+    // Records only accept payloads of exactly `PAYLOAD_SIZE` bytes.
+    let mut rng = XorShiftRng::from_seed([0x5dbe6259, 0x8d313d76, 0x3237db17, 0xe5bc0654]);
+    let comm_and_crh_pp = InstantiatedDPC::generate_comm_and_crh_parameters(&mut rng).unwrap();
+    let predicate = Predicate::new(vec![0u8; 48]);
+    let owner = DPC::create_address_helper(&comm_and_crh_pp, &[1u8; 32], &mut rng).unwrap();
+    let sn_nonce = SnNonceCRH::evaluate(&comm_and_crh_pp.sn_nonce_crh_pp, &[0u8; 1]).unwrap();
+    assert!(DPC::generate_record(
+        &comm_and_crh_pp,
+        &sn_nonce,
+        &owner.public_key,
+        false,
+        &payload[1..],
+        &predicate,
+        &predicate,
+        &mut rng,
+    )
+    .is_err());
+}
+
+#[test]
+fn test_merkle_tree_crh_cost() {
+    use crate::{
+        crypto_primitives::crh::{injective_map::PedersenCRHCompressor, pedersen::PedersenWindow},
+        gadgets::crh::{injective_map::PedersenCRHCompressorGadget, FixedLengthCRHGadget},
+    };
+    use rand::Rng;
+    use snark_gadgets::{
+        fields::FieldGadget, groups::curves::twisted_edwards::edwards_bls12::EdwardsBlsGadget,
+        uint8::UInt8, utils::AllocGadget,
+    };
+
+    // The Pedersen hash of the same 2 * 256 bits of input.
+    #[derive(Clone, PartialEq, Eq, Hash)]
+    struct PedersenTwoToOneWindow;
+    impl PedersenWindow for PedersenTwoToOneWindow {
+        const WINDOW_SIZE: usize = 128;
+        const NUM_WINDOWS: usize = 4;
+    }
+    type PedersenMerkleTreeCRH =
+        PedersenCRHCompressor<EdwardsBls, EdwardsCompressor, PedersenTwoToOneWindow>;
+    type PedersenMerkleTreeCRHGadget = PedersenCRHCompressorGadget<
+        EdwardsBls,
+        EdwardsCompressor,
+        CoreEngine,
+        EdwardsBlsGadget,
+        EdwardsCompressorGadget,
+    >;
+
+    let mut rng = XorShiftRng::from_seed([0x5dbe6259, 0x8d313d76, 0x3237db17, 0xe5bc0654]);
+    let parameters = MerkleTreeCRH::setup(&mut rng).unwrap();
+    let mut input = [0u8; 64];
+    rng.fill_bytes(&mut input);
+
+    type Gadget = MerkleTreeCRHGadget;
+    type PedersenGadget = PedersenMerkleTreeCRHGadget;
+
+    let mut cs = TestConstraintSystem::<CoreEngine>::new();
+    let input_bytes = UInt8::alloc_vec(cs.ns(|| "input"), &input).unwrap();
+    let parameters_gadget =
+        <Gadget as FixedLengthCRHGadget<MerkleTreeCRH, _>>::ParametersGadget::alloc(
+            cs.ns(|| "parameters"),
+            || Ok(&parameters),
+        )
+        .unwrap();
+    let num_constraints = cs.num_constraints();
+    let output = <Gadget as FixedLengthCRHGadget<MerkleTreeCRH, _>>::check_evaluation_gadget(
+        cs.ns(|| "evaluate"),
+        &parameters_gadget,
+        &input_bytes,
+    )
+    .unwrap();
+    let cost = cs.num_constraints() - num_constraints;
+    assert!(cs.is_satisfied());
+    assert_eq!(
+        output.get_value().unwrap(),
+        MerkleTreeCRH::evaluate(&parameters, &input).unwrap()
+    );
+
+    // Every hash along a membership path costs 3 segments of 57 chunks, each
+    // chunk an AND and two lookups, each segment 56 Montgomery additions and a
+    // conversion to Edwards form, and 2 Edwards additions to sum the segments.
+    assert_eq!(
+        cost,
+        <Gadget as FixedLengthCRHGadget<MerkleTreeCRH, _>>::cost()
+    );
+    assert_eq!(cost, 3 * (57 * 3 + 56 * 3 + 2) + 2 * 6);
+    assert!(cost < <PedersenGadget as FixedLengthCRHGadget<PedersenMerkleTreeCRH, _>>::cost());
+}
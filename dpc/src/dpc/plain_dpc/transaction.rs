@@ -1,7 +1,15 @@
 use crate::{
-    crypto_primitives::{CommitmentScheme, NIZK, PRF},
-    dpc::{plain_dpc::PlainDPCComponents, Transaction},
+    crypto_primitives::{CommitmentScheme, EncryptionScheme, NIZK, PRF},
+    dpc::{
+        plain_dpc::PlainDPCComponents,
+        serialization::{
+            invalid_data, read_fixed_bytes, read_vec, read_version, write_vec, write_version,
+        },
+        Arity, Transaction,
+    },
 };
+use algebra::bytes::{FromBytes, ToBytes};
+use std::io::{Read, Result as IoResult, Write};
 
 #[derive(Derivative)]
 #[derivative(
@@ -12,7 +20,8 @@ use crate::{
 pub struct DPCTransaction<C: PlainDPCComponents> {
     old_serial_numbers: Vec<<C::P as PRF>::Output>,
     new_commitments:    Vec<<C::RecC as CommitmentScheme>::Output>,
-    memorandum:         [u8; 32],
+    memorandum:         Vec<u8>,
+    encrypted_records:  Vec<<C::RecEnc as EncryptionScheme>::Ciphertext>,
     pub stuff:          DPCStuff<C>,
 }
 
@@ -39,6 +48,7 @@ impl<C: PlainDPCComponents> DPCTransaction<C> {
         old_serial_numbers: Vec<<Self as Transaction>::SerialNumber>,
         new_commitments: Vec<<Self as Transaction>::Commitment>,
         memorandum: <Self as Transaction>::Memorandum,
+        encrypted_records: Vec<<C::RecEnc as EncryptionScheme>::Ciphertext>,
         digest: C::D,
         core_proof: <C::MainNIZK as NIZK>::Proof,
         predicate_proof: <C::ProofCheckNIZK as NIZK>::Proof,
@@ -56,16 +66,89 @@ impl<C: PlainDPCComponents> DPCTransaction<C> {
             old_serial_numbers,
             new_commitments,
             memorandum,
+            encrypted_records,
             stuff,
         }
     }
+
+    /// Returns the new records encrypted to their owners, in the same order as
+    /// `new_commitments`.
+    pub fn encrypted_records(&self) -> &[<C::RecEnc as EncryptionScheme>::Ciphertext] {
+        self.encrypted_records.as_slice()
+    }
+}
+
+impl<C: PlainDPCComponents> ToBytes for DPCTransaction<C> {
+    fn write<W: Write>(&self, mut writer: W) -> IoResult<()> {
+        write_version(&mut writer)?;
+        write_vec(&self.old_serial_numbers, &mut writer)?;
+        write_vec(&self.new_commitments, &mut writer)?;
+        self.memorandum.write(&mut writer)?;
+        // There is one encrypted record per new commitment.
+        self.encrypted_records.write(&mut writer)?;
+        self.stuff.write(&mut writer)
+    }
+}
+
+impl<C: PlainDPCComponents> FromBytes for DPCTransaction<C> {
+    fn read<R: Read>(mut reader: R) -> IoResult<Self> {
+        read_version(&mut reader)?;
+        let max_arity = Arity::max_of(C::ARITIES);
+        let old_serial_numbers: Vec<_> = read_vec(&mut reader, max_arity.num_input_records)?;
+        let new_commitments: Vec<_> = read_vec(&mut reader, max_arity.num_output_records)?;
+        let arity = Arity::new(old_serial_numbers.len(), new_commitments.len());
+        if !C::ARITIES.contains(&arity) {
+            return Err(invalid_data("transaction has an unsupported arity"));
+        }
+        let memorandum = read_fixed_bytes(&mut reader, C::MEMO_SIZE)?;
+        let encrypted_records = (0..new_commitments.len())
+            .map(|_| FromBytes::read(&mut reader))
+            .collect::<IoResult<Vec<_>>>()?;
+        let stuff = FromBytes::read(&mut reader)?;
+        Ok(Self {
+            old_serial_numbers,
+            new_commitments,
+            memorandum,
+            encrypted_records,
+            stuff,
+        })
+    }
+}
+
+impl<C: PlainDPCComponents> ToBytes for DPCStuff<C> {
+    fn write<W: Write>(&self, mut writer: W) -> IoResult<()> {
+        write_version(&mut writer)?;
+        self.digest.write(&mut writer)?;
+        self.core_proof.write(&mut writer)?;
+        self.predicate_proof.write(&mut writer)?;
+        self.predicate_comm.write(&mut writer)?;
+        self.local_data_comm.write(&mut writer)
+    }
+}
+
+impl<C: PlainDPCComponents> FromBytes for DPCStuff<C> {
+    fn read<R: Read>(mut reader: R) -> IoResult<Self> {
+        read_version(&mut reader)?;
+        let digest = FromBytes::read(&mut reader)?;
+        let core_proof = FromBytes::read(&mut reader)?;
+        let predicate_proof = FromBytes::read(&mut reader)?;
+        let predicate_comm = FromBytes::read(&mut reader)?;
+        let local_data_comm = FromBytes::read(&mut reader)?;
+        Ok(Self {
+            digest,
+            core_proof,
+            predicate_proof,
+            predicate_comm,
+            local_data_comm,
+        })
+    }
 }
 
 impl<C: PlainDPCComponents> Transaction for DPCTransaction<C> {
     type Stuff = DPCStuff<C>;
     type SerialNumber = <C::P as PRF>::Output;
     type Commitment = <C::RecC as CommitmentScheme>::Output;
-    type Memorandum = [u8; 32];
+    type Memorandum = Vec<u8>;
 
     fn old_serial_numbers(&self) -> &[Self::SerialNumber] {
         self.old_serial_numbers.as_slice()
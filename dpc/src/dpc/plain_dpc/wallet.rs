@@ -0,0 +1,253 @@
+use failure::{format_err, Error};
+use rand::Rng;
+use std::marker::PhantomData;
+
+use crate::{
+    crypto_primitives::{CommitmentScheme, PRF},
+    dpc::{
+        plain_dpc::{
+            address::{AddressPair, AddressPublicKey},
+            parameters::{CommAndCRHPublicParameters, PublicParameters},
+            predicate::{DPCPredicate, PrivatePredInput},
+            record::DPCRecord,
+            transaction::DPCTransaction,
+            LocalData, PlainDPCComponents, DPC,
+        },
+        Arity, DPCScheme, Record,
+    },
+    ledger::{Ledger, LedgerDigest, LedgerWitness},
+};
+
+/// A record owned by a `Wallet`, together with the data needed to spend it.
+#[derive(Derivative)]
+#[derivative(Clone(bound = "C: PlainDPCComponents"))]
+pub struct WalletRecord<C: PlainDPCComponents> {
+    pub record:        DPCRecord<C>,
+    pub serial_number: <C::P as PRF>::Output,
+    // Witness of membership of the record commitment in the ledger, as of the
+    // last `Wallet::sync`.
+    pub witness: C::LCW,
+    pub spent:   bool,
+}
+
+/// Tracks the records owned by a single address as the ledger grows.
+pub struct Wallet<C: PlainDPCComponents, L: Ledger> {
+    address: AddressPair<C>,
+    records: Vec<WalletRecord<C>>,
+    // The number of transactions and the digest of the ledger at each sync, in
+    // the order of the syncs. Used to find where the ledger forked from what
+    // the wallet scanned.
+    checkpoints: Vec<(usize, C::D)>,
+    _ledger:     PhantomData<L>,
+}
+
+impl<C: PlainDPCComponents, L> Wallet<C, L>
+where
+    L: Ledger<
+        Parameters = <C::D as LedgerDigest>::Parameters,
+        Commitment = <C::RecC as CommitmentScheme>::Output,
+        SerialNumber = <C::P as PRF>::Output,
+        LedgerStateDigest = C::D,
+        CommWitness = C::LCW,
+        Transaction = DPCTransaction<C>,
+    >,
+    <L as Ledger>::SnWitness: LedgerWitness<C::D>,
+    <L as Ledger>::MemoWitness: LedgerWitness<C::D>,
+{
+    pub fn new(address: AddressPair<C>) -> Self {
+        Self {
+            address,
+            records: Vec::new(),
+            checkpoints: Vec::new(),
+            _ledger: PhantomData,
+        }
+    }
+
+    pub fn address(&self) -> &AddressPair<C> {
+        &self.address
+    }
+
+    /// Returns every record found so far, including spent ones.
+    pub fn records(&self) -> &[WalletRecord<C>] {
+        &self.records
+    }
+
+    pub fn unspent_records(&self) -> impl Iterator<Item = &DPCRecord<C>> {
+        self.records.iter().filter(|r| !r.spent).map(|r| &r.record)
+    }
+
+    /// Starts tracking a record that was not delivered through the ledger,
+    /// such as a genesis record.
+    pub fn add_record(&mut self, record: DPCRecord<C>, ledger: &L) -> Result<(), Error> {
+        if record.address_public_key().public_key != self.address.public_key.public_key {
+            return Err(format_err!("record is not owned by this wallet"));
+        }
+        if self.contains(&record) {
+            return Ok(());
+        }
+        let serial_number = DPC::generate_sn(&record, &self.address.secret_key)?;
+        let witness = Self::witness(&record, ledger)?;
+        let spent = ledger.contains_sn(&serial_number);
+        self.records.push(WalletRecord {
+            record,
+            serial_number,
+            witness,
+            spent,
+        });
+        Ok(())
+    }
+
+    /// Scans the transactions appended to `ledger` since the last call for
+    /// records owned by this wallet, refreshes the ledger witnesses of unspent
+    /// records, and marks records whose serial numbers have appeared as spent.
+    /// If the ledger no longer has the state of the last call, the wallet is
+    /// first rolled back to where the ledger forked.
+    pub fn sync(
+        &mut self,
+        parameters: &CommAndCRHPublicParameters<C>,
+        ledger: &L,
+    ) -> Result<(), Error> {
+        let sync_time = timer_start!(|| "Wallet::Sync");
+        if !self
+            .checkpoints
+            .last()
+            .map_or(true, |checkpoint| Self::is_on_ledger(checkpoint, ledger))
+        {
+            self.rollback(ledger)?;
+        }
+
+        let found = DPC::scan_transactions(
+            parameters,
+            &self.address.viewing_key(),
+            &ledger.transactions()[self.num_scanned_txs()..],
+        )?;
+        if let Some(digest) = ledger.digest() {
+            if self.checkpoints.last().map(|(_, last)| last) != Some(&digest) {
+                self.checkpoints.push((ledger.len(), digest));
+            }
+        }
+        for record in found {
+            self.add_record(record, ledger)?;
+        }
+
+        for wallet_record in self.records.iter_mut().filter(|r| !r.spent) {
+            if ledger.contains_sn(&wallet_record.serial_number) {
+                wallet_record.spent = true;
+            } else {
+                wallet_record.witness = Self::witness(&wallet_record.record, ledger)?;
+            }
+        }
+        timer_end!(sync_time);
+        Ok(())
+    }
+
+    /// Forgets the records and spends found in transactions that are no longer
+    /// on `ledger`, and makes the next `sync` rescan `ledger` from the last
+    /// synced state it still has. `sync` calls this itself when the ledger
+    /// changed other than by appending blocks.
+    pub fn rollback(&mut self, ledger: &L) -> Result<(), Error> {
+        while let Some(checkpoint) = self.checkpoints.last() {
+            if Self::is_on_ledger(checkpoint, ledger) {
+                break;
+            }
+            self.checkpoints.pop();
+        }
+        self.records
+            .retain(|r| r.record.is_dummy() || ledger.contains_cm(&r.record.commitment()));
+        for wallet_record in &mut self.records {
+            wallet_record.spent = ledger.contains_sn(&wallet_record.serial_number);
+            if !wallet_record.spent {
+                wallet_record.witness = Self::witness(&wallet_record.record, ledger)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Selects as many unspent records accepted by `filter` as the largest
+    /// supported arity can spend. `execute` pads the transaction with dummy
+    /// records to the smallest arity that fits the selection.
+    pub fn select_inputs(&self, filter: impl Fn(&DPCRecord<C>) -> bool) -> Vec<DPCRecord<C>> {
+        self.unspent_records()
+            .filter(|record| !record.is_dummy() && filter(*record))
+            .take(Arity::max_of(C::ARITIES).num_input_records)
+            .cloned()
+            .collect()
+    }
+
+    /// Spends `old_records`, which must be dummy records or unspent records of
+    /// this wallet, via `DPCScheme::execute`.
+    pub fn execute<R: Rng>(
+        &self,
+        parameters: &PublicParameters<C>,
+
+        old_records: &[DPCRecord<C>],
+        old_death_pred_proof_generator: impl FnMut(&LocalData<C>) -> Vec<PrivatePredInput<C>>,
+
+        new_address_public_keys: &[AddressPublicKey<C>],
+        new_is_dummy_flags: &[bool],
+        new_payloads: &[Vec<u8>],
+        new_birth_predicates: &[DPCPredicate<C>],
+        new_death_predicates: &[DPCPredicate<C>],
+        new_birth_pred_proof_generator: impl FnMut(&LocalData<C>) -> Vec<PrivatePredInput<C>>,
+
+        auxiliary: &[u8; 32],
+        memorandum: &[u8],
+        ledger: &L,
+        rng: &mut R,
+    ) -> Result<(Vec<DPCRecord<C>>, DPCTransaction<C>), Error> {
+        for record in old_records {
+            if !record.is_dummy() && !self.unspent_records().any(|r| same_record(r, record)) {
+                return Err(format_err!(
+                    "input record is not an unspent record of this wallet"
+                ));
+            }
+        }
+
+        let old_address_secret_keys = vec![self.address.secret_key.clone(); old_records.len()];
+        <DPC<C> as DPCScheme<L>>::execute(
+            parameters,
+            old_records,
+            &old_address_secret_keys,
+            old_death_pred_proof_generator,
+            new_address_public_keys,
+            new_is_dummy_flags,
+            new_payloads,
+            new_birth_predicates,
+            new_death_predicates,
+            new_birth_pred_proof_generator,
+            auxiliary,
+            &memorandum.to_vec(),
+            ledger,
+            rng,
+        )
+    }
+
+    fn num_scanned_txs(&self) -> usize {
+        self.checkpoints.last().map_or(0, |(num_txs, _)| *num_txs)
+    }
+
+    // Returns whether `ledger` went through the synced state `checkpoint`, so
+    // that its first transactions are the ones scanned up to that sync. A
+    // state too old for `Ledger::validate_digest` counts as gone, which only
+    // makes the wallet rescan more of the ledger.
+    fn is_on_ledger(checkpoint: &(usize, C::D), ledger: &L) -> bool {
+        let (num_txs, digest) = checkpoint;
+        *num_txs <= ledger.len() && ledger.validate_digest(digest)
+    }
+
+    fn contains(&self, record: &DPCRecord<C>) -> bool {
+        self.records.iter().any(|r| same_record(&r.record, record))
+    }
+
+    fn witness(record: &DPCRecord<C>, ledger: &L) -> Result<C::LCW, Error> {
+        if record.is_dummy() {
+            Ok(C::LCW::dummy_witness())
+        } else {
+            ledger.prove_cm(&record.commitment())
+        }
+    }
+}
+
+fn same_record<C: PlainDPCComponents>(a: &DPCRecord<C>, b: &DPCRecord<C>) -> bool {
+    a.commitment() == b.commitment()
+}
@@ -0,0 +1,104 @@
+use algebra::{
+    bytes::{FromBytes, ToBytes},
+    to_bytes,
+};
+use blake2::Blake2s;
+use digest::Digest;
+use failure::Error;
+use std::io::{Error as IoError, ErrorKind, Read, Result as IoResult, Write};
+
+/// Version of the binary encoding of transactions, records, addresses and
+/// public parameters. Each of these encodings starts with this byte, and
+/// decoding rejects any other version.
+pub const SERIALIZATION_VERSION: u8 = 1;
+
+/// Decodes a value from `bytes`, which must contain exactly one encoded value.
+/// Malformed input, such as trailing bytes, non-canonical field elements or
+/// points outside the prime-order subgroup, fails with `ErrorKind::InvalidData`.
+pub fn from_bytes<T: FromBytes>(bytes: &[u8]) -> Result<T, Error> {
+    let mut reader = bytes;
+    let value = T::read(&mut reader)?;
+    if !reader.is_empty() {
+        return Err(invalid_data(&format!(
+            "{} unexpected bytes after the encoded value",
+            reader.len()
+        ))
+        .into());
+    }
+    Ok(value)
+}
+
+pub(crate) fn write_version<W: Write>(writer: W) -> IoResult<()> {
+    SERIALIZATION_VERSION.write(writer)
+}
+
+pub(crate) fn read_version<R: Read>(reader: R) -> IoResult<()> {
+    let version = u8::read(reader)?;
+    if version != SERIALIZATION_VERSION {
+        return Err(invalid_data(&format!(
+            "unsupported serialization version {}",
+            version
+        )));
+    }
+    Ok(())
+}
+
+/// Writes `items` prefixed with their number.
+pub(crate) fn write_vec<T: ToBytes, W: Write>(items: &[T], mut writer: W) -> IoResult<()> {
+    (items.len() as u64).write(&mut writer)?;
+    items.write(&mut writer)
+}
+
+/// Reads a sequence written by `write_vec` that may hold at most `max_len`
+/// items.
+pub(crate) fn read_vec<T: FromBytes, R: Read>(mut reader: R, max_len: usize) -> IoResult<Vec<T>> {
+    let len = u64::read(&mut reader)?;
+    if len > max_len as u64 {
+        return Err(invalid_data(&format!(
+            "expected at most {} items, got {}",
+            max_len, len
+        )));
+    }
+    (0..len).map(|_| T::read(&mut reader)).collect()
+}
+
+pub(crate) fn read_fixed_bytes<R: Read>(mut reader: R, len: usize) -> IoResult<Vec<u8>> {
+    let mut bytes = vec![0u8; len];
+    reader.read_exact(&mut bytes)?;
+    Ok(bytes)
+}
+
+/// Writes the encoding of `value` prefixed with its length and followed by its
+/// Blake2s hash, so that `read_component` can detect corruption.
+///
+/// The hash is not keyed and sits next to the data it covers, so it only
+/// catches accidental corruption: whoever can change the bytes can also
+/// recompute it. Parameters from an untrusted source have to be checked
+/// against a digest of their whole encoding obtained out of band.
+pub(crate) fn write_component<T: ToBytes, W: Write>(value: &T, mut writer: W) -> Result<(), Error> {
+    let bytes = to_bytes![value]?;
+    write_vec(&bytes, &mut writer)?;
+    writer.write_all(&Blake2s::digest(&bytes))?;
+    Ok(())
+}
+
+/// Reads a value written by `write_component`, checking its hash against
+/// accidental corruption. `name` only appears in error messages.
+pub(crate) fn read_component<T: FromBytes, R: Read>(mut reader: R, name: &str) -> Result<T, Error> {
+    let len = u64::read(&mut reader)?;
+    // Do not trust `len` to preallocate: the input may be truncated.
+    let mut bytes = Vec::new();
+    (&mut reader).take(len).read_to_end(&mut bytes)?;
+    if bytes.len() as u64 != len {
+        return Err(IoError::new(ErrorKind::UnexpectedEof, "truncated component").into());
+    }
+    let hash = <[u8; 32]>::read(&mut reader)?;
+    if Blake2s::digest(&bytes).as_slice() != &hash[..] {
+        return Err(invalid_data(&format!("integrity check failed for {}", name)).into());
+    }
+    from_bytes(&bytes).map_err(|e| invalid_data(&format!("invalid {}: {}", name, e)).into())
+}
+
+pub(crate) fn invalid_data(message: &str) -> IoError {
+    IoError::new(ErrorKind::InvalidData, message)
+}
@@ -0,0 +1,274 @@
+use algebra::PairingEngine;
+use std::hash::Hash;
+
+use crate::{
+    crypto_primitives::crh::{
+        bowe_hopwood::{BoweHopwoodPedersenCRH, BoweHopwoodPedersenParameters, CHUNK_SIZE},
+        pedersen::PedersenWindow,
+        FixedLengthCRH,
+    },
+    gadgets::crh::FixedLengthCRHGadget,
+};
+use algebra::groups::Group;
+use snark::{ConstraintSystem, SynthesisError};
+use snark_gadgets::{
+    boolean::Boolean,
+    groups::{GroupGadget, SignedDigitScalarMulGadget},
+    uint8::UInt8,
+    utils::AllocGadget,
+};
+
+use std::{borrow::Borrow, marker::PhantomData};
+
+#[derive(Derivative)]
+#[derivative(Clone(
+    bound = "G: Group, W: PedersenWindow, E: PairingEngine, GG: GroupGadget<G, E>"
+))]
+pub struct BoweHopwoodPedersenCRHGadgetParameters<
+    G: Group,
+    W: PedersenWindow,
+    E: PairingEngine,
+    GG: GroupGadget<G, E>,
+> {
+    params:   BoweHopwoodPedersenParameters<G>,
+    _group_g: PhantomData<GG>,
+    _engine:  PhantomData<E>,
+    _window:  PhantomData<W>,
+}
+
+pub struct BoweHopwoodPedersenCRHGadget<G: Group, E: PairingEngine, GG: GroupGadget<G, E>> {
+    _group:        PhantomData<*const G>,
+    _group_gadget: PhantomData<*const GG>,
+    _engine:       PhantomData<E>,
+}
+
+impl<E, G, GG, W> FixedLengthCRHGadget<BoweHopwoodPedersenCRH<G, W>, E>
+    for BoweHopwoodPedersenCRHGadget<G, E, GG>
+where
+    E: PairingEngine,
+    G: Group + Hash,
+    GG: SignedDigitScalarMulGadget<G, E>,
+    W: PedersenWindow,
+{
+    type OutputGadget = GG;
+    type ParametersGadget = BoweHopwoodPedersenCRHGadgetParameters<G, W, E, GG>;
+
+    fn check_evaluation_gadget<CS: ConstraintSystem<E>>(
+        cs: CS,
+        parameters: &Self::ParametersGadget,
+        input: &[UInt8],
+    ) -> Result<Self::OutputGadget, SynthesisError> {
+        let input_size_bits = BoweHopwoodPedersenCRH::<G, W>::INPUT_SIZE_BITS;
+        assert!(input.len() * 8 <= input_size_bits);
+        assert_eq!(parameters.params.generators.len(), W::NUM_WINDOWS);
+        // Longer segments would let the partial sums of a segment collide.
+        if W::WINDOW_SIZE > BoweHopwoodPedersenCRH::<G, W>::max_window_size() {
+            return Err(SynthesisError::Unsatisfiable);
+        }
+
+        // Pad the input with zero bits to the full length.
+        let mut input_in_bits: Vec<_> = input.iter().flat_map(|byte| byte.into_bits_le()).collect();
+        input_in_bits.resize(input_size_bits, Boolean::constant(false));
+        let segments: Vec<_> = input_in_bits.chunks(W::WINDOW_SIZE * CHUNK_SIZE).collect();
+
+        GG::precomputed_base_3_bit_signed_digit_scalar_mul(
+            cs,
+            &parameters.params.generators,
+            &segments,
+        )
+    }
+
+    fn cost() -> usize {
+        GG::cost_of_precomputed_base_3_bit_signed_digit_scalar_mul(&vec![
+            W::WINDOW_SIZE;
+            W::NUM_WINDOWS
+        ])
+    }
+}
+
+impl<G: Group, W: PedersenWindow, E: PairingEngine, GG: GroupGadget<G, E>>
+    AllocGadget<BoweHopwoodPedersenParameters<G>, E>
+    for BoweHopwoodPedersenCRHGadgetParameters<G, W, E, GG>
+{
+    fn alloc<F, T, CS: ConstraintSystem<E>>(_cs: CS, value_gen: F) -> Result<Self, SynthesisError>
+    where
+        F: FnOnce() -> Result<T, SynthesisError>,
+        T: Borrow<BoweHopwoodPedersenParameters<G>>,
+    {
+        let params = value_gen()?.borrow().clone();
+        Ok(BoweHopwoodPedersenCRHGadgetParameters {
+            params,
+            _group_g: PhantomData,
+            _engine: PhantomData,
+            _window: PhantomData,
+        })
+    }
+
+    fn alloc_input<F, T, CS: ConstraintSystem<E>>(
+        _cs: CS,
+        value_gen: F,
+    ) -> Result<Self, SynthesisError>
+    where
+        F: FnOnce() -> Result<T, SynthesisError>,
+        T: Borrow<BoweHopwoodPedersenParameters<G>>,
+    {
+        let params = value_gen()?.borrow().clone();
+        Ok(BoweHopwoodPedersenCRHGadgetParameters {
+            params,
+            _group_g: PhantomData,
+            _engine: PhantomData,
+            _window: PhantomData,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use algebra::curves::bls12_381::Bls12_381;
+    use rand::{thread_rng, Rng};
+
+    use crate::{
+        crypto_primitives::crh::{
+            bowe_hopwood::{BoweHopwoodPedersenCRH, BoweHopwoodPedersenParameters},
+            pedersen::{PedersenCRH, PedersenWindow},
+            FixedLengthCRH,
+        },
+        gadgets::crh::{
+            bowe_hopwood::BoweHopwoodPedersenCRHGadget, pedersen::PedersenCRHGadget,
+            FixedLengthCRHGadget,
+        },
+    };
+    use algebra::curves::{jubjub::JubJubProjective as JubJub, ProjectiveCurve};
+    use snark::ConstraintSystem;
+    use snark_gadgets::{
+        groups::curves::twisted_edwards::jubjub::JubJubGadget,
+        test_constraint_system::TestConstraintSystem, uint8::UInt8, utils::AllocGadget,
+    };
+
+    type TestCRH = BoweHopwoodPedersenCRH<JubJub, Window>;
+    type TestCRHGadget = BoweHopwoodPedersenCRHGadget<JubJub, Bls12_381, JubJubGadget>;
+
+    #[derive(Clone, PartialEq, Eq, Hash)]
+    pub(super) struct Window;
+
+    // 8 segments of 32 3-bit chunks take 96 bytes of input.
+    impl PedersenWindow for Window {
+        const WINDOW_SIZE: usize = 32;
+        const NUM_WINDOWS: usize = 8;
+    }
+
+    #[derive(Clone, PartialEq, Eq, Hash)]
+    pub(super) struct PedersenTestWindow;
+
+    impl PedersenWindow for PedersenTestWindow {
+        const WINDOW_SIZE: usize = 96;
+        const NUM_WINDOWS: usize = 8;
+    }
+
+    fn generate_input<CS: ConstraintSystem<Bls12_381>>(
+        mut cs: CS,
+        rng: &mut dyn Rng,
+    ) -> ([u8; 96], Vec<UInt8>) {
+        let mut input = [1u8; 96];
+        rng.fill_bytes(&mut input);
+
+        let mut input_bytes = vec![];
+        for (byte_i, input_byte) in input.iter().enumerate() {
+            let cs = cs.ns(|| format!("input_byte_gadget_{}", byte_i));
+            input_bytes.push(UInt8::alloc(cs, || Ok(*input_byte)).unwrap());
+        }
+        (input, input_bytes)
+    }
+
+    #[test]
+    fn num_constraints() {
+        assert_eq!(
+            <TestCRHGadget as FixedLengthCRHGadget<TestCRH, Bls12_381>>::cost(),
+            // Each chunk costs an AND and two lookups, each segment 31
+            // Montgomery additions and a conversion to Edwards form, and the
+            // segments are summed with 7 Edwards additions.
+            8 * (32 * 3 + 31 * 3 + 2) + 7 * 6
+        );
+
+        // The same input costs over half as much again with the Pedersen hash.
+        type PedersenTestCRH = PedersenCRH<JubJub, PedersenTestWindow>;
+        type PedersenTestCRHGadget = PedersenCRHGadget<JubJub, Bls12_381, JubJubGadget>;
+        assert_eq!(
+            <PedersenTestCRHGadget as FixedLengthCRHGadget<PedersenTestCRH, Bls12_381>>::cost(),
+            8 * 32 * (2 * 2 + 6)
+        );
+    }
+
+    #[test]
+    fn crh_primitive_gadget_test() {
+        let rng = &mut thread_rng();
+        let mut cs = TestConstraintSystem::<Bls12_381>::new();
+
+        let (input, input_bytes) = generate_input(&mut cs, rng);
+        let input_constraints = cs.num_constraints();
+
+        let parameters = TestCRH::setup(rng).unwrap();
+        let primitive_result = TestCRH::evaluate(&parameters, &input).unwrap();
+
+        let gadget_parameters =
+            <TestCRHGadget as FixedLengthCRHGadget<TestCRH, Bls12_381>>::ParametersGadget::alloc(
+                &mut cs.ns(|| "gadget_parameters"),
+                || Ok(&parameters),
+            )
+            .unwrap();
+
+        let gadget_result =
+            <TestCRHGadget as FixedLengthCRHGadget<TestCRH, Bls12_381>>::check_evaluation_gadget(
+                &mut cs.ns(|| "gadget_evaluation"),
+                &gadget_parameters,
+                &input_bytes,
+            )
+            .unwrap();
+
+        assert_eq!(
+            <TestCRHGadget as FixedLengthCRHGadget<TestCRH, Bls12_381>>::cost(),
+            cs.num_constraints() - input_constraints
+        );
+
+        let primitive_result = primitive_result.into_affine();
+        assert_eq!(primitive_result.x, gadget_result.x.value.unwrap());
+        assert_eq!(primitive_result.y, gadget_result.y.value.unwrap());
+        assert!(cs.is_satisfied());
+    }
+
+    #[test]
+    fn large_window_test() {
+        #[derive(Clone, PartialEq, Eq, Hash)]
+        struct LargeWindow;
+
+        impl PedersenWindow for LargeWindow {
+            const WINDOW_SIZE: usize = 63;
+            const NUM_WINDOWS: usize = 1;
+        }
+
+        type LargeCRH = BoweHopwoodPedersenCRH<JubJub, LargeWindow>;
+
+        let rng = &mut thread_rng();
+        let mut cs = TestConstraintSystem::<Bls12_381>::new();
+
+        // `setup` refuses such windows, so build the generators directly.
+        let parameters = BoweHopwoodPedersenParameters {
+            generators: LargeCRH::create_generators(rng),
+        };
+        let input_bytes = UInt8::alloc_vec(cs.ns(|| "input"), &[0u8; 23]).unwrap();
+        let gadget_parameters =
+            <TestCRHGadget as FixedLengthCRHGadget<LargeCRH, Bls12_381>>::ParametersGadget::alloc(
+                &mut cs.ns(|| "gadget_parameters"),
+                || Ok(&parameters),
+            )
+            .unwrap();
+        assert!(
+            <TestCRHGadget as FixedLengthCRHGadget<LargeCRH, Bls12_381>>::check_evaluation_gadget(
+                &mut cs.ns(|| "gadget_evaluation"),
+                &gadget_parameters,
+                &input_bytes,
+            )
+            .is_err()
+        );
+    }
+}
@@ -2,6 +2,7 @@ use algebra::PairingEngine;
 use std::{fmt::Debug, marker::PhantomData};
 
 use crate::gadgets::crh::{
+    bowe_hopwood::{BoweHopwoodPedersenCRHGadget, BoweHopwoodPedersenCRHGadgetParameters},
     pedersen::{PedersenCRHGadget, PedersenCRHGadgetParameters},
     FixedLengthCRHGadget,
 };
@@ -15,13 +16,19 @@ use algebra::{
 use snark::{ConstraintSystem, SynthesisError};
 use snark_gadgets::{
     fields::fp::FpGadget,
-    groups::{curves::twisted_edwards::AffineGadget as TwistedEdwardsGadget, GroupGadget},
+    groups::{
+        curves::twisted_edwards::AffineGadget as TwistedEdwardsGadget, GroupGadget,
+        SignedDigitScalarMulGadget,
+    },
     uint8::UInt8,
     utils::{AllocGadget, CondSelectGadget, EqGadget, ToBytesGadget},
 };
 
 use crate::crypto_primitives::crh::{
-    injective_map::{InjectiveMap, PedersenCRHCompressor, TECompressor},
+    bowe_hopwood::BoweHopwoodPedersenCRH,
+    injective_map::{
+        BoweHopwoodPedersenCRHCompressor, InjectiveMap, PedersenCRHCompressor, TECompressor,
+    },
     pedersen::{PedersenCRH, PedersenWindow},
 };
 
@@ -128,3 +135,50 @@ where
             + IG::cost()
     }
 }
+
+pub struct BoweHopwoodPedersenCRHCompressorGadget<
+    G: Group,
+    I: InjectiveMap<G>,
+    E: PairingEngine,
+    GG: GroupGadget<G, E>,
+    IG: InjectiveMapGadget<G, I, E, GG>,
+> {
+    _compressor:        PhantomData<I>,
+    _compressor_gadget: PhantomData<IG>,
+    _crh:               BoweHopwoodPedersenCRHGadget<G, E, GG>,
+}
+
+impl<G, I, E, GG, IG, W> FixedLengthCRHGadget<BoweHopwoodPedersenCRHCompressor<G, I, W>, E>
+    for BoweHopwoodPedersenCRHCompressorGadget<G, I, E, GG, IG>
+where
+    G: Group,
+    I: InjectiveMap<G>,
+    E: PairingEngine,
+    GG: SignedDigitScalarMulGadget<G, E>,
+    IG: InjectiveMapGadget<G, I, E, GG>,
+    W: PedersenWindow,
+{
+    type OutputGadget = IG::OutputGadget;
+    type ParametersGadget = BoweHopwoodPedersenCRHGadgetParameters<G, W, E, GG>;
+
+    fn check_evaluation_gadget<CS: ConstraintSystem<E>>(
+        mut cs: CS,
+        parameters: &Self::ParametersGadget,
+        input: &[UInt8],
+    ) -> Result<Self::OutputGadget, SynthesisError> {
+        let result = BoweHopwoodPedersenCRHGadget::<G, E, GG>::check_evaluation_gadget(
+            cs.ns(|| "BoweHopwoodPedCRH"),
+            parameters,
+            input,
+        )?;
+        IG::evaluate_map(cs.ns(|| "InjectiveMap"), &result)
+    }
+
+    fn cost() -> usize {
+        <BoweHopwoodPedersenCRHGadget<G, E, GG> as FixedLengthCRHGadget<
+            BoweHopwoodPedersenCRH<G, W>,
+            E,
+        >>::cost()
+            + IG::cost()
+    }
+}
@@ -9,6 +9,7 @@ use snark_gadgets::{
     utils::{AllocGadget, CondSelectGadget, ConditionalEqGadget, EqGadget, ToBytesGadget},
 };
 
+pub mod bowe_hopwood;
 pub mod injective_map;
 pub mod pedersen;
 
@@ -72,8 +72,7 @@ where
     }
 
     fn cost() -> usize {
-        use snark_gadgets::utils::CondSelectGadget;
-        W::NUM_WINDOWS * W::WINDOW_SIZE * (GG::cost_of_add() + <GG as CondSelectGadget<E>>::cost())
+        W::NUM_WINDOWS * GG::cost_of_precomputed_base_scalar_mul(W::WINDOW_SIZE)
     }
 }
 
@@ -181,7 +180,9 @@ mod test {
         );
         assert_eq!(
             <TestCRHGadget as FixedLengthCRHGadget<TestCRH, Bls12_381>>::cost(),
-            256 * (6 + 4 * (6 + 2))
+            // Each window is looked up in 42 three-bit chunks and one
+            // two-bit chunk.
+            8 * (42 * (2 * 2 + 6) + (2 * 1 + 6))
         );
     }
 
@@ -188,8 +188,8 @@ where
     // 3. crh_pp.
     // 4. ledger_parameters.
     // 5. ledger_digest.
-    // 6. for i in 0..NUM_INPUT_RECORDS: old_serial_numbers[i].
-    // 7. for j in 0..NUM_OUTPUT_RECORDS: new_commitments[i].
+    // 6. for i in 0..old_records.len(): old_serial_numbers[i].
+    // 7. for j in 0..new_records.len(): new_commitments[i].
     let (
         addr_comm_pp,
         rec_comm_pp,
@@ -625,11 +625,11 @@ where
         let comm_cs = &mut cs.ns(|| "Check that predicate commitment is well-formed");
 
         let mut input = Vec::new();
-        for i in 0..C::NUM_INPUT_RECORDS {
+        for i in 0..old_records.len() {
             input.extend_from_slice(&old_death_pred_hashes[i]);
         }
 
-        for j in 0..C::NUM_OUTPUT_RECORDS {
+        for j in 0..new_records.len() {
             input.extend_from_slice(&new_birth_pred_hashes[j]);
         }
 
@@ -662,7 +662,7 @@ where
         let mut cs = cs.ns(|| "Check that local data commitment is valid.");
 
         let mut local_data_bytes = Vec::new();
-        for i in 0..C::NUM_INPUT_RECORDS {
+        for i in 0..old_records.len() {
             let mut cs = cs.ns(|| format!("Construct local data with Input Record {}", i));
             local_data_bytes
                 .extend_from_slice(&old_rec_comms[i].to_bytes(&mut cs.ns(|| "Record Comm"))?);
@@ -675,7 +675,7 @@ where
             local_data_bytes.extend_from_slice(&old_sns[i].to_bytes(&mut cs.ns(|| "Sn"))?);
         }
 
-        for j in 0..C::NUM_OUTPUT_RECORDS {
+        for j in 0..new_records.len() {
             let mut cs = cs.ns(|| format!("Construct local data with Output Record {}", j));
             local_data_bytes
                 .extend_from_slice(&new_rec_comms[j].to_bytes(&mut cs.ns(|| "Record Comm"))?);
@@ -799,7 +799,7 @@ where
 
     let mut old_death_pred_hashes = Vec::new();
     let mut new_birth_pred_hashes = Vec::new();
-    for i in 0..C::NUM_INPUT_RECORDS {
+    for i in 0..old_death_pred_vk_and_pf.len() {
         let cs = &mut cs.ns(|| format!("Check death predicate for input record {}", i));
 
         let death_pred_proof =
@@ -837,7 +837,7 @@ where
         )?;
     }
 
-    for j in 0..C::NUM_OUTPUT_RECORDS {
+    for j in 0..new_birth_pred_vk_and_pf.len() {
         let cs = &mut cs.ns(|| format!("Check birth predicate for output record {}", j));
 
         let birth_pred_proof =
@@ -878,11 +878,11 @@ where
         let comm_cs = &mut cs.ns(|| "Check that predicate commitment is well-formed");
 
         let mut input = Vec::new();
-        for i in 0..C::NUM_INPUT_RECORDS {
+        for i in 0..old_death_pred_vk_and_pf.len() {
             input.extend_from_slice(&old_death_pred_hashes[i]);
         }
 
-        for j in 0..C::NUM_OUTPUT_RECORDS {
+        for j in 0..new_birth_pred_vk_and_pf.len() {
             input.extend_from_slice(&new_birth_pred_hashes[j]);
         }
 
@@ -46,8 +46,9 @@ pub fn execute_core_checks_gadget<C: PlainDPCComponents, CS: ConstraintSystem<C:
     predicate_rand: &<C::PredVkComm as CommitmentScheme>::Randomness,
     local_data_comm: &<C::LocalDataComm as CommitmentScheme>::Output,
     local_data_rand: &<C::LocalDataComm as CommitmentScheme>::Randomness,
-    memo: &[u8; 32],
+    memo: &[u8],
     auxiliary: &[u8; 32],
+    encrypted_records_digest: &[u8; 32],
 ) -> Result<(), SynthesisError> {
     execute_core_checks_gadget_helper::<
         C,
@@ -86,6 +87,7 @@ pub fn execute_core_checks_gadget<C: PlainDPCComponents, CS: ConstraintSystem<C:
         local_data_rand,
         memo,
         auxiliary,
+        encrypted_records_digest,
     )
 }
 
@@ -129,8 +131,9 @@ fn execute_core_checks_gadget_helper<
     predicate_rand: &<C::PredVkComm as CommitmentScheme>::Randomness,
     local_data_comm: &<C::LocalDataComm as CommitmentScheme>::Output,
     local_data_rand: &<C::LocalDataComm as CommitmentScheme>::Randomness,
-    memo: &[u8; 32],
+    memo: &[u8],
     auxiliary: &[u8; 32],
+    encrypted_records_digest: &[u8; 32],
 ) -> Result<(), SynthesisError>
 where
     C: PlainDPCComponents<
@@ -186,8 +189,8 @@ where
     // 3. crh_pp.
     // 4. ledger_parameters.
     // 5. ledger_digest.
-    // 6. for i in 0..NUM_INPUT_RECORDS: old_serial_numbers[i].
-    // 7. for j in 0..NUM_OUTPUT_RECORDS: new_commitments[i].
+    // 6. for i in 0..old_records.len(): old_serial_numbers[i].
+    // 7. for j in 0..new_records.len(): new_commitments[i].
     let (
         addr_comm_pp,
         rec_comm_pp,
@@ -364,8 +367,15 @@ where
                 || Ok(&secret_key.r_pk),
             )?;
 
+            let encryption_key = UInt8::alloc_vec(
+                &mut address_cs.ns(|| "Declare encryption key"),
+                &to_bytes![record.address_public_key().encryption_key]
+                    .map_err(|_| SynthesisError::AssignmentMissing)?,
+            )?;
+
             let mut apk_input = sk_prf.clone();
             apk_input.extend_from_slice(&metadata);
+            apk_input.extend_from_slice(&encryption_key);
 
             let candidate_apk = AddrCGadget::check_commitment_gadget(
                 &mut address_cs.ns(|| "Compute Addr PubKey"),
@@ -600,11 +610,11 @@ where
         let comm_cs = &mut cs.ns(|| "Check that predicate commitment is well-formed");
 
         let mut input = Vec::new();
-        for i in 0..C::NUM_INPUT_RECORDS {
+        for i in 0..old_records.len() {
             input.extend_from_slice(&old_death_pred_hashes[i]);
         }
 
-        for j in 0..C::NUM_OUTPUT_RECORDS {
+        for j in 0..new_records.len() {
             input.extend_from_slice(&new_birth_pred_hashes[j]);
         }
 
@@ -637,7 +647,7 @@ where
         let mut cs = cs.ns(|| "Check that local data commitment is valid.");
 
         let mut local_data_bytes = Vec::new();
-        for i in 0..C::NUM_INPUT_RECORDS {
+        for i in 0..old_records.len() {
             let mut cs = cs.ns(|| format!("Construct local data with Input Record {}", i));
             local_data_bytes
                 .extend_from_slice(&old_rec_comms[i].to_bytes(&mut cs.ns(|| "Record Comm"))?);
@@ -650,7 +660,7 @@ where
             local_data_bytes.extend_from_slice(&old_sns[i].to_bytes(&mut cs.ns(|| "Sn"))?);
         }
 
-        for j in 0..C::NUM_OUTPUT_RECORDS {
+        for j in 0..new_records.len() {
             let mut cs = cs.ns(|| format!("Construct local data with Output Record {}", j));
             local_data_bytes
                 .extend_from_slice(&new_rec_comms[j].to_bytes(&mut cs.ns(|| "Record Comm"))?);
@@ -664,6 +674,13 @@ where
         let memo = UInt8::alloc_input_vec(cs.ns(|| "Allocate memorandum"), memo)?;
         local_data_bytes.extend_from_slice(&memo);
 
+        // The digest of the encrypted records is only a public input, so that
+        // the proof does not verify with any other ciphertexts.
+        UInt8::alloc_input_vec(
+            cs.ns(|| "Allocate encrypted records digest"),
+            encrypted_records_digest,
+        )?;
+
         let auxiliary = UInt8::alloc_vec(cs.ns(|| "Allocate auxiliary input"), auxiliary)?;
         local_data_bytes.extend_from_slice(&auxiliary);
 
@@ -770,7 +787,7 @@ where
 
     let mut old_death_pred_hashes = Vec::new();
     let mut new_birth_pred_hashes = Vec::new();
-    for i in 0..C::NUM_INPUT_RECORDS {
+    for i in 0..old_death_pred_vk_and_pf.len() {
         let cs = &mut cs.ns(|| format!("Check death predicate for input record {}", i));
 
         let death_pred_proof =
@@ -810,7 +827,7 @@ where
         )?;
     }
 
-    for j in 0..C::NUM_OUTPUT_RECORDS {
+    for j in 0..new_birth_pred_vk_and_pf.len() {
         let cs = &mut cs.ns(|| format!("Check birth predicate for output record {}", j));
 
         let birth_pred_proof =
@@ -853,11 +870,11 @@ where
         let comm_cs = &mut cs.ns(|| "Check that predicate commitment is well-formed");
 
         let mut input = Vec::new();
-        for i in 0..C::NUM_INPUT_RECORDS {
+        for i in 0..old_death_pred_vk_and_pf.len() {
             input.extend_from_slice(&old_death_pred_hashes[i]);
         }
 
-        for j in 0..C::NUM_OUTPUT_RECORDS {
+        for j in 0..new_birth_pred_vk_and_pf.len() {
             input.extend_from_slice(&new_birth_pred_hashes[j]);
         }
 
@@ -13,6 +13,9 @@ use crate::ledger::{CommPath, Digest, LedgerDigest, LedgerWitness};
 
 use std::{borrow::Borrow, marker::PhantomData};
 
+pub mod sparse;
+pub use self::sparse::*;
+
 pub trait LCWGadget<C: CommitmentScheme, D: LedgerDigest, CW: LedgerWitness<D>, E: PairingEngine> {
     type ParametersGadget: AllocGadget<D::Parameters, E>;
     type CommitmentGadget: AllocGadget<C::Output, E>;
@@ -0,0 +1,546 @@
+use algebra::{bytes::ToBytes, PairingEngine};
+use snark::{ConstraintSystem, SynthesisError};
+
+use crate::{
+    config::SPARSE_MERKLE_TREE_DEPTH,
+    crypto_primitives::FixedLengthCRH,
+    gadgets::crh::FixedLengthCRHGadget,
+    ledger::{LedgerDigest, LedgerWitness, SnProof, StateDigest},
+};
+use snark_gadgets::{
+    boolean::Boolean,
+    uint8::UInt8,
+    utils::{AllocGadget, CondSelectGadget, ConditionalEqGadget, ToBytesGadget},
+};
+
+use super::hash_inner_node_gadget;
+
+use std::{borrow::Borrow, marker::PhantomData};
+
+/// Checks in a circuit that a serial number is, or is not, on the ledger with
+/// a given digest.
+pub trait SNWGadget<D: LedgerDigest, SW: LedgerWitness<D>, E: PairingEngine> {
+    type ParametersGadget: AllocGadget<D::Parameters, E>;
+    type DigestGadget: AllocGadget<D, E>;
+    type WitnessGadget: AllocGadget<SW, E>;
+
+    fn check_membership_gadget<CS: ConstraintSystem<E>>(
+        cs: CS,
+        parameters: &Self::ParametersGadget,
+        ledger_state_digest: &Self::DigestGadget,
+        serial_number: &[UInt8],
+        witness: &Self::WitnessGadget,
+    ) -> Result<(), SynthesisError> {
+        Self::conditionally_check_membership_gadget(
+            cs,
+            parameters,
+            ledger_state_digest,
+            serial_number,
+            witness,
+            &Boolean::Constant(true),
+        )
+    }
+
+    fn conditionally_check_membership_gadget<CS: ConstraintSystem<E>>(
+        cs: CS,
+        parameters: &Self::ParametersGadget,
+        ledger_state_digest: &Self::DigestGadget,
+        serial_number: &[UInt8],
+        witness: &Self::WitnessGadget,
+        should_enforce: &Boolean,
+    ) -> Result<(), SynthesisError>;
+
+    fn check_non_membership_gadget<CS: ConstraintSystem<E>>(
+        cs: CS,
+        parameters: &Self::ParametersGadget,
+        ledger_state_digest: &Self::DigestGadget,
+        serial_number: &[UInt8],
+        witness: &Self::WitnessGadget,
+    ) -> Result<(), SynthesisError> {
+        Self::conditionally_check_non_membership_gadget(
+            cs,
+            parameters,
+            ledger_state_digest,
+            serial_number,
+            witness,
+            &Boolean::Constant(true),
+        )
+    }
+
+    fn conditionally_check_non_membership_gadget<CS: ConstraintSystem<E>>(
+        cs: CS,
+        parameters: &Self::ParametersGadget,
+        ledger_state_digest: &Self::DigestGadget,
+        serial_number: &[UInt8],
+        witness: &Self::WitnessGadget,
+        should_enforce: &Boolean,
+    ) -> Result<(), SynthesisError>;
+}
+
+pub struct SparseLedgerGadget<H, HGadget> {
+    _hash:        PhantomData<H>,
+    _hash_gadget: PhantomData<HGadget>,
+}
+
+pub struct StateDigestGadget<
+    H: FixedLengthCRH,
+    HGadget: FixedLengthCRHGadget<H, E>,
+    E: PairingEngine,
+> {
+    cm_root: HGadget::OutputGadget,
+    sn_root: HGadget::OutputGadget,
+    _crh:    PhantomData<H>,
+    _engine: PhantomData<E>,
+}
+
+pub struct SnProofGadget<H: FixedLengthCRH, HGadget: FixedLengthCRHGadget<H, E>, E: PairingEngine> {
+    siblings: Vec<HGadget::OutputGadget>,
+    _crh:     PhantomData<H>,
+    _engine:  PhantomData<E>,
+}
+
+impl<H, HGadget, E> StateDigestGadget<H, HGadget, E>
+where
+    H: FixedLengthCRH,
+    HGadget: FixedLengthCRHGadget<H, E>,
+    E: PairingEngine,
+{
+    /// Returns the root of the commitment tree, against which commitments are
+    /// checked with `IdealLedgerGadget`.
+    pub fn cm_root(&self) -> &HGadget::OutputGadget {
+        &self.cm_root
+    }
+}
+
+impl<H, HGadget> SparseLedgerGadget<H, HGadget> {
+    /// Returns the bits of `serial_number_hash` that give its position in the
+    /// serial number tree. The hash is encoded strictly: otherwise a prover
+    /// could encode a field element `x` as `x + p`, and open the empty leaf at
+    /// another position to show that a spent serial number is absent.
+    fn position_bits<E, CS>(
+        mut cs: CS,
+        serial_number_hash: &HGadget::OutputGadget,
+    ) -> Result<Vec<Boolean>, SynthesisError>
+    where
+        E: PairingEngine,
+        CS: ConstraintSystem<E>,
+        H: FixedLengthCRH,
+        HGadget: FixedLengthCRHGadget<H, E>,
+    {
+        let position_bits = serial_number_hash
+            .to_bytes_strict(&mut cs.ns(|| "serial_number_hash_to_bytes"))?
+            .iter()
+            .flat_map(|byte| byte.into_bits_le())
+            .take(SPARSE_MERKLE_TREE_DEPTH)
+            .collect::<Vec<_>>();
+        assert_eq!(position_bits.len(), SPARSE_MERKLE_TREE_DEPTH);
+        Ok(position_bits)
+    }
+
+    /// Enforces that `leaf_hash` is at the position of `serial_number_hash`
+    /// in the serial number tree, if `should_enforce` is true.
+    fn conditionally_check_leaf<E, CS>(
+        mut cs: CS,
+        parameters: &HGadget::ParametersGadget,
+        sn_root: &HGadget::OutputGadget,
+        serial_number_hash: &HGadget::OutputGadget,
+        leaf_hash: HGadget::OutputGadget,
+        witness: &SnProofGadget<H, HGadget, E>,
+        should_enforce: &Boolean,
+    ) -> Result<(), SynthesisError>
+    where
+        E: PairingEngine,
+        CS: ConstraintSystem<E>,
+        H: FixedLengthCRH,
+        HGadget: FixedLengthCRHGadget<H, E>,
+    {
+        assert_eq!(witness.siblings.len(), SPARSE_MERKLE_TREE_DEPTH);
+        let position_bits = Self::position_bits(cs.ns(|| "position"), serial_number_hash)?;
+
+        let mut current = leaf_hash;
+        for (i, (sibling, is_right)) in witness.siblings.iter().zip(&position_bits).enumerate() {
+            let left = HGadget::OutputGadget::conditionally_select(
+                &mut cs.ns(|| format!("select_left_{}", i)),
+                is_right,
+                sibling,
+                &current,
+            )?;
+            let right = HGadget::OutputGadget::conditionally_select(
+                &mut cs.ns(|| format!("select_right_{}", i)),
+                is_right,
+                &current,
+                sibling,
+            )?;
+            current = hash_inner_node_gadget::<H, HGadget, E, _>(
+                &mut cs.ns(|| format!("hash_inner_node_{}", i)),
+                parameters,
+                &left,
+                &right,
+            )?;
+        }
+
+        sn_root.conditional_enforce_equal(&mut cs.ns(|| "root_is_last"), &current, should_enforce)
+    }
+}
+
+impl<L, E, H, HGadget> SNWGadget<StateDigest<H>, SnProof<H, L>, E>
+    for SparseLedgerGadget<H, HGadget>
+where
+    L: ToBytes,
+    E: PairingEngine,
+    H: FixedLengthCRH,
+    HGadget: FixedLengthCRHGadget<H, E>,
+{
+    type ParametersGadget = <HGadget as FixedLengthCRHGadget<H, E>>::ParametersGadget;
+    type DigestGadget = StateDigestGadget<H, HGadget, E>;
+    type WitnessGadget = SnProofGadget<H, HGadget, E>;
+
+    fn conditionally_check_membership_gadget<CS: ConstraintSystem<E>>(
+        mut cs: CS,
+        parameters: &Self::ParametersGadget,
+        ledger_state_digest: &Self::DigestGadget,
+        serial_number: &[UInt8],
+        witness: &Self::WitnessGadget,
+        should_enforce: &Boolean,
+    ) -> Result<(), SynthesisError> {
+        let serial_number_hash = HGadget::check_evaluation_gadget(
+            cs.ns(|| "hash_serial_number"),
+            parameters,
+            serial_number,
+        )?;
+        Self::conditionally_check_leaf(
+            cs.ns(|| "check_path"),
+            parameters,
+            &ledger_state_digest.sn_root,
+            &serial_number_hash,
+            serial_number_hash.clone(),
+            witness,
+            should_enforce,
+        )
+    }
+
+    fn conditionally_check_non_membership_gadget<CS: ConstraintSystem<E>>(
+        mut cs: CS,
+        parameters: &Self::ParametersGadget,
+        ledger_state_digest: &Self::DigestGadget,
+        serial_number: &[UInt8],
+        witness: &Self::WitnessGadget,
+        should_enforce: &Boolean,
+    ) -> Result<(), SynthesisError> {
+        let serial_number_hash = HGadget::check_evaluation_gadget(
+            cs.ns(|| "hash_serial_number"),
+            parameters,
+            serial_number,
+        )?;
+        let empty_leaf = vec![UInt8::constant(0); H::INPUT_SIZE_BITS / 8];
+        let empty_hash =
+            HGadget::check_evaluation_gadget(cs.ns(|| "hash_empty"), parameters, &empty_leaf)?;
+        Self::conditionally_check_leaf(
+            cs.ns(|| "check_path"),
+            parameters,
+            &ledger_state_digest.sn_root,
+            &serial_number_hash,
+            empty_hash,
+            witness,
+            should_enforce,
+        )
+    }
+}
+
+impl<H, HGadget, E> AllocGadget<StateDigest<H>, E> for StateDigestGadget<H, HGadget, E>
+where
+    H: FixedLengthCRH,
+    HGadget: FixedLengthCRHGadget<H, E>,
+    E: PairingEngine,
+{
+    fn alloc<F, T, CS: ConstraintSystem<E>>(
+        mut cs: CS,
+        value_gen: F,
+    ) -> Result<Self, SynthesisError>
+    where
+        F: FnOnce() -> Result<T, SynthesisError>,
+        T: Borrow<StateDigest<H>>,
+    {
+        let (cm_root, sn_root) = match value_gen() {
+            Ok(digest) => {
+                let digest = digest.borrow().clone();
+                (Ok(digest.cm_root), Ok(digest.sn_root))
+            },
+            Err(_) => (
+                Err(SynthesisError::AssignmentMissing),
+                Err(SynthesisError::AssignmentMissing),
+            ),
+        };
+
+        let cm_root = HGadget::OutputGadget::alloc(&mut cs.ns(|| "cm_root"), || cm_root)?;
+        let sn_root = HGadget::OutputGadget::alloc(&mut cs.ns(|| "sn_root"), || sn_root)?;
+
+        Ok(StateDigestGadget {
+            cm_root,
+            sn_root,
+            _crh: PhantomData,
+            _engine: PhantomData,
+        })
+    }
+
+    fn alloc_input<F, T, CS: ConstraintSystem<E>>(
+        mut cs: CS,
+        value_gen: F,
+    ) -> Result<Self, SynthesisError>
+    where
+        F: FnOnce() -> Result<T, SynthesisError>,
+        T: Borrow<StateDigest<H>>,
+    {
+        let (cm_root, sn_root) = match value_gen() {
+            Ok(digest) => {
+                let digest = digest.borrow().clone();
+                (Ok(digest.cm_root), Ok(digest.sn_root))
+            },
+            Err(_) => (
+                Err(SynthesisError::AssignmentMissing),
+                Err(SynthesisError::AssignmentMissing),
+            ),
+        };
+
+        let cm_root =
+            HGadget::OutputGadget::alloc_input(&mut cs.ns(|| "input_cm_root"), || cm_root)?;
+        let sn_root =
+            HGadget::OutputGadget::alloc_input(&mut cs.ns(|| "input_sn_root"), || sn_root)?;
+
+        Ok(StateDigestGadget {
+            cm_root,
+            sn_root,
+            _crh: PhantomData,
+            _engine: PhantomData,
+        })
+    }
+}
+
+impl<H, L, HGadget, E> AllocGadget<SnProof<H, L>, E> for SnProofGadget<H, HGadget, E>
+where
+    H: FixedLengthCRH,
+    L: ToBytes,
+    HGadget: FixedLengthCRHGadget<H, E>,
+    E: PairingEngine,
+{
+    fn alloc<F, T, CS: ConstraintSystem<E>>(
+        mut cs: CS,
+        value_gen: F,
+    ) -> Result<Self, SynthesisError>
+    where
+        F: FnOnce() -> Result<T, SynthesisError>,
+        T: Borrow<SnProof<H, L>>,
+    {
+        let mut siblings = Vec::new();
+        for (i, sibling) in value_gen()?.borrow().0.siblings.iter().enumerate() {
+            let sibling =
+                HGadget::OutputGadget::alloc(&mut cs.ns(|| format!("sibling_{}", i)), || {
+                    Ok(sibling.clone())
+                })?;
+            siblings.push(sibling);
+        }
+        Ok(SnProofGadget {
+            siblings,
+            _crh: PhantomData,
+            _engine: PhantomData,
+        })
+    }
+
+    fn alloc_input<F, T, CS: ConstraintSystem<E>>(
+        mut cs: CS,
+        value_gen: F,
+    ) -> Result<Self, SynthesisError>
+    where
+        F: FnOnce() -> Result<T, SynthesisError>,
+        T: Borrow<SnProof<H, L>>,
+    {
+        let mut siblings = Vec::new();
+        for (i, sibling) in value_gen()?.borrow().0.siblings.iter().enumerate() {
+            let sibling = HGadget::OutputGadget::alloc_input(
+                &mut cs.ns(|| format!("sibling_{}", i)),
+                || Ok(sibling.clone()),
+            )?;
+            siblings.push(sibling);
+        }
+        Ok(SnProofGadget {
+            siblings,
+            _crh: PhantomData,
+            _engine: PhantomData,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::rc::Rc;
+
+    use algebra::{
+        curves::{bls12_381::Bls12_381, jubjub::JubJubAffine as JubJub},
+        fields::bls12_381::Fr,
+        BigInteger, Field, FpParameters, PrimeField,
+    };
+    use rand::{ChaChaRng, Rng, SeedableRng};
+    use snark::ConstraintSystem;
+
+    use super::*;
+    use crate::{
+        crypto_primitives::{
+            crh::{
+                pedersen::{PedersenCRH, PedersenWindow},
+                FixedLengthCRH,
+            },
+            mht::SparseMerkleTree,
+        },
+        gadgets::crh::pedersen::PedersenCRHGadget,
+    };
+    use snark_gadgets::{
+        groups::curves::twisted_edwards::jubjub::JubJubGadget,
+        test_constraint_system::TestConstraintSystem,
+    };
+
+    #[derive(Clone)]
+    pub(super) struct Window4x256;
+    impl PedersenWindow for Window4x256 {
+        const WINDOW_SIZE: usize = 4;
+        const NUM_WINDOWS: usize = 256;
+    }
+
+    type H = PedersenCRH<JubJub, Window4x256>;
+    type HG = PedersenCRHGadget<JubJub, Bls12_381, JubJubGadget>;
+    type SNWG = SparseLedgerGadget<H, HG>;
+    type DG = StateDigestGadget<H, HG, Bls12_381>;
+    type WG = SnProofGadget<H, HG, Bls12_381>;
+
+    /// Returns whether the circuit checking the (non-)membership of `sn` in
+    /// `tree` is satisfied.
+    fn check_sn(
+        crh_parameters: &<H as FixedLengthCRH>::Parameters,
+        tree: &SparseMerkleTree<H, [u8; 32]>,
+        sn: &[u8; 32],
+        membership: bool,
+    ) -> bool {
+        let mut cs = TestConstraintSystem::<Bls12_381>::new();
+        let digest = StateDigest {
+            cm_root: JubJub::default(),
+            sn_root: tree.root(),
+        };
+        let proof = SnProof(tree.generate_proof(sn).unwrap());
+
+        let digest = DG::alloc(&mut cs.ns(|| "digest"), || Ok(digest)).unwrap();
+        let parameters = <HG as FixedLengthCRHGadget<H, Bls12_381>>::ParametersGadget::alloc(
+            &mut cs.ns(|| "parameters"),
+            || Ok(crh_parameters),
+        )
+        .unwrap();
+        let sn = UInt8::alloc_vec(&mut cs.ns(|| "sn"), &sn[..]).unwrap();
+        let witness =
+            <WG as AllocGadget<SnProof<H, [u8; 32]>, _>>::alloc(&mut cs.ns(|| "witness"), || {
+                Ok(&proof)
+            })
+            .unwrap();
+        if membership {
+            <SNWG as SNWGadget<_, SnProof<H, [u8; 32]>, _>>::check_membership_gadget(
+                &mut cs.ns(|| "check_membership"),
+                &parameters,
+                &digest,
+                &sn,
+                &witness,
+            )
+            .unwrap();
+        } else {
+            <SNWG as SNWGadget<_, SnProof<H, [u8; 32]>, _>>::check_non_membership_gadget(
+                &mut cs.ns(|| "check_non_membership"),
+                &parameters,
+                &digest,
+                &sn,
+                &witness,
+            )
+            .unwrap();
+        }
+        cs.is_satisfied()
+    }
+
+    #[test]
+    fn sparse_merkle_tree_gadget_test() {
+        let seed: [u32; 8] = [
+            2053759276, 152413135, 1690980041, 4293109333, 2390175708, 686052238, 1844363894,
+            1379683288,
+        ];
+        let mut rng = ChaChaRng::from_seed(&seed);
+        let crh_parameters = Rc::new(H::setup(&mut rng).unwrap());
+
+        let mut tree = SparseMerkleTree::new(crh_parameters.clone()).unwrap();
+        let serial_numbers = (1..4u8).map(|i| [i; 32]).collect::<Vec<_>>();
+        for sn in &serial_numbers {
+            tree.insert(sn).unwrap();
+        }
+
+        for sn in &serial_numbers {
+            assert!(check_sn(&crh_parameters, &tree, sn, true));
+            assert!(!check_sn(&crh_parameters, &tree, sn, false));
+        }
+        let absent = [42u8; 32];
+        assert!(check_sn(&crh_parameters, &tree, &absent, false));
+        assert!(!check_sn(&crh_parameters, &tree, &absent, true));
+    }
+    /// Overwrites the bits of the bytes of the x-coordinate `x` allocated
+    /// under `path` with those of `x + p`, which satisfy the unpacking
+    /// constraint just as well.
+    fn encode_x_non_canonically(cs: &mut TestConstraintSystem<Bls12_381>, path: &str, x: Fr) {
+        let mut repr = x.into_repr();
+        assert!(!repr.add_nocarry(&<Fr as PrimeField>::Params::MODULUS));
+        let mut bytes = Vec::new();
+        repr.write(&mut bytes).unwrap();
+        for (i, byte) in bytes.iter().enumerate() {
+            for j in 0..8 {
+                let bit = if (byte >> j) & 1 == 1 {
+                    Fr::one()
+                } else {
+                    Fr::zero()
+                };
+                cs.set(
+                    &format!(
+                        "{}/x/Alloc bytes/byte_{}/allocated bit_gadget {}/boolean",
+                        path, i, j
+                    ),
+                    bit,
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn non_canonical_position_test() {
+        let seed: [u32; 8] = [
+            2053759276, 152413135, 1690980041, 4293109333, 2390175708, 686052238, 1844363894,
+            1379683288,
+        ];
+        let mut rng = ChaChaRng::from_seed(&seed);
+        let hash: JubJub = rng.gen();
+
+        // A plain byte encoding accepts `x + p`, whose low bits, and thus
+        // position, differ from those of `x`.
+        let mut cs = TestConstraintSystem::<Bls12_381>::new();
+        let hash_gadget = <HG as FixedLengthCRHGadget<H, Bls12_381>>::OutputGadget::alloc(
+            &mut cs.ns(|| "hash"),
+            || Ok(hash),
+        )
+        .unwrap();
+        hash_gadget.to_bytes(&mut cs.ns(|| "to_bytes")).unwrap();
+        assert!(cs.is_satisfied());
+        encode_x_non_canonically(&mut cs, "to_bytes", hash.x);
+        assert!(cs.is_satisfied());
+
+        // The position bits reject it.
+        let mut cs = TestConstraintSystem::<Bls12_381>::new();
+        let hash_gadget = <HG as FixedLengthCRHGadget<H, Bls12_381>>::OutputGadget::alloc(
+            &mut cs.ns(|| "hash"),
+            || Ok(hash),
+        )
+        .unwrap();
+        SNWG::position_bits(&mut cs.ns(|| "position"), &hash_gadget).unwrap();
+        assert!(cs.is_satisfied());
+        encode_x_non_canonically(&mut cs, "position/serial_number_hash_to_bytes", hash.x);
+        assert!(!cs.is_satisfied());
+    }
+}
@@ -1,12 +1,14 @@
 use algebra::PairingEngine;
 use snark::{ConstraintSystem, SynthesisError};
 use snark_gadgets::{
+    boolean::Boolean,
     uint8::UInt8,
     utils::{AllocGadget, EqGadget, ToBytesGadget},
 };
 
 use crate::crypto_primitives::signature::SignatureScheme;
 
+pub mod reddsa;
 pub mod schnorr;
 
 pub trait SigRandomizePkGadget<S: SignatureScheme, E: PairingEngine> {
@@ -21,3 +23,41 @@ pub trait SigRandomizePkGadget<S: SignatureScheme, E: PairingEngine> {
         randomness: &[UInt8],
     ) -> Result<Self::PublicKeyGadget, SynthesisError>;
 }
+
+pub trait SigVerifyGadget<S: SignatureScheme, E: PairingEngine> {
+    type ParametersGadget: AllocGadget<S::Parameters, E> + Clone;
+
+    type PublicKeyGadget: AllocGadget<S::PublicKey, E> + Clone;
+
+    type SignatureGadget: AllocGadget<S::Signature, E> + Clone;
+
+    /// Enforces that `signature` is a valid signature on `message` under
+    /// `public_key`.
+    fn check_verification_gadget<CS: ConstraintSystem<E>>(
+        cs: CS,
+        parameters: &Self::ParametersGadget,
+        public_key: &Self::PublicKeyGadget,
+        message: &[UInt8],
+        signature: &Self::SignatureGadget,
+    ) -> Result<(), SynthesisError> {
+        Self::conditionally_check_verification_gadget(
+            cs,
+            parameters,
+            public_key,
+            message,
+            signature,
+            &Boolean::constant(true),
+        )
+    }
+
+    /// Enforces that `signature` is a valid signature on `message` under
+    /// `public_key` if `condition` is set.
+    fn conditionally_check_verification_gadget<CS: ConstraintSystem<E>>(
+        cs: CS,
+        parameters: &Self::ParametersGadget,
+        public_key: &Self::PublicKeyGadget,
+        message: &[UInt8],
+        signature: &Self::SignatureGadget,
+        condition: &Boolean,
+    ) -> Result<(), SynthesisError>;
+}
@@ -0,0 +1,549 @@
+use algebra::{
+    bytes::ToBytes,
+    curves::{models::TEModelParameters, twisted_edwards_extended::GroupAffine as TEAffine},
+    to_bytes, BitIterator, PairingEngine,
+};
+use snark::{ConstraintSystem, SynthesisError};
+
+use crate::gadgets::{
+    prf::blake2s::blake2s_gadget,
+    signature::{SigRandomizePkGadget, SigVerifyGadget},
+};
+use snark_gadgets::{
+    boolean::Boolean,
+    fields::FieldGadget,
+    groups::{curves::twisted_edwards::AffineGadget, GroupGadget},
+    uint8::UInt8,
+    utils::{AllocGadget, ConditionalEqGadget, EqGadget, ToBytesGadget},
+};
+
+use std::{borrow::Borrow, marker::PhantomData};
+
+use crate::crypto_primitives::signature::reddsa::{
+    RedDSAParameters, RedDSAPublicKey, RedDSASig, RedDSASignature,
+};
+use blake2::Blake2s;
+
+#[derive(Derivative)]
+#[derivative(Clone(
+    bound = "P: TEModelParameters, E: PairingEngine, F: FieldGadget<P::BaseField, E>"
+))]
+pub struct RedDSAParametersGadget<
+    P: TEModelParameters,
+    E: PairingEngine,
+    F: FieldGadget<P::BaseField, E>,
+> {
+    generator: AffineGadget<P, E, F>,
+    salt:      Vec<UInt8>,
+}
+
+#[derive(Derivative)]
+#[derivative(
+    Debug(bound = "P: TEModelParameters, E: PairingEngine, F: FieldGadget<P::BaseField, E>"),
+    Clone(bound = "P: TEModelParameters, E: PairingEngine, F: FieldGadget<P::BaseField, E>"),
+    PartialEq(bound = "P: TEModelParameters, E: PairingEngine, F: FieldGadget<P::BaseField, E>"),
+    Eq(bound = "P: TEModelParameters, E: PairingEngine, F: FieldGadget<P::BaseField, E>")
+)]
+pub struct RedDSAPublicKeyGadget<
+    P: TEModelParameters,
+    E: PairingEngine,
+    F: FieldGadget<P::BaseField, E>,
+> {
+    pub_key: AffineGadget<P, E, F>,
+}
+
+#[derive(Derivative)]
+#[derivative(Clone(
+    bound = "P: TEModelParameters, E: PairingEngine, F: FieldGadget<P::BaseField, E>"
+))]
+pub struct RedDSASignatureGadget<
+    P: TEModelParameters,
+    E: PairingEngine,
+    F: FieldGadget<P::BaseField, E>,
+> {
+    prover_commitment: AffineGadget<P, E, F>,
+    // The little-endian bytes of the prover response.
+    prover_response:   Vec<UInt8>,
+}
+
+/// Randomizes and verifies `RedDSASignature`s over the curve `P`, whose base
+/// field is the scalar field of `E`. The challenge is recomputed in the
+/// circuit, so only signatures that use Blake2s are supported.
+pub struct RedDSAGadget<P: TEModelParameters, E: PairingEngine, F: FieldGadget<P::BaseField, E>> {
+    _curve:        PhantomData<*const P>,
+    _engine:       PhantomData<*const E>,
+    _field_gadget: PhantomData<*const F>,
+}
+
+impl<P, E, F> SigRandomizePkGadget<RedDSASignature<P, Blake2s>, E> for RedDSAGadget<P, E, F>
+where
+    P: TEModelParameters,
+    E: PairingEngine,
+    F: FieldGadget<P::BaseField, E>,
+{
+    type ParametersGadget = RedDSAParametersGadget<P, E, F>;
+    type PublicKeyGadget = RedDSAPublicKeyGadget<P, E, F>;
+
+    fn check_randomization_gadget<CS: ConstraintSystem<E>>(
+        mut cs: CS,
+        parameters: &Self::ParametersGadget,
+        public_key: &Self::PublicKeyGadget,
+        randomness: &[UInt8],
+    ) -> Result<Self::PublicKeyGadget, SynthesisError> {
+        let randomness = randomness
+            .iter()
+            .flat_map(|b| b.into_bits_le())
+            .collect::<Vec<_>>();
+        let rand_pk = <AffineGadget<P, E, F> as GroupGadget<TEAffine<P>, E>>::mul_bits(
+            &parameters.generator,
+            &mut cs.ns(|| "Compute Randomizer"),
+            &public_key.pub_key,
+            randomness.iter(),
+        )?;
+        Ok(RedDSAPublicKeyGadget { pub_key: rand_pk })
+    }
+}
+
+impl<P, E, F> SigVerifyGadget<RedDSASignature<P, Blake2s>, E> for RedDSAGadget<P, E, F>
+where
+    P: TEModelParameters,
+    E: PairingEngine,
+    F: FieldGadget<P::BaseField, E>,
+{
+    type ParametersGadget = RedDSAParametersGadget<P, E, F>;
+    type PublicKeyGadget = RedDSAPublicKeyGadget<P, E, F>;
+    type SignatureGadget = RedDSASignatureGadget<P, E, F>;
+
+    fn conditionally_check_verification_gadget<CS: ConstraintSystem<E>>(
+        mut cs: CS,
+        parameters: &Self::ParametersGadget,
+        public_key: &Self::PublicKeyGadget,
+        message: &[UInt8],
+        signature: &Self::SignatureGadget,
+        condition: &Boolean,
+    ) -> Result<(), SynthesisError> {
+        // c := H(salt || pk || R || msg)
+        let public_key_bytes = public_key
+            .pub_key
+            .to_bytes(&mut cs.ns(|| "PubKey To Bytes"))?;
+        let commitment_bytes = signature
+            .prover_commitment
+            .to_bytes(&mut cs.ns(|| "Commitment To Bytes"))?;
+        let mut hash_input = Vec::new();
+        for byte in parameters
+            .salt
+            .iter()
+            .chain(&public_key_bytes)
+            .chain(&commitment_bytes)
+            .chain(message)
+        {
+            hash_input.extend_from_slice(&byte.into_bits_le());
+        }
+        let challenge = blake2s_gadget(cs.ns(|| "Compute Challenge"), &hash_input)?
+            .iter()
+            .flat_map(|int| int.to_bits_le())
+            .collect::<Vec<_>>();
+
+        // [h](R + c · pk - s · g) = 0. Points allocated as inputs are not
+        // checked to be in the prime order subgroup, so the cofactor is
+        // cleared in the circuit as it is in `RedDSASignature::verify`.
+        let claimed_commitment = <AffineGadget<P, E, F> as GroupGadget<TEAffine<P>, E>>::mul_bits(
+            &public_key.pub_key,
+            &mut cs.ns(|| "Add Challenge Times PubKey"),
+            &signature.prover_commitment,
+            challenge.iter(),
+        )?;
+        let response = signature
+            .prover_response
+            .iter()
+            .flat_map(|b| b.into_bits_le())
+            .collect::<Vec<_>>();
+        let zero =
+            <AffineGadget<P, E, F> as GroupGadget<TEAffine<P>, E>>::zero(&mut cs.ns(|| "Zero"))?;
+        let response_times_generator =
+            <AffineGadget<P, E, F> as GroupGadget<TEAffine<P>, E>>::mul_bits(
+                &parameters.generator,
+                &mut cs.ns(|| "Response Times Generator"),
+                &zero,
+                response.iter(),
+            )?;
+        let difference = <AffineGadget<P, E, F> as GroupGadget<TEAffine<P>, E>>::sub(
+            &claimed_commitment,
+            &mut cs.ns(|| "Subtract Response Times Generator"),
+            &response_times_generator,
+        )?;
+        let claimed_zero = mul_by_cofactor(cs.ns(|| "Clear Cofactor"), &difference)?;
+        claimed_zero.conditional_enforce_equal(
+            &mut cs.ns(|| "Check Verification Equation"),
+            &zero,
+            condition,
+        )
+    }
+}
+
+/// Returns `[h] point`, where `h` is the cofactor of `P`.
+fn mul_by_cofactor<P, E, F, CS>(
+    mut cs: CS,
+    point: &AffineGadget<P, E, F>,
+) -> Result<AffineGadget<P, E, F>, SynthesisError>
+where
+    P: TEModelParameters,
+    E: PairingEngine,
+    F: FieldGadget<P::BaseField, E>,
+    CS: ConstraintSystem<E>,
+{
+    let mut result =
+        <AffineGadget<P, E, F> as GroupGadget<TEAffine<P>, E>>::zero(&mut cs.ns(|| "Zero"))?;
+    let mut seen_one = false;
+    for (i, bit) in BitIterator::new(P::COFACTOR).enumerate() {
+        let mut cs = cs.ns(|| format!("Iteration {}", i));
+        if seen_one {
+            <AffineGadget<P, E, F> as GroupGadget<TEAffine<P>, E>>::double_in_place(
+                &mut result,
+                cs.ns(|| "Double"),
+            )?;
+        }
+        if bit {
+            result = if seen_one {
+                <AffineGadget<P, E, F> as GroupGadget<TEAffine<P>, E>>::add(
+                    &result,
+                    cs.ns(|| "Add"),
+                    point,
+                )?
+            } else {
+                point.clone()
+            };
+            seen_one = true;
+        }
+    }
+    Ok(result)
+}
+
+impl<P, E, F> AllocGadget<RedDSAParameters<P, Blake2s>, E> for RedDSAParametersGadget<P, E, F>
+where
+    P: TEModelParameters,
+    E: PairingEngine,
+    F: FieldGadget<P::BaseField, E>,
+{
+    fn alloc<FN, T, CS: ConstraintSystem<E>>(mut cs: CS, f: FN) -> Result<Self, SynthesisError>
+    where
+        FN: FnOnce() -> Result<T, SynthesisError>,
+        T: Borrow<RedDSAParameters<P, Blake2s>>,
+    {
+        let (generator, salt) = match f() {
+            Ok(pp) => {
+                let pp = pp.borrow();
+                (Ok(pp.generator), Some(pp.salt))
+            },
+            _ => (Err(SynthesisError::AssignmentMissing), None),
+        };
+        let generator = <AffineGadget<P, E, F> as AllocGadget<TEAffine<P>, E>>::alloc_checked(
+            cs.ns(|| "Generator"),
+            || generator,
+        )?;
+        let salt = UInt8::alloc_vec(cs.ns(|| "Salt"), &option_bytes(salt, 32))?;
+        Ok(Self { generator, salt })
+    }
+
+    fn alloc_input<FN, T, CS: ConstraintSystem<E>>(
+        mut cs: CS,
+        f: FN,
+    ) -> Result<Self, SynthesisError>
+    where
+        FN: FnOnce() -> Result<T, SynthesisError>,
+        T: Borrow<RedDSAParameters<P, Blake2s>>,
+    {
+        let (generator, salt) = match f() {
+            Ok(pp) => {
+                let pp = pp.borrow();
+                (Ok(pp.generator), pp.salt)
+            },
+            _ => (Err(SynthesisError::AssignmentMissing), [0u8; 32]),
+        };
+        let generator = <AffineGadget<P, E, F> as AllocGadget<TEAffine<P>, E>>::alloc_input(
+            cs.ns(|| "Generator"),
+            || generator,
+        )?;
+        let salt = UInt8::alloc_input_vec(cs.ns(|| "Salt"), &salt)?;
+        Ok(Self { generator, salt })
+    }
+}
+
+impl<P, E, F> AllocGadget<RedDSAPublicKey<P>, E> for RedDSAPublicKeyGadget<P, E, F>
+where
+    P: TEModelParameters,
+    E: PairingEngine,
+    F: FieldGadget<P::BaseField, E>,
+{
+    fn alloc<FN, T, CS: ConstraintSystem<E>>(cs: CS, f: FN) -> Result<Self, SynthesisError>
+    where
+        FN: FnOnce() -> Result<T, SynthesisError>,
+        T: Borrow<RedDSAPublicKey<P>>,
+    {
+        let pub_key =
+            <AffineGadget<P, E, F> as AllocGadget<TEAffine<P>, E>>::alloc_checked(cs, || {
+                f().map(|pk| *pk.borrow())
+            })?;
+        Ok(Self { pub_key })
+    }
+
+    fn alloc_input<FN, T, CS: ConstraintSystem<E>>(cs: CS, f: FN) -> Result<Self, SynthesisError>
+    where
+        FN: FnOnce() -> Result<T, SynthesisError>,
+        T: Borrow<RedDSAPublicKey<P>>,
+    {
+        let pub_key =
+            <AffineGadget<P, E, F> as AllocGadget<TEAffine<P>, E>>::alloc_input(cs, || {
+                f().map(|pk| *pk.borrow())
+            })?;
+        Ok(Self { pub_key })
+    }
+}
+
+impl<P, E, F> AllocGadget<RedDSASig<P>, E> for RedDSASignatureGadget<P, E, F>
+where
+    P: TEModelParameters,
+    E: PairingEngine,
+    F: FieldGadget<P::BaseField, E>,
+{
+    fn alloc<FN, T, CS: ConstraintSystem<E>>(mut cs: CS, f: FN) -> Result<Self, SynthesisError>
+    where
+        FN: FnOnce() -> Result<T, SynthesisError>,
+        T: Borrow<RedDSASig<P>>,
+    {
+        let (prover_commitment, prover_response) = match f() {
+            Ok(sig) => {
+                let sig = sig.borrow();
+                let response = to_bytes![sig.prover_response].ok();
+                (Ok(sig.prover_commitment), response)
+            },
+            _ => (Err(SynthesisError::AssignmentMissing), None),
+        };
+        let prover_commitment =
+            <AffineGadget<P, E, F> as AllocGadget<TEAffine<P>, E>>::alloc_checked(
+                cs.ns(|| "Prover Commitment"),
+                || prover_commitment,
+            )?;
+        let response_len = to_bytes![P::ScalarField::default()].unwrap().len();
+        let prover_response = UInt8::alloc_vec(
+            cs.ns(|| "Prover Response"),
+            &option_bytes(prover_response, response_len),
+        )?;
+        Ok(Self {
+            prover_commitment,
+            prover_response,
+        })
+    }
+
+    fn alloc_input<FN, T, CS: ConstraintSystem<E>>(
+        mut cs: CS,
+        f: FN,
+    ) -> Result<Self, SynthesisError>
+    where
+        FN: FnOnce() -> Result<T, SynthesisError>,
+        T: Borrow<RedDSASig<P>>,
+    {
+        let (prover_commitment, prover_response) = match f() {
+            Ok(sig) => {
+                let sig = sig.borrow();
+                let response = to_bytes![sig.prover_response].unwrap();
+                (Ok(sig.prover_commitment), response)
+            },
+            _ => {
+                let response_len = to_bytes![P::ScalarField::default()].unwrap().len();
+                (
+                    Err(SynthesisError::AssignmentMissing),
+                    vec![0u8; response_len],
+                )
+            },
+        };
+        let prover_commitment =
+            <AffineGadget<P, E, F> as AllocGadget<TEAffine<P>, E>>::alloc_input(
+                cs.ns(|| "Prover Commitment"),
+                || prover_commitment,
+            )?;
+        let prover_response =
+            UInt8::alloc_input_vec(cs.ns(|| "Prover Response"), &prover_response)?;
+        Ok(Self {
+            prover_commitment,
+            prover_response,
+        })
+    }
+}
+
+/// Returns `len` bytes, which are unknown if `bytes` is `None`.
+fn option_bytes<B: AsRef<[u8]>>(bytes: Option<B>, len: usize) -> Vec<Option<u8>> {
+    match bytes {
+        Some(bytes) => bytes.as_ref().iter().map(|b| Some(*b)).collect(),
+        None => vec![None; len],
+    }
+}
+
+impl<P, E, F> ConditionalEqGadget<E> for RedDSAPublicKeyGadget<P, E, F>
+where
+    P: TEModelParameters,
+    E: PairingEngine,
+    F: FieldGadget<P::BaseField, E>,
+{
+    #[inline]
+    fn conditional_enforce_equal<CS: ConstraintSystem<E>>(
+        &self,
+        mut cs: CS,
+        other: &Self,
+        condition: &Boolean,
+    ) -> Result<(), SynthesisError> {
+        self.pub_key.conditional_enforce_equal(
+            &mut cs.ns(|| "PubKey equality"),
+            &other.pub_key,
+            condition,
+        )
+    }
+
+    fn cost() -> usize {
+        <AffineGadget<P, E, F> as ConditionalEqGadget<E>>::cost()
+    }
+}
+
+impl<P, E, F> EqGadget<E> for RedDSAPublicKeyGadget<P, E, F>
+where
+    P: TEModelParameters,
+    E: PairingEngine,
+    F: FieldGadget<P::BaseField, E>,
+{
+}
+
+impl<P, E, F> ToBytesGadget<E> for RedDSAPublicKeyGadget<P, E, F>
+where
+    P: TEModelParameters,
+    E: PairingEngine,
+    F: FieldGadget<P::BaseField, E>,
+{
+    fn to_bytes<CS: ConstraintSystem<E>>(&self, mut cs: CS) -> Result<Vec<UInt8>, SynthesisError> {
+        self.pub_key.to_bytes(&mut cs.ns(|| "PubKey To Bytes"))
+    }
+
+    fn to_bytes_strict<CS: ConstraintSystem<E>>(
+        &self,
+        mut cs: CS,
+    ) -> Result<Vec<UInt8>, SynthesisError> {
+        self.pub_key
+            .to_bytes_strict(&mut cs.ns(|| "PubKey To Bytes"))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::crypto_primitives::SignatureScheme;
+    use algebra::curves::{bls12_381::Bls12_381, jubjub::JubJubParameters};
+    use rand::thread_rng;
+    use snark_gadgets::{fields::jubjub::FqGadget, test_constraint_system::TestConstraintSystem};
+
+    type JubJubSig = RedDSASignature<JubJubParameters, Blake2s>;
+    type JubJubSigGadget = RedDSAGadget<JubJubParameters, Bls12_381, FqGadget>;
+
+    fn verify_in_circuit(
+        parameters: &RedDSAParameters<JubJubParameters, Blake2s>,
+        public_key: &RedDSAPublicKey<JubJubParameters>,
+        message: &[u8],
+        signature: &RedDSASig<JubJubParameters>,
+    ) -> bool {
+        let mut cs = TestConstraintSystem::<Bls12_381>::new();
+        let parameters_gadget =
+            <JubJubSigGadget as SigVerifyGadget<JubJubSig, _>>::ParametersGadget::alloc(
+                &mut cs.ns(|| "Parameters"),
+                || Ok(parameters),
+            )
+            .unwrap();
+        let public_key_gadget =
+            <JubJubSigGadget as SigVerifyGadget<JubJubSig, _>>::PublicKeyGadget::alloc(
+                &mut cs.ns(|| "Public Key"),
+                || Ok(public_key),
+            )
+            .unwrap();
+        let signature_gadget =
+            <JubJubSigGadget as SigVerifyGadget<JubJubSig, _>>::SignatureGadget::alloc(
+                &mut cs.ns(|| "Signature"),
+                || Ok(signature),
+            )
+            .unwrap();
+        let message_gadget = UInt8::alloc_vec(&mut cs.ns(|| "Message"), message).unwrap();
+        <JubJubSigGadget as SigVerifyGadget<JubJubSig, _>>::check_verification_gadget(
+            &mut cs.ns(|| "Verify"),
+            &parameters_gadget,
+            &public_key_gadget,
+            &message_gadget,
+            &signature_gadget,
+        )
+        .unwrap();
+        cs.is_satisfied()
+    }
+
+    #[test]
+    fn reddsa_verification_gadget_test() {
+        let rng = &mut thread_rng();
+        let message = b"Hi, I am a RedDSA signature!";
+        let parameters = JubJubSig::setup(rng).unwrap();
+        let (pk, sk) = JubJubSig::keygen(&parameters, rng).unwrap();
+        let signature = JubJubSig::sign(&parameters, &sk, message, rng).unwrap();
+        assert!(verify_in_circuit(&parameters, &pk, message, &signature));
+        assert!(!verify_in_circuit(
+            &parameters,
+            &pk,
+            b"Hi, I am a bad message!!!!!!",
+            &signature
+        ));
+
+        // A signature made with the randomized secret key verifies under the
+        // public key that the randomization gadget computes.
+        let randomness = [7u8; 32];
+        let randomized_pk = JubJubSig::randomize_public_key(&parameters, &pk, &randomness).unwrap();
+        let randomized_sk = JubJubSig::randomize_secret_key(&sk, &randomness);
+        let randomized_sig = JubJubSig::sign(&parameters, &randomized_sk, message, rng).unwrap();
+        assert!(verify_in_circuit(
+            &parameters,
+            &randomized_pk,
+            message,
+            &randomized_sig
+        ));
+        assert!(!verify_in_circuit(
+            &parameters,
+            &pk,
+            message,
+            &randomized_sig
+        ));
+
+        let mut cs = TestConstraintSystem::<Bls12_381>::new();
+        let parameters_gadget =
+            <JubJubSigGadget as SigRandomizePkGadget<JubJubSig, _>>::ParametersGadget::alloc(
+                &mut cs.ns(|| "Parameters"),
+                || Ok(&parameters),
+            )
+            .unwrap();
+        let public_key_gadget =
+            <JubJubSigGadget as SigRandomizePkGadget<JubJubSig, _>>::PublicKeyGadget::alloc(
+                &mut cs.ns(|| "Public Key"),
+                || Ok(&pk),
+            )
+            .unwrap();
+        let randomized_pk_gadget =
+            <JubJubSigGadget as SigRandomizePkGadget<JubJubSig, _>>::PublicKeyGadget::alloc(
+                &mut cs.ns(|| "Randomized Public Key"),
+                || Ok(&randomized_pk),
+            )
+            .unwrap();
+        let randomness_gadget = UInt8::alloc_vec(&mut cs.ns(|| "Randomness"), &randomness).unwrap();
+        let result =
+            <JubJubSigGadget as SigRandomizePkGadget<JubJubSig, _>>::check_randomization_gadget(
+                &mut cs.ns(|| "Randomize"),
+                &parameters_gadget,
+                &public_key_gadget,
+                &randomness_gadget,
+            )
+            .unwrap();
+        result
+            .enforce_equal(&mut cs.ns(|| "Check Randomization"), &randomized_pk_gadget)
+            .unwrap();
+        assert!(cs.is_satisfied());
+    }
+}
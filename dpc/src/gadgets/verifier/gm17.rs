@@ -392,6 +392,10 @@ where
                 .h_gamma_g2
                 .to_bytes(&mut cs.ns(|| "h_gamma_g2 to bytes"))?,
         );
+        // The number of query elements is fixed by the circuit.
+        bytes.extend_from_slice(&UInt8::constant_vec(
+            &(self.query.len() as u64).to_le_bytes(),
+        ));
         for (i, q) in self.query.iter().enumerate() {
             let mut cs = cs.ns(|| format!("Iteration {}", i));
             bytes.extend_from_slice(&q.to_bytes(&mut cs.ns(|| "q"))?);
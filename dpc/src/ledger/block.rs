@@ -0,0 +1,61 @@
+use algebra::{bytes::ToBytes, to_bytes};
+use blake2::Blake2s;
+use digest::Digest;
+use failure::Error;
+use std::{
+    fmt,
+    io::{Result as IoResult, Write},
+};
+
+use crate::crypto_primitives::FixedLengthCRH;
+
+/// The header of a block of the ledger. It commits to the previous header and
+/// to the state of the ledger after the block has been appended.
+#[derive(Derivative)]
+#[derivative(
+    Clone(bound = "H: FixedLengthCRH"),
+    PartialEq(bound = "H: FixedLengthCRH"),
+    Eq(bound = "H: FixedLengthCRH"),
+    Debug(bound = "H: FixedLengthCRH, H::Output: fmt::Debug")
+)]
+pub struct BlockHeader<H: FixedLengthCRH> {
+    pub height:            u64,
+    /// Hash of the previous header, or zeros for the genesis block.
+    pub previous_hash:     [u8; 32],
+    /// Root of the tree of all record commitments on the ledger. This is the
+    /// ledger digest that transactions refer to.
+    pub cm_root:           H::Output,
+    /// Root of the tree of all serial numbers on the ledger.
+    pub sn_root:           H::Output,
+    /// Root of the tree of the ids of the transactions in this block.
+    pub transactions_root: H::Output,
+}
+
+impl<H: FixedLengthCRH> ToBytes for BlockHeader<H> {
+    #[inline]
+    fn write<W: Write>(&self, mut writer: W) -> IoResult<()> {
+        self.height.write(&mut writer)?;
+        self.previous_hash.write(&mut writer)?;
+        self.cm_root.write(&mut writer)?;
+        self.sn_root.write(&mut writer)?;
+        self.transactions_root.write(&mut writer)
+    }
+}
+
+impl<H: FixedLengthCRH> BlockHeader<H> {
+    /// Returns the hash of this header, which the next header commits to.
+    pub fn hash(&self) -> Result<[u8; 32], Error> {
+        Ok(blake2s(&to_bytes![self]?))
+    }
+}
+
+/// Returns the id of `transaction`: the hash of its encoding.
+pub fn transaction_id<T: ToBytes>(transaction: &T) -> Result<[u8; 32], Error> {
+    Ok(blake2s(&to_bytes![transaction]?))
+}
+
+fn blake2s(input: &[u8]) -> [u8; 32] {
+    let mut output = [0u8; 32];
+    output.copy_from_slice(&Blake2s::digest(input));
+    output
+}
@@ -4,16 +4,19 @@ use std::{
     collections::{HashMap, HashSet},
     fmt,
     hash::Hash,
-    io::{Result as IoResult, Write},
+    io::{Read, Result as IoResult, Write},
     rc::Rc,
 };
 
 use crate::{
     crypto_primitives::{FixedLengthCRH, HashMembershipProof, MerkleHashTree},
     dpc::Transaction,
-    ledger::{Ledger, LedgerDigest, LedgerWitness},
+    ledger::{
+        block::{transaction_id, BlockHeader},
+        Ledger, LedgerDigest, LedgerWitness,
+    },
 };
-use algebra::bytes::ToBytes;
+use algebra::bytes::{FromBytes, ToBytes};
 
 #[derive(Debug, Fail)]
 pub enum LedgerError {
@@ -25,6 +28,14 @@ pub enum LedgerError {
     DuplicateMemo,
     #[fail(display = "invalid cm index during proving.")]
     InvalidCmIndex,
+    #[fail(display = "sn collides with a different sn in the sn tree.")]
+    SnCollision,
+    #[fail(display = "invalid sn during proving.")]
+    InvalidSn,
+    #[fail(display = "digest is not a past ledger digest.")]
+    InvalidDigest,
+    #[fail(display = "height is beyond the current ledger height.")]
+    InvalidHeight,
 }
 
 #[derive(Derivative)]
@@ -45,6 +56,13 @@ impl<H: FixedLengthCRH> ToBytes for Digest<H> {
     }
 }
 
+impl<H: FixedLengthCRH> FromBytes for Digest<H> {
+    #[inline]
+    fn read<R: Read>(reader: R) -> IoResult<Self> {
+        Ok(Digest(H::Output::read(reader)?))
+    }
+}
+
 impl<H: FixedLengthCRH> LedgerDigest for Digest<H> {
     type Parameters = H::Parameters;
 }
@@ -84,10 +102,15 @@ impl<H: FixedLengthCRH, L: ToBytes + Eq> LedgerWitness<Digest<H>> for CommPath<H
 pub struct IdealLedger<T: Transaction, H: FixedLengthCRH>
 where
     T::Commitment: ToBytes,
+    T::SerialNumber: ToBytes,
 {
     crh_params:     Rc<H::Parameters>,
     transactions:   Vec<T>,
+    // Index into `transactions` of the first transaction of each block.
+    block_starts:   Vec<usize>,
+    headers:        Vec<BlockHeader<H>>,
     cm_merkle_tree: MerkleHashTree<H, T::Commitment>,
+    sn_merkle_tree: MerkleHashTree<H, T::SerialNumber>,
     cur_cm_index:   usize,
     cur_sn_index:   usize,
     cur_memo_index: usize,
@@ -95,15 +118,124 @@ where
     sn_to_index:    HashMap<T::SerialNumber, usize>,
     memo_to_index:  HashMap<T::Memorandum, usize>,
     current_digest: Option<Digest<H>>,
-    past_digests:   HashSet<Digest<H>>,
+    // The last height at which each past digest was the ledger digest.
+    past_digests:   HashMap<Digest<H>, usize>,
+    max_digest_age: Option<usize>,
     genesis_cm:     T::Commitment,
     genesis_sn:     T::SerialNumber,
     genesis_memo:   T::Memorandum,
 }
 
+impl<T: Transaction, H: FixedLengthCRH> IdealLedger<T, H>
+where
+    T: Eq + ToBytes,
+    T::Commitment: ToBytes + Clone,
+    T::SerialNumber: ToBytes + Clone,
+    T::Memorandum: Hash + Clone,
+{
+    /// Makes `validate_digest` reject digests of ledger states more than
+    /// `max_digest_age` blocks old. By default, every past digest is valid.
+    pub fn set_max_digest_age(&mut self, max_digest_age: Option<usize>) {
+        self.max_digest_age = max_digest_age;
+    }
+
+    /// Returns the block headers, indexed by height.
+    pub fn headers(&self) -> &[BlockHeader<H>] {
+        &self.headers
+    }
+
+    /// Returns the transactions of the block at `height`, or `None` if
+    /// `height` is beyond the current ledger height.
+    pub fn block_transactions(&self, height: usize) -> Option<&[T]> {
+        let start = *self.block_starts.get(height)?;
+        let end = self
+            .block_starts
+            .get(height + 1)
+            .cloned()
+            .unwrap_or_else(|| self.transactions.len());
+        Some(&self.transactions[start..end])
+    }
+
+    /// Returns the last height at which the ledger had digest `digest`.
+    pub fn digest_height(&self, digest: &Digest<H>) -> Option<usize> {
+        self.past_digests.get(digest).cloned()
+    }
+
+    /// Removes the blocks appended after `height`, and returns their
+    /// transactions in the order they were appended.
+    pub fn rollback_to_height(&mut self, height: usize) -> Result<Vec<T>, Error> {
+        if height > self.height() {
+            Err(LedgerError::InvalidHeight)?;
+        }
+        let rollback_time = timer_start!(|| "IdealLedger::Rollback");
+
+        let removed = match self.block_starts.get(height + 1) {
+            Some(&start) => self.transactions.split_off(start),
+            None => Vec::new(),
+        };
+        for transaction in &removed {
+            for sn in transaction.old_serial_numbers() {
+                if sn != &self.genesis_sn {
+                    self.sn_to_index.remove(sn);
+                }
+            }
+            for cm in transaction.new_commitments() {
+                self.comm_to_index.remove(cm);
+            }
+            if transaction.memorandum() != &self.genesis_memo {
+                self.memo_to_index.remove(transaction.memorandum());
+            }
+        }
+        // Indices are assigned consecutively, so the remaining ones are the
+        // first ones.
+        self.cur_cm_index = self.comm_to_index.len();
+        self.cur_sn_index = self.sn_to_index.len();
+        self.cur_memo_index = self.memo_to_index.len();
+
+        let commitments = sorted_by_index(&self.comm_to_index);
+        self.cm_merkle_tree = MerkleHashTree::new(self.crh_params.clone(), &commitments)?;
+        let serial_numbers = sorted_by_index(&self.sn_to_index);
+        self.sn_merkle_tree = MerkleHashTree::new(self.crh_params.clone(), &serial_numbers)?;
+        self.block_starts.truncate(height + 1);
+        self.headers.truncate(height + 1);
+
+        self.past_digests.clear();
+        for (height, header) in self.headers.iter().enumerate() {
+            self.past_digests
+                .insert(Digest(header.cm_root.clone()), height);
+        }
+        self.current_digest = Some(Digest(self.headers[height].cm_root.clone()));
+
+        timer_end!(rollback_time);
+        Ok(removed)
+    }
+
+    fn push_header(&mut self, transactions_root: H::Output) -> Result<(), Error> {
+        let previous_hash = match self.headers.last() {
+            Some(header) => header.hash()?,
+            None => [0u8; 32],
+        };
+        let sn_root = self.sn_merkle_tree.root();
+        let cm_root = self.cm_merkle_tree.root();
+
+        let height = self.headers.len();
+        let digest = Digest(cm_root.clone());
+        self.past_digests.insert(digest.clone(), height);
+        self.current_digest = Some(digest);
+        self.headers.push(BlockHeader {
+            height: height as u64,
+            previous_hash,
+            cm_root,
+            sn_root,
+            transactions_root,
+        });
+        Ok(())
+    }
+}
+
 impl<T: Transaction, H: FixedLengthCRH> Ledger for IdealLedger<T, H>
 where
-    T: Eq,
+    T: Eq + ToBytes,
     T::Commitment: ToBytes + Clone,
     T::SerialNumber: ToBytes + Clone,
     T::Memorandum: Hash + Clone,
@@ -132,20 +264,22 @@ where
     ) -> Self {
         let params = Rc::new(parameters);
         let cm_merkle_tree = MerkleHashTree::new(params.clone(), &[genesis_cm.clone()]).unwrap();
+        let sn_merkle_tree = MerkleHashTree::new(params.clone(), &[]).unwrap();
 
         let mut cur_cm_index = 0;
         let mut comm_to_index = HashMap::new();
         comm_to_index.insert(genesis_cm.clone(), cur_cm_index);
         cur_cm_index += 1;
 
-        let root = Digest(cm_merkle_tree.root());
-        let mut past_digests = HashSet::new();
-        past_digests.insert(root.clone());
-
-        IdealLedger {
+        let empty_tree: MerkleHashTree<H, [u8; 32]> =
+            MerkleHashTree::new(params.clone(), &[]).unwrap();
+        let mut ledger = IdealLedger {
             crh_params: params,
             transactions: Vec::new(),
+            block_starts: vec![0],
+            headers: Vec::new(),
             cm_merkle_tree,
+            sn_merkle_tree,
             cur_cm_index,
             cur_sn_index: 0,
             cur_memo_index: 0,
@@ -153,51 +287,86 @@ where
             comm_to_index,
             sn_to_index: HashMap::new(),
             memo_to_index: HashMap::new(),
-            current_digest: Some(root),
-            past_digests,
+            current_digest: None,
+            past_digests: HashMap::new(),
+            max_digest_age: None,
             genesis_cm,
             genesis_sn,
             genesis_memo,
-        }
+        };
+        // The genesis block contains no transactions.
+        ledger.push_header(empty_tree.root()).unwrap();
+        ledger
     }
 
     fn len(&self) -> usize {
         self.transactions.len()
     }
 
+    fn height(&self) -> usize {
+        self.headers.len() - 1
+    }
+
+    fn transactions(&self) -> &[Self::Transaction] {
+        &self.transactions
+    }
+
     fn parameters(&self) -> &Self::Parameters {
         &self.crh_params
     }
 
     fn push(&mut self, transaction: Self::Transaction) -> Result<(), Error> {
-        let push_time = timer_start!(|| "IdealLedger::PushTx");
+        self.push_block(vec![transaction])
+    }
 
-        let mut cur_sn_index = self.cur_sn_index;
-        for sn in transaction.old_serial_numbers() {
-            if sn != &self.genesis_sn {
-                if self.sn_to_index.contains_key(sn) {
+    fn push_block(&mut self, transactions: Vec<Self::Transaction>) -> Result<(), Error> {
+        let push_time = timer_start!(|| "IdealLedger::PushBlock");
+
+        // Check the whole block first, so that a rejected block leaves the
+        // ledger unchanged.
+        let mut block_sns = HashSet::new();
+        let mut block_cms = HashSet::new();
+        let mut block_memos = HashSet::new();
+        for transaction in &transactions {
+            for sn in transaction.old_serial_numbers() {
+                if sn != &self.genesis_sn
+                    && (self.sn_to_index.contains_key(sn) || !block_sns.insert(sn))
+                {
                     Err(LedgerError::DuplicateSn)?;
                 }
-                self.sn_to_index.insert(sn.clone(), cur_sn_index);
-                cur_sn_index += 1;
             }
-        }
-        self.cur_sn_index = cur_sn_index;
-
-        let mut cur_cm_index = self.cur_cm_index;
-        for cm in transaction.new_commitments() {
-            if cm == &self.genesis_cm || self.comm_to_index.contains_key(cm) {
-                Err(LedgerError::InvalidCm)?;
+            for cm in transaction.new_commitments() {
+                if cm == &self.genesis_cm
+                    || self.comm_to_index.contains_key(cm)
+                    || !block_cms.insert(cm)
+                {
+                    Err(LedgerError::InvalidCm)?;
+                }
+            }
+            let memo = transaction.memorandum();
+            if memo != &self.genesis_memo
+                && (self.memo_to_index.contains_key(memo) || !block_memos.insert(memo))
+            {
+                Err(LedgerError::DuplicateMemo)?;
             }
-            self.comm_to_index.insert(cm.clone(), cur_cm_index);
-            cur_cm_index += 1;
         }
-        self.cur_cm_index = cur_cm_index;
 
-        if transaction.memorandum() != &self.genesis_memo {
-            if self.memo_to_index.contains_key(transaction.memorandum()) {
-                Err(LedgerError::DuplicateMemo)?;
-            } else {
+        let mut transaction_ids = Vec::with_capacity(transactions.len());
+        let mut new_serial_numbers = Vec::new();
+        for transaction in &transactions {
+            transaction_ids.push(transaction_id(transaction)?);
+            for sn in transaction.old_serial_numbers() {
+                if sn != &self.genesis_sn {
+                    self.sn_to_index.insert(sn.clone(), self.cur_sn_index);
+                    self.cur_sn_index += 1;
+                    new_serial_numbers.push(sn.clone());
+                }
+            }
+            for cm in transaction.new_commitments() {
+                self.comm_to_index.insert(cm.clone(), self.cur_cm_index);
+                self.cur_cm_index += 1;
+            }
+            if transaction.memorandum() != &self.genesis_memo {
                 self.memo_to_index
                     .insert(transaction.memorandum().clone(), self.cur_memo_index);
                 self.cur_memo_index += 1;
@@ -205,32 +374,38 @@ where
         }
 
         // Rebuild the tree.
-        let mut cm_and_indices = self.comm_to_index.iter().collect::<Vec<_>>();
-        cm_and_indices.sort_by(|&(_, i), &(_, j)| i.cmp(j));
-        let commitments = cm_and_indices
-            .into_iter()
-            .map(|(cm, _)| cm)
-            .cloned()
-            .collect::<Vec<_>>();
+        let commitments = sorted_by_index(&self.comm_to_index);
         assert!(commitments[0] == self.genesis_cm);
         self.cm_merkle_tree = MerkleHashTree::new(self.crh_params.clone(), &commitments)?;
+        self.sn_merkle_tree.append(&new_serial_numbers)?;
 
-        let new_digest = Digest(self.cm_merkle_tree.root());
-        self.past_digests.insert(new_digest.clone());
-        self.current_digest = Some(new_digest);
-
-        self.transactions.push(transaction);
+        let transactions_tree = MerkleHashTree::new(self.crh_params.clone(), &transaction_ids)?;
+        self.block_starts.push(self.transactions.len());
+        self.transactions.extend(transactions);
+        self.push_header(transactions_tree.root())?;
 
         timer_end!(push_time);
         Ok(())
     }
 
+    fn rollback(&mut self, digest: &Self::LedgerStateDigest) -> Result<Vec<T>, Error> {
+        let height = self
+            .digest_height(digest)
+            .ok_or(LedgerError::InvalidDigest)?;
+        self.rollback_to_height(height)
+    }
+
     fn digest(&self) -> Option<Self::LedgerStateDigest> {
         self.current_digest.clone()
     }
 
     fn validate_digest(&self, digest: &Self::LedgerStateDigest) -> bool {
-        self.past_digests.contains(digest)
+        match self.past_digests.get(digest) {
+            Some(height) => self
+                .max_digest_age
+                .map_or(true, |max_age| self.height() - height <= max_age),
+            None => false,
+        }
     }
 
     fn contains_cm(&self, cm: &Self::Commitment) -> bool {
@@ -294,3 +469,14 @@ where
         true
     }
 }
+
+/// Returns the keys of `map` ordered by their values.
+fn sorted_by_index<K: Clone>(map: &HashMap<K, usize>) -> Vec<K> {
+    let mut keys_and_indices = map.iter().collect::<Vec<_>>();
+    keys_and_indices.sort_by(|&(_, i), &(_, j)| i.cmp(j));
+    keys_and_indices
+        .into_iter()
+        .map(|(key, _)| key)
+        .cloned()
+        .collect()
+}
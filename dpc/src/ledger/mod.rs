@@ -1,12 +1,21 @@
 use crate::dpc::Transaction;
-use algebra::bytes::ToBytes;
+use algebra::bytes::{FromBytes, ToBytes};
 use failure::Error;
 use rand::Rng;
 
+pub mod block;
+pub use self::block::*;
+
 pub mod ideal_ledger;
 pub use self::ideal_ledger::*;
 
-pub trait LedgerDigest: Clone + ToBytes + Default + Eq {
+pub mod sparse_ledger;
+pub use self::sparse_ledger::*;
+
+#[cfg(test)]
+mod test;
+
+pub trait LedgerDigest: Clone + ToBytes + FromBytes + Default + Eq {
     type Parameters: Clone + Default;
 }
 pub trait LedgerWitness<D: LedgerDigest>: Clone + Default {
@@ -41,17 +50,37 @@ pub trait Ledger {
     /// Return the current number of transactions on the ledger.
     fn len(&self) -> usize;
 
+    /// Return the transactions on the ledger, in the order they were appended.
+    fn transactions(&self) -> &[Self::Transaction];
+
     /// Return the parameters used to construct the ledger data structure.
     fn parameters(&self) -> &Self::Parameters;
 
-    /// Append a (valid) transaction tx to the ledger.
+    /// Return the number of blocks appended to the ledger after the genesis
+    /// block.
+    fn height(&self) -> usize;
+
+    /// Append a (valid) transaction tx to the ledger, in a block of its own.
     fn push(&mut self, transaction: Self::Transaction) -> Result<(), Error>;
 
+    /// Append a block of (valid) transactions to the ledger. If any
+    /// transaction is rejected, the ledger is left unchanged.
+    fn push_block(&mut self, transactions: Vec<Self::Transaction>) -> Result<(), Error>;
+
+    /// Roll the ledger back to the most recent state with digest `digest`,
+    /// removing the blocks appended after it, and return their transactions
+    /// in the order they were appended.
+    fn rollback(
+        &mut self,
+        digest: &Self::LedgerStateDigest,
+    ) -> Result<Vec<Self::Transaction>, Error>;
+
     /// Return a short digest of the current state of the transaction set data
     /// structure.
     fn digest(&self) -> Option<Self::LedgerStateDigest>;
 
-    /// Check that st_{ts} is a valid digest for some (past) ledger state.
+    /// Check that st_{ts} is a valid digest for some (past) ledger state that
+    /// is recent enough to be spent against.
     fn validate_digest(&self, digest: &Self::LedgerStateDigest) -> bool;
 
     fn contains_cm(&self, cm: &Self::Commitment) -> bool;
@@ -0,0 +1,356 @@
+use failure::Error;
+use rand::Rng;
+use std::{
+    collections::HashMap,
+    fmt,
+    hash::Hash,
+    io::{Read, Result as IoResult, Write},
+    rc::Rc,
+};
+
+use crate::{
+    crypto_primitives::{FixedLengthCRH, SparseMerkleProof, SparseMerkleTree},
+    dpc::Transaction,
+    ledger::{
+        CommPath, Digest, IdealLedger, Ledger, LedgerDigest, LedgerError, LedgerWitness, MemoPath,
+    },
+};
+use algebra::bytes::{FromBytes, ToBytes};
+
+/// The digest of a `SparseLedger`: the root of the commitment tree and the
+/// root of the sparse Merkle tree of serial numbers.
+#[derive(Derivative)]
+#[derivative(
+    Default(bound = "H: FixedLengthCRH"),
+    Clone(bound = "H: FixedLengthCRH"),
+    PartialEq(bound = "H: FixedLengthCRH"),
+    Eq(bound = "H: FixedLengthCRH"),
+    Hash(bound = "H: FixedLengthCRH"),
+    Debug(bound = "H: FixedLengthCRH, H::Output: fmt::Debug")
+)]
+pub struct StateDigest<H: FixedLengthCRH> {
+    pub(crate) cm_root: H::Output,
+    pub(crate) sn_root: H::Output,
+}
+
+impl<H: FixedLengthCRH> ToBytes for StateDigest<H> {
+    #[inline]
+    fn write<W: Write>(&self, mut writer: W) -> IoResult<()> {
+        self.cm_root.write(&mut writer)?;
+        self.sn_root.write(&mut writer)
+    }
+}
+
+impl<H: FixedLengthCRH> FromBytes for StateDigest<H> {
+    #[inline]
+    fn read<R: Read>(mut reader: R) -> IoResult<Self> {
+        let cm_root = H::Output::read(&mut reader)?;
+        let sn_root = H::Output::read(&mut reader)?;
+        Ok(StateDigest { cm_root, sn_root })
+    }
+}
+
+impl<H: FixedLengthCRH> LedgerDigest for StateDigest<H> {
+    type Parameters = H::Parameters;
+}
+
+/// A proof that a serial number is, or is not, in the serial number tree.
+#[derive(Derivative)]
+#[derivative(
+    Default(bound = "H: FixedLengthCRH, L: ToBytes"),
+    Clone(bound = "H: FixedLengthCRH, L: ToBytes"),
+    Debug(bound = "H: FixedLengthCRH, L: ToBytes, H::Output: fmt::Debug")
+)]
+pub struct SnProof<H: FixedLengthCRH, L: ToBytes>(pub(crate) SparseMerkleProof<H, L>);
+
+impl<H: FixedLengthCRH, L: ToBytes> LedgerWitness<StateDigest<H>> for SnProof<H, L> {
+    fn dummy_witness() -> Self {
+        SnProof(SparseMerkleProof::default())
+    }
+}
+
+impl<H: FixedLengthCRH, L: ToBytes + Eq> LedgerWitness<StateDigest<H>> for CommPath<H, L> {
+    fn dummy_witness() -> Self {
+        <Self as LedgerWitness<Digest<H>>>::dummy_witness()
+    }
+}
+
+impl<H: FixedLengthCRH> LedgerWitness<StateDigest<H>> for MemoPath {
+    fn dummy_witness() -> Self {
+        MemoPath
+    }
+}
+
+/// An `IdealLedger` that also keeps its serial numbers in a sparse Merkle
+/// tree, so that `prove_sn` returns a succinct proof that a serial number is
+/// on the ledger, and `prove_sn_non_membership` one that it is not.
+pub struct SparseLedger<T: Transaction, H: FixedLengthCRH>
+where
+    T::Commitment: ToBytes,
+    T::SerialNumber: ToBytes,
+{
+    ledger:         IdealLedger<T, H>,
+    sn_tree:        SparseMerkleTree<H, T::SerialNumber>,
+    // The digest at each height.
+    digests:        Vec<StateDigest<H>>,
+    // The last height at which each past digest was the ledger digest.
+    past_digests:   HashMap<StateDigest<H>, usize>,
+    max_digest_age: Option<usize>,
+    genesis_sn:     T::SerialNumber,
+}
+
+impl<T: Transaction, H: FixedLengthCRH> SparseLedger<T, H>
+where
+    T: Eq + ToBytes,
+    T::Commitment: ToBytes + Clone,
+    T::SerialNumber: ToBytes + Clone,
+    T::Memorandum: Hash + Clone,
+{
+    /// Makes `validate_digest` reject digests of ledger states more than
+    /// `max_digest_age` blocks old. By default, every past digest is valid.
+    pub fn set_max_digest_age(&mut self, max_digest_age: Option<usize>) {
+        self.max_digest_age = max_digest_age;
+    }
+
+    /// Returns the underlying ledger.
+    pub fn ledger(&self) -> &IdealLedger<T, H> {
+        &self.ledger
+    }
+
+    /// Returns a proof that `sn` is not on the ledger.
+    pub fn prove_sn_non_membership(
+        &self,
+        sn: &T::SerialNumber,
+    ) -> Result<SnProof<H, T::SerialNumber>, Error> {
+        if self.sn_tree.contains(sn)? {
+            Err(LedgerError::DuplicateSn)?;
+        }
+        Ok(SnProof(self.sn_tree.generate_proof(sn)?))
+    }
+
+    /// Checks that `witness` proves that `sn` is not on the ledger with
+    /// digest `digest`.
+    pub fn verify_sn_non_membership(
+        params: &H::Parameters,
+        digest: &StateDigest<H>,
+        sn: &T::SerialNumber,
+        witness: &SnProof<H, T::SerialNumber>,
+    ) -> bool {
+        witness
+            .0
+            .verify_non_membership(params, &digest.sn_root, sn)
+            .unwrap_or(false)
+    }
+
+    fn record_digest(&mut self) {
+        let digest = self.current_digest();
+        self.past_digests
+            .insert(digest.clone(), self.ledger.height());
+        self.digests.push(digest);
+    }
+
+    fn current_digest(&self) -> StateDigest<H> {
+        StateDigest {
+            cm_root: self.ledger.digest().unwrap().0,
+            sn_root: self.sn_tree.root(),
+        }
+    }
+}
+
+impl<T: Transaction, H: FixedLengthCRH> Ledger for SparseLedger<T, H>
+where
+    T: Eq + ToBytes,
+    T::Commitment: ToBytes + Clone,
+    T::SerialNumber: ToBytes + Clone,
+    T::Memorandum: Hash + Clone,
+{
+    type Parameters = H::Parameters;
+    type LedgerStateDigest = StateDigest<H>;
+    type Commitment = T::Commitment;
+    type CommWitness = CommPath<H, T::Commitment>;
+
+    type SerialNumber = T::SerialNumber;
+    type SnWitness = SnProof<H, T::SerialNumber>;
+
+    type Memo = T::Memorandum;
+    type MemoWitness = MemoPath;
+    type Transaction = T;
+
+    fn setup<R: Rng>(rng: &mut R) -> Result<Self::Parameters, Error> {
+        H::setup(rng)
+    }
+
+    fn new(
+        parameters: Self::Parameters,
+        genesis_cm: Self::Commitment,
+        genesis_sn: Self::SerialNumber,
+        genesis_memo: Self::Memo,
+    ) -> Self {
+        let sn_tree = SparseMerkleTree::new(Rc::new(parameters.clone())).unwrap();
+        let ledger = IdealLedger::new(parameters, genesis_cm, genesis_sn.clone(), genesis_memo);
+        let mut ledger = SparseLedger {
+            ledger,
+            sn_tree,
+            digests: Vec::new(),
+            past_digests: HashMap::new(),
+            max_digest_age: None,
+            genesis_sn,
+        };
+        ledger.record_digest();
+        ledger
+    }
+
+    fn len(&self) -> usize {
+        self.ledger.len()
+    }
+
+    fn height(&self) -> usize {
+        self.ledger.height()
+    }
+
+    fn transactions(&self) -> &[Self::Transaction] {
+        self.ledger.transactions()
+    }
+
+    fn parameters(&self) -> &Self::Parameters {
+        self.ledger.parameters()
+    }
+
+    fn push(&mut self, transaction: Self::Transaction) -> Result<(), Error> {
+        self.push_block(vec![transaction])
+    }
+
+    fn push_block(&mut self, transactions: Vec<Self::Transaction>) -> Result<(), Error> {
+        // Two serial numbers can only both be in the tree if their positions
+        // differ, so check the new ones against the tree before pushing.
+        let mut serial_numbers = Vec::new();
+        let mut block_positions = HashMap::new();
+        for transaction in &transactions {
+            for sn in transaction.old_serial_numbers() {
+                if sn == &self.genesis_sn {
+                    continue;
+                }
+                if self.ledger.contains_sn(sn) {
+                    Err(LedgerError::DuplicateSn)?;
+                }
+                let position = self.sn_tree.position(sn)?;
+                if let Some(other) = block_positions.insert(position, sn) {
+                    if other == sn {
+                        Err(LedgerError::DuplicateSn)?;
+                    }
+                    Err(LedgerError::SnCollision)?;
+                }
+                if self.sn_tree.is_occupied(position) {
+                    Err(LedgerError::SnCollision)?;
+                }
+                serial_numbers.push(sn.clone());
+            }
+        }
+
+        self.ledger.push_block(transactions)?;
+        for sn in &serial_numbers {
+            self.sn_tree.insert(sn)?;
+        }
+        self.record_digest();
+        Ok(())
+    }
+
+    fn rollback(&mut self, digest: &Self::LedgerStateDigest) -> Result<Vec<T>, Error> {
+        let height = *self
+            .past_digests
+            .get(digest)
+            .ok_or(LedgerError::InvalidDigest)?;
+        let removed = self.ledger.rollback_to_height(height)?;
+        for transaction in &removed {
+            for sn in transaction.old_serial_numbers() {
+                if sn != &self.genesis_sn {
+                    self.sn_tree.remove(sn)?;
+                }
+            }
+        }
+
+        self.digests.truncate(height + 1);
+        self.past_digests = self
+            .digests
+            .iter()
+            .enumerate()
+            .map(|(height, digest)| (digest.clone(), height))
+            .collect();
+        Ok(removed)
+    }
+
+    fn digest(&self) -> Option<Self::LedgerStateDigest> {
+        Some(self.current_digest())
+    }
+
+    fn validate_digest(&self, digest: &Self::LedgerStateDigest) -> bool {
+        match self.past_digests.get(digest) {
+            Some(height) => self
+                .max_digest_age
+                .map_or(true, |max_age| self.height() - height <= max_age),
+            None => false,
+        }
+    }
+
+    fn contains_cm(&self, cm: &Self::Commitment) -> bool {
+        self.ledger.contains_cm(cm)
+    }
+
+    fn contains_sn(&self, sn: &Self::SerialNumber) -> bool {
+        self.ledger.contains_sn(sn)
+    }
+
+    fn contains_memo(&self, memo: &Self::Memo) -> bool {
+        self.ledger.contains_memo(memo)
+    }
+
+    fn prove_cm(&self, cm: &Self::Commitment) -> Result<Self::CommWitness, Error> {
+        self.ledger.prove_cm(cm)
+    }
+
+    fn prove_sn(&self, sn: &Self::SerialNumber) -> Result<Self::SnWitness, Error> {
+        let witness_time = timer_start!(|| "Generate sn membership witness");
+
+        if !self.sn_tree.contains(sn)? {
+            Err(LedgerError::InvalidSn)?;
+        }
+        let result = SnProof(self.sn_tree.generate_proof(sn)?);
+
+        timer_end!(witness_time);
+        Ok(result)
+    }
+
+    fn prove_memo(&self, _memo: &Self::Memo) -> Result<Self::MemoWitness, Error> {
+        Ok(MemoPath)
+    }
+
+    fn verify_cm(
+        params: &Self::Parameters,
+        digest: &Self::LedgerStateDigest,
+        cm: &Self::Commitment,
+        witness: &Self::CommWitness,
+    ) -> bool {
+        witness.0.verify(params, &digest.cm_root, cm).unwrap()
+    }
+
+    fn verify_sn(
+        params: &Self::Parameters,
+        digest: &Self::LedgerStateDigest,
+        sn: &Self::SerialNumber,
+        witness: &Self::SnWitness,
+    ) -> bool {
+        witness
+            .0
+            .verify_membership(params, &digest.sn_root, sn)
+            .unwrap_or(false)
+    }
+
+    fn verify_memo(
+        _params: &Self::Parameters,
+        _digest: &Self::LedgerStateDigest,
+        _memo: &Self::Memo,
+        _witness: &Self::MemoWitness,
+    ) -> bool {
+        true
+    }
+}
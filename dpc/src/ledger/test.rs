@@ -0,0 +1,253 @@
+use super::*;
+use crate::{
+    crypto_primitives::{
+        crh::pedersen::{PedersenCRH, PedersenWindow},
+        MerkleHashTree,
+    },
+    dpc::Transaction,
+};
+use algebra::{bytes::ToBytes, curves::jubjub::JubJubAffine as JubJub};
+use rand::{ChaChaRng, SeedableRng};
+use std::{
+    fmt,
+    io::{Result as IoResult, Write},
+    rc::Rc,
+};
+
+#[derive(Clone)]
+pub(super) struct Window4x256;
+impl PedersenWindow for Window4x256 {
+    const WINDOW_SIZE: usize = 4;
+    const NUM_WINDOWS: usize = 256;
+}
+
+type H = PedersenCRH<JubJub, Window4x256>;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct MockTransaction {
+    serial_numbers: Vec<[u8; 32]>,
+    commitments:    Vec<[u8; 32]>,
+    memo:           [u8; 32],
+}
+
+impl MockTransaction {
+    fn new(i: u8) -> Self {
+        Self {
+            serial_numbers: vec![[i; 32]],
+            commitments:    vec![[i; 32], [i + 100; 32]],
+            memo:           [i; 32],
+        }
+    }
+}
+
+impl Transaction for MockTransaction {
+    type SerialNumber = [u8; 32];
+    type Commitment = [u8; 32];
+    type Memorandum = [u8; 32];
+    type Stuff = ();
+
+    fn old_serial_numbers(&self) -> &[[u8; 32]] {
+        &self.serial_numbers
+    }
+
+    fn new_commitments(&self) -> &[[u8; 32]] {
+        &self.commitments
+    }
+
+    fn memorandum(&self) -> &[u8; 32] {
+        &self.memo
+    }
+
+    fn stuff(&self) -> &() {
+        &()
+    }
+}
+
+impl ToBytes for MockTransaction {
+    fn write<W: Write>(&self, mut writer: W) -> IoResult<()> {
+        self.serial_numbers.write(&mut writer)?;
+        self.commitments.write(&mut writer)?;
+        self.memo.write(&mut writer)
+    }
+}
+
+fn new_ledger<L: Ledger<Commitment = [u8; 32], SerialNumber = [u8; 32], Memo = [u8; 32]>>() -> L {
+    let seed: [u32; 8] = [
+        2053759276, 152413135, 1690980041, 4293109333, 2390175708, 686052238, 1844363894,
+        1379683288,
+    ];
+    let mut rng = ChaChaRng::from_seed(&seed);
+    let parameters = L::setup(&mut rng).unwrap();
+    L::new(parameters, [0u8; 32], [0u8; 32], [0u8; 32])
+}
+
+/// Pushes two blocks, rolls back the second one, switches to a fork of it and
+/// back, and checks that the ledger returns to the same states.
+fn rollback_and_repush<L>(mut ledger: L)
+where
+    L: Ledger<
+        Commitment = [u8; 32],
+        SerialNumber = [u8; 32],
+        Memo = [u8; 32],
+        Transaction = MockTransaction,
+    >,
+    L::LedgerStateDigest: fmt::Debug,
+{
+    let genesis_digest = ledger.digest().unwrap();
+    ledger.push(MockTransaction::new(1)).unwrap();
+    let digest = ledger.digest().unwrap();
+    let cm_witness = ledger.prove_cm(&[1; 32]).unwrap();
+
+    ledger
+        .push_block(vec![MockTransaction::new(2), MockTransaction::new(3)])
+        .unwrap();
+    let next_digest = ledger.digest().unwrap();
+    assert_eq!(ledger.height(), 2);
+
+    let removed = ledger.rollback(&digest).unwrap();
+    assert_eq!(
+        removed,
+        vec![MockTransaction::new(2), MockTransaction::new(3)]
+    );
+    assert_eq!(ledger.height(), 1);
+    assert_eq!(ledger.len(), 1);
+    assert_eq!(ledger.digest().unwrap(), digest);
+    assert!(ledger.validate_digest(&digest));
+    assert!(!ledger.validate_digest(&next_digest));
+    assert!(ledger.contains_sn(&[1; 32]) && ledger.contains_cm(&[101; 32]));
+    assert!(!ledger.contains_sn(&[2; 32]) && !ledger.contains_cm(&[102; 32]));
+    assert!(!ledger.contains_memo(&[3; 32]));
+    assert!(ledger.prove_cm(&[2; 32]).is_err());
+    let params = ledger.parameters().clone();
+    assert!(L::verify_cm(&params, &digest, &[1; 32], &cm_witness));
+
+    // Switch to a fork of the second block, and back again.
+    ledger.push(MockTransaction::new(4)).unwrap();
+    let fork_digest = ledger.digest().unwrap();
+    assert!(ledger.rollback(&next_digest).is_err());
+    ledger.rollback(&digest).unwrap();
+    assert!(!ledger.validate_digest(&fork_digest));
+    ledger
+        .push_block(vec![MockTransaction::new(2), MockTransaction::new(3)])
+        .unwrap();
+    assert_eq!(ledger.digest().unwrap(), next_digest);
+    assert!(ledger.contains_sn(&[3; 32]));
+
+    ledger.rollback(&genesis_digest).unwrap();
+    assert_eq!(ledger.height(), 0);
+    assert!(ledger.transactions().is_empty());
+    assert_eq!(ledger.digest().unwrap(), genesis_digest);
+    ledger.push(MockTransaction::new(1)).unwrap();
+    assert_eq!(ledger.digest().unwrap(), digest);
+}
+
+#[test]
+fn ideal_ledger_rollback_test() {
+    rollback_and_repush(new_ledger::<IdealLedger<MockTransaction, H>>());
+}
+
+#[test]
+fn ideal_ledger_sn_root_test() {
+    let mut ledger = new_ledger::<IdealLedger<MockTransaction, H>>();
+    let params = Rc::new(ledger.parameters().clone());
+    let sn_root = |serial_numbers: &[[u8; 32]]| {
+        MerkleHashTree::<H, [u8; 32]>::new(params.clone(), serial_numbers)
+            .unwrap()
+            .root()
+    };
+
+    // The serial-number tree is extended block by block, across a rollback.
+    ledger.push(MockTransaction::new(1)).unwrap();
+    let digest = ledger.digest().unwrap();
+    ledger
+        .push_block(vec![MockTransaction::new(2), MockTransaction::new(3)])
+        .unwrap();
+    assert_eq!(
+        ledger.headers()[2].sn_root,
+        sn_root(&[[1; 32], [2; 32], [3; 32]])
+    );
+    ledger.rollback(&digest).unwrap();
+    assert_eq!(ledger.headers()[1].sn_root, sn_root(&[[1; 32]]));
+    ledger.push(MockTransaction::new(4)).unwrap();
+    assert_eq!(ledger.headers()[2].sn_root, sn_root(&[[1; 32], [4; 32]]));
+    assert!(ledger.block_transactions(2).unwrap() == &[MockTransaction::new(4)]);
+    assert!(ledger.block_transactions(3).is_none());
+}
+
+#[test]
+fn sparse_ledger_membership_test() {
+    type L = SparseLedger<MockTransaction, H>;
+
+    let mut ledger = new_ledger::<L>();
+    let genesis_digest = ledger.digest().unwrap();
+    ledger
+        .push_block(vec![MockTransaction::new(1), MockTransaction::new(2)])
+        .unwrap();
+    let digest = ledger.digest().unwrap();
+    let params = ledger.parameters().clone();
+
+    // Serial numbers on the ledger have membership proofs and no
+    // non-membership proofs.
+    for sn in &[[1u8; 32], [2; 32]] {
+        let witness = ledger.prove_sn(sn).unwrap();
+        assert!(L::verify_sn(&params, &digest, sn, &witness));
+        assert!(!L::verify_sn_non_membership(&params, &digest, sn, &witness));
+        assert!(ledger.prove_sn_non_membership(sn).is_err());
+    }
+
+    // A membership proof only holds for its own serial number and digest.
+    let witness = ledger.prove_sn(&[1; 32]).unwrap();
+    assert!(!L::verify_sn(&params, &digest, &[2; 32], &witness));
+    assert!(!L::verify_sn(&params, &genesis_digest, &[1; 32], &witness));
+
+    // Serial numbers not on the ledger have non-membership proofs only.
+    for sn in &[[3u8; 32], [101; 32]] {
+        assert!(ledger.prove_sn(sn).is_err());
+        let witness = ledger.prove_sn_non_membership(sn).unwrap();
+        assert!(L::verify_sn_non_membership(&params, &digest, sn, &witness));
+        assert!(!L::verify_sn(&params, &digest, sn, &witness));
+    }
+
+    // A non-membership proof goes stale once the serial number is spent.
+    let witness = ledger.prove_sn_non_membership(&[3; 32]).unwrap();
+    ledger.push(MockTransaction::new(3)).unwrap();
+    let next_digest = ledger.digest().unwrap();
+    assert!(!L::verify_sn_non_membership(
+        &params,
+        &next_digest,
+        &[3; 32],
+        &witness
+    ));
+    let witness = ledger.prove_sn(&[3; 32]).unwrap();
+    assert!(L::verify_sn(&params, &next_digest, &[3; 32], &witness));
+}
+
+#[test]
+fn sparse_ledger_rollback_test() {
+    let mut ledger = new_ledger::<SparseLedger<MockTransaction, H>>();
+    ledger.push(MockTransaction::new(1)).unwrap();
+    let digest = ledger.digest().unwrap();
+    ledger.push(MockTransaction::new(2)).unwrap();
+    let sn_witness = ledger.prove_sn(&[2; 32]).unwrap();
+    let params = ledger.parameters().clone();
+    assert!(SparseLedger::<MockTransaction, H>::verify_sn(
+        &params,
+        &ledger.digest().unwrap(),
+        &[2; 32],
+        &sn_witness
+    ));
+
+    ledger.rollback(&digest).unwrap();
+    assert!(ledger.prove_sn(&[2; 32]).is_err());
+    let non_membership = ledger.prove_sn_non_membership(&[2; 32]).unwrap();
+    assert!(
+        SparseLedger::<MockTransaction, H>::verify_sn_non_membership(
+            &params,
+            &digest,
+            &[2; 32],
+            &non_membership
+        )
+    );
+
+    rollback_and_repush(new_ledger::<SparseLedger<MockTransaction, H>>());
+}
@@ -1 +1 @@
-
+pub mod plain_dpc;
@@ -0,0 +1,181 @@
+use algebra::{
+    bytes::{FromBytes, ToBytes},
+    to_bytes,
+};
+use failure::Error;
+use std::{
+    io::{Read, Result as IoResult, Write},
+    marker::PhantomData,
+};
+
+use crate::{
+    crypto_primitives::PRF,
+    dpc::{
+        plain_dpc::{
+            payload::decode_payload, predicate::DPCPredicate, LocalData, PlainDPCComponents,
+        },
+        Predicate, Record,
+    },
+};
+
+pub mod predicate_circuit;
+
+#[cfg(test)]
+mod test;
+
+/// Size in bytes of the asset id stored in the payload of an asset record.
+pub const ASSET_ID_SIZE: usize = 24;
+
+/// Size in bytes of the encoding of an `AssetPayload`.
+pub const ASSET_PAYLOAD_SIZE: usize = ASSET_ID_SIZE + 8;
+
+/// Payload of a record of a fungible asset. It is encoded in the first
+/// `ASSET_PAYLOAD_SIZE` bytes of the payload: the asset id, followed by the
+/// value of the record as a little-endian `u64`. The rest of the payload is
+/// left to the application.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct AssetPayload {
+    pub asset_id: [u8; ASSET_ID_SIZE],
+    pub value:    u64,
+}
+
+impl AssetPayload {
+    pub fn new(asset_id: [u8; ASSET_ID_SIZE], value: u64) -> Self {
+        Self { asset_id, value }
+    }
+}
+
+impl ToBytes for AssetPayload {
+    fn write<W: Write>(&self, mut writer: W) -> IoResult<()> {
+        self.asset_id.write(&mut writer)?;
+        self.value.write(&mut writer)
+    }
+}
+
+impl FromBytes for AssetPayload {
+    fn read<R: Read>(mut reader: R) -> IoResult<Self> {
+        let asset_id = FromBytes::read(&mut reader)?;
+        let value = FromBytes::read(&mut reader)?;
+        Ok(Self { asset_id, value })
+    }
+}
+
+/// Returns the id of the asset minted by a transaction whose first input
+/// record has serial number `sn`. Serial numbers never repeat on the ledger,
+/// so neither do the ids of minted assets.
+pub fn mint_asset_id<C: PlainDPCComponents>(
+    sn: &<C::P as PRF>::Output,
+) -> Result<[u8; ASSET_ID_SIZE], Error> {
+    let sn_bytes = to_bytes![sn]?;
+    let mut asset_id = [0u8; ASSET_ID_SIZE];
+    asset_id.copy_from_slice(&sn_bytes[..ASSET_ID_SIZE]);
+    Ok(asset_id)
+}
+
+/// Returns the id of the asset handled by the transaction described by
+/// `local_data`: the asset id of its first non-dummy record or, if every
+/// record is dummy, the id of the asset it would mint.
+pub fn transaction_asset_id<C: PlainDPCComponents>(
+    local_data: &LocalData<C>,
+) -> Result<[u8; ASSET_ID_SIZE], Error> {
+    let first_non_dummy = local_data
+        .old_records
+        .iter()
+        .chain(&local_data.new_records)
+        .find(|record| !record.is_dummy());
+    match first_non_dummy {
+        Some(record) => Ok(decode_payload::<C, AssetPayload>(record.payload())?.asset_id),
+        None => mint_asset_id::<C>(&local_data.old_serial_numbers[0]),
+    }
+}
+
+/// Checks the rules of the fungible asset predicate, whose compact
+/// representation is `predicate`, on the transaction described by
+/// `local_data`. Every non-dummy record of the transaction must have been
+/// born under `predicate` and carry the same asset id, and
+/// * if every input record is dummy, the transaction mints new value of the
+///   asset whose id is given by `mint_asset_id`;
+/// * if every output record is dummy, the transaction burns the value of its
+///   input records;
+/// * otherwise, the transaction transfers value, and the values of its input
+///   and output records must sum to the same amount.
+pub fn check_conservation<C: PlainDPCComponents>(
+    predicate: &[u8],
+    local_data: &LocalData<C>,
+) -> Result<bool, Error> {
+    let all_born_under_predicate = local_data
+        .old_records
+        .iter()
+        .chain(&local_data.new_records)
+        .filter(|record| !record.is_dummy())
+        .all(|record| record.birth_predicate_repr() == predicate);
+    if !all_born_under_predicate {
+        return Ok(false);
+    }
+
+    let asset_id = transaction_asset_id(local_data)?;
+
+    let old_payloads = local_data
+        .old_records
+        .iter()
+        .filter(|record| !record.is_dummy())
+        .map(|record| decode_payload::<C, AssetPayload>(record.payload()))
+        .collect::<Result<Vec<_>, _>>()?;
+    let new_payloads = local_data
+        .new_records
+        .iter()
+        .filter(|record| !record.is_dummy())
+        .map(|record| decode_payload::<C, AssetPayload>(record.payload()))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let all_share_asset_id = old_payloads
+        .iter()
+        .chain(&new_payloads)
+        .all(|payload| payload.asset_id == asset_id);
+    if !all_share_asset_id {
+        return Ok(false);
+    }
+
+    let is_mint = old_payloads.is_empty();
+    let is_burn = new_payloads.is_empty();
+    if is_mint {
+        Ok(asset_id == mint_asset_id::<C>(&local_data.old_serial_numbers[0])?)
+    } else if is_burn {
+        Ok(true)
+    } else {
+        let old_value: u128 = old_payloads.iter().map(|p| u128::from(p.value)).sum();
+        let new_value: u128 = new_payloads.iter().map(|p| u128::from(p.value)).sum();
+        Ok(old_value == new_value)
+    }
+}
+
+/// Native counterpart of `ConserveCircuit`, identified by the predicate that
+/// was registered for that circuit.
+#[derive(Derivative)]
+#[derivative(Clone(bound = "C: PlainDPCComponents"))]
+pub struct ConservePredicate<C: PlainDPCComponents> {
+    identity:    Vec<u8>,
+    _components: PhantomData<C>,
+}
+
+impl<C: PlainDPCComponents> ConservePredicate<C> {
+    pub fn new(predicate: &DPCPredicate<C>) -> Self {
+        Self {
+            identity:    predicate.into_compact_repr(),
+            _components: PhantomData,
+        }
+    }
+}
+
+impl<C: PlainDPCComponents> Predicate for ConservePredicate<C> {
+    type PublicInput = LocalData<C>;
+    type PrivateWitness = ();
+
+    fn evaluate(&self, local_data: &Self::PublicInput, _w: &Self::PrivateWitness) -> bool {
+        check_conservation(&self.identity, local_data).unwrap_or(false)
+    }
+
+    fn into_compact_repr(&self) -> Vec<u8> {
+        self.identity.clone()
+    }
+}
@@ -1,140 +1,475 @@
-use crate::plain_dpc::*;
-use crate::dpc::Record;
-use crate::common::ToEngineFr;
-use algebra::bytes::ToBytes;
-use std::io::{Write, Result as IoResult};
-use crate::crypto_primitives::{CommitmentScheme, PRF};
-use crate::dpc::plain_dpc::DPCRecord;
-use snark_gadgets::{utils::AllocGadget, uint8::UInt8};
-use crate::gadgets::Assignment;
-
-
-use algebra::PairingEngine;
-
-use snark::{
-    Circuit,
-    ConstraintSystem,
-    SynthesisError
+use algebra::{Field, FpParameters, PairingEngine, PrimeField};
+use snark::{Circuit, ConstraintSystem, LinearCombination, SynthesisError, Variable};
+use snark_gadgets::{
+    boolean::Boolean,
+    uint8::UInt8,
+    utils::{AllocGadget, EqGadget, ToBytesGadget},
 };
 
-use failure::Error;
+use crate::{
+    crypto_primitives::{CommitmentScheme, PRF},
+    dpc::{
+        plain_dpc::{
+            parameters::CommAndCRHPublicParameters,
+            predicate_circuit::{alloc_predicate_local_data, PredicateCircuit},
+            record::DPCRecord,
+            LocalData, PlainDPCComponents,
+        },
+        Arity, Record,
+    },
+    gadgets::{Assignment, CommitmentGadget, PRFGadget},
+    predicates::plain_dpc::{transaction_asset_id, ASSET_ID_SIZE, ASSET_PAYLOAD_SIZE},
+};
 
+/// Predicate circuit for a fungible asset. It opens the local data commitment
+/// of the transaction and enforces the rules described in
+/// `check_conservation`: all non-dummy records were born under this predicate
+/// and carry the same asset id, a transaction without non-dummy inputs may
+/// only mint the asset derived from its first serial number, and a
+/// transaction with both non-dummy inputs and non-dummy outputs must conserve
+/// value.
+///
+/// Every non-dummy record in a transaction that invokes this predicate is
+/// treated as a record of the asset. The circuit reads every record of the
+/// transaction, so a predicate set up with `ConserveCircuit` only guards
+/// records spent and created in transactions of the arity it was set up for.
+/// Records must have payloads of at least `ASSET_PAYLOAD_SIZE` bytes, and
+/// synthesis fails with `SynthesisError::Unsatisfiable` otherwise.
 pub struct ConserveCircuit<C: PlainDPCComponents> {
     // Parameters
     comm_and_crh_parameters: Option<CommAndCRHPublicParameters<C>>,
 
-    // Commitment to Predicate input.
-    local_data_comm:         Option<<C::LocalDataComm as CommitmentScheme>::Output>,
-    position:                u8,
-}
+    // Local data
+    old_records:        Option<Vec<DPCRecord<C>>>,
+    old_serial_numbers: Option<Vec<<C::P as PRF>::Output>>,
+    new_records:        Option<Vec<DPCRecord<C>>>,
+    memo:               Option<Vec<u8>>,
+    auxiliary:          Option<[u8; 32]>,
+
+    // Commitment to the local data.
+    local_data_comm: Option<<C::LocalDataComm as CommitmentScheme>::Output>,
+    local_data_rand: Option<<C::LocalDataComm as CommitmentScheme>::Randomness>,
+    position:        u8,
+
+    // Whether the circuit is checked as the birth predicate of the output
+    // record at `position`, rather than as the death predicate of the input
+    // record there.
+    is_birth_predicate: Option<bool>,
 
-impl<C: PlainDPCComponents> EmptyPredicateCircuit<C> {
-    pub fn blank(
-        comm_and_crh_parameters: &CommAndCRHPublicParameters<C>,
-    ) -> Self {
-        let local_data_comm = <C::LocalDataComm as CommitmentScheme>::Output::default();
+    // Asset id shared by all non-dummy records.
+    asset_id: Option<[u8; ASSET_ID_SIZE]>,
+}
 
+impl<C: PlainDPCComponents> PredicateCircuit<C> for ConserveCircuit<C> {
+    fn blank(comm_and_crh_parameters: &CommAndCRHPublicParameters<C>, arity: Arity) -> Self {
+        let num_input_records = arity.num_input_records;
+        let num_output_records = arity.num_output_records;
         Self {
             comm_and_crh_parameters: Some(comm_and_crh_parameters.clone()),
-            local_data_comm:         Some(local_data_comm),
-            position:                0u8,
+
+            old_records:        Some(vec![DPCRecord::default(); num_input_records]),
+            old_serial_numbers: Some(vec![<C::P as PRF>::Output::default(); num_input_records]),
+            new_records:        Some(vec![DPCRecord::default(); num_output_records]),
+            memo:               Some(vec![0u8; C::MEMO_SIZE]),
+            auxiliary:          Some([0u8; 32]),
+
+            local_data_comm: Some(<C::LocalDataComm as CommitmentScheme>::Output::default()),
+            local_data_rand: Some(<C::LocalDataComm as CommitmentScheme>::Randomness::default()),
+            position:        0u8,
+
+            is_birth_predicate: Some(num_input_records == 0),
+
+            asset_id: Some([0u8; ASSET_ID_SIZE]),
         }
     }
 
-    pub fn new(
-        comm_amd_crh_parameters: &CommAndCRHPublicParameters<C>,
-        local_data_comm:         &<C::LocalDataComm as CommitmentScheme>::Output,
-        position:                u8
-    ) -> Self {
-
+    fn from_local_data(local_data: &LocalData<C>, position: u8) -> Self {
         Self {
-            // Parameters
-            comm_and_crh_parameters:   Some(comm_amd_crh_parameters.clone()),
+            comm_and_crh_parameters: Some(local_data.comm_and_crh_pp.clone()),
 
-            // Other stuff
-            local_data_comm:           Some(local_data_comm.clone()),
+            old_records:        Some(local_data.old_records.clone()),
+            old_serial_numbers: Some(local_data.old_serial_numbers.clone()),
+            new_records:        Some(local_data.new_records.clone()),
+            memo:               Some(local_data.memo.clone()),
+            auxiliary:          Some(local_data.auxiliary),
+
+            local_data_comm: Some(local_data.local_data_comm.clone()),
+            local_data_rand: Some(local_data.local_data_rand.clone()),
             position,
+
+            is_birth_predicate: Some(is_birth_predicate(local_data, position)),
+
+            asset_id: transaction_asset_id(local_data).ok(),
         }
     }
 }
 
-impl<C: PlainDPCComponents> Circuit<C::E> for EmptyPredicateCircuit<C> {
-    fn synthesize<CS: ConstraintSystem<C::E>>(
-        self,
-        cs: &mut CS
-    ) -> Result<(), SynthesisError> {
-        let _position = UInt8::alloc_input_vec(
-            cs.ns(|| "Alloc position"),
-            &[self.position]
-        )?;
-
-        {
-            let mut cs = cs.ns(|| "Declare public parameters");
-            let _local_data_comm_pp = <C::LocalDataCommGadget as CommitmentGadget<_, _>>::ParametersGadget::alloc_input_from_value(
-                &mut cs.ns(|| "Declare Pred Input Comm parameters"),
-                || self.comm_and_crh_parameters.get().map(|pp| &pp.local_data_comm_pp),
-            )?;
+impl<C: PlainDPCComponents> Circuit<C::E> for ConserveCircuit<C> {
+    fn synthesize<CS: ConstraintSystem<C::E>>(self, cs: &mut CS) -> Result<(), SynthesisError> {
+        if C::PAYLOAD_SIZE < ASSET_PAYLOAD_SIZE {
+            return Err(SynthesisError::Unsatisfiable);
         }
-        
 
-        let _local_data_comm = <C::LocalDataCommGadget as CommitmentGadget<_, _>>::OutputGadget::alloc_from_value(
-            cs.ns(|| "Allocate predicate commitment"),
-            || self.local_data_comm.get()
+        let (position, local_data_comm_pp, declared_local_data_comm) = alloc_predicate_local_data(
+            cs.ns(|| "Declare predicate local data"),
+            self.comm_and_crh_parameters.as_ref(),
+            self.local_data_comm.as_ref(),
+            self.position,
         )?;
 
-        {
-            let mut cs = cs.ns(|| "Check that local data commitment is valid.");
-
-            let mut local_data_bytes = Vec::new();
-            for i in 0..C::NUM_INPUT_RECORDS {
-                let mut cs = cs.ns(|| format!("Construct local data with Input Record {}", i));
-                local_data_bytes.extend_from_slice(&old_rec_comms[i].to_bytes(&mut cs.ns(|| "Record Comm"))?);
-                local_data_bytes.extend_from_slice(&old_apks[i].to_bytes(&mut cs.ns(|| "Apk"))?);
-                local_data_bytes.extend_from_slice(&old_dummy_flags[i].to_bytes(&mut cs.ns(|| "IsDummy"))?);
-                local_data_bytes.extend_from_slice(&old_payloads[i]);
-                local_data_bytes.extend_from_slice(&old_birth_pred_hashes[i]);
-                local_data_bytes.extend_from_slice(&old_death_pred_hashes[i]);
-                local_data_bytes.extend_from_slice(&old_sns[i].to_bytes(&mut cs.ns(|| "Sn"))?);
-            }
+        let old_records = self.old_records.get()?;
+        let old_serial_numbers = self.old_serial_numbers.get()?;
+        let new_records = self.new_records.get()?;
 
-            for j in 0..C::NUM_OUTPUT_RECORDS {
-                let mut cs = cs.ns(|| format!("Construct local data with Output Record {}", j));
-                local_data_bytes.extend_from_slice(&new_rec_comms[j].to_bytes(&mut cs.ns(|| "Record Comm"))?);
-                local_data_bytes.extend_from_slice(&new_apks[j].to_bytes(&mut cs.ns(|| "Apk"))?);
-                local_data_bytes.extend_from_slice(&new_dummy_flags[j].to_bytes(&mut cs.ns(|| "IsDummy"))?);
-                local_data_bytes.extend_from_slice(&new_payloads[j]);
-                local_data_bytes.extend_from_slice(&new_birth_pred_hashes[j]);
-                local_data_bytes.extend_from_slice(&new_death_pred_hashes[j]);
+        // Declare the local data, and check that it opens the local data
+        // commitment.
+        let mut local_data_bytes = Vec::new();
+        let mut old_dummy_flags = Vec::new();
+        let mut old_payloads = Vec::new();
+        let mut old_birth_predicates = Vec::new();
+        let mut old_death_predicates = Vec::new();
+        let mut mint_asset_id = Vec::new();
+        for (i, record) in old_records.iter().enumerate() {
+            let mut cs = cs.ns(|| format!("Declare input record {}", i));
+            let (is_dummy, payload, birth_predicate, death_predicate) =
+                declare_record(cs.ns(|| "Declare record"), record, &mut local_data_bytes)?;
+
+            let sn = <C::PGadget as PRFGadget<_, _>>::OutputGadget::alloc(
+                &mut cs.ns(|| "Declare serial number"),
+                || Ok(&old_serial_numbers[i]),
+            )?;
+            let sn_bytes = sn.to_bytes(&mut cs.ns(|| "Convert serial number to bytes"))?;
+            if i == 0 {
+                mint_asset_id.extend_from_slice(&sn_bytes[..ASSET_ID_SIZE]);
             }
-            let memo = UInt8::alloc_input_vec(cs.ns(|| "Allocate memorandum"), memo)?;
-            local_data_bytes.extend_from_slice(&memo);
+            local_data_bytes.extend_from_slice(&sn_bytes);
 
-            let auxiliary = UInt8::alloc_vec(cs.ns(|| "Allocate auxiliary input"), auxiliary)?;
-            local_data_bytes.extend_from_slice(&auxiliary);
+            old_dummy_flags.push(is_dummy);
+            old_payloads.push(payload);
+            old_birth_predicates.push(birth_predicate);
+            old_death_predicates.push(death_predicate);
+        }
 
-            let local_data_comm_rand = <C::LocalDataCommGadget as CommitmentGadget<_, _>>::RandomnessGadget::alloc_from_value(
-                cs.ns(|| "Allocate local data commitment randomness"),
-                || Ok(local_data_rand)
+        let mut new_dummy_flags = Vec::new();
+        let mut new_payloads = Vec::new();
+        let mut new_birth_predicates = Vec::new();
+        for (j, record) in new_records.iter().enumerate() {
+            let (is_dummy, payload, birth_predicate, _) = declare_record(
+                cs.ns(|| format!("Declare output record {}", j)),
+                record,
+                &mut local_data_bytes,
             )?;
+            new_dummy_flags.push(is_dummy);
+            new_payloads.push(payload);
+            new_birth_predicates.push(birth_predicate);
+        }
+
+        let memo = UInt8::alloc_vec(cs.ns(|| "Allocate memorandum"), self.memo.get()?)?;
+        local_data_bytes.extend_from_slice(&memo);
 
-            let declared_local_data_comm = <C::LocalDataCommGadget as CommitmentGadget<_, _>>::OutputGadget::alloc_input_from_value(
-                cs.ns(|| "Allocate local data commitment"),
-                || self.local_data_comm.get()
+        let auxiliary =
+            UInt8::alloc_vec(cs.ns(|| "Allocate auxiliary input"), self.auxiliary.get()?)?;
+        local_data_bytes.extend_from_slice(&auxiliary);
+
+        let local_data_rand =
+            <C::LocalDataCommGadget as CommitmentGadget<_, _>>::RandomnessGadget::alloc(
+                cs.ns(|| "Allocate local data commitment randomness"),
+                || self.local_data_rand.get(),
             )?;
 
-            let comm = C::LocalDataCommGadget::check_commitment_gadget(
-                cs.ns(|| "Commit to local data"),
-                &local_data_comm_pp,
-                &local_data_bytes,
-                &local_data_comm_rand,
+        let local_data_comm = C::LocalDataCommGadget::check_commitment_gadget(
+            cs.ns(|| "Commit to local data"),
+            &local_data_comm_pp,
+            &local_data_bytes,
+            &local_data_rand,
+        )?;
+
+        local_data_comm.enforce_equal(
+            &mut cs.ns(|| "Check that local data commitment is valid"),
+            &declared_local_data_comm,
+        )?;
+
+        // Check the asset rules.
+        let one = <C::E as PairingEngine>::Fr::one();
+        let asset_id = cs.alloc(
+            || "Allocate asset id",
+            || Ok(pack_bytes(self.asset_id.get()?)),
+        )?;
+
+        let mut old_value = LinearCombination::<C::E>::zero();
+        let mut new_value = LinearCombination::<C::E>::zero();
+        let records = old_dummy_flags
+            .iter()
+            .zip(&old_payloads)
+            .map(|record| (true, record))
+            .chain(
+                new_dummy_flags
+                    .iter()
+                    .zip(&new_payloads)
+                    .map(|record| (false, record)),
+            );
+        for (i, (is_old, (is_dummy, payload))) in records.enumerate() {
+            let mut cs = cs.ns(|| format!("Check record {}", i));
+            let is_real = is_dummy.not().lc::<C::E>(CS::one(), one);
+
+            // A non-dummy record must carry the asset id of the transaction.
+            let record_asset_id = pack_bytes_lc::<C::E>(CS::one(), &payload[..ASSET_ID_SIZE]);
+            cs.enforce(
+                || "Check asset id",
+                |lc| lc + &record_asset_id - asset_id,
+                |lc| lc + &is_real,
+                |lc| lc,
+            );
+
+            // Only non-dummy records contribute to the value of the transaction.
+            let value =
+                pack_bytes_lc::<C::E>(CS::one(), &payload[ASSET_ID_SIZE..ASSET_PAYLOAD_SIZE]);
+            let real_value = cs.alloc(
+                || "Allocate value of non-dummy record",
+                || {
+                    let is_dummy = is_dummy
+                        .get_value()
+                        .ok_or(SynthesisError::AssignmentMissing)?;
+                    let value = payload[ASSET_ID_SIZE..ASSET_PAYLOAD_SIZE]
+                        .iter()
+                        .map(|byte| byte.get_value())
+                        .collect::<Option<Vec<_>>>()
+                        .ok_or(SynthesisError::AssignmentMissing)?;
+                    if is_dummy {
+                        Ok(<C::E as PairingEngine>::Fr::zero())
+                    } else {
+                        Ok(pack_bytes(&value))
+                    }
+                },
             )?;
+            cs.enforce(
+                || "Mask value of dummy record",
+                |lc| lc + &value,
+                |lc| lc + &is_real,
+                |lc| lc + real_value,
+            );
+            if is_old {
+                old_value = old_value + real_value;
+            } else {
+                new_value = new_value + real_value;
+            }
+        }
+
+        let is_mint = Boolean::kary_and(cs.ns(|| "Check if mint"), &old_dummy_flags)?;
+        let is_burn = Boolean::kary_and(cs.ns(|| "Check if burn"), &new_dummy_flags)?;
+        let is_transfer = Boolean::and(
+            cs.ns(|| "Check if transfer"),
+            &is_mint.not(),
+            &is_burn.not(),
+        )?;
+
+        // A mint must create the asset derived from the first serial number.
+        let mint_asset_id = pack_bytes_lc::<C::E>(CS::one(), &mint_asset_id);
+        cs.enforce(
+            || "Check minted asset id",
+            |lc| lc + &mint_asset_id - asset_id,
+            |lc| lc + &is_mint.lc::<C::E>(CS::one(), one),
+            |lc| lc,
+        );
 
-            comm.enforce_equal(
-                &mut cs.ns(|| "Check that local data commitment is valid"),
-                &declared_local_data_comm,
+        // A transfer must conserve value.
+        cs.enforce(
+            || "Check conservation of value",
+            |lc| lc + &old_value - &new_value,
+            |lc| lc + &is_transfer.lc::<C::E>(CS::one(), one),
+            |lc| lc,
+        );
+
+        // Every non-dummy record must have been born under this predicate, or
+        // value could be brought in from records created under another one.
+        // The circuit cannot hash its own verification key, so it reads the
+        // hash from the local data: this predicate is the death predicate of
+        // the input record at `position`, or the birth predicate of the output
+        // record there, as declared by the prover. Declaring the wrong one only
+        // matters if that record is dummy, as non-dummy records must share
+        // their birth predicate.
+        let is_birth_predicate = Boolean::alloc(cs.ns(|| "Allocate is birth predicate"), || {
+            self.is_birth_predicate.get()
+        })?;
+        let position_bits = position[0].into_bits_le();
+        let mut candidates = Vec::new();
+        for k in 0..old_records.len().max(new_records.len()) {
+            let mut cs = cs.ns(|| format!("Select predicate at position {}", k));
+            let matches_k = position_bits
+                .iter()
+                .zip(UInt8::constant(k as u8).into_bits_le())
+                .map(|(bit, k_bit)| match k_bit {
+                    Boolean::Constant(true) => *bit,
+                    _ => bit.not(),
+                })
+                .collect::<Vec<_>>();
+            let is_at_k = Boolean::kary_and(cs.ns(|| "Check position"), &matches_k)?;
+            if k < old_records.len() {
+                let selector = Boolean::and(
+                    cs.ns(|| "Select death predicate"),
+                    &is_at_k,
+                    &is_birth_predicate.not(),
+                )?;
+                candidates.push((selector, &old_death_predicates[k]));
+            }
+            if k < new_records.len() {
+                let selector = Boolean::and(
+                    cs.ns(|| "Select birth predicate"),
+                    &is_at_k,
+                    &is_birth_predicate,
+                )?;
+                candidates.push((selector, &new_birth_predicates[k]));
+            }
+        }
+        cs.enforce(
+            || "Check that the position holds a record",
+            |lc| {
+                candidates.iter().fold(lc, |lc, (selector, _)| {
+                    lc + &selector.lc::<C::E>(CS::one(), one)
+                })
+            },
+            |lc| lc + CS::one(),
+            |lc| lc + CS::one(),
+        );
+
+        let own_predicate = candidates
+            .iter()
+            .find(|(selector, _)| selector.get_value() == Some(true))
+            .and_then(|(_, predicate)| {
+                predicate
+                    .iter()
+                    .map(|byte| byte.get_value())
+                    .collect::<Option<Vec<_>>>()
+            });
+        let birth_predicates = old_dummy_flags
+            .iter()
+            .zip(&old_birth_predicates)
+            .chain(new_dummy_flags.iter().zip(&new_birth_predicates));
+        let chunk_size =
+            (<<C::E as PairingEngine>::Fr as PrimeField>::Params::CAPACITY / 8) as usize;
+        let predicate_size = old_death_predicates
+            .first()
+            .or_else(|| new_birth_predicates.first())
+            .map_or(0, |predicate| predicate.len());
+        for (c, start) in (0..predicate_size).step_by(chunk_size).enumerate() {
+            let mut cs = cs.ns(|| format!("Check predicate chunk {}", c));
+            let end = (start + chunk_size).min(predicate_size);
+            let own_chunk = cs.alloc(
+                || "Allocate predicate chunk",
+                || {
+                    let own_predicate = own_predicate
+                        .as_ref()
+                        .ok_or(SynthesisError::AssignmentMissing)?;
+                    Ok(pack_bytes(&own_predicate[start..end]))
+                },
             )?;
+            for (t, (selector, predicate)) in candidates.iter().enumerate() {
+                let chunk = pack_bytes_lc::<C::E>(CS::one(), &predicate[start..end]);
+                cs.enforce(
+                    || format!("Check candidate {}", t),
+                    |lc| lc + &chunk - own_chunk,
+                    |lc| lc + &selector.lc::<C::E>(CS::one(), one),
+                    |lc| lc,
+                );
+            }
+            for (i, (is_dummy, birth_predicate)) in birth_predicates.clone().enumerate() {
+                let chunk = pack_bytes_lc::<C::E>(CS::one(), &birth_predicate[start..end]);
+                cs.enforce(
+                    || format!("Check birth predicate of record {}", i),
+                    |lc| lc + &chunk - own_chunk,
+                    |lc| lc + &is_dummy.not().lc::<C::E>(CS::one(), one),
+                    |lc| lc,
+                );
+            }
         }
 
         Ok(())
     }
 }
+
+// Returns whether the predicate checked at `position` is the birth predicate
+// of the output record there, rather than the death predicate of the input
+// record there. Honest records of the asset share their birth predicate, so
+// the output record is chosen if its birth predicate is theirs.
+fn is_birth_predicate<C: PlainDPCComponents>(local_data: &LocalData<C>, position: u8) -> bool {
+    let position = position as usize;
+    let asset_predicate = local_data
+        .old_records
+        .iter()
+        .chain(&local_data.new_records)
+        .find(|record| !record.is_dummy())
+        .map(|record| record.birth_predicate_repr());
+    match local_data.new_records.get(position) {
+        Some(record) => {
+            position >= local_data.old_records.len()
+                || asset_predicate
+                    .map_or(true, |predicate| predicate == record.birth_predicate_repr())
+        },
+        None => false,
+    }
+}
+
+// Declares the fields of `record` that go into the local data, appends their
+// byte representation to `local_data_bytes`, and returns the dummy flag,
+// payload, birth predicate and death predicate of the record.
+fn declare_record<C: PlainDPCComponents, CS: ConstraintSystem<C::E>>(
+    mut cs: CS,
+    record: &DPCRecord<C>,
+    local_data_bytes: &mut Vec<UInt8>,
+) -> Result<(Boolean, Vec<UInt8>, Vec<UInt8>, Vec<UInt8>), SynthesisError> {
+    let commitment = <C::RecCGadget as CommitmentGadget<_, _>>::OutputGadget::alloc(
+        &mut cs.ns(|| "Commitment"),
+        || Ok(record.commitment()),
+    )?;
+    let apk = <C::AddrCGadget as CommitmentGadget<_, _>>::OutputGadget::alloc(
+        &mut cs.ns(|| "Addr PubKey"),
+        || Ok(&record.address_public_key().public_key),
+    )?;
+    let is_dummy = Boolean::alloc(&mut cs.ns(|| "is_dummy"), || Ok(record.is_dummy()))?;
+    let payload = UInt8::alloc_vec(&mut cs.ns(|| "Payload"), record.payload())?;
+    let birth_pred_hash = UInt8::alloc_vec(
+        &mut cs.ns(|| "Birth predicate"),
+        &record.birth_predicate_repr(),
+    )?;
+    let death_pred_hash = UInt8::alloc_vec(
+        &mut cs.ns(|| "Death predicate"),
+        &record.death_predicate_repr(),
+    )?;
+
+    local_data_bytes.extend_from_slice(&commitment.to_bytes(&mut cs.ns(|| "Record Comm"))?);
+    local_data_bytes.extend_from_slice(&apk.to_bytes(&mut cs.ns(|| "Apk"))?);
+    local_data_bytes.extend_from_slice(&is_dummy.to_bytes(&mut cs.ns(|| "IsDummy"))?);
+    local_data_bytes.extend_from_slice(&payload);
+    local_data_bytes.extend_from_slice(&birth_pred_hash);
+    local_data_bytes.extend_from_slice(&death_pred_hash);
+
+    Ok((is_dummy, payload, birth_pred_hash, death_pred_hash))
+}
+
+// Interprets `bytes` as a little-endian integer, and returns it as a field
+// element.
+fn pack_bytes<F: Field>(bytes: &[u8]) -> F {
+    let mut result = F::zero();
+    let mut coeff = F::one();
+    for byte in bytes {
+        for i in 0..8 {
+            if (byte >> i) & 1 == 1 {
+                result += &coeff;
+            }
+            coeff.double_in_place();
+        }
+    }
+    result
+}
+
+// Returns the linear combination of the bits of `bytes` that equals the
+// little-endian integer they represent.
+fn pack_bytes_lc<E: PairingEngine>(one: Variable, bytes: &[UInt8]) -> LinearCombination<E> {
+    let mut lc = LinearCombination::<E>::zero();
+    let mut coeff = E::Fr::one();
+    for bit in bytes.iter().flat_map(|byte| byte.into_bits_le()) {
+        lc = lc + bit.lc::<E>(one, coeff);
+        coeff.double_in_place();
+    }
+    lc
+}
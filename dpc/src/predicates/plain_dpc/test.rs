@@ -0,0 +1,207 @@
+use algebra::curves::bls12_377::Bls12_377;
+use rand::{SeedableRng, XorShiftRng};
+use snark::{Circuit, ConstraintSystem};
+use snark_gadgets::test_constraint_system::TestConstraintSystem;
+
+use crate::{
+    crypto_primitives::FixedLengthCRH,
+    dpc::{
+        plain_dpc::{
+            address::AddressPair,
+            instantiated::*,
+            payload::{decode_payload, encode_payload},
+            predicate::DPCPredicate,
+            predicate_circuit::PredicateCircuit,
+            record::DPCRecord,
+            CommAndCRHPublicParameters, DPC,
+        },
+        Arity, Predicate as _, Record,
+    },
+    ledger::Ledger,
+    predicates::plain_dpc::{
+        check_conservation, mint_asset_id, predicate_circuit::ConserveCircuit, AssetPayload,
+        ASSET_ID_SIZE, ASSET_PAYLOAD_SIZE,
+    },
+};
+
+#[test]
+fn asset_payload_serialization_test() {
+    let payload = AssetPayload::new([7u8; ASSET_ID_SIZE], 0x0102_0304_0506_0708);
+    let bytes = encode_payload::<Components, _>(&payload).unwrap();
+    assert_eq!(bytes.len(), PAYLOAD_SIZE);
+    assert_eq!(&bytes[..ASSET_ID_SIZE], &[7u8; ASSET_ID_SIZE]);
+    assert_eq!(
+        &bytes[ASSET_ID_SIZE..ASSET_PAYLOAD_SIZE],
+        &[0x08, 0x07, 0x06, 0x05, 0x04, 0x03, 0x02, 0x01]
+    );
+    assert!(bytes[ASSET_PAYLOAD_SIZE..].iter().all(|byte| *byte == 0));
+    assert_eq!(
+        decode_payload::<Components, AssetPayload>(&bytes).unwrap(),
+        payload
+    );
+}
+
+// Checks that `ConserveCircuit` and `check_conservation` agree on whether a
+// transaction consuming `old_records` of `address` and creating records with
+// `new_payloads` follows the asset rules.
+fn check_transaction(
+    comm_and_crh_pp: &CommAndCRHPublicParameters<Components>,
+    ledger: &MerkleTreeIdealLedger,
+    address: &AddressPair<Components>,
+    predicate: &Predicate,
+    old_records: &[DPCRecord<Components>],
+    new_payloads: &[Vec<u8>],
+    expected: bool,
+    rng: &mut XorShiftRng,
+) {
+    let old_asks = vec![address.secret_key.clone(); NUM_INPUT_RECORDS];
+
+    let context = DPC::execute_helper(
+        comm_and_crh_pp,
+        old_records,
+        &old_asks,
+        &vec![address.public_key.clone(); NUM_OUTPUT_RECORDS],
+        &vec![false; NUM_OUTPUT_RECORDS],
+        new_payloads,
+        &vec![predicate.clone(); NUM_OUTPUT_RECORDS],
+        &vec![predicate.clone(); NUM_OUTPUT_RECORDS],
+        &[0u8; MEMO_SIZE],
+        &[0u8; 32],
+        ledger,
+        rng,
+    )
+    .unwrap();
+    let local_data = context.into_local_data();
+    assert_eq!(
+        check_conservation(&predicate.into_compact_repr(), &local_data).unwrap(),
+        expected
+    );
+
+    let mut blank_cs = TestConstraintSystem::<Bls12_377>::new();
+    let arity = Arity::new(NUM_INPUT_RECORDS, NUM_OUTPUT_RECORDS);
+    ConserveCircuit::blank(comm_and_crh_pp, arity)
+        .synthesize(&mut blank_cs.ns(|| "Blank conserve circuit"))
+        .unwrap();
+
+    let mut cs = TestConstraintSystem::<Bls12_377>::new();
+    ConserveCircuit::from_local_data(&local_data, 0)
+        .synthesize(&mut cs.ns(|| "Conserve circuit"))
+        .unwrap();
+    if expected && !cs.is_satisfied() {
+        println!(
+            "Unsatisfied constraint: {}",
+            cs.which_is_unsatisfied().unwrap()
+        );
+    }
+    assert_eq!(cs.is_satisfied(), expected);
+    assert_eq!(cs.num_constraints(), blank_cs.num_constraints());
+}
+
+#[test]
+fn conserve_circuit_test() {
+    let mut rng = XorShiftRng::from_seed([0x5dbe6259, 0x8d313d76, 0x3237db17, 0xe5bc0654]);
+    let ledger_parameters = MerkleTreeIdealLedger::setup(&mut rng).expect("Ledger setup failed");
+    let comm_and_crh_pp = InstantiatedDPC::generate_comm_and_crh_parameters(&mut rng).unwrap();
+    let pred_nizk_pp =
+        InstantiatedDPC::generate_pred_nizk_parameters(&comm_and_crh_pp, &mut rng).unwrap();
+    let predicate =
+        DPCPredicate::from_verification_key(&comm_and_crh_pp.pred_vk_crh_pp, &pred_nizk_pp.vk)
+            .unwrap();
+
+    let address = DPC::create_address_helper(&comm_and_crh_pp, &[1u8; 32], &mut rng).unwrap();
+    let asset_id = [3u8; ASSET_ID_SIZE];
+    let asset_payload =
+        |value| encode_payload::<Components, _>(&AssetPayload::new(asset_id, value)).unwrap();
+
+    // The first input record holds 100 units of the asset, and the others are
+    // dummy.
+    let mut old_records = vec![];
+    for i in 0..NUM_INPUT_RECORDS {
+        let sn_nonce =
+            SnNonceCRH::evaluate(&comm_and_crh_pp.sn_nonce_crh_pp, &[i as u8; 1]).unwrap();
+        let record = DPC::generate_record(
+            &comm_and_crh_pp,
+            &sn_nonce,
+            &address.public_key,
+            i != 0,
+            &asset_payload(100),
+            &predicate,
+            &predicate,
+            &mut rng,
+        )
+        .unwrap();
+        old_records.push(record);
+    }
+    let sn = DPC::generate_sn(&old_records[0], &address.secret_key).unwrap();
+
+    // Store the non-dummy input record on the ledger.
+    let ledger = MerkleTreeIdealLedger::new(
+        ledger_parameters,
+        old_records[0].commitment(),
+        sn.clone(),
+        vec![0u8; MEMO_SIZE],
+    );
+
+    // Transfers must conserve value.
+    let valid_transfer = [asset_payload(60), asset_payload(40)];
+    let invalid_transfer = [asset_payload(60), asset_payload(50)];
+    let other_asset = [
+        asset_payload(60),
+        encode_payload::<Components, _>(&AssetPayload::new([4u8; 24], 40)).unwrap(),
+    ];
+    for (new_payloads, expected) in &[
+        (valid_transfer, true),
+        (invalid_transfer, false),
+        (other_asset, false),
+    ] {
+        check_transaction(
+            &comm_and_crh_pp,
+            &ledger,
+            &address,
+            &predicate,
+            &old_records,
+            new_payloads,
+            *expected,
+            &mut rng,
+        );
+    }
+
+    // Mints may create any amount of a fresh asset, but not of an existing one.
+    let sn_nonce = SnNonceCRH::evaluate(
+        &comm_and_crh_pp.sn_nonce_crh_pp,
+        &[NUM_INPUT_RECORDS as u8; 1],
+    )
+    .unwrap();
+    let mut dummy_records = old_records.clone();
+    dummy_records[0] = DPC::generate_record(
+        &comm_and_crh_pp,
+        &sn_nonce,
+        &address.public_key,
+        true,
+        &asset_payload(100),
+        &predicate,
+        &predicate,
+        &mut rng,
+    )
+    .unwrap();
+    let minted_asset_id = mint_asset_id::<Components>(
+        &DPC::generate_sn(&dummy_records[0], &address.secret_key).unwrap(),
+    )
+    .unwrap();
+    let minted_payload =
+        encode_payload::<Components, _>(&AssetPayload::new(minted_asset_id, 1000)).unwrap();
+    let valid_mint = vec![minted_payload; 2];
+    let invalid_mint = vec![asset_payload(1000); 2];
+    for (new_payloads, expected) in &[(valid_mint, true), (invalid_mint, false)] {
+        check_transaction(
+            &comm_and_crh_pp,
+            &ledger,
+            &address,
+            &predicate,
+            &dummy_records,
+            new_payloads,
+            *expected,
+            &mut rng,
+        );
+    }
+}
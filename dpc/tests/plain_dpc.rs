@@ -1,19 +1,42 @@
-use algebra::{to_bytes, ToBytes};
+use algebra::{fields::edwards_bls12::fq::Fq as EdwardsBlsFq, to_bytes, Field, ToBytes};
 use rand::{SeedableRng, XorShiftRng};
 #[cfg(debug_assertions)]
 use snark::gm17::PreparedVerifyingKey;
+use std::io::{Error as IoError, ErrorKind};
 
 use dpc::crypto_primitives::{nizk::NIZK, FixedLengthCRH};
 
 use dpc::{
     plain_dpc::{
-        instantiated::*, predicate::PrivatePredInput, predicate_circuit::*, LocalData, DPC,
+        address::{AddressPublicKey, AddressSecretKey, AddressViewingKey},
+        instantiated::*,
+        mempool::{Mempool, MempoolError},
+        parameters::{PublicParameters, VerifierParameters},
+        payload::{decode_payload, encode_payload},
+        predicate::{PrivatePredInput, RegisteredPredicate},
+        predicate_circuit::*,
+        record::DPCRecord,
+        wallet::Wallet,
+        LocalData, DPC,
     },
-    DPCScheme, Record,
+    predicates::plain_dpc::{mint_asset_id, AssetPayload, ConservePredicate},
+    serialization::{from_bytes, SERIALIZATION_VERSION},
+    Arity, DPCScheme, Predicate as _, Record, Transaction as _,
 };
 
 use dpc::ledger::Ledger;
 
+/// Returns whether decoding failed because the input is malformed, rather
+/// than truncated.
+fn is_invalid_data<T>(result: Result<T, failure::Error>) -> bool {
+    match result {
+        Ok(_) => false,
+        Err(error) => error
+            .downcast_ref::<IoError>()
+            .map_or(false, |error| error.kind() == ErrorKind::InvalidData),
+    }
+}
+
 #[test]
 fn integration_test() {
     let mut rng = XorShiftRng::from_seed([0x5dbe6259, 0x8d313d76, 0x3237db17, 0xe5bc0654]);
@@ -24,6 +47,33 @@ fn integration_test() {
         <InstantiatedDPC as DPCScheme<MerkleTreeIdealLedger>>::setup(&ledger_parameters, &mut rng)
             .expect("DPC setup failed");
 
+    // Store the parameters and continue with the ones loaded back.
+    let mut parameters_bytes = vec![];
+    parameters.store(&mut parameters_bytes).unwrap();
+    let parameters = PublicParameters::<Components>::load(&parameters_bytes[..]).unwrap();
+    let mut reloaded_parameters_bytes = vec![];
+    parameters.store(&mut reloaded_parameters_bytes).unwrap();
+    assert!(reloaded_parameters_bytes == parameters_bytes);
+    let mut wrong_version_bytes = parameters_bytes.clone();
+    wrong_version_bytes[0] = SERIALIZATION_VERSION + 1;
+    assert!(PublicParameters::<Components>::load(&wrong_version_bytes[..]).is_err());
+    assert!(
+        PublicParameters::<Components>::load(&parameters_bytes[..parameters_bytes.len() - 1])
+            .is_err()
+    );
+
+    // Corrupting a proving key is detected, but verifiers never read it, and
+    // the rest of this test verifies transactions with the parameters they
+    // load.
+    let mut corrupted_parameters_bytes = parameters_bytes.clone();
+    let corrupted_index = corrupted_parameters_bytes.len() - 33;
+    corrupted_parameters_bytes[corrupted_index] ^= 1;
+    assert!(PublicParameters::<Components>::load(&corrupted_parameters_bytes[..]).is_err());
+    let verifier_parameters =
+        VerifierParameters::<Components>::load(&corrupted_parameters_bytes[..]).unwrap();
+    assert_eq!(verifier_parameters.arity_vk.len(), ARITIES.len());
+    assert!(verifier_parameters.pred_nizk_vk == parameters.pred_nizk_pp.vk);
+
     #[cfg(debug_assertions)]
     let pred_nizk_pvk: PreparedVerifyingKey<_> = parameters.pred_nizk_pp.vk.clone().into();
     // Generate metadata and an address for a dummy initial, or "genesis", record.
@@ -44,7 +94,7 @@ fn integration_test() {
         &genesis_sn_nonce,
         &genesis_address.public_key,
         true, // The inital record should be dummy
-        &[2u8; 32],
+        &[2u8; PAYLOAD_SIZE],
         &Predicate::new(genesis_pred_vk_bytes.clone()),
         &Predicate::new(genesis_pred_vk_bytes.clone()),
         &mut rng,
@@ -53,7 +103,7 @@ fn integration_test() {
 
     // Generate serial number for the genesis record.
     let genesis_sn = DPC::generate_sn(&genesis_record, &genesis_address.secret_key).unwrap();
-    let genesis_memo = [1u8; 32];
+    let genesis_memo = vec![1u8; MEMO_SIZE];
 
     // Use genesis record, serial number, and memo to initialize the ledger.
     let mut ledger = MerkleTreeIdealLedger::new(
@@ -77,7 +127,7 @@ fn integration_test() {
             &old_sn_nonce,
             &genesis_address.public_key,
             true, // The input record is dummy
-            &[2u8; 32],
+            &[2u8; PAYLOAD_SIZE],
             &Predicate::new(genesis_pred_vk_bytes.clone()),
             &Predicate::new(genesis_pred_vk_bytes.clone()),
             &mut rng,
@@ -94,7 +144,7 @@ fn integration_test() {
         DPC::create_address_helper(&parameters.comm_and_crh_pp, &new_metadata, &mut rng).unwrap();
 
     // Create a payload.
-    let new_payload = [2u8; 32];
+    let new_payload = vec![2u8; PAYLOAD_SIZE];
     // Set the new records' predicate to be the "always-accept" predicate.
     let new_predicate = Predicate::new(genesis_pred_vk_bytes.clone());
 
@@ -105,7 +155,7 @@ fn integration_test() {
     let new_dummy_flags = vec![false; NUM_OUTPUT_RECORDS];
 
     let auxiliary = [3u8; 32];
-    let memo = [4u8; 32];
+    let memo = vec![4u8; MEMO_SIZE];
 
     let old_death_vk_and_proof_generator = |local_data: &LocalData<Components>| {
         let mut rng = XorShiftRng::from_seed([0x5dbe6259, 0x8d313d76, 0x3237db17, 0xe5bc0654]);
@@ -176,7 +226,7 @@ fn integration_test() {
         }
         new_proof_and_vk
     };
-    let (_new_records, transaction) = InstantiatedDPC::execute(
+    let (new_records, transaction) = InstantiatedDPC::execute(
         &parameters,
         &old_records,
         &old_asks,
@@ -194,8 +244,820 @@ fn integration_test() {
     )
     .unwrap();
 
-    assert!(InstantiatedDPC::verify(&parameters, &transaction, &ledger).unwrap());
+    assert!(InstantiatedDPC::verify(&verifier_parameters, &transaction, &ledger).unwrap());
+
+    // The core proof commits to the ciphertexts, so re-encrypting a record
+    // invalidates the transaction.
+    let mut encrypted_records = transaction.encrypted_records().to_vec();
+    encrypted_records[0] =
+        InstantiatedDPC::encrypt_record(&parameters.comm_and_crh_pp, &new_records[0], &mut rng)
+            .unwrap();
+    let reencrypted_transaction = Tx::new(
+        transaction.old_serial_numbers().to_vec(),
+        transaction.new_commitments().to_vec(),
+        transaction.memorandum().clone(),
+        encrypted_records,
+        transaction.stuff.digest.clone(),
+        transaction.stuff.core_proof.clone(),
+        transaction.stuff.predicate_proof.clone(),
+        transaction.stuff.predicate_comm.clone(),
+        transaction.stuff.local_data_comm.clone(),
+    );
+    assert!(
+        !InstantiatedDPC::verify(&verifier_parameters, &reencrypted_transaction, &ledger).unwrap()
+    );
+
+    // Transactions, records and addresses round-trip through their encodings.
+    let transaction_bytes = to_bytes![transaction].unwrap();
+    let decoded_transaction: Tx = from_bytes(&transaction_bytes).unwrap();
+    assert_eq!(to_bytes![decoded_transaction].unwrap(), transaction_bytes);
+    assert!(InstantiatedDPC::verify(&verifier_parameters, &decoded_transaction, &ledger).unwrap());
+
+    let record_bytes = to_bytes![new_records[0]].unwrap();
+    let decoded_record: DPCRecord<Components> = from_bytes(&record_bytes).unwrap();
+    assert_eq!(decoded_record.commitment(), new_records[0].commitment());
+    assert_eq!(to_bytes![decoded_record].unwrap(), record_bytes);
+
+    let public_key_bytes = to_bytes![new_address.public_key].unwrap();
+    let decoded_public_key: AddressPublicKey<Components> = from_bytes(&public_key_bytes).unwrap();
+    assert_eq!(to_bytes![decoded_public_key].unwrap(), public_key_bytes);
+    let secret_key_bytes = to_bytes![new_address.secret_key].unwrap();
+    let decoded_secret_key: AddressSecretKey<Components> = from_bytes(&secret_key_bytes).unwrap();
+    assert_eq!(to_bytes![decoded_secret_key].unwrap(), secret_key_bytes);
+    let viewing_key_bytes = to_bytes![new_address.viewing_key()].unwrap();
+    let decoded_viewing_key: AddressViewingKey<Components> =
+        from_bytes(&viewing_key_bytes).unwrap();
+    assert_eq!(to_bytes![decoded_viewing_key].unwrap(), viewing_key_bytes);
+
+    // Malformed encodings are rejected.
+    assert!(from_bytes::<Tx>(&transaction_bytes[..transaction_bytes.len() - 1]).is_err());
+    let mut trailing_bytes = transaction_bytes.clone();
+    trailing_bytes.push(0u8);
+    assert!(is_invalid_data(from_bytes::<Tx>(&trailing_bytes)));
+    let mut wrong_version = transaction_bytes.clone();
+    wrong_version[0] = SERIALIZATION_VERSION + 1;
+    assert!(is_invalid_data(from_bytes::<Tx>(&wrong_version)));
+    // The first byte after the version is the low byte of the number of serial
+    // numbers; no supported arity spends 0 records.
+    let mut wrong_arity = transaction_bytes.clone();
+    wrong_arity[1] = 0;
+    assert!(is_invalid_data(from_bytes::<Tx>(&wrong_arity)));
+    // The transaction ends with the local data commitment, a field element;
+    // all-ones bytes exceed the modulus.
+    let mut non_canonical = transaction_bytes.clone();
+    let len = non_canonical.len();
+    for byte in &mut non_canonical[len - 32..] {
+        *byte = 0xff;
+    }
+    assert!(is_invalid_data(from_bytes::<Tx>(&non_canonical)));
+
+    // An address public key ends with the affine encryption key; (1, 1) is
+    // not on the curve.
+    let (one, zero) = (EdwardsBlsFq::one(), EdwardsBlsFq::zero());
+    let mut off_curve = public_key_bytes.clone();
+    let len = off_curve.len();
+    off_curve[len - 64..].copy_from_slice(&to_bytes![one, one].unwrap());
+    let decoded: Result<AddressPublicKey<Components>, _> = from_bytes(&off_curve);
+    assert!(is_invalid_data(decoded));
+    // (0, -1) is on the curve, but has order two.
+    let mut small_order = public_key_bytes.clone();
+    small_order[len - 64..].copy_from_slice(&to_bytes![zero, -one].unwrap());
+    let decoded: Result<AddressPublicKey<Components>, _> = from_bytes(&small_order);
+    assert!(is_invalid_data(decoded));
+    // Viewing keys are versioned like the other address types.
+    let mut wrong_version = viewing_key_bytes.clone();
+    wrong_version[0] = SERIALIZATION_VERSION + 1;
+    let decoded: Result<AddressViewingKey<Components>, _> = from_bytes(&wrong_version);
+    assert!(is_invalid_data(decoded));
+
+    ledger.push(transaction).unwrap();
+    assert_eq!(ledger.len(), 1);
+}
+
+#[test]
+fn fungible_asset_integration_test() {
+    let mut rng = XorShiftRng::from_seed([0x5dbe6259, 0x8d313d76, 0x3237db17, 0xe5bc0654]);
+    let ledger_parameters = MerkleTreeIdealLedger::setup(&mut rng).expect("Ledger setup failed");
+    let parameters =
+        <InstantiatedDPC as DPCScheme<MerkleTreeIdealLedger>>::setup(&ledger_parameters, &mut rng)
+            .expect("DPC setup failed");
+    let verifier_parameters = parameters.verifier_parameters();
+    let comm_and_crh_pp = &parameters.comm_and_crh_pp;
+
+    // Dummy records are guarded by the "always-accept" predicate, and asset
+    // records by the conserve predicate.
+    let empty_predicate: RegisteredPredicate<Components, PredicateNIZK<Components>> =
+        RegisteredPredicate {
+            predicate: Predicate::from_verification_key(
+                &comm_and_crh_pp.pred_vk_crh_pp,
+                &parameters.pred_nizk_pp.vk,
+            )
+            .unwrap(),
+            pk:        parameters.pred_nizk_pp.pk.clone(),
+            vk:        parameters.pred_nizk_pp.vk.clone(),
+        };
+    // The conserve predicate is set up for, and every transaction has, the
+    // largest arity.
+    let arity = Arity::new(NUM_INPUT_RECORDS, NUM_OUTPUT_RECORDS);
+    let conserve_predicate =
+        RegisteredPredicate::<Components, ConservePredicateNIZK<Components>>::setup(
+            comm_and_crh_pp,
+            arity,
+            &mut rng,
+        )
+        .expect("Conserve predicate setup failed");
+    let conserve = ConservePredicate::new(&conserve_predicate.predicate);
+
+    let genesis_address =
+        DPC::create_address_helper(comm_and_crh_pp, &[1u8; 32], &mut rng).unwrap();
+    let genesis_sn_nonce =
+        SnNonceCRH::evaluate(&comm_and_crh_pp.sn_nonce_crh_pp, &[34u8; 1]).unwrap();
+    let genesis_record = DPC::generate_record(
+        comm_and_crh_pp,
+        &genesis_sn_nonce,
+        &genesis_address.public_key,
+        true,
+        &[2u8; PAYLOAD_SIZE],
+        &empty_predicate.predicate,
+        &empty_predicate.predicate,
+        &mut rng,
+    )
+    .unwrap();
+    let genesis_sn = DPC::generate_sn(&genesis_record, &genesis_address.secret_key).unwrap();
+    let mut ledger = MerkleTreeIdealLedger::new(
+        ledger_parameters,
+        genesis_record.commitment(),
+        genesis_sn,
+        vec![1u8; MEMO_SIZE],
+    );
+
+    let alice = DPC::create_address_helper(comm_and_crh_pp, &[2u8; 32], &mut rng).unwrap();
+    let bob = DPC::create_address_helper(comm_and_crh_pp, &[3u8; 32], &mut rng).unwrap();
+    let alice_asks = vec![alice.secret_key.clone(); NUM_INPUT_RECORDS];
+    let asset_predicates = vec![conserve_predicate.predicate.clone(); NUM_OUTPUT_RECORDS];
+
+    // Generators of predicate proofs for dummy input records, and for input
+    // and output asset records.
+    let dummy_death_proofs = |local_data: &LocalData<Components>| {
+        let mut rng = XorShiftRng::from_seed([0x5dbe6259, 0x8d313d76, 0x3237db17, 0xe5bc0654]);
+        (0..NUM_INPUT_RECORDS)
+            .map(|i| {
+                empty_predicate
+                    .prove(local_data, i as u8, &mut rng)
+                    .unwrap()
+            })
+            .collect::<Vec<_>>()
+    };
+    let asset_death_proofs = |local_data: &LocalData<Components>| {
+        let mut rng = XorShiftRng::from_seed([0x5dbe6259, 0x8d313d76, 0x3237db17, 0xe5bc0654]);
+        (0..NUM_INPUT_RECORDS)
+            .map(|i| {
+                conserve_predicate
+                    .prove(local_data, i as u8, &mut rng)
+                    .unwrap()
+            })
+            .collect::<Vec<_>>()
+    };
+    let asset_birth_proofs = |local_data: &LocalData<Components>| {
+        let mut rng = XorShiftRng::from_seed([0x5dbe6259, 0x8d313d76, 0x3237db17, 0xe5bc0654]);
+        (0..NUM_OUTPUT_RECORDS)
+            .map(|j| {
+                conserve_predicate
+                    .prove(local_data, j as u8, &mut rng)
+                    .unwrap()
+            })
+            .collect::<Vec<_>>()
+    };
+
+    // Alice mints 100 units of a new asset.
+    let mut dummy_records = vec![];
+    for i in 0..NUM_INPUT_RECORDS {
+        let sn_nonce =
+            SnNonceCRH::evaluate(&comm_and_crh_pp.sn_nonce_crh_pp, &[64u8 + (i as u8); 1]).unwrap();
+        let record = DPC::generate_record(
+            comm_and_crh_pp,
+            &sn_nonce,
+            &alice.public_key,
+            true,
+            &[0u8; PAYLOAD_SIZE],
+            &empty_predicate.predicate,
+            &empty_predicate.predicate,
+            &mut rng,
+        )
+        .unwrap();
+        dummy_records.push(record);
+    }
+    let asset_id = mint_asset_id::<Components>(
+        &DPC::generate_sn(&dummy_records[0], &alice.secret_key).unwrap(),
+    )
+    .unwrap();
+
+    let asset_payload =
+        |value| encode_payload::<Components, _>(&AssetPayload::new(asset_id, value)).unwrap();
+    let mint_payloads = [asset_payload(60), asset_payload(40)];
+    let (minted_records, transaction) = InstantiatedDPC::execute(
+        &parameters,
+        &dummy_records,
+        &alice_asks,
+        &dummy_death_proofs,
+        &vec![alice.public_key.clone(); NUM_OUTPUT_RECORDS],
+        &[false; NUM_OUTPUT_RECORDS],
+        &mint_payloads,
+        &asset_predicates,
+        &asset_predicates,
+        |local_data: &LocalData<Components>| {
+            assert!(conserve.evaluate(local_data, &()));
+            asset_birth_proofs(local_data)
+        },
+        &[3u8; 32],
+        &[4u8; MEMO_SIZE],
+        &ledger,
+        &mut rng,
+    )
+    .unwrap();
+    assert!(InstantiatedDPC::verify(&verifier_parameters, &transaction, &ledger).unwrap());
+    ledger.push(transaction).unwrap();
+
+    // Alice cannot spend her records to create more value than they hold.
+    let inflating_payloads = [asset_payload(100), asset_payload(100)];
+    let (_, transaction) = InstantiatedDPC::execute(
+        &parameters,
+        &minted_records,
+        &alice_asks,
+        &asset_death_proofs,
+        &[bob.public_key.clone(), alice.public_key.clone()],
+        &[false; NUM_OUTPUT_RECORDS],
+        &inflating_payloads,
+        &asset_predicates,
+        &asset_predicates,
+        |local_data: &LocalData<Components>| {
+            assert!(!conserve.evaluate(local_data, &()));
+            asset_birth_proofs(local_data)
+        },
+        &[3u8; 32],
+        &[5u8; MEMO_SIZE],
+        &ledger,
+        &mut rng,
+    )
+    .unwrap();
+    assert!(!InstantiatedDPC::verify(&verifier_parameters, &transaction, &ledger).unwrap());
+
+    // Alice transfers 70 units to Bob, and keeps 30 as change.
+    let transfer_payloads = [asset_payload(70), asset_payload(30)];
+    let (transferred_records, transaction) = InstantiatedDPC::execute(
+        &parameters,
+        &minted_records,
+        &alice_asks,
+        &asset_death_proofs,
+        &[bob.public_key.clone(), alice.public_key.clone()],
+        &[false; NUM_OUTPUT_RECORDS],
+        &transfer_payloads,
+        &asset_predicates,
+        &asset_predicates,
+        |local_data: &LocalData<Components>| {
+            assert!(conserve.evaluate(local_data, &()));
+            asset_birth_proofs(local_data)
+        },
+        &[3u8; 32],
+        &[6u8; MEMO_SIZE],
+        &ledger,
+        &mut rng,
+    )
+    .unwrap();
+    assert!(InstantiatedDPC::verify(&verifier_parameters, &transaction, &ledger).unwrap());
+    ledger.push(transaction).unwrap();
+    assert_eq!(ledger.len(), 2);
+
+    // Bob discovers the record sent to him by scanning the ledger.
+    let bob_records =
+        DPC::scan_transactions(comm_and_crh_pp, &bob.viewing_key(), ledger.transactions()).unwrap();
+    assert_eq!(bob_records.len(), 1);
+    assert_eq!(
+        bob_records[0].commitment(),
+        transferred_records[0].commitment()
+    );
+    let bob_payload = decode_payload::<Components, AssetPayload>(bob_records[0].payload()).unwrap();
+    assert_eq!(bob_payload, AssetPayload::new(asset_id, 70));
+
+    // An auditor holding only Alice's viewing key finds both minted records
+    // and her change, and can tell that the minted records have been spent.
+    let alice_view = alice.viewing_key();
+    let alice_records =
+        DPC::scan_transactions(comm_and_crh_pp, &alice_view, ledger.transactions()).unwrap();
+    assert_eq!(alice_records.len(), NUM_OUTPUT_RECORDS + 1);
+    assert_eq!(
+        alice_records[2].commitment(),
+        transferred_records[1].commitment()
+    );
+    let spent = alice_records
+        .iter()
+        .map(|record| {
+            let sn = DPC::generate_sn_with_viewing_key(record, &alice_view).unwrap();
+            ledger.contains_sn(&sn)
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(spent, vec![true, true, false]);
+
+    // Alice creates records carrying 1000 units of the asset under the empty
+    // predicate. Spending them into records of the asset would conserve value,
+    // but they were not born under the conserve predicate.
+    let empty_predicates = vec![empty_predicate.predicate.clone(); NUM_OUTPUT_RECORDS];
+    let empty_birth_proofs = |local_data: &LocalData<Components>| {
+        let mut rng = XorShiftRng::from_seed([0x5dbe6259, 0x8d313d76, 0x3237db17, 0xe5bc0654]);
+        (0..NUM_OUTPUT_RECORDS)
+            .map(|j| {
+                empty_predicate
+                    .prove(local_data, j as u8, &mut rng)
+                    .unwrap()
+            })
+            .collect::<Vec<_>>()
+    };
+    let mut dummy_records = vec![];
+    for i in 0..NUM_INPUT_RECORDS {
+        let sn_nonce =
+            SnNonceCRH::evaluate(&comm_and_crh_pp.sn_nonce_crh_pp, &[96u8 + (i as u8); 1]).unwrap();
+        let record = DPC::generate_record(
+            comm_and_crh_pp,
+            &sn_nonce,
+            &alice.public_key,
+            true,
+            &[0u8; PAYLOAD_SIZE],
+            &empty_predicate.predicate,
+            &empty_predicate.predicate,
+            &mut rng,
+        )
+        .unwrap();
+        dummy_records.push(record);
+    }
+    let forged_payloads = [asset_payload(500), asset_payload(500)];
+    let (forged_records, transaction) = InstantiatedDPC::execute(
+        &parameters,
+        &dummy_records,
+        &alice_asks,
+        &dummy_death_proofs,
+        &vec![alice.public_key.clone(); NUM_OUTPUT_RECORDS],
+        &[false; NUM_OUTPUT_RECORDS],
+        &forged_payloads,
+        &empty_predicates,
+        &empty_predicates,
+        &empty_birth_proofs,
+        &[3u8; 32],
+        &[7u8; MEMO_SIZE],
+        &ledger,
+        &mut rng,
+    )
+    .unwrap();
+    assert!(InstantiatedDPC::verify(&verifier_parameters, &transaction, &ledger).unwrap());
+    ledger.push(transaction).unwrap();
+
+    let (_, transaction) = InstantiatedDPC::execute(
+        &parameters,
+        &forged_records,
+        &alice_asks,
+        &dummy_death_proofs,
+        &[bob.public_key.clone(), alice.public_key.clone()],
+        &[false; NUM_OUTPUT_RECORDS],
+        &forged_payloads,
+        &asset_predicates,
+        &asset_predicates,
+        |local_data: &LocalData<Components>| {
+            assert!(!conserve.evaluate(local_data, &()));
+            asset_birth_proofs(local_data)
+        },
+        &[3u8; 32],
+        &[8u8; MEMO_SIZE],
+        &ledger,
+        &mut rng,
+    )
+    .unwrap();
+    assert!(!InstantiatedDPC::verify(&verifier_parameters, &transaction, &ledger).unwrap());
+}
+
+#[test]
+fn wallet_integration_test() {
+    let mut rng = XorShiftRng::from_seed([0x5dbe6259, 0x8d313d76, 0x3237db17, 0xe5bc0654]);
+    let ledger_parameters = MerkleTreeIdealLedger::setup(&mut rng).expect("Ledger setup failed");
+    let parameters =
+        <InstantiatedDPC as DPCScheme<MerkleTreeIdealLedger>>::setup(&ledger_parameters, &mut rng)
+            .expect("DPC setup failed");
+    let verifier_parameters = parameters.verifier_parameters();
+    let comm_and_crh_pp = &parameters.comm_and_crh_pp;
+
+    // Every record is guarded by the "always-accept" predicate.
+    let predicate: RegisteredPredicate<Components, PredicateNIZK<Components>> =
+        RegisteredPredicate {
+            predicate: Predicate::from_verification_key(
+                &comm_and_crh_pp.pred_vk_crh_pp,
+                &parameters.pred_nizk_pp.vk,
+            )
+            .unwrap(),
+            pk:        parameters.pred_nizk_pp.pk.clone(),
+            vk:        parameters.pred_nizk_pp.vk.clone(),
+        };
+    let predicates = vec![predicate.predicate.clone(); NUM_OUTPUT_RECORDS];
+    let payload = |byte: u8| vec![byte; PAYLOAD_SIZE];
+    // Proves the predicate for the first `count` old or new records; `execute`
+    // proves the dummy records it pads the transaction with.
+    let predicate = &predicate;
+    let proofs = move |count: usize| {
+        move |local_data: &LocalData<Components>| {
+            let mut rng = XorShiftRng::from_seed([0x5dbe6259, 0x8d313d76, 0x3237db17, 0xe5bc0654]);
+            (0..count)
+                .map(|i| predicate.prove(local_data, i as u8, &mut rng).unwrap())
+                .collect::<Vec<_>>()
+        }
+    };
+
+    let genesis_address =
+        DPC::create_address_helper(comm_and_crh_pp, &[1u8; 32], &mut rng).unwrap();
+    let genesis_sn_nonce =
+        SnNonceCRH::evaluate(&comm_and_crh_pp.sn_nonce_crh_pp, &[34u8; 1]).unwrap();
+    let genesis_record = DPC::generate_record(
+        comm_and_crh_pp,
+        &genesis_sn_nonce,
+        &genesis_address.public_key,
+        true,
+        &[2u8; PAYLOAD_SIZE],
+        &predicate.predicate,
+        &predicate.predicate,
+        &mut rng,
+    )
+    .unwrap();
+    let genesis_sn = DPC::generate_sn(&genesis_record, &genesis_address.secret_key).unwrap();
+    let mut ledger = MerkleTreeIdealLedger::new(
+        ledger_parameters,
+        genesis_record.commitment(),
+        genesis_sn,
+        vec![1u8; MEMO_SIZE],
+    );
+
+    let mut alice: Wallet<Components, MerkleTreeIdealLedger> =
+        Wallet::new(DPC::create_address_helper(comm_and_crh_pp, &[2u8; 32], &mut rng).unwrap());
+    let mut bob: Wallet<Components, MerkleTreeIdealLedger> =
+        Wallet::new(DPC::create_address_helper(comm_and_crh_pp, &[3u8; 32], &mut rng).unwrap());
 
+    // Alice owns nothing yet, so she selects no inputs. She creates two
+    // records for herself, so the transaction is padded with two dummy inputs.
+    let inputs = alice.select_inputs(|_| true);
+    assert!(inputs.is_empty());
+    let (_, transaction) = alice
+        .execute(
+            &parameters,
+            &inputs,
+            proofs(inputs.len()),
+            &vec![alice.address().public_key.clone(); NUM_OUTPUT_RECORDS],
+            &[false; NUM_OUTPUT_RECORDS],
+            &[payload(1), payload(2)],
+            &predicates,
+            &predicates,
+            proofs(NUM_OUTPUT_RECORDS),
+            &[0u8; 32],
+            &[1u8; MEMO_SIZE],
+            &ledger,
+            &mut rng,
+        )
+        .unwrap();
+    assert_eq!(transaction.old_serial_numbers().len(), 2);
+    assert!(InstantiatedDPC::verify(&verifier_parameters, &transaction, &ledger).unwrap());
     ledger.push(transaction).unwrap();
+
+    alice.sync(comm_and_crh_pp, &ledger).unwrap();
+    bob.sync(comm_and_crh_pp, &ledger).unwrap();
+    assert_eq!(alice.unspent_records().count(), 2);
+    assert_eq!(bob.unspent_records().count(), 0);
+
+    // Alice sends one of her records to Bob in a one-in-one-out transaction.
+    let inputs = alice.select_inputs(|record| record.payload() == &payload(1));
+    assert_eq!(inputs.len(), 1);
+    assert_eq!(inputs[0].payload(), &payload(1));
+    let (_, transaction) = alice
+        .execute(
+            &parameters,
+            &inputs,
+            proofs(1),
+            &[bob.address().public_key.clone()],
+            &[false],
+            &[payload(1)],
+            &predicates[..1],
+            &predicates[..1],
+            proofs(1),
+            &[0u8; 32],
+            &[2u8; MEMO_SIZE],
+            &ledger,
+            &mut rng,
+        )
+        .unwrap();
+    assert_eq!(transaction.old_serial_numbers().len(), 1);
+    assert_eq!(transaction.new_commitments().len(), 1);
+    assert!(InstantiatedDPC::verify(&verifier_parameters, &transaction, &ledger).unwrap());
+    let digest_before_payment = ledger.digest().unwrap();
+    ledger.push(transaction).unwrap();
+
+    alice.sync(comm_and_crh_pp, &ledger).unwrap();
+    bob.sync(comm_and_crh_pp, &ledger).unwrap();
+    assert_eq!(alice.records().len(), 2);
+    assert_eq!(alice.unspent_records().count(), 1);
+    assert_eq!(
+        alice.unspent_records().next().unwrap().payload(),
+        &payload(2)
+    );
+    assert_eq!(bob.unspent_records().count(), 1);
+
+    // Witnesses are kept current with the ledger.
+    let digest = ledger.digest().unwrap();
+    for wallet_record in alice.records().iter().chain(bob.records()) {
+        if !wallet_record.spent {
+            assert!(MerkleTreeIdealLedger::verify_cm(
+                ledger.parameters(),
+                &digest,
+                &wallet_record.record.commitment(),
+                &wallet_record.witness,
+            ));
+        }
+    }
+
+    // Spent records cannot be spent again.
+    let spent = alice
+        .records()
+        .iter()
+        .find(|r| r.spent)
+        .unwrap()
+        .record
+        .clone();
+    assert!(alice
+        .execute(
+            &parameters,
+            &[spent],
+            proofs(1),
+            &[alice.address().public_key.clone()],
+            &[false],
+            &[payload(1)],
+            &predicates[..1],
+            &predicates[..1],
+            proofs(1),
+            &[0u8; 32],
+            &[3u8; MEMO_SIZE],
+            &ledger,
+            &mut rng,
+        )
+        .is_err());
+
+    // Rolling the payment back gives Alice her record back and takes Bob's
+    // away. Bob's wallet notices the rollback when it syncs.
+    let spent_sn = alice
+        .records()
+        .iter()
+        .find(|r| r.spent)
+        .unwrap()
+        .serial_number
+        .clone();
+    assert_eq!(ledger.rollback(&digest_before_payment).unwrap().len(), 1);
+    assert!(!ledger.contains_sn(&spent_sn));
+    alice.rollback(&ledger).unwrap();
+    bob.sync(comm_and_crh_pp, &ledger).unwrap();
+    assert_eq!(alice.records().len(), 2);
+    assert!(alice.records().iter().all(|r| !r.spent));
+    assert_eq!(alice.unspent_records().count(), 2);
+    assert!(bob.records().is_empty());
+
+    alice.sync(comm_and_crh_pp, &ledger).unwrap();
+    bob.sync(comm_and_crh_pp, &ledger).unwrap();
+    assert_eq!(alice.unspent_records().count(), 2);
+    assert!(bob.records().is_empty());
+    let digest = ledger.digest().unwrap();
+    for wallet_record in alice.records() {
+        assert!(MerkleTreeIdealLedger::verify_cm(
+            ledger.parameters(),
+            &digest,
+            &wallet_record.record.commitment(),
+            &wallet_record.witness,
+        ));
+    }
+
+    // Alice pays Bob again, but the payment is replaced by a transaction to
+    // herself. The ledger has as many transactions as when Bob last synced,
+    // but Bob's wallet still notices the fork and forgets the payment.
+    let mut pay = |recipient: &AddressPublicKey<Components>, memo: u8| {
+        let inputs = alice.select_inputs(|record| record.payload() == &payload(1));
+        alice
+            .execute(
+                &parameters,
+                &inputs,
+                proofs(1),
+                &[recipient.clone()],
+                &[false],
+                &[payload(1)],
+                &predicates[..1],
+                &predicates[..1],
+                proofs(1),
+                &[0u8; 32],
+                &[memo; MEMO_SIZE],
+                &ledger,
+                &mut rng,
+            )
+            .unwrap()
+            .1
+    };
+    let payment = pay(&bob.address().public_key, 4);
+    let to_self = pay(&alice.address().public_key, 5);
+    ledger.push(payment).unwrap();
+    bob.sync(comm_and_crh_pp, &ledger).unwrap();
+    assert_eq!(bob.unspent_records().count(), 1);
+
+    ledger.rollback(&digest).unwrap();
+    ledger.push(to_self).unwrap();
+    alice.sync(comm_and_crh_pp, &ledger).unwrap();
+    bob.sync(comm_and_crh_pp, &ledger).unwrap();
+    assert!(bob.records().is_empty());
+    assert_eq!(alice.records().len(), 3);
+    assert_eq!(alice.unspent_records().count(), 2);
+}
+
+#[test]
+fn mempool_integration_test() {
+    let mut rng = XorShiftRng::from_seed([0x5dbe6259, 0x8d313d76, 0x3237db17, 0xe5bc0654]);
+    let ledger_parameters = MerkleTreeIdealLedger::setup(&mut rng).expect("Ledger setup failed");
+    let parameters =
+        <InstantiatedDPC as DPCScheme<MerkleTreeIdealLedger>>::setup(&ledger_parameters, &mut rng)
+            .expect("DPC setup failed");
+    let verifier_parameters = parameters.verifier_parameters();
+    let comm_and_crh_pp = &parameters.comm_and_crh_pp;
+
+    let predicate: RegisteredPredicate<Components, PredicateNIZK<Components>> =
+        RegisteredPredicate {
+            predicate: Predicate::from_verification_key(
+                &comm_and_crh_pp.pred_vk_crh_pp,
+                &parameters.pred_nizk_pp.vk,
+            )
+            .unwrap(),
+            pk:        parameters.pred_nizk_pp.pk.clone(),
+            vk:        parameters.pred_nizk_pp.vk.clone(),
+        };
+    let predicate = &predicate;
+    let proofs = move |count: usize| {
+        move |local_data: &LocalData<Components>| {
+            let mut rng = XorShiftRng::from_seed([0x5dbe6259, 0x8d313d76, 0x3237db17, 0xe5bc0654]);
+            (0..count)
+                .map(|i| predicate.prove(local_data, i as u8, &mut rng).unwrap())
+                .collect::<Vec<_>>()
+        }
+    };
+
+    let genesis_address =
+        DPC::create_address_helper(comm_and_crh_pp, &[1u8; 32], &mut rng).unwrap();
+    let genesis_sn_nonce =
+        SnNonceCRH::evaluate(&comm_and_crh_pp.sn_nonce_crh_pp, &[34u8; 1]).unwrap();
+    let genesis_record = DPC::generate_record(
+        comm_and_crh_pp,
+        &genesis_sn_nonce,
+        &genesis_address.public_key,
+        true,
+        &[2u8; PAYLOAD_SIZE],
+        &predicate.predicate,
+        &predicate.predicate,
+        &mut rng,
+    )
+    .unwrap();
+    let genesis_sn = DPC::generate_sn(&genesis_record, &genesis_address.secret_key).unwrap();
+    let mut ledger = MerkleTreeIdealLedger::new(
+        ledger_parameters,
+        genesis_record.commitment(),
+        genesis_sn,
+        vec![1u8; MEMO_SIZE],
+    );
+
+    // Alice creates two independent transactions, each spending a fresh dummy
+    // record.
+    let alice: Wallet<Components, MerkleTreeIdealLedger> =
+        Wallet::new(DPC::create_address_helper(comm_and_crh_pp, &[2u8; 32], &mut rng).unwrap());
+    let mut transactions = vec![];
+    for i in 2..4u8 {
+        let inputs = alice.select_inputs(|_| true);
+        let (_, transaction) = alice
+            .execute(
+                &parameters,
+                &inputs,
+                proofs(inputs.len()),
+                &[alice.address().public_key.clone()],
+                &[false],
+                &[vec![i; PAYLOAD_SIZE]],
+                &[predicate.predicate.clone()],
+                &[predicate.predicate.clone()],
+                proofs(1),
+                &[0u8; 32],
+                &[i; MEMO_SIZE],
+                &ledger,
+                &mut rng,
+            )
+            .unwrap();
+        transactions.push(transaction);
+    }
+
+    let mut mempool: Mempool<Components, MerkleTreeIdealLedger> = Mempool::new();
+    let insert_error = |mempool: &mut Mempool<Components, MerkleTreeIdealLedger>,
+                        transaction: &Tx,
+                        ledger: &MerkleTreeIdealLedger| {
+        mempool
+            .insert(&verifier_parameters, transaction.clone(), ledger)
+            .unwrap_err()
+            .downcast::<MempoolError>()
+            .unwrap()
+    };
+
+    // Transactions must be created against a known ledger state.
+    let mut stale_transaction = transactions[0].clone();
+    stale_transaction.stuff.digest = MerkleTreeDigest::default();
+    assert_eq!(
+        insert_error(&mut mempool, &stale_transaction, &ledger),
+        MempoolError::StaleDigest
+    );
+
+    // Pending transactions cannot spend the same serial numbers.
+    mempool
+        .insert(&verifier_parameters, transactions[0].clone(), &ledger)
+        .unwrap();
+    assert!(mempool.contains_sn(&transactions[0].old_serial_numbers()[0]));
+    assert_eq!(
+        insert_error(&mut mempool, &transactions[0], &ledger),
+        MempoolError::ConflictingSn
+    );
+
+    // Nor can they create the same commitments. The pool rejects such a
+    // transaction before checking its proofs.
+    let stuff = transactions[1].stuff.clone();
+    let conflicting_transaction = Tx::new(
+        vec![Default::default()],
+        transactions[0].new_commitments().to_vec(),
+        vec![100u8; MEMO_SIZE],
+        transactions[1].encrypted_records().to_vec(),
+        stuff.digest,
+        stuff.core_proof,
+        stuff.predicate_proof,
+        stuff.predicate_comm,
+        stuff.local_data_comm,
+    );
+    assert_eq!(
+        insert_error(&mut mempool, &conflicting_transaction, &ledger),
+        MempoolError::ConflictingCm
+    );
+    mempool
+        .insert(&verifier_parameters, transactions[1].clone(), &ledger)
+        .unwrap();
+    assert_eq!(mempool.len(), 2);
+
+    // Once a candidate block is appended to the ledger, its transactions leave
+    // the pool and can no longer be inserted.
+    let block = mempool.candidate_block(1);
+    assert_eq!(block.len(), 1);
+    assert!(block[0] == transactions[0]);
+    ledger.push_block(block).unwrap();
+    let removed = mempool.prune(&ledger);
+    assert_eq!(removed.len(), 1);
+    assert!(removed[0] == transactions[0]);
+    assert_eq!(mempool.len(), 1);
+    assert!(mempool.transactions()[0] == transactions[1]);
+    assert_eq!(
+        insert_error(&mut mempool, &transactions[0], &ledger),
+        MempoolError::SpentSn
+    );
+
+    // The remaining transaction was created against an older ledger state, but
+    // is still valid.
+    assert!(InstantiatedDPC::verify(&verifier_parameters, &transactions[1], &ledger).unwrap());
+
+    // Each header commits to the previous one.
+    assert_eq!(ledger.height(), 1);
+    let headers = ledger.headers();
+    assert_eq!(headers.len(), 2);
+    assert_eq!(headers[0].previous_hash, [0u8; 32]);
+    assert_eq!(headers[1].height, 1);
+    assert_eq!(headers[1].previous_hash, headers[0].hash().unwrap());
+    assert!(headers[0].cm_root != headers[1].cm_root);
+    assert!(headers[0].sn_root != headers[1].sn_root);
+    assert!(ledger.block_transactions(0).unwrap().is_empty());
+    assert!(ledger.block_transactions(1).unwrap() == &transactions[..1]);
+    assert!(ledger.block_transactions(2).is_none());
+
+    // A block with a conflicting transaction is rejected as a whole.
+    let tip_hash = headers[1].hash().unwrap();
+    assert!(ledger
+        .push_block(vec![transactions[1].clone(), transactions[0].clone()])
+        .is_err());
+    assert_eq!(ledger.height(), 1);
     assert_eq!(ledger.len(), 1);
+    assert_eq!(ledger.headers()[1].hash().unwrap(), tip_hash);
+    assert!(!ledger.contains_sn(&transactions[1].old_serial_numbers()[0]));
+
+    // Digests older than the maximum age are no longer accepted.
+    ledger.set_max_digest_age(Some(0));
+    assert!(!InstantiatedDPC::verify(&verifier_parameters, &transactions[1], &ledger).unwrap());
+    assert_eq!(
+        insert_error(&mut mempool, &transactions[1], &ledger),
+        MempoolError::StaleDigest
+    );
+    ledger.set_max_digest_age(Some(1));
+    assert!(InstantiatedDPC::verify(&verifier_parameters, &transactions[1], &ledger).unwrap());
+    ledger.push_block(vec![transactions[1].clone()]).unwrap();
+    assert_eq!(ledger.height(), 2);
+    assert_eq!(ledger.headers()[2].previous_hash, tip_hash);
 }
@@ -10,8 +10,8 @@ use crate::{
 };
 
 use crate::utils::{
-    AllocGadget, CondSelectGadget, ConditionalEqGadget, EqGadget, NEqGadget, ToBitsGadget,
-    ToBytesGadget, TwoBitLookupGadget,
+    AllocGadget, CondSelectGadget, ConditionalEqGadget, EqGadget, KBitLookupGadget, NEqGadget,
+    ThreeBitCondNegLookupGadget, ToBitsGadget, ToBytesGadget, TwoBitLookupGadget,
 };
 
 use crate::{
@@ -262,6 +262,10 @@ impl<E: PairingEngine> FieldGadget<E::Fr, E> for FpGadget<E> {
         1
     }
 
+    fn cost_of_mul_equals() -> usize {
+        1
+    }
+
     fn cost_of_inv() -> usize {
         1
     }
@@ -515,6 +519,134 @@ impl<E: PairingEngine> TwoBitLookupGadget<E> for FpGadget<E> {
     }
 }
 
+/// Uses `k` bits to perform a lookup into a table of `2^k` constants.
+/// `b` is little-endian: `b[0]` is LSB.
+///
+/// The table is interpolated as a multilinear polynomial in the bits. All
+/// monomials in the lower `k - 1` bits are computed explicitly, and a single
+/// constraint multiplies by the top bit.
+impl<E: PairingEngine> KBitLookupGadget<E> for FpGadget<E> {
+    type TableConstant = E::Fr;
+    fn k_bit_lookup<CS: ConstraintSystem<E>>(
+        mut cs: CS,
+        b: &[Boolean],
+        c: &[Self::TableConstant],
+    ) -> Result<Self, SynthesisError> {
+        let k = b.len();
+        assert!(k > 0);
+        assert_eq!(c.len(), 1 << k);
+
+        let result = Self::alloc(cs.ns(|| "Allocate lookup result"), || {
+            let mut index = 0;
+            for (i, bit) in b.iter().enumerate() {
+                if bit.get_value().get()? {
+                    index |= 1 << i;
+                }
+            }
+            Ok(c[index])
+        })?;
+
+        // Coefficients of the multilinear polynomial, indexed by the set of
+        // bits in each monomial.
+        let mut coeffs = c.to_vec();
+        for i in 0..k {
+            for mask in 0..coeffs.len() {
+                if mask & (1 << i) != 0 {
+                    let lower = coeffs[mask ^ (1 << i)];
+                    coeffs[mask] -= &lower;
+                }
+            }
+        }
+
+        // Monomials in the lower `k - 1` bits.
+        let half = 1 << (k - 1);
+        let mut monomials = Vec::with_capacity(half);
+        monomials.push(Boolean::constant(true));
+        for mask in 1..half {
+            let top = (0..k).rev().find(|i| mask & (1 << i) != 0).unwrap();
+            let monomial = if mask == 1 << top {
+                b[top]
+            } else {
+                Boolean::and(
+                    cs.ns(|| format!("Monomial {}", mask)),
+                    &monomials[mask ^ (1 << top)],
+                    &b[top],
+                )?
+            };
+            monomials.push(monomial);
+        }
+
+        let one = CS::one();
+        let mut lower_lc = LinearCombination::zero();
+        let mut upper_lc = LinearCombination::zero();
+        for (mask, monomial) in monomials.iter().enumerate() {
+            lower_lc = lower_lc + monomial.lc(one, coeffs[mask]);
+            upper_lc = upper_lc + monomial.lc(one, coeffs[mask + half]);
+        }
+        cs.enforce(
+            || "Enforce lookup",
+            |lc| lc + &upper_lc,
+            |lc| lc + b[k - 1].lc(one, E::Fr::one()),
+            |lc| result.get_variable() + lc - &lower_lc,
+        );
+
+        Ok(result)
+    }
+
+    fn cost(k: usize) -> usize {
+        (1 << (k - 1)) - k + 1
+    }
+}
+
+/// Uses three bits to perform a lookup into a table, where the last bit
+/// conditionally negates the looked-up value.
+/// `b` is little-endian: `b[0]` is LSB.
+impl<E: PairingEngine> ThreeBitCondNegLookupGadget<E> for FpGadget<E> {
+    type TableConstant = E::Fr;
+    fn three_bit_cond_neg_lookup<CS: ConstraintSystem<E>>(
+        mut cs: CS,
+        b: &[Boolean],
+        b0b1: &Boolean,
+        c: &[Self::TableConstant],
+    ) -> Result<Self, SynthesisError> {
+        debug_assert!(b.len() == 3);
+        debug_assert!(c.len() == 4);
+
+        let result = Self::alloc(cs.ns(|| "Allocate lookup result"), || {
+            let y = match (b[0].get_value().get()?, b[1].get_value().get()?) {
+                (false, false) => c[0],
+                (false, true) => c[2],
+                (true, false) => c[1],
+                (true, true) => c[3],
+            };
+            if b[2].get_value().get()? {
+                Ok(-y)
+            } else {
+                Ok(y)
+            }
+        })?;
+
+        let one = CS::one();
+        let y_lc = b0b1.lc(one, c[3] - &c[2] - &c[1] + &c[0])
+            + b[0].lc(one, c[1] - &c[0])
+            + b[1].lc(one, c[2] - &c[0])
+            + (c[0], one);
+        // -2 * y * b[2] = result - y
+        cs.enforce(
+            || "Enforce lookup",
+            |lc| lc - &y_lc - &y_lc,
+            |lc| lc + b[2].lc(one, E::Fr::one()),
+            |lc| result.get_variable() + lc - &y_lc,
+        );
+
+        Ok(result)
+    }
+
+    fn cost() -> usize {
+        1
+    }
+}
+
 impl<E: PairingEngine> Clone for FpGadget<E> {
     fn clone(&self) -> Self {
         Self {
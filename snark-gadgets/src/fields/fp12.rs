@@ -16,8 +16,8 @@ use crate::{
     boolean::Boolean,
     uint8::UInt8,
     utils::{
-        AllocGadget, CondSelectGadget, ConditionalEqGadget, EqGadget, NEqGadget, ToBitsGadget,
-        ToBytesGadget, TwoBitLookupGadget,
+        AllocGadget, CondSelectGadget, ConditionalEqGadget, EqGadget, KBitLookupGadget, NEqGadget,
+        ThreeBitCondNegLookupGadget, ToBitsGadget, ToBytesGadget, TwoBitLookupGadget,
     },
 };
 
@@ -615,11 +615,19 @@ where
     }
 
     fn cost_of_mul() -> usize {
-        unimplemented!()
+        3 * Fp6Gadget::<P, E>::cost_of_mul()
+    }
+
+    fn cost_of_square() -> usize {
+        2 * Fp6Gadget::<P, E>::cost_of_mul()
+    }
+
+    fn cost_of_mul_equals() -> usize {
+        Fp6Gadget::<P, E>::cost_of_mul() + 2 * Fp6Gadget::<P, E>::cost_of_mul_equals()
     }
 
     fn cost_of_inv() -> usize {
-        Self::cost_of_mul() + <Self as EqGadget<E>>::cost()
+        Fp6Gadget::<P, E>::cost_of_mul() + Fp6Gadget::<P, E>::cost_of_mul_equals()
     }
 }
 
@@ -804,6 +812,55 @@ where
     }
 }
 
+impl<P, E: PairingEngine> KBitLookupGadget<E> for Fp12Gadget<P, E>
+where
+    P: Fp12Parameters,
+    <P::Fp6Params as Fp6Parameters>::Fp2Params: Fp2Parameters<Fp = E::Fr>,
+{
+    type TableConstant = Fp12<P>;
+    fn k_bit_lookup<CS: ConstraintSystem<E>>(
+        mut cs: CS,
+        b: &[Boolean],
+        c: &[Self::TableConstant],
+    ) -> Result<Self, SynthesisError> {
+        let c0s = c.iter().map(|f| f.c0).collect::<Vec<_>>();
+        let c1s = c.iter().map(|f| f.c1).collect::<Vec<_>>();
+        let c0 = Fp6Gadget::<P, E>::k_bit_lookup(cs.ns(|| "Lookup c0"), b, &c0s)?;
+        let c1 = Fp6Gadget::<P, E>::k_bit_lookup(cs.ns(|| "Lookup c1"), b, &c1s)?;
+        Ok(Self::new(c0, c1))
+    }
+
+    fn cost(k: usize) -> usize {
+        2 * <Fp6Gadget<P, E> as KBitLookupGadget<E>>::cost(k)
+    }
+}
+
+impl<P, E: PairingEngine> ThreeBitCondNegLookupGadget<E> for Fp12Gadget<P, E>
+where
+    P: Fp12Parameters,
+    <P::Fp6Params as Fp6Parameters>::Fp2Params: Fp2Parameters<Fp = E::Fr>,
+{
+    type TableConstant = Fp12<P>;
+    fn three_bit_cond_neg_lookup<CS: ConstraintSystem<E>>(
+        mut cs: CS,
+        b: &[Boolean],
+        b0b1: &Boolean,
+        c: &[Self::TableConstant],
+    ) -> Result<Self, SynthesisError> {
+        let c0s = c.iter().map(|f| f.c0).collect::<Vec<_>>();
+        let c1s = c.iter().map(|f| f.c1).collect::<Vec<_>>();
+        let c0 =
+            Fp6Gadget::<P, E>::three_bit_cond_neg_lookup(cs.ns(|| "Lookup c0"), b, b0b1, &c0s)?;
+        let c1 =
+            Fp6Gadget::<P, E>::three_bit_cond_neg_lookup(cs.ns(|| "Lookup c1"), b, b0b1, &c1s)?;
+        Ok(Self::new(c0, c1))
+    }
+
+    fn cost() -> usize {
+        2 * <Fp6Gadget<P, E> as ThreeBitCondNegLookupGadget<E>>::cost()
+    }
+}
+
 impl<P, E: PairingEngine> AllocGadget<Fp12<P>, E> for Fp12Gadget<P, E>
 where
     P: Fp12Parameters,
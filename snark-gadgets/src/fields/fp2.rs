@@ -11,8 +11,8 @@ use crate::{
     boolean::Boolean,
     uint8::UInt8,
     utils::{
-        AllocGadget, CondSelectGadget, ConditionalEqGadget, EqGadget, NEqGadget, ToBitsGadget,
-        ToBytesGadget, TwoBitLookupGadget,
+        AllocGadget, CondSelectGadget, ConditionalEqGadget, EqGadget, KBitLookupGadget, NEqGadget,
+        ThreeBitCondNegLookupGadget, ToBitsGadget, ToBytesGadget, TwoBitLookupGadget,
     },
     Assignment, ConstraintVar,
 };
@@ -441,6 +441,14 @@ impl<P: Fp2Parameters<Fp = E::Fr>, E: PairingEngine> FieldGadget<Fp2<P>, E> for
         3
     }
 
+    fn cost_of_square() -> usize {
+        2
+    }
+
+    fn cost_of_mul_equals() -> usize {
+        3
+    }
+
     fn cost_of_inv() -> usize {
         2
     }
@@ -581,6 +589,47 @@ impl<P: Fp2Parameters<Fp = E::Fr>, E: PairingEngine> TwoBitLookupGadget<E> for F
     }
 }
 
+impl<P: Fp2Parameters<Fp = E::Fr>, E: PairingEngine> KBitLookupGadget<E> for Fp2Gadget<P, E> {
+    type TableConstant = Fp2<P>;
+    fn k_bit_lookup<CS: ConstraintSystem<E>>(
+        mut cs: CS,
+        b: &[Boolean],
+        c: &[Self::TableConstant],
+    ) -> Result<Self, SynthesisError> {
+        let c0s = c.iter().map(|f| f.c0).collect::<Vec<_>>();
+        let c1s = c.iter().map(|f| f.c1).collect::<Vec<_>>();
+        let c0 = FpGadget::k_bit_lookup(cs.ns(|| "Lookup c0"), b, &c0s)?;
+        let c1 = FpGadget::k_bit_lookup(cs.ns(|| "Lookup c1"), b, &c1s)?;
+        Ok(Self::new(c0, c1))
+    }
+
+    fn cost(k: usize) -> usize {
+        2 * <FpGadget<E> as KBitLookupGadget<E>>::cost(k)
+    }
+}
+
+impl<P: Fp2Parameters<Fp = E::Fr>, E: PairingEngine> ThreeBitCondNegLookupGadget<E>
+    for Fp2Gadget<P, E>
+{
+    type TableConstant = Fp2<P>;
+    fn three_bit_cond_neg_lookup<CS: ConstraintSystem<E>>(
+        mut cs: CS,
+        b: &[Boolean],
+        b0b1: &Boolean,
+        c: &[Self::TableConstant],
+    ) -> Result<Self, SynthesisError> {
+        let c0s = c.iter().map(|f| f.c0).collect::<Vec<_>>();
+        let c1s = c.iter().map(|f| f.c1).collect::<Vec<_>>();
+        let c0 = FpGadget::three_bit_cond_neg_lookup(cs.ns(|| "Lookup c0"), b, b0b1, &c0s)?;
+        let c1 = FpGadget::three_bit_cond_neg_lookup(cs.ns(|| "Lookup c1"), b, b0b1, &c1s)?;
+        Ok(Self::new(c0, c1))
+    }
+
+    fn cost() -> usize {
+        2 * <FpGadget<E> as ThreeBitCondNegLookupGadget<E>>::cost()
+    }
+}
+
 impl<P: Fp2Parameters<Fp = E::Fr>, E: PairingEngine> AllocGadget<Fp2<P>, E> for Fp2Gadget<P, E> {
     #[inline]
     fn alloc<F, T, CS: ConstraintSystem<E>>(
@@ -0,0 +1,750 @@
+use algebra::{
+    fields::{
+        fp3::{Fp3, Fp3Parameters},
+        Field,
+    },
+    PairingEngine,
+};
+use snark::{ConstraintSystem, SynthesisError};
+use std::{borrow::Borrow, fmt::Debug, marker::PhantomData};
+
+use super::{fp::FpGadget, FieldGadget};
+
+use crate::{
+    boolean::Boolean,
+    uint8::UInt8,
+    utils::{
+        AllocGadget, CondSelectGadget, ConditionalEqGadget, EqGadget, KBitLookupGadget, NEqGadget,
+        ThreeBitCondNegLookupGadget, ToBitsGadget, ToBytesGadget, TwoBitLookupGadget,
+    },
+    Assignment, ConstraintVar,
+};
+
+#[derive(Derivative)]
+#[derivative(Debug(bound = "P: Fp3Parameters, E::Fr: Debug"))]
+#[must_use]
+pub struct Fp3Gadget<P: Fp3Parameters<Fp = E::Fr>, E: PairingEngine> {
+    pub c0: FpGadget<E>,
+    pub c1: FpGadget<E>,
+    pub c2: FpGadget<E>,
+    #[derivative(Debug = "ignore")]
+    _params: PhantomData<P>,
+}
+
+impl<P: Fp3Parameters<Fp = E::Fr>, E: PairingEngine> Fp3Gadget<P, E> {
+    #[inline]
+    pub fn new(c0: FpGadget<E>, c1: FpGadget<E>, c2: FpGadget<E>) -> Self {
+        Self {
+            c0,
+            c1,
+            c2,
+            _params: PhantomData,
+        }
+    }
+
+    /// Multiply a FpGadget by cubic nonresidue P::NONRESIDUE.
+    #[inline]
+    pub fn mul_fp_gadget_by_nonresidue<CS: ConstraintSystem<E>>(
+        cs: CS,
+        fe: &FpGadget<E>,
+    ) -> Result<FpGadget<E>, SynthesisError> {
+        fe.mul_by_constant(cs, &P::NONRESIDUE)
+    }
+
+    /// Multiply a Fp3Gadget by an element of fp.
+    #[inline]
+    pub fn mul_by_fp_constant_in_place<CS: ConstraintSystem<E>>(
+        &mut self,
+        mut cs: CS,
+        fe: &P::Fp,
+    ) -> Result<&mut Self, SynthesisError> {
+        self.c0.mul_by_constant_in_place(cs.ns(|| "c0"), fe)?;
+        self.c1.mul_by_constant_in_place(cs.ns(|| "c1"), fe)?;
+        self.c2.mul_by_constant_in_place(cs.ns(|| "c2"), fe)?;
+        Ok(self)
+    }
+
+    /// Multiply a Fp3Gadget by an element of fp.
+    #[inline]
+    pub fn mul_by_fp_constant<CS: ConstraintSystem<E>>(
+        &self,
+        cs: CS,
+        fe: &P::Fp,
+    ) -> Result<Self, SynthesisError> {
+        let mut result = self.clone();
+        result.mul_by_fp_constant_in_place(cs, fe)?;
+        Ok(result)
+    }
+
+    /// Recovers the product from the evaluations `v0, .., v4` of the Toom-Cook-3x
+    /// method. Doesn't need any constraints.
+    #[inline]
+    fn interpolate<CS: ConstraintSystem<E>>(
+        mut cs: CS,
+        v0: FpGadget<E>,
+        v1: FpGadget<E>,
+        v2: FpGadget<E>,
+        v3: FpGadget<E>,
+        v4: FpGadget<E>,
+    ) -> Result<Self, SynthesisError> {
+        let two = P::Fp::one().double();
+        let six = two.double() + &two;
+        let mut two_and_six = [two, six];
+        algebra::fields::batch_inversion(&mut two_and_six);
+        let (two_inverse, six_inverse) = (two_and_six[0], two_and_six[1]);
+
+        let half_v0 = v0.mul_by_constant(cs.ns(|| "half_v0"), &two_inverse)?;
+        let half_v1 = v1.mul_by_constant(cs.ns(|| "half_v1"), &two_inverse)?;
+        let one_sixth_v2 = v2.mul_by_constant(cs.ns(|| "v2_by_six"), &six_inverse)?;
+        let one_sixth_v3 = v3.mul_by_constant(cs.ns(|| "v3_by_six"), &six_inverse)?;
+        let two_v4 = v4.double(cs.ns(|| "2 * v4"))?;
+
+        // c0 = v0 + β((1/2)v0 − (1/2)v1 − (1/6)v2 + (1/6)v3 − 2v4)
+        let c0 = {
+            let c0_cs = &mut cs.ns(|| "c0");
+
+            let temp = half_v0
+                .sub(c0_cs.ns(|| "sub1"), &half_v1)?
+                .sub(c0_cs.ns(|| "sub2"), &one_sixth_v2)?
+                .add(c0_cs.ns(|| "add3"), &one_sixth_v3)?
+                .sub(c0_cs.ns(|| "sub4"), &two_v4)?;
+            let non_residue_times_inner =
+                Self::mul_fp_gadget_by_nonresidue(&mut c0_cs.ns(|| "mul5"), &temp)?;
+            v0.add(c0_cs.ns(|| "add6"), &non_residue_times_inner)?
+        };
+
+        // −(1/2)v0 + v1 − (1/3)v2 − (1/6)v3 + 2v4 + βv4
+        let c1 = {
+            let c1_cs = &mut cs.ns(|| "c1");
+            let one_third_v2 = one_sixth_v2.double(&mut c1_cs.ns(|| "v2_by_3"))?;
+            let non_residue_v4 =
+                Self::mul_fp_gadget_by_nonresidue(&mut c1_cs.ns(|| "mul_by_beta"), &v4)?;
+
+            half_v0
+                .negate(c1_cs.ns(|| "neg1"))?
+                .add(c1_cs.ns(|| "add2"), &v1)?
+                .sub(c1_cs.ns(|| "sub3"), &one_third_v2)?
+                .sub(c1_cs.ns(|| "sub4"), &one_sixth_v3)?
+                .add(c1_cs.ns(|| "sub5"), &two_v4)?
+                .add(c1_cs.ns(|| "sub6"), &non_residue_v4)?
+        };
+
+        // -v0 + (1/2)v1 + (1/2)v2 −v4
+        let c2 = {
+            let c2_cs = &mut cs.ns(|| "c2");
+            let half_v2 = v2.mul_by_constant(&mut c2_cs.ns(|| "mul1"), &two_inverse)?;
+            half_v1
+                .add(c2_cs.ns(|| "add1"), &half_v2)?
+                .sub(c2_cs.ns(|| "sub1"), &v4)?
+                .sub(c2_cs.ns(|| "sub2"), &v0)?
+        };
+
+        Ok(Self::new(c0, c1, c2))
+    }
+}
+
+impl<P: Fp3Parameters<Fp = E::Fr>, E: PairingEngine> FieldGadget<Fp3<P>, E> for Fp3Gadget<P, E> {
+    type Variable = (ConstraintVar<E>, ConstraintVar<E>, ConstraintVar<E>);
+
+    #[inline]
+    fn get_value(&self) -> Option<Fp3<P>> {
+        match (self.c0.value, self.c1.value, self.c2.value) {
+            (Some(c0), Some(c1), Some(c2)) => Some(Fp3::new(c0, c1, c2)),
+            (..) => None,
+        }
+    }
+
+    #[inline]
+    fn get_variable(&self) -> Self::Variable {
+        (
+            self.c0.get_variable().clone(),
+            self.c1.get_variable().clone(),
+            self.c2.get_variable().clone(),
+        )
+    }
+
+    #[inline]
+    fn zero<CS: ConstraintSystem<E>>(mut cs: CS) -> Result<Self, SynthesisError> {
+        let c0 = FpGadget::zero(cs.ns(|| "c0"))?;
+        let c1 = FpGadget::zero(cs.ns(|| "c1"))?;
+        let c2 = FpGadget::zero(cs.ns(|| "c2"))?;
+        Ok(Self::new(c0, c1, c2))
+    }
+
+    #[inline]
+    fn one<CS: ConstraintSystem<E>>(mut cs: CS) -> Result<Self, SynthesisError> {
+        let c0 = FpGadget::one(cs.ns(|| "c0"))?;
+        let c1 = FpGadget::zero(cs.ns(|| "c1"))?;
+        let c2 = FpGadget::zero(cs.ns(|| "c2"))?;
+        Ok(Self::new(c0, c1, c2))
+    }
+
+    #[inline]
+    fn add<CS: ConstraintSystem<E>>(
+        &self,
+        mut cs: CS,
+        other: &Self,
+    ) -> Result<Self, SynthesisError> {
+        let c0 = self.c0.add(&mut cs.ns(|| "add c0"), &other.c0)?;
+        let c1 = self.c1.add(&mut cs.ns(|| "add c1"), &other.c1)?;
+        let c2 = self.c2.add(&mut cs.ns(|| "add c2"), &other.c2)?;
+        Ok(Self::new(c0, c1, c2))
+    }
+
+    #[inline]
+    fn sub<CS: ConstraintSystem<E>>(
+        &self,
+        mut cs: CS,
+        other: &Self,
+    ) -> Result<Self, SynthesisError> {
+        let c0 = self.c0.sub(&mut cs.ns(|| "sub c0"), &other.c0)?;
+        let c1 = self.c1.sub(&mut cs.ns(|| "sub c1"), &other.c1)?;
+        let c2 = self.c2.sub(&mut cs.ns(|| "sub c2"), &other.c2)?;
+        Ok(Self::new(c0, c1, c2))
+    }
+
+    #[inline]
+    fn double<CS: ConstraintSystem<E>>(&self, cs: CS) -> Result<Self, SynthesisError> {
+        let mut result = self.clone();
+        result.double_in_place(cs)?;
+        Ok(result)
+    }
+
+    #[inline]
+    fn double_in_place<CS: ConstraintSystem<E>>(
+        &mut self,
+        mut cs: CS,
+    ) -> Result<&mut Self, SynthesisError> {
+        self.c0.double_in_place(&mut cs.ns(|| "double c0"))?;
+        self.c1.double_in_place(&mut cs.ns(|| "double c1"))?;
+        self.c2.double_in_place(&mut cs.ns(|| "double c2"))?;
+        Ok(self)
+    }
+
+    #[inline]
+    fn negate<CS: ConstraintSystem<E>>(&self, cs: CS) -> Result<Self, SynthesisError> {
+        let mut result = self.clone();
+        result.negate_in_place(cs)?;
+        Ok(result)
+    }
+
+    #[inline]
+    fn negate_in_place<CS: ConstraintSystem<E>>(
+        &mut self,
+        mut cs: CS,
+    ) -> Result<&mut Self, SynthesisError> {
+        self.c0.negate_in_place(&mut cs.ns(|| "negate c0"))?;
+        self.c1.negate_in_place(&mut cs.ns(|| "negate c1"))?;
+        self.c2.negate_in_place(&mut cs.ns(|| "negate c2"))?;
+        Ok(self)
+    }
+
+    /// Use the Toom-Cook-3x method to compute multiplication.
+    #[inline]
+    fn mul<CS: ConstraintSystem<E>>(
+        &self,
+        mut cs: CS,
+        other: &Self,
+    ) -> Result<Self, SynthesisError> {
+        // Uses Toom-Cook-3x multiplication from
+        //
+        // Reference:
+        // "Multiplication and Squaring on Pairing-Friendly Fields"
+        //    Devegili, OhEigeartaigh, Scott, Dahab
+
+        // v0 = a(0)b(0)   = a0 * b0
+        let v0 = self.c0.mul(&mut cs.ns(|| "Calc v0"), &other.c0)?;
+
+        // v1 = a(1)b(1)   = (a0 + a1 + a2)(b0 + b1 + b2)
+        let v1 = {
+            let mut v1_cs = cs.ns(|| "compute v1");
+            let a0_plus_a1_plus_a2 = self
+                .c0
+                .add(v1_cs.ns(|| "a0 + a1"), &self.c1)?
+                .add(v1_cs.ns(|| "a0 + a1 + a2"), &self.c2)?;
+            let b0_plus_b1_plus_b2 = other
+                .c0
+                .add(v1_cs.ns(|| "b0 + b1"), &other.c1)?
+                .add(v1_cs.ns(|| "b0 + b1 + b2"), &other.c2)?;
+
+            a0_plus_a1_plus_a2.mul(
+                v1_cs.ns(|| "(a0 + a1 + a2)(b0 + b1 + b2)"),
+                &b0_plus_b1_plus_b2,
+            )?
+        };
+
+        // v2 = a(−1)b(−1) = (a0 − a1 + a2)(b0 − b1 + b2)
+        let v2 = {
+            let mut v2_cs = cs.ns(|| "compute v2");
+
+            let a0_minus_a1_plus_a2 = self
+                .c0
+                .sub(v2_cs.ns(|| "a0 - a1"), &self.c1)?
+                .add(v2_cs.ns(|| "a0 - a1 + a2"), &self.c2)?;
+
+            let b0_minus_b1_plus_b2 = other
+                .c0
+                .sub(v2_cs.ns(|| "b0 - b1"), &other.c1)?
+                .add(v2_cs.ns(|| "b0 - b1 + b2"), &other.c2)?;
+
+            a0_minus_a1_plus_a2.mul(
+                v2_cs.ns(|| "(a0 - a1 + a2)(b0 - b1 + b2)"),
+                &b0_minus_b1_plus_b2,
+            )?
+        };
+
+        // v3 = a(2)b(2)   = (a0 + 2a1 + 4a2)(b0 + 2b1 + 4b2)
+        let v3 = {
+            let v3_cs = &mut cs.ns(|| "compute v3");
+
+            let a1_double = self.c1.double(v3_cs.ns(|| "2 * a1"))?;
+            let a2_quad = self
+                .c2
+                .double(v3_cs.ns(|| "2 * a2"))?
+                .double(v3_cs.ns(|| "4 * a2"))?;
+
+            let a0_plus_2_a1_plus_4_a2 = self
+                .c0
+                .add(v3_cs.ns(|| "a0 + 2a1"), &a1_double)?
+                .add(v3_cs.ns(|| "a0 + 2a1 + 4a2"), &a2_quad)?;
+
+            let b1_double = other.c1.double(v3_cs.ns(|| "2 * b1"))?;
+            let b2_quad = other
+                .c2
+                .double(v3_cs.ns(|| "2 * b2"))?
+                .double(v3_cs.ns(|| "4 * b2"))?;
+            let b0_plus_2_b1_plus_4_b2 = other
+                .c0
+                .add(v3_cs.ns(|| "b0 + 2b1"), &b1_double)?
+                .add(v3_cs.ns(|| "b0 + 2b1 + 4b2"), &b2_quad)?;
+
+            a0_plus_2_a1_plus_4_a2.mul(
+                v3_cs.ns(|| "(a0 + 2a1 + 4a2)(b0 + 2b1 + 4b2)"),
+                &b0_plus_2_b1_plus_4_b2,
+            )?
+        };
+
+        // v4 = a(∞)b(∞)   = a2 * b2
+        let v4 = self.c2.mul(cs.ns(|| "v2: a2 * b2"), &other.c2)?;
+
+        Self::interpolate(cs.ns(|| "interpolate"), v0, v1, v2, v3, v4)
+    }
+
+    /// Use the Toom-Cook-3x method to compute squaring.
+    #[inline]
+    fn square<CS: ConstraintSystem<E>>(&self, mut cs: CS) -> Result<Self, SynthesisError> {
+        // Uses Toom-Cook-3x multiplication from
+        //
+        // Reference:
+        // "Multiplication and Squaring on Pairing-Friendly Fields"
+        //    Devegili, OhEigeartaigh, Scott, Dahab
+
+        // v0 = a(0)^2 = a0^2
+        let v0 = self.c0.square(&mut cs.ns(|| "Calc v0"))?;
+
+        // v1 = a(1)^2 = (a0 + a1 + a2)^2
+        let v1 = {
+            let a0_plus_a1_plus_a2 = self
+                .c0
+                .add(cs.ns(|| "a0 + a1"), &self.c1)?
+                .add(cs.ns(|| "a0 + a1 + a2"), &self.c2)?;
+            a0_plus_a1_plus_a2.square(&mut cs.ns(|| "(a0 + a1 + a2)^2"))?
+        };
+
+        // v2 = a(−1)^2 = (a0 − a1 + a2)^2
+        let v2 = {
+            let a0_minus_a1_plus_a2 = self
+                .c0
+                .sub(cs.ns(|| "a0 - a1"), &self.c1)?
+                .add(cs.ns(|| "a0 - a1 + a2"), &self.c2)?;
+            a0_minus_a1_plus_a2.square(&mut cs.ns(|| "(a0 - a1 + a2)^2"))?
+        };
+
+        // v3 = a(2)^2 = (a0 + 2a1 + 4a2)^2
+        let v3 = {
+            let a1_double = self.c1.double(cs.ns(|| "2a1"))?;
+            let a2_quad = self.c2.double(cs.ns(|| "2a2"))?.double(cs.ns(|| "4a2"))?;
+            let a0_plus_2_a1_plus_4_a2 = self
+                .c0
+                .add(cs.ns(|| "a0 + 2a1"), &a1_double)?
+                .add(cs.ns(|| "a0 + 2a1 + 4a2"), &a2_quad)?;
+
+            a0_plus_2_a1_plus_4_a2.square(&mut cs.ns(|| "(a0 + 2a1 + 4a2)^2"))?
+        };
+
+        // v4 = a(∞)^2 = a2^2
+        let v4 = self.c2.square(&mut cs.ns(|| "a2^2"))?;
+
+        Self::interpolate(cs.ns(|| "interpolate"), v0, v1, v2, v3, v4)
+    }
+
+    #[inline]
+    fn inverse<CS: ConstraintSystem<E>>(&self, mut cs: CS) -> Result<Self, SynthesisError> {
+        let inverse = Self::alloc(&mut cs.ns(|| "alloc inverse"), || {
+            self.get_value().and_then(|val| val.inverse()).get()
+        })?;
+        let one = Self::one(cs.ns(|| "one"))?;
+        inverse.mul_equals(cs.ns(|| "check inverse"), &self, &one)?;
+        Ok(inverse)
+    }
+
+    #[inline]
+    fn add_constant<CS: ConstraintSystem<E>>(
+        &self,
+        cs: CS,
+        other: &Fp3<P>,
+    ) -> Result<Self, SynthesisError> {
+        let mut result = self.clone();
+        let _ = result.add_constant_in_place(cs, other)?;
+        Ok(result)
+    }
+
+    #[inline]
+    fn add_constant_in_place<CS: ConstraintSystem<E>>(
+        &mut self,
+        mut cs: CS,
+        other: &Fp3<P>,
+    ) -> Result<&mut Self, SynthesisError> {
+        self.c0.add_constant_in_place(cs.ns(|| "c0"), &other.c0)?;
+        self.c1.add_constant_in_place(cs.ns(|| "c1"), &other.c1)?;
+        self.c2.add_constant_in_place(cs.ns(|| "c2"), &other.c2)?;
+        Ok(self)
+    }
+
+    fn mul_by_constant<CS: ConstraintSystem<E>>(
+        &self,
+        mut cs: CS,
+        fe: &Fp3<P>,
+    ) -> Result<Self, SynthesisError> {
+        // Schoolbook multiplication (see `Fp3::mul_assign`).
+        // Doesn't need any constraints; returns linear combinations of
+        // `self`'s variables.
+        let (a0, a1, a2) = (&self.c0, &self.c1, &self.c2);
+        let (b0, b1, b2) = (fe.c0, fe.c1, fe.c2);
+        let beta_b1 = b1 * &P::NONRESIDUE;
+        let beta_b2 = b2 * &P::NONRESIDUE;
+
+        // c0 = a0 * b0 + beta * (a1 * b2 + a2 * b1)
+        let c0 = {
+            let mut cs = cs.ns(|| "c0");
+            let a0b0 = a0.mul_by_constant(cs.ns(|| "a0 * b0"), &b0)?;
+            let a1b2 = a1.mul_by_constant(cs.ns(|| "beta * a1 * b2"), &beta_b2)?;
+            let a2b1 = a2.mul_by_constant(cs.ns(|| "beta * a2 * b1"), &beta_b1)?;
+            a0b0.add(cs.ns(|| "add 1"), &a1b2)?
+                .add(cs.ns(|| "add 2"), &a2b1)?
+        };
+        // c1 = a0 * b1 + a1 * b0 + beta * a2 * b2
+        let c1 = {
+            let mut cs = cs.ns(|| "c1");
+            let a0b1 = a0.mul_by_constant(cs.ns(|| "a0 * b1"), &b1)?;
+            let a1b0 = a1.mul_by_constant(cs.ns(|| "a1 * b0"), &b0)?;
+            let a2b2 = a2.mul_by_constant(cs.ns(|| "beta * a2 * b2"), &beta_b2)?;
+            a0b1.add(cs.ns(|| "add 1"), &a1b0)?
+                .add(cs.ns(|| "add 2"), &a2b2)?
+        };
+        // c2 = a0 * b2 + a1 * b1 + a2 * b0
+        let c2 = {
+            let mut cs = cs.ns(|| "c2");
+            let a0b2 = a0.mul_by_constant(cs.ns(|| "a0 * b2"), &b2)?;
+            let a1b1 = a1.mul_by_constant(cs.ns(|| "a1 * b1"), &b1)?;
+            let a2b0 = a2.mul_by_constant(cs.ns(|| "a2 * b0"), &b0)?;
+            a0b2.add(cs.ns(|| "add 1"), &a1b1)?
+                .add(cs.ns(|| "add 2"), &a2b0)?
+        };
+        Ok(Self::new(c0, c1, c2))
+    }
+
+    fn frobenius_map<CS: ConstraintSystem<E>>(
+        &self,
+        cs: CS,
+        power: usize,
+    ) -> Result<Self, SynthesisError> {
+        let mut result = self.clone();
+        result.frobenius_map_in_place(cs, power)?;
+        Ok(result)
+    }
+
+    fn frobenius_map_in_place<CS: ConstraintSystem<E>>(
+        &mut self,
+        mut cs: CS,
+        power: usize,
+    ) -> Result<&mut Self, SynthesisError> {
+        self.c1.mul_by_constant_in_place(
+            cs.ns(|| "c1_power"),
+            &P::FROBENIUS_COEFF_FP3_C1[power % 3],
+        )?;
+        self.c2.mul_by_constant_in_place(
+            cs.ns(|| "c2_power"),
+            &P::FROBENIUS_COEFF_FP3_C2[power % 3],
+        )?;
+
+        Ok(self)
+    }
+
+    fn cost_of_mul() -> usize {
+        5 * FpGadget::<E>::cost_of_mul()
+    }
+
+    fn cost_of_square() -> usize {
+        5 * FpGadget::<E>::cost_of_square()
+    }
+
+    fn cost_of_inv() -> usize {
+        Self::cost_of_mul_equals()
+    }
+}
+
+impl<P: Fp3Parameters<Fp = E::Fr>, E: PairingEngine> PartialEq for Fp3Gadget<P, E> {
+    fn eq(&self, other: &Self) -> bool {
+        self.c0 == other.c0 && self.c1 == other.c1 && self.c2 == other.c2
+    }
+}
+
+impl<P: Fp3Parameters<Fp = E::Fr>, E: PairingEngine> Eq for Fp3Gadget<P, E> {}
+
+impl<P: Fp3Parameters<Fp = E::Fr>, E: PairingEngine> EqGadget<E> for Fp3Gadget<P, E> {}
+
+impl<P: Fp3Parameters<Fp = E::Fr>, E: PairingEngine> ConditionalEqGadget<E> for Fp3Gadget<P, E> {
+    #[inline]
+    fn conditional_enforce_equal<CS: ConstraintSystem<E>>(
+        &self,
+        mut cs: CS,
+        other: &Self,
+        condition: &Boolean,
+    ) -> Result<(), SynthesisError> {
+        self.c0
+            .conditional_enforce_equal(&mut cs.ns(|| "c0"), &other.c0, condition)?;
+        self.c1
+            .conditional_enforce_equal(&mut cs.ns(|| "c1"), &other.c1, condition)?;
+        self.c2
+            .conditional_enforce_equal(&mut cs.ns(|| "c2"), &other.c2, condition)?;
+        Ok(())
+    }
+
+    fn cost() -> usize {
+        3 * <FpGadget<E> as ConditionalEqGadget<E>>::cost()
+    }
+}
+
+impl<P: Fp3Parameters<Fp = E::Fr>, E: PairingEngine> NEqGadget<E> for Fp3Gadget<P, E> {
+    #[inline]
+    fn enforce_not_equal<CS: ConstraintSystem<E>>(
+        &self,
+        mut cs: CS,
+        other: &Self,
+    ) -> Result<(), SynthesisError> {
+        self.c0.enforce_not_equal(&mut cs.ns(|| "c0"), &other.c0)?;
+        self.c1.enforce_not_equal(&mut cs.ns(|| "c1"), &other.c1)?;
+        self.c2.enforce_not_equal(&mut cs.ns(|| "c2"), &other.c2)?;
+        Ok(())
+    }
+
+    fn cost() -> usize {
+        3 * <FpGadget<E> as NEqGadget<E>>::cost()
+    }
+}
+
+impl<P: Fp3Parameters<Fp = E::Fr>, E: PairingEngine> ToBitsGadget<E> for Fp3Gadget<P, E> {
+    fn to_bits<CS: ConstraintSystem<E>>(&self, mut cs: CS) -> Result<Vec<Boolean>, SynthesisError> {
+        let mut c0 = self.c0.to_bits(cs.ns(|| "c0"))?;
+        let mut c1 = self.c1.to_bits(cs.ns(|| "c1"))?;
+        let mut c2 = self.c2.to_bits(cs.ns(|| "c2"))?;
+
+        c0.append(&mut c1);
+        c0.append(&mut c2);
+
+        Ok(c0)
+    }
+
+    fn to_bits_strict<CS: ConstraintSystem<E>>(
+        &self,
+        mut cs: CS,
+    ) -> Result<Vec<Boolean>, SynthesisError> {
+        let mut c0 = self.c0.to_bits_strict(cs.ns(|| "c0"))?;
+        let mut c1 = self.c1.to_bits_strict(cs.ns(|| "c1"))?;
+        let mut c2 = self.c2.to_bits_strict(cs.ns(|| "c2"))?;
+
+        c0.append(&mut c1);
+        c0.append(&mut c2);
+
+        Ok(c0)
+    }
+}
+
+impl<P: Fp3Parameters<Fp = E::Fr>, E: PairingEngine> ToBytesGadget<E> for Fp3Gadget<P, E> {
+    fn to_bytes<CS: ConstraintSystem<E>>(&self, mut cs: CS) -> Result<Vec<UInt8>, SynthesisError> {
+        let mut c0 = self.c0.to_bytes(cs.ns(|| "c0"))?;
+        let mut c1 = self.c1.to_bytes(cs.ns(|| "c1"))?;
+        let mut c2 = self.c2.to_bytes(cs.ns(|| "c2"))?;
+
+        c0.append(&mut c1);
+        c0.append(&mut c2);
+
+        Ok(c0)
+    }
+
+    fn to_bytes_strict<CS: ConstraintSystem<E>>(
+        &self,
+        mut cs: CS,
+    ) -> Result<Vec<UInt8>, SynthesisError> {
+        let mut c0 = self.c0.to_bytes_strict(cs.ns(|| "c0"))?;
+        let mut c1 = self.c1.to_bytes_strict(cs.ns(|| "c1"))?;
+        let mut c2 = self.c2.to_bytes_strict(cs.ns(|| "c2"))?;
+
+        c0.append(&mut c1);
+        c0.append(&mut c2);
+
+        Ok(c0)
+    }
+}
+
+impl<P: Fp3Parameters<Fp = E::Fr>, E: PairingEngine> Clone for Fp3Gadget<P, E> {
+    fn clone(&self) -> Self {
+        Self::new(self.c0.clone(), self.c1.clone(), self.c2.clone())
+    }
+}
+
+impl<P: Fp3Parameters<Fp = E::Fr>, E: PairingEngine> CondSelectGadget<E> for Fp3Gadget<P, E> {
+    #[inline]
+    fn conditionally_select<CS: ConstraintSystem<E>>(
+        mut cs: CS,
+        cond: &Boolean,
+        first: &Self,
+        second: &Self,
+    ) -> Result<Self, SynthesisError> {
+        let c0 =
+            FpGadget::<E>::conditionally_select(&mut cs.ns(|| "c0"), cond, &first.c0, &second.c0)?;
+        let c1 =
+            FpGadget::<E>::conditionally_select(&mut cs.ns(|| "c1"), cond, &first.c1, &second.c1)?;
+        let c2 =
+            FpGadget::<E>::conditionally_select(&mut cs.ns(|| "c2"), cond, &first.c2, &second.c2)?;
+
+        Ok(Self::new(c0, c1, c2))
+    }
+
+    fn cost() -> usize {
+        3 * <FpGadget<E> as CondSelectGadget<E>>::cost()
+    }
+}
+
+impl<P: Fp3Parameters<Fp = E::Fr>, E: PairingEngine> TwoBitLookupGadget<E> for Fp3Gadget<P, E> {
+    type TableConstant = Fp3<P>;
+    fn two_bit_lookup<CS: ConstraintSystem<E>>(
+        mut cs: CS,
+        b: &[Boolean],
+        c: &[Self::TableConstant],
+    ) -> Result<Self, SynthesisError> {
+        let c0s = c.iter().map(|f| f.c0).collect::<Vec<_>>();
+        let c1s = c.iter().map(|f| f.c1).collect::<Vec<_>>();
+        let c2s = c.iter().map(|f| f.c2).collect::<Vec<_>>();
+        let c0 = FpGadget::two_bit_lookup(cs.ns(|| "Lookup c0"), b, &c0s)?;
+        let c1 = FpGadget::two_bit_lookup(cs.ns(|| "Lookup c1"), b, &c1s)?;
+        let c2 = FpGadget::two_bit_lookup(cs.ns(|| "Lookup c2"), b, &c2s)?;
+        Ok(Self::new(c0, c1, c2))
+    }
+
+    fn cost() -> usize {
+        3 * <FpGadget<E> as TwoBitLookupGadget<E>>::cost()
+    }
+}
+
+impl<P: Fp3Parameters<Fp = E::Fr>, E: PairingEngine> KBitLookupGadget<E> for Fp3Gadget<P, E> {
+    type TableConstant = Fp3<P>;
+    fn k_bit_lookup<CS: ConstraintSystem<E>>(
+        mut cs: CS,
+        b: &[Boolean],
+        c: &[Self::TableConstant],
+    ) -> Result<Self, SynthesisError> {
+        let c0s = c.iter().map(|f| f.c0).collect::<Vec<_>>();
+        let c1s = c.iter().map(|f| f.c1).collect::<Vec<_>>();
+        let c2s = c.iter().map(|f| f.c2).collect::<Vec<_>>();
+        let c0 = FpGadget::k_bit_lookup(cs.ns(|| "Lookup c0"), b, &c0s)?;
+        let c1 = FpGadget::k_bit_lookup(cs.ns(|| "Lookup c1"), b, &c1s)?;
+        let c2 = FpGadget::k_bit_lookup(cs.ns(|| "Lookup c2"), b, &c2s)?;
+        Ok(Self::new(c0, c1, c2))
+    }
+
+    fn cost(k: usize) -> usize {
+        3 * <FpGadget<E> as KBitLookupGadget<E>>::cost(k)
+    }
+}
+
+impl<P: Fp3Parameters<Fp = E::Fr>, E: PairingEngine> ThreeBitCondNegLookupGadget<E>
+    for Fp3Gadget<P, E>
+{
+    type TableConstant = Fp3<P>;
+    fn three_bit_cond_neg_lookup<CS: ConstraintSystem<E>>(
+        mut cs: CS,
+        b: &[Boolean],
+        b0b1: &Boolean,
+        c: &[Self::TableConstant],
+    ) -> Result<Self, SynthesisError> {
+        let c0s = c.iter().map(|f| f.c0).collect::<Vec<_>>();
+        let c1s = c.iter().map(|f| f.c1).collect::<Vec<_>>();
+        let c2s = c.iter().map(|f| f.c2).collect::<Vec<_>>();
+        let c0 = FpGadget::three_bit_cond_neg_lookup(cs.ns(|| "Lookup c0"), b, b0b1, &c0s)?;
+        let c1 = FpGadget::three_bit_cond_neg_lookup(cs.ns(|| "Lookup c1"), b, b0b1, &c1s)?;
+        let c2 = FpGadget::three_bit_cond_neg_lookup(cs.ns(|| "Lookup c2"), b, b0b1, &c2s)?;
+        Ok(Self::new(c0, c1, c2))
+    }
+
+    fn cost() -> usize {
+        3 * <FpGadget<E> as ThreeBitCondNegLookupGadget<E>>::cost()
+    }
+}
+
+impl<P: Fp3Parameters<Fp = E::Fr>, E: PairingEngine> AllocGadget<Fp3<P>, E> for Fp3Gadget<P, E> {
+    #[inline]
+    fn alloc<F, T, CS: ConstraintSystem<E>>(
+        mut cs: CS,
+        value_gen: F,
+    ) -> Result<Self, SynthesisError>
+    where
+        F: FnOnce() -> Result<T, SynthesisError>,
+        T: Borrow<Fp3<P>>,
+    {
+        let (c0, c1, c2) = match value_gen() {
+            Ok(fe) => {
+                let fe = *fe.borrow();
+                (Ok(fe.c0), Ok(fe.c1), Ok(fe.c2))
+            },
+            Err(_) => (
+                Err(SynthesisError::AssignmentMissing),
+                Err(SynthesisError::AssignmentMissing),
+                Err(SynthesisError::AssignmentMissing),
+            ),
+        };
+
+        let c0 = FpGadget::alloc(&mut cs.ns(|| "c0"), || c0)?;
+        let c1 = FpGadget::alloc(&mut cs.ns(|| "c1"), || c1)?;
+        let c2 = FpGadget::alloc(&mut cs.ns(|| "c2"), || c2)?;
+        Ok(Self::new(c0, c1, c2))
+    }
+
+    #[inline]
+    fn alloc_input<F, T, CS: ConstraintSystem<E>>(
+        mut cs: CS,
+        value_gen: F,
+    ) -> Result<Self, SynthesisError>
+    where
+        F: FnOnce() -> Result<T, SynthesisError>,
+        T: Borrow<Fp3<P>>,
+    {
+        let (c0, c1, c2) = match value_gen() {
+            Ok(fe) => {
+                let fe = *fe.borrow();
+                (Ok(fe.c0), Ok(fe.c1), Ok(fe.c2))
+            },
+            Err(_) => (
+                Err(SynthesisError::AssignmentMissing),
+                Err(SynthesisError::AssignmentMissing),
+                Err(SynthesisError::AssignmentMissing),
+            ),
+        };
+
+        let c0 = FpGadget::alloc_input(&mut cs.ns(|| "c0"), || c0)?;
+        let c1 = FpGadget::alloc_input(&mut cs.ns(|| "c1"), || c1)?;
+        let c2 = FpGadget::alloc_input(&mut cs.ns(|| "c2"), || c2)?;
+        Ok(Self::new(c0, c1, c2))
+    }
+}
@@ -0,0 +1,776 @@
+use algebra::{
+    fields::{
+        fp3::{Fp3, Fp3Parameters},
+        fp6_2over3::{Fp6, Fp6Parameters},
+        Field,
+    },
+    BitIterator, PairingEngine,
+};
+use snark::{ConstraintSystem, SynthesisError};
+use std::{borrow::Borrow, fmt::Debug, marker::PhantomData};
+
+use super::{fp::FpGadget, FieldGadget};
+
+use crate::{
+    boolean::Boolean,
+    uint8::UInt8,
+    utils::{
+        AllocGadget, CondSelectGadget, ConditionalEqGadget, EqGadget, KBitLookupGadget, NEqGadget,
+        ThreeBitCondNegLookupGadget, ToBitsGadget, ToBytesGadget, TwoBitLookupGadget,
+    },
+    Assignment,
+};
+
+type Fp3Gadget<P, E> = super::fp3::Fp3Gadget<<P as Fp6Parameters>::Fp3Params, E>;
+type Fp3GadgetVariable<P, E> =
+    <Fp3Gadget<P, E> as FieldGadget<Fp3<<P as Fp6Parameters>::Fp3Params>, E>>::Variable;
+
+#[derive(Derivative)]
+#[derivative(Debug(bound = "E::Fr: Debug"))]
+#[must_use]
+pub struct Fp6Gadget<P, E: PairingEngine>
+where
+    P: Fp6Parameters,
+    P::Fp3Params: Fp3Parameters<Fp = E::Fr>,
+{
+    pub c0: Fp3Gadget<P, E>,
+    pub c1: Fp3Gadget<P, E>,
+    #[derivative(Debug = "ignore")]
+    _params: PhantomData<P>,
+}
+
+impl<P, E: PairingEngine> Fp6Gadget<P, E>
+where
+    P: Fp6Parameters,
+    P::Fp3Params: Fp3Parameters<Fp = E::Fr>,
+{
+    #[inline]
+    pub fn new(c0: Fp3Gadget<P, E>, c1: Fp3Gadget<P, E>) -> Self {
+        Self {
+            c0,
+            c1,
+            _params: PhantomData,
+        }
+    }
+
+    /// Multiply by quadratic nonresidue v.
+    #[inline]
+    pub fn mul_fp3_by_nonresidue<CS: ConstraintSystem<E>>(
+        cs: CS,
+        fe: &Fp3Gadget<P, E>,
+    ) -> Result<Fp3Gadget<P, E>, SynthesisError> {
+        let new_c0 = Fp3Gadget::<P, E>::mul_fp_gadget_by_nonresidue(cs, &fe.c2)?;
+        let new_c1 = fe.c0.clone();
+        let new_c2 = fe.c1.clone();
+        Ok(Fp3Gadget::<P, E>::new(new_c0, new_c1, new_c2))
+    }
+
+    /// Computes the inverse of `self`, assuming `self` lies in the cyclotomic
+    /// subgroup.
+    #[inline]
+    pub fn unitary_inverse<CS: ConstraintSystem<E>>(&self, cs: CS) -> Result<Self, SynthesisError> {
+        Ok(Self::new(self.c0.clone(), self.c1.negate(cs)?))
+    }
+
+    #[inline]
+    pub fn conjugate_in_place<CS: ConstraintSystem<E>>(
+        &mut self,
+        cs: CS,
+    ) -> Result<&mut Self, SynthesisError> {
+        self.c1.negate_in_place(cs)?;
+        Ok(self)
+    }
+
+    /// Squares `(x0 + x1 * s)` in `Fp[s]/(s^2 - NONRESIDUE)`, where
+    /// `NONRESIDUE` is the cubic nonresidue of `Fp3`.
+    #[inline]
+    fn square_in_fp2<CS: ConstraintSystem<E>>(
+        mut cs: CS,
+        x0: &FpGadget<E>,
+        x1: &FpGadget<E>,
+    ) -> Result<(FpGadget<E>, FpGadget<E>), SynthesisError> {
+        let non_residue = <P::Fp3Params as Fp3Parameters>::NONRESIDUE;
+
+        let v0 = x0.mul(cs.ns(|| "x0 * x1"), x1)?;
+        let x0_plus_x1 = x0.add(cs.ns(|| "x0 + x1"), x1)?;
+        let x0_plus_nr_x1 = x1
+            .mul_by_constant(cs.ns(|| "nr * x1"), &non_residue)?
+            .add(cs.ns(|| "x0 + nr * x1"), x0)?;
+        let one_plus_nr_v0 = v0.mul_by_constant(
+            cs.ns(|| "(1 + nr) * v0"),
+            &(<P::Fp3Params as Fp3Parameters>::Fp::one() + &non_residue),
+        )?;
+        let c0 = x0_plus_x1
+            .mul(cs.ns(|| "(x0 + x1) * (x0 + nr * x1)"), &x0_plus_nr_x1)?
+            .sub(cs.ns(|| "- (1 + nr) * v0"), &one_plus_nr_v0)?;
+        let c1 = v0.double(cs.ns(|| "2 * v0"))?;
+        Ok((c0, c1))
+    }
+
+    /// Squares `self`, assuming it lies in the cyclotomic subgroup.
+    ///
+    /// Writes `Fp6` as `Fp2[w]/(w^3 - s)` with `Fp2 = Fp[s]/(s^2 - NONRESIDUE)`
+    /// and uses the Granger-Scott formulas from "Faster Squaring in the
+    /// Cyclotomic Subgroup of Sixth Degree Extensions".
+    pub fn cyclotomic_square<CS: ConstraintSystem<E>>(
+        &self,
+        mut cs: CS,
+    ) -> Result<Self, SynthesisError> {
+        let non_residue = <P::Fp3Params as Fp3Parameters>::NONRESIDUE;
+
+        // a = (c0.c0, c1.c1), b = (c1.c0, c0.c2), c = (c0.c1, c1.c2)
+        let (a0, a1) = (&self.c0.c0, &self.c1.c1);
+        let (b0, b1) = (&self.c1.c0, &self.c0.c2);
+        let (c0, c1) = (&self.c0.c1, &self.c1.c2);
+
+        let (a_sq0, a_sq1) = Self::square_in_fp2(cs.ns(|| "a^2"), a0, a1)?;
+        let (b_sq0, b_sq1) = Self::square_in_fp2(cs.ns(|| "b^2"), b0, b1)?;
+        let (c_sq0, c_sq1) = Self::square_in_fp2(cs.ns(|| "c^2"), c0, c1)?;
+
+        // A = 3 * a^2 - 2 * conj(a)
+        let a_a = {
+            let mut cs = cs.ns(|| "A.0");
+            a_sq0
+                .sub(cs.ns(|| "1"), a0)?
+                .double(cs.ns(|| "2"))?
+                .add(cs.ns(|| "3"), &a_sq0)?
+        };
+        let a_b = {
+            let mut cs = cs.ns(|| "A.1");
+            a_sq1
+                .add(cs.ns(|| "1"), a1)?
+                .double(cs.ns(|| "2"))?
+                .add(cs.ns(|| "3"), &a_sq1)?
+        };
+
+        // B = 3 * s * c^2 + 2 * conj(b)
+        let b_a = {
+            let mut cs = cs.ns(|| "B.0");
+            let tmp = c_sq1.mul_by_constant(cs.ns(|| "1"), &non_residue)?;
+            tmp.add(cs.ns(|| "2"), b0)?
+                .double(cs.ns(|| "3"))?
+                .add(cs.ns(|| "4"), &tmp)?
+        };
+        let b_b = {
+            let mut cs = cs.ns(|| "B.1");
+            c_sq0
+                .sub(cs.ns(|| "1"), b1)?
+                .double(cs.ns(|| "2"))?
+                .add(cs.ns(|| "3"), &c_sq0)?
+        };
+
+        // C = 3 * b^2 - 2 * conj(c)
+        let c_a = {
+            let mut cs = cs.ns(|| "C.0");
+            b_sq0
+                .sub(cs.ns(|| "1"), c0)?
+                .double(cs.ns(|| "2"))?
+                .add(cs.ns(|| "3"), &b_sq0)?
+        };
+        let c_b = {
+            let mut cs = cs.ns(|| "C.1");
+            b_sq1
+                .add(cs.ns(|| "1"), c1)?
+                .double(cs.ns(|| "2"))?
+                .add(cs.ns(|| "3"), &b_sq1)?
+        };
+
+        Ok(Self::new(
+            Fp3Gadget::<P, E>::new(a_a, c_a, b_b),
+            Fp3Gadget::<P, E>::new(b_a, a_b, c_b),
+        ))
+    }
+
+    #[inline]
+    pub fn cyclotomic_exp<CS: ConstraintSystem<E>, S: AsRef<[u64]>>(
+        &self,
+        mut cs: CS,
+        exp: S,
+    ) -> Result<Self, SynthesisError> {
+        let mut res = Self::one(cs.ns(|| "one"))?;
+        let mut found_one = false;
+        for (j, i) in BitIterator::new(exp).enumerate() {
+            if found_one {
+                res = res.cyclotomic_square(cs.ns(|| format!("res_square_{:?}", j)))?;
+            } else {
+                found_one = i;
+            }
+            if i {
+                res.mul_in_place(cs.ns(|| format!("res_mul2_{:?}", j)), self)?;
+            }
+        }
+        Ok(res)
+    }
+}
+
+impl<P, E: PairingEngine> FieldGadget<Fp6<P>, E> for Fp6Gadget<P, E>
+where
+    P: Fp6Parameters,
+    P::Fp3Params: Fp3Parameters<Fp = E::Fr>,
+{
+    type Variable = (Fp3GadgetVariable<P, E>, Fp3GadgetVariable<P, E>);
+
+    #[inline]
+    fn get_value(&self) -> Option<Fp6<P>> {
+        match (self.c0.get_value(), self.c1.get_value()) {
+            (Some(c0), Some(c1)) => Some(Fp6::new(c0, c1)),
+            (..) => None,
+        }
+    }
+
+    #[inline]
+    fn get_variable(&self) -> Self::Variable {
+        (self.c0.get_variable(), self.c1.get_variable())
+    }
+
+    #[inline]
+    fn zero<CS: ConstraintSystem<E>>(mut cs: CS) -> Result<Self, SynthesisError> {
+        let c0 = Fp3Gadget::<P, E>::zero(cs.ns(|| "c0"))?;
+        let c1 = Fp3Gadget::<P, E>::zero(cs.ns(|| "c1"))?;
+        Ok(Self::new(c0, c1))
+    }
+
+    #[inline]
+    fn one<CS: ConstraintSystem<E>>(mut cs: CS) -> Result<Self, SynthesisError> {
+        let c0 = Fp3Gadget::<P, E>::one(cs.ns(|| "c0"))?;
+        let c1 = Fp3Gadget::<P, E>::zero(cs.ns(|| "c1"))?;
+        Ok(Self::new(c0, c1))
+    }
+
+    #[inline]
+    fn add<CS: ConstraintSystem<E>>(
+        &self,
+        mut cs: CS,
+        other: &Self,
+    ) -> Result<Self, SynthesisError> {
+        let c0 = self.c0.add(cs.ns(|| "c0"), &other.c0)?;
+        let c1 = self.c1.add(cs.ns(|| "c1"), &other.c1)?;
+        Ok(Self::new(c0, c1))
+    }
+
+    #[inline]
+    fn add_in_place<CS: ConstraintSystem<E>>(
+        &mut self,
+        mut cs: CS,
+        other: &Self,
+    ) -> Result<&mut Self, SynthesisError> {
+        self.c0.add_in_place(cs.ns(|| "c0"), &other.c0)?;
+        self.c1.add_in_place(cs.ns(|| "c1"), &other.c1)?;
+        Ok(self)
+    }
+
+    #[inline]
+    fn sub<CS: ConstraintSystem<E>>(
+        &self,
+        mut cs: CS,
+        other: &Self,
+    ) -> Result<Self, SynthesisError> {
+        let c0 = self.c0.sub(cs.ns(|| "c0"), &other.c0)?;
+        let c1 = self.c1.sub(cs.ns(|| "c1"), &other.c1)?;
+        Ok(Self::new(c0, c1))
+    }
+
+    #[inline]
+    fn sub_in_place<CS: ConstraintSystem<E>>(
+        &mut self,
+        mut cs: CS,
+        other: &Self,
+    ) -> Result<&mut Self, SynthesisError> {
+        self.c0.sub_in_place(cs.ns(|| "c0"), &other.c0)?;
+        self.c1.sub_in_place(cs.ns(|| "c1"), &other.c1)?;
+        Ok(self)
+    }
+
+    #[inline]
+    fn negate<CS: ConstraintSystem<E>>(&self, cs: CS) -> Result<Self, SynthesisError> {
+        let mut result = self.clone();
+        result.negate_in_place(cs)?;
+        Ok(result)
+    }
+
+    #[inline]
+    fn negate_in_place<CS: ConstraintSystem<E>>(
+        &mut self,
+        mut cs: CS,
+    ) -> Result<&mut Self, SynthesisError> {
+        self.c0.negate_in_place(cs.ns(|| "c0"))?;
+        self.c1.negate_in_place(cs.ns(|| "c1"))?;
+        Ok(self)
+    }
+
+    #[inline]
+    fn mul<CS: ConstraintSystem<E>>(
+        &self,
+        mut cs: CS,
+        other: &Self,
+    ) -> Result<Self, SynthesisError> {
+        // Karatsuba multiplication for Fp6:
+        //     v0 = A.c0 * B.c0
+        //     v1 = A.c1 * B.c1
+        //     result.c0 = v0 + non_residue * v1
+        //     result.c1 = (A.c0 + A.c1) * (B.c0 + B.c1) - v0 - v1
+        // Enforced with 3 Fp3_mul_gadget's that ensure that:
+        //     A.c1 * B.c1 = v1
+        //     A.c0 * B.c0 = v0
+        //     (A.c0+A.c1)*(B.c0+B.c1) = result.c1 + v0 + v1
+        let v0 = self.c0.mul(cs.ns(|| "v0"), &other.c0)?;
+        let v1 = self.c1.mul(cs.ns(|| "v1"), &other.c1)?;
+        let c0 = {
+            let non_residue_times_v1 =
+                Self::mul_fp3_by_nonresidue(cs.ns(|| "first mul_by_nr"), &v1)?;
+            v0.add(cs.ns(|| "v0 + beta * v1"), &non_residue_times_v1)?
+        };
+        let c1 = {
+            let a0_plus_a1 = self.c0.add(cs.ns(|| "a0 + a1"), &self.c1)?;
+            let b0_plus_b1 = other.c0.add(cs.ns(|| "b0 + b1"), &other.c1)?;
+            let a0_plus_a1_times_b0_plus_b1 =
+                a0_plus_a1.mul(&mut cs.ns(|| "(a0 + a1) * (b0 + b1)"), &b0_plus_b1)?;
+            a0_plus_a1_times_b0_plus_b1
+                .sub(cs.ns(|| "res - v0"), &v0)?
+                .sub(cs.ns(|| "res - v0 - v1"), &v1)?
+        };
+
+        Ok(Self::new(c0, c1))
+    }
+
+    #[inline]
+    fn square<CS: ConstraintSystem<E>>(&self, mut cs: CS) -> Result<Self, SynthesisError> {
+        // Complex squaring for Fp6:
+        //     v0 = A.c0 * A.c1
+        //     result.c0 = (A.c0 + A.c1) * (A.c0 + non_residue * A.c1)
+        //                 - v0 - non_residue * v0
+        //     result.c1 = 2 * v0
+        // Reference:
+        // "Multiplication and Squaring on Pairing-Friendly Fields"
+        // Devegili, OhEigeartaigh, Scott, Dahab
+        let v0 = self.c0.mul(cs.ns(|| "v0"), &self.c1)?;
+        let a0_plus_a1 = self.c0.add(cs.ns(|| "a0 + a1"), &self.c1)?;
+        let a0_plus_non_residue_a1 = Self::mul_fp3_by_nonresidue(cs.ns(|| "nr * a1"), &self.c1)?
+            .add(cs.ns(|| "a0 + nr * a1"), &self.c0)?;
+        let non_residue_v0 = Self::mul_fp3_by_nonresidue(cs.ns(|| "nr * v0"), &v0)?;
+
+        let c0 = a0_plus_a1
+            .mul(
+                cs.ns(|| "(a0 + a1) * (a0 + nr * a1)"),
+                &a0_plus_non_residue_a1,
+            )?
+            .sub(cs.ns(|| "- v0"), &v0)?
+            .sub(cs.ns(|| "- nr * v0"), &non_residue_v0)?;
+        let c1 = v0.double(cs.ns(|| "2v0"))?;
+
+        Ok(Self::new(c0, c1))
+    }
+
+    #[inline]
+    fn inverse<CS: ConstraintSystem<E>>(&self, mut cs: CS) -> Result<Self, SynthesisError> {
+        let inverse = Self::alloc(&mut cs.ns(|| "alloc inverse"), || {
+            self.get_value().and_then(|val| val.inverse()).get()
+        })?;
+
+        // Karatsuba multiplication for Fp6 with the inverse:
+        //     v0 = A.c0 * B.c0
+        //     v1 = A.c1 * B.c1
+        //
+        //      1 = v0 + non_residue * v1
+        //  => v0 = 1 - non_residue * v1
+        //
+        //      0 = result.c1 = (A.c0 + A.c1) * (B.c0 + B.c1) - v0 - v1
+        //  => v0 + v1 = (A.c0 + A.c1) * (B.c0 + B.c1)
+        //  => 1 + v1 - non_residue * v1 = (A.c0 + A.c1) * (B.c0 + B.c1)
+        // Enforced with 2 constraints:
+        //     A.c1 * B.c1 = v1
+        //  => 1 + v1 - non_residue * v1 = (A.c0 + A.c1) * (B.c0 + B.c1)
+        // Reference:
+        // "Multiplication and Squaring on Pairing-Friendly Fields"
+        // Devegili, OhEigeartaigh, Scott, Dahab
+
+        // Constraint 1
+        let v1 = self.c1.mul(cs.ns(|| "inv_constraint_1"), &inverse.c1)?;
+
+        // Constraint 2
+        let a0_plus_a1 = self.c0.add(cs.ns(|| "a0 + a1"), &self.c1)?;
+        let b0_plus_b1 = inverse.c0.add(cs.ns(|| "b0 + b1"), &inverse.c1)?;
+
+        let one = Fp3::<P::Fp3Params>::one();
+        let rhs = Self::mul_fp3_by_nonresidue(cs.ns(|| "nr * v1"), &v1)?
+            .sub(cs.ns(|| "sub v1"), &v1)?
+            .negate(cs.ns(|| "negate it"))?
+            .add_constant(cs.ns(|| "add one"), &one)?;
+        a0_plus_a1.mul_equals(cs.ns(|| "inv_constraint_2"), &b0_plus_b1, &rhs)?;
+        Ok(inverse)
+    }
+
+    #[inline]
+    fn add_constant<CS: ConstraintSystem<E>>(
+        &self,
+        mut cs: CS,
+        other: &Fp6<P>,
+    ) -> Result<Self, SynthesisError> {
+        let c0 = self.c0.add_constant(cs.ns(|| "c0"), &other.c0)?;
+        let c1 = self.c1.add_constant(cs.ns(|| "c1"), &other.c1)?;
+
+        Ok(Self::new(c0, c1))
+    }
+
+    #[inline]
+    fn add_constant_in_place<CS: ConstraintSystem<E>>(
+        &mut self,
+        mut cs: CS,
+        other: &Fp6<P>,
+    ) -> Result<&mut Self, SynthesisError> {
+        self.c0.add_constant_in_place(cs.ns(|| "c0"), &other.c0)?;
+        self.c1.add_constant_in_place(cs.ns(|| "c1"), &other.c1)?;
+        Ok(self)
+    }
+
+    fn mul_by_constant<CS: ConstraintSystem<E>>(
+        &self,
+        mut cs: CS,
+        other: &Fp6<P>,
+    ) -> Result<Self, SynthesisError> {
+        // Karatsuba multiplication (see mul above).
+        // Doesn't need any constraints; returns linear combinations of
+        // `self`'s variables.
+        //
+        // (The operations below are guaranteed to return linear combinations)
+        let (a0, a1) = (&self.c0, &self.c1);
+        let (b0, b1) = (other.c0, other.c1);
+        let mut v0 = a0.mul_by_constant(&mut cs.ns(|| "v0"), &b0)?;
+        let mut v1 = Self::mul_fp3_by_nonresidue(&mut cs.ns(|| "v1"), a1)?;
+        let beta_v1 = v1.mul_by_constant_in_place(&mut cs.ns(|| "beta * v1"), &b1)?;
+
+        v0.add_in_place(&mut cs.ns(|| "c0"), beta_v1)?;
+        let c0 = v0;
+
+        let mut a0b1 = a0.mul_by_constant(&mut cs.ns(|| "a0b1"), &b1)?;
+        let a1b0 = a1.mul_by_constant(&mut cs.ns(|| "a1b0"), &b0)?;
+        a0b1.add_in_place(&mut cs.ns(|| "c1"), &a1b0)?;
+        let c1 = a0b1;
+        Ok(Self::new(c0, c1))
+    }
+
+    fn frobenius_map<CS: ConstraintSystem<E>>(
+        &self,
+        cs: CS,
+        power: usize,
+    ) -> Result<Self, SynthesisError> {
+        let mut res = self.clone();
+        res.frobenius_map_in_place(cs, power)?;
+        Ok(res)
+    }
+
+    fn frobenius_map_in_place<CS: ConstraintSystem<E>>(
+        &mut self,
+        mut cs: CS,
+        power: usize,
+    ) -> Result<&mut Self, SynthesisError> {
+        self.c0
+            .frobenius_map_in_place(cs.ns(|| "frob_map1"), power)?;
+        self.c1
+            .frobenius_map_in_place(cs.ns(|| "frob_map2"), power)?;
+
+        self.c1.mul_by_fp_constant_in_place(
+            cs.ns(|| "mul"),
+            &P::FROBENIUS_COEFF_FP6_C1[power % 6],
+        )?;
+        Ok(self)
+    }
+
+    fn cost_of_mul() -> usize {
+        3 * Fp3Gadget::<P, E>::cost_of_mul()
+    }
+
+    fn cost_of_square() -> usize {
+        2 * Fp3Gadget::<P, E>::cost_of_mul()
+    }
+
+    fn cost_of_inv() -> usize {
+        Fp3Gadget::<P, E>::cost_of_mul() + Fp3Gadget::<P, E>::cost_of_mul_equals()
+    }
+}
+
+impl<P, E: PairingEngine> PartialEq for Fp6Gadget<P, E>
+where
+    P: Fp6Parameters,
+    P::Fp3Params: Fp3Parameters<Fp = E::Fr>,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.c0 == other.c0 && self.c1 == other.c1
+    }
+}
+
+impl<P, E: PairingEngine> Eq for Fp6Gadget<P, E>
+where
+    P: Fp6Parameters,
+    P::Fp3Params: Fp3Parameters<Fp = E::Fr>,
+{
+}
+
+impl<P, E: PairingEngine> EqGadget<E> for Fp6Gadget<P, E>
+where
+    P: Fp6Parameters,
+    P::Fp3Params: Fp3Parameters<Fp = E::Fr>,
+{
+}
+
+impl<P, E: PairingEngine> ConditionalEqGadget<E> for Fp6Gadget<P, E>
+where
+    P: Fp6Parameters,
+    P::Fp3Params: Fp3Parameters<Fp = E::Fr>,
+{
+    #[inline]
+    fn conditional_enforce_equal<CS: ConstraintSystem<E>>(
+        &self,
+        mut cs: CS,
+        other: &Self,
+        condition: &Boolean,
+    ) -> Result<(), SynthesisError> {
+        self.c0
+            .conditional_enforce_equal(&mut cs.ns(|| "c0"), &other.c0, condition)?;
+        self.c1
+            .conditional_enforce_equal(&mut cs.ns(|| "c1"), &other.c1, condition)?;
+        Ok(())
+    }
+
+    fn cost() -> usize {
+        2 * <Fp3Gadget<P, E> as ConditionalEqGadget<E>>::cost()
+    }
+}
+
+impl<P, E: PairingEngine> NEqGadget<E> for Fp6Gadget<P, E>
+where
+    P: Fp6Parameters,
+    P::Fp3Params: Fp3Parameters<Fp = E::Fr>,
+{
+    #[inline]
+    fn enforce_not_equal<CS: ConstraintSystem<E>>(
+        &self,
+        mut cs: CS,
+        other: &Self,
+    ) -> Result<(), SynthesisError> {
+        self.c0.enforce_not_equal(&mut cs.ns(|| "c0"), &other.c0)?;
+        self.c1.enforce_not_equal(&mut cs.ns(|| "c1"), &other.c1)?;
+        Ok(())
+    }
+
+    fn cost() -> usize {
+        2 * <Fp3Gadget<P, E> as NEqGadget<E>>::cost()
+    }
+}
+
+impl<P, E: PairingEngine> ToBitsGadget<E> for Fp6Gadget<P, E>
+where
+    P: Fp6Parameters,
+    P::Fp3Params: Fp3Parameters<Fp = E::Fr>,
+{
+    fn to_bits<CS: ConstraintSystem<E>>(&self, mut cs: CS) -> Result<Vec<Boolean>, SynthesisError> {
+        let mut c0 = self.c0.to_bits(cs.ns(|| "c0"))?;
+        let mut c1 = self.c1.to_bits(cs.ns(|| "c1"))?;
+        c0.append(&mut c1);
+        Ok(c0)
+    }
+
+    fn to_bits_strict<CS: ConstraintSystem<E>>(
+        &self,
+        mut cs: CS,
+    ) -> Result<Vec<Boolean>, SynthesisError> {
+        let mut c0 = self.c0.to_bits_strict(cs.ns(|| "c0"))?;
+        let mut c1 = self.c1.to_bits_strict(cs.ns(|| "c1"))?;
+        c0.append(&mut c1);
+        Ok(c0)
+    }
+}
+
+impl<P, E: PairingEngine> ToBytesGadget<E> for Fp6Gadget<P, E>
+where
+    P: Fp6Parameters,
+    P::Fp3Params: Fp3Parameters<Fp = E::Fr>,
+{
+    fn to_bytes<CS: ConstraintSystem<E>>(&self, mut cs: CS) -> Result<Vec<UInt8>, SynthesisError> {
+        let mut c0 = self.c0.to_bytes(cs.ns(|| "c0"))?;
+        let mut c1 = self.c1.to_bytes(cs.ns(|| "c1"))?;
+        c0.append(&mut c1);
+        Ok(c0)
+    }
+
+    fn to_bytes_strict<CS: ConstraintSystem<E>>(
+        &self,
+        mut cs: CS,
+    ) -> Result<Vec<UInt8>, SynthesisError> {
+        let mut c0 = self.c0.to_bytes_strict(cs.ns(|| "c0"))?;
+        let mut c1 = self.c1.to_bytes_strict(cs.ns(|| "c1"))?;
+        c0.append(&mut c1);
+        Ok(c0)
+    }
+}
+
+impl<P, E: PairingEngine> Clone for Fp6Gadget<P, E>
+where
+    P: Fp6Parameters,
+    P::Fp3Params: Fp3Parameters<Fp = E::Fr>,
+{
+    fn clone(&self) -> Self {
+        Self::new(self.c0.clone(), self.c1.clone())
+    }
+}
+
+impl<P, E: PairingEngine> CondSelectGadget<E> for Fp6Gadget<P, E>
+where
+    P: Fp6Parameters,
+    P::Fp3Params: Fp3Parameters<Fp = E::Fr>,
+{
+    #[inline]
+    fn conditionally_select<CS: ConstraintSystem<E>>(
+        mut cs: CS,
+        cond: &Boolean,
+        first: &Self,
+        second: &Self,
+    ) -> Result<Self, SynthesisError> {
+        let c0 = Fp3Gadget::<P, E>::conditionally_select(
+            &mut cs.ns(|| "c0"),
+            cond,
+            &first.c0,
+            &second.c0,
+        )?;
+        let c1 = Fp3Gadget::<P, E>::conditionally_select(
+            &mut cs.ns(|| "c1"),
+            cond,
+            &first.c1,
+            &second.c1,
+        )?;
+
+        Ok(Self::new(c0, c1))
+    }
+
+    fn cost() -> usize {
+        2 * <Fp3Gadget<P, E> as CondSelectGadget<E>>::cost()
+    }
+}
+
+impl<P, E: PairingEngine> TwoBitLookupGadget<E> for Fp6Gadget<P, E>
+where
+    P: Fp6Parameters,
+    P::Fp3Params: Fp3Parameters<Fp = E::Fr>,
+{
+    type TableConstant = Fp6<P>;
+    fn two_bit_lookup<CS: ConstraintSystem<E>>(
+        mut cs: CS,
+        b: &[Boolean],
+        c: &[Self::TableConstant],
+    ) -> Result<Self, SynthesisError> {
+        let c0s = c.iter().map(|f| f.c0).collect::<Vec<_>>();
+        let c1s = c.iter().map(|f| f.c1).collect::<Vec<_>>();
+        let c0 = Fp3Gadget::<P, E>::two_bit_lookup(cs.ns(|| "Lookup c0"), b, &c0s)?;
+        let c1 = Fp3Gadget::<P, E>::two_bit_lookup(cs.ns(|| "Lookup c1"), b, &c1s)?;
+        Ok(Self::new(c0, c1))
+    }
+
+    fn cost() -> usize {
+        2 * <Fp3Gadget<P, E> as TwoBitLookupGadget<E>>::cost()
+    }
+}
+
+impl<P, E: PairingEngine> KBitLookupGadget<E> for Fp6Gadget<P, E>
+where
+    P: Fp6Parameters,
+    P::Fp3Params: Fp3Parameters<Fp = E::Fr>,
+{
+    type TableConstant = Fp6<P>;
+    fn k_bit_lookup<CS: ConstraintSystem<E>>(
+        mut cs: CS,
+        b: &[Boolean],
+        c: &[Self::TableConstant],
+    ) -> Result<Self, SynthesisError> {
+        let c0s = c.iter().map(|f| f.c0).collect::<Vec<_>>();
+        let c1s = c.iter().map(|f| f.c1).collect::<Vec<_>>();
+        let c0 = Fp3Gadget::<P, E>::k_bit_lookup(cs.ns(|| "Lookup c0"), b, &c0s)?;
+        let c1 = Fp3Gadget::<P, E>::k_bit_lookup(cs.ns(|| "Lookup c1"), b, &c1s)?;
+        Ok(Self::new(c0, c1))
+    }
+
+    fn cost(k: usize) -> usize {
+        2 * <Fp3Gadget<P, E> as KBitLookupGadget<E>>::cost(k)
+    }
+}
+
+impl<P, E: PairingEngine> ThreeBitCondNegLookupGadget<E> for Fp6Gadget<P, E>
+where
+    P: Fp6Parameters,
+    P::Fp3Params: Fp3Parameters<Fp = E::Fr>,
+{
+    type TableConstant = Fp6<P>;
+    fn three_bit_cond_neg_lookup<CS: ConstraintSystem<E>>(
+        mut cs: CS,
+        b: &[Boolean],
+        b0b1: &Boolean,
+        c: &[Self::TableConstant],
+    ) -> Result<Self, SynthesisError> {
+        let c0s = c.iter().map(|f| f.c0).collect::<Vec<_>>();
+        let c1s = c.iter().map(|f| f.c1).collect::<Vec<_>>();
+        let c0 =
+            Fp3Gadget::<P, E>::three_bit_cond_neg_lookup(cs.ns(|| "Lookup c0"), b, b0b1, &c0s)?;
+        let c1 =
+            Fp3Gadget::<P, E>::three_bit_cond_neg_lookup(cs.ns(|| "Lookup c1"), b, b0b1, &c1s)?;
+        Ok(Self::new(c0, c1))
+    }
+
+    fn cost() -> usize {
+        2 * <Fp3Gadget<P, E> as ThreeBitCondNegLookupGadget<E>>::cost()
+    }
+}
+
+impl<P, E: PairingEngine> AllocGadget<Fp6<P>, E> for Fp6Gadget<P, E>
+where
+    P: Fp6Parameters,
+    P::Fp3Params: Fp3Parameters<Fp = E::Fr>,
+{
+    #[inline]
+    fn alloc<F, T, CS: ConstraintSystem<E>>(
+        mut cs: CS,
+        value_gen: F,
+    ) -> Result<Self, SynthesisError>
+    where
+        F: FnOnce() -> Result<T, SynthesisError>,
+        T: Borrow<Fp6<P>>,
+    {
+        let (c0, c1) = match value_gen() {
+            Ok(fe) => {
+                let fe = *fe.borrow();
+                (Ok(fe.c0), Ok(fe.c1))
+            },
+            _ => (
+                Err(SynthesisError::AssignmentMissing),
+                Err(SynthesisError::AssignmentMissing),
+            ),
+        };
+
+        let c0 = Fp3Gadget::<P, E>::alloc(&mut cs.ns(|| "c0"), || c0)?;
+        let c1 = Fp3Gadget::<P, E>::alloc(&mut cs.ns(|| "c1"), || c1)?;
+        Ok(Self::new(c0, c1))
+    }
+
+    #[inline]
+    fn alloc_input<F, T, CS: ConstraintSystem<E>>(
+        mut cs: CS,
+        value_gen: F,
+    ) -> Result<Self, SynthesisError>
+    where
+        F: FnOnce() -> Result<T, SynthesisError>,
+        T: Borrow<Fp6<P>>,
+    {
+        let (c0, c1) = match value_gen() {
+            Ok(fe) => {
+                let fe = *fe.borrow();
+                (Ok(fe.c0), Ok(fe.c1))
+            },
+            _ => (
+                Err(SynthesisError::AssignmentMissing),
+                Err(SynthesisError::AssignmentMissing),
+            ),
+        };
+
+        let c0 = Fp3Gadget::<P, E>::alloc_input(&mut cs.ns(|| "c0"), || c0)?;
+        let c1 = Fp3Gadget::<P, E>::alloc_input(&mut cs.ns(|| "c1"), || c1)?;
+        Ok(Self::new(c0, c1))
+    }
+}
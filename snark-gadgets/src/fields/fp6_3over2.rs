@@ -14,8 +14,8 @@ use crate::{
     bits::boolean::Boolean,
     uint8::UInt8,
     utils::{
-        AllocGadget, CondSelectGadget, ConditionalEqGadget, EqGadget, NEqGadget, ToBitsGadget,
-        ToBytesGadget, TwoBitLookupGadget,
+        AllocGadget, CondSelectGadget, ConditionalEqGadget, EqGadget, KBitLookupGadget, NEqGadget,
+        ThreeBitCondNegLookupGadget, ToBitsGadget, ToBytesGadget, TwoBitLookupGadget,
     },
     ConstraintVar,
 };
@@ -680,8 +680,12 @@ where
         5 * Fp2Gadget::<P, E>::cost_of_mul()
     }
 
+    fn cost_of_square() -> usize {
+        5 * Fp2Gadget::<P, E>::cost_of_square()
+    }
+
     fn cost_of_inv() -> usize {
-        Self::cost_of_mul() + <Self as EqGadget<E>>::cost()
+        Self::cost_of_mul_equals()
     }
 }
 
@@ -886,6 +890,60 @@ where
     }
 }
 
+impl<P, E: PairingEngine> KBitLookupGadget<E> for Fp6Gadget<P, E>
+where
+    P: Fp6Parameters,
+    P::Fp2Params: Fp2Parameters<Fp = E::Fr>,
+{
+    type TableConstant = Fp6<P>;
+    fn k_bit_lookup<CS: ConstraintSystem<E>>(
+        mut cs: CS,
+        b: &[Boolean],
+        c: &[Self::TableConstant],
+    ) -> Result<Self, SynthesisError> {
+        let c0s = c.iter().map(|f| f.c0).collect::<Vec<_>>();
+        let c1s = c.iter().map(|f| f.c1).collect::<Vec<_>>();
+        let c2s = c.iter().map(|f| f.c2).collect::<Vec<_>>();
+        let c0 = Fp2Gadget::<P, E>::k_bit_lookup(cs.ns(|| "Lookup c0"), b, &c0s)?;
+        let c1 = Fp2Gadget::<P, E>::k_bit_lookup(cs.ns(|| "Lookup c1"), b, &c1s)?;
+        let c2 = Fp2Gadget::<P, E>::k_bit_lookup(cs.ns(|| "Lookup c2"), b, &c2s)?;
+        Ok(Self::new(c0, c1, c2))
+    }
+
+    fn cost(k: usize) -> usize {
+        3 * <Fp2Gadget<P, E> as KBitLookupGadget<E>>::cost(k)
+    }
+}
+
+impl<P, E: PairingEngine> ThreeBitCondNegLookupGadget<E> for Fp6Gadget<P, E>
+where
+    P: Fp6Parameters,
+    P::Fp2Params: Fp2Parameters<Fp = E::Fr>,
+{
+    type TableConstant = Fp6<P>;
+    fn three_bit_cond_neg_lookup<CS: ConstraintSystem<E>>(
+        mut cs: CS,
+        b: &[Boolean],
+        b0b1: &Boolean,
+        c: &[Self::TableConstant],
+    ) -> Result<Self, SynthesisError> {
+        let c0s = c.iter().map(|f| f.c0).collect::<Vec<_>>();
+        let c1s = c.iter().map(|f| f.c1).collect::<Vec<_>>();
+        let c2s = c.iter().map(|f| f.c2).collect::<Vec<_>>();
+        let c0 =
+            Fp2Gadget::<P, E>::three_bit_cond_neg_lookup(cs.ns(|| "Lookup c0"), b, b0b1, &c0s)?;
+        let c1 =
+            Fp2Gadget::<P, E>::three_bit_cond_neg_lookup(cs.ns(|| "Lookup c1"), b, b0b1, &c1s)?;
+        let c2 =
+            Fp2Gadget::<P, E>::three_bit_cond_neg_lookup(cs.ns(|| "Lookup c2"), b, b0b1, &c2s)?;
+        Ok(Self::new(c0, c1, c2))
+    }
+
+    fn cost() -> usize {
+        3 * <Fp2Gadget<P, E> as ThreeBitCondNegLookupGadget<E>>::cost()
+    }
+}
+
 impl<P, E: PairingEngine> AllocGadget<Fp6<P>, E> for Fp6Gadget<P, E>
 where
     P: Fp6Parameters,
@@ -0,0 +1,10 @@
+use algebra::{
+    curves::mnt4::MNT4,
+    fields::mnt6::{Fq3Parameters, Fq6Parameters},
+};
+
+use super::{fp::FpGadget, fp3::Fp3Gadget, fp6_2over3::Fp6Gadget};
+
+pub type FqGadget = FpGadget<MNT4>;
+pub type Fq3Gadget = Fp3Gadget<Fq3Parameters, MNT4>;
+pub type Fq6Gadget = Fp6Gadget<Fq6Parameters, MNT4>;
@@ -6,20 +6,24 @@ use std::fmt::Debug;
 use crate::{
     bits::boolean::Boolean,
     utils::{
-        AllocGadget, CondSelectGadget, ConditionalEqGadget, EqGadget, NEqGadget, ToBitsGadget,
-        ToBytesGadget, TwoBitLookupGadget,
+        AllocGadget, CondSelectGadget, ConditionalEqGadget, EqGadget, KBitLookupGadget, NEqGadget,
+        ThreeBitCondNegLookupGadget, ToBitsGadget, ToBytesGadget, TwoBitLookupGadget,
     },
 };
 
 pub mod fp;
 pub mod fp12;
 pub mod fp2;
+pub mod fp3;
+pub mod fp6_2over3;
 pub mod fp6_3over2;
 
 pub mod bls12_377;
 pub mod edwards_bls12;
 pub mod edwards_sw6;
 pub mod jubjub;
+pub mod mnt6;
+pub mod sw6;
 
 pub trait FieldGadget<F: Field, E: PairingEngine>:
     Sized
@@ -32,6 +36,8 @@ pub trait FieldGadget<F: Field, E: PairingEngine>:
     + ToBytesGadget<E>
     + CondSelectGadget<E>
     + TwoBitLookupGadget<E, TableConstant = F>
+    + KBitLookupGadget<E, TableConstant = F>
+    + ThreeBitCondNegLookupGadget<E, TableConstant = F>
     + Debug
 {
     type Variable: Clone + Debug;
@@ -214,6 +220,14 @@ pub trait FieldGadget<F: Field, E: PairingEngine>:
 
     fn cost_of_mul() -> usize;
 
+    fn cost_of_square() -> usize {
+        Self::cost_of_mul()
+    }
+
+    fn cost_of_mul_equals() -> usize {
+        Self::cost_of_mul() + <Self as EqGadget<E>>::cost()
+    }
+
     fn cost_of_inv() -> usize;
 }
 
@@ -412,6 +426,122 @@ mod test {
         let _ = n.to_bytes_strict(&mut cs.ns(|| "ToBytes Strict")).unwrap();
     }
 
+    // Checks each declared constraint cost against the number of constraints
+    // actually generated for allocated (non-constant) operands.
+    fn field_constraint_costs<
+        FE: Field,
+        E: PairingEngine,
+        F: FieldGadget<FE, E>,
+        CS: ConstraintSystem<E>,
+    >(
+        mut cs: CS,
+    ) {
+        use crate::utils::{
+            CondSelectGadget, ConditionalEqGadget, KBitLookupGadget, MultiplexerGadget, NEqGadget,
+            ThreeBitCondNegLookupGadget, TwoBitLookupGadget,
+        };
+
+        let mut rng = XorShiftRng::from_seed([0x5dbe6259, 0x8d313d76, 0x3237db17, 0xe5bc0655]);
+        let a_native = FE::rand(&mut rng);
+        let b_native = FE::rand(&mut rng);
+        let a = F::alloc(&mut cs.ns(|| "a"), || Ok(a_native)).unwrap();
+        let b = F::alloc(&mut cs.ns(|| "b"), || Ok(b_native)).unwrap();
+        let bits = [
+            Boolean::alloc(&mut cs.ns(|| "bit 0"), || Ok(true)).unwrap(),
+            Boolean::alloc(&mut cs.ns(|| "bit 1"), || Ok(false)).unwrap(),
+            Boolean::alloc(&mut cs.ns(|| "bit 2"), || Ok(true)).unwrap(),
+        ];
+
+        let num_constraints = cs.num_constraints();
+        let ab = a.mul(cs.ns(|| "a * b"), &b).unwrap();
+        assert_eq!(cs.num_constraints() - num_constraints, F::cost_of_mul());
+
+        let num_constraints = cs.num_constraints();
+        let _ = a.square(cs.ns(|| "a^2")).unwrap();
+        assert_eq!(cs.num_constraints() - num_constraints, F::cost_of_square());
+
+        let num_constraints = cs.num_constraints();
+        a.mul_equals(cs.ns(|| "a * b == ab"), &b, &ab).unwrap();
+        assert_eq!(cs.num_constraints() - num_constraints, F::cost_of_mul_equals());
+
+        let num_constraints = cs.num_constraints();
+        let _ = a.inverse(cs.ns(|| "a^-1")).unwrap();
+        assert_eq!(cs.num_constraints() - num_constraints, F::cost_of_inv());
+
+        let num_constraints = cs.num_constraints();
+        a.conditional_enforce_equal(cs.ns(|| "a == a if bit"), &a, &bits[0])
+            .unwrap();
+        assert_eq!(
+            cs.num_constraints() - num_constraints,
+            <F as ConditionalEqGadget<E>>::cost()
+        );
+
+        let num_constraints = cs.num_constraints();
+        a.enforce_not_equal(cs.ns(|| "a != b"), &b).unwrap();
+        assert_eq!(
+            cs.num_constraints() - num_constraints,
+            <F as NEqGadget<E>>::cost()
+        );
+
+        let num_constraints = cs.num_constraints();
+        let _ = F::conditionally_select(cs.ns(|| "select"), &bits[0], &a, &b).unwrap();
+        assert_eq!(
+            cs.num_constraints() - num_constraints,
+            <F as CondSelectGadget<E>>::cost()
+        );
+
+        let table = [
+            FE::rand(&mut rng),
+            FE::rand(&mut rng),
+            FE::rand(&mut rng),
+            FE::rand(&mut rng),
+        ];
+        let num_constraints = cs.num_constraints();
+        let looked_up = F::two_bit_lookup(cs.ns(|| "lookup"), &bits[..2], &table).unwrap();
+        assert_eq!(
+            cs.num_constraints() - num_constraints,
+            <F as TwoBitLookupGadget<E>>::cost()
+        );
+        assert_eq!(looked_up.get_value().unwrap(), table[1]);
+
+        let b0b1 = Boolean::and(cs.ns(|| "b0 and b1"), &bits[0], &bits[1]).unwrap();
+        let num_constraints = cs.num_constraints();
+        let looked_up =
+            F::three_bit_cond_neg_lookup(cs.ns(|| "cond neg lookup"), &bits, &b0b1, &table)
+                .unwrap();
+        assert_eq!(
+            cs.num_constraints() - num_constraints,
+            <F as ThreeBitCondNegLookupGadget<E>>::cost()
+        );
+        assert_eq!(looked_up.get_value().unwrap(), -table[1]);
+
+        for k in 1..=3 {
+            let table = (0..(1 << k))
+                .map(|_| FE::rand(&mut rng))
+                .collect::<Vec<_>>();
+            let num_constraints = cs.num_constraints();
+            let looked_up =
+                F::k_bit_lookup(cs.ns(|| format!("{}-bit lookup", k)), &bits[..k], &table)
+                    .unwrap();
+            assert_eq!(
+                cs.num_constraints() - num_constraints,
+                <F as KBitLookupGadget<E>>::cost(k)
+            );
+            assert_eq!(looked_up.get_value().unwrap(), table[5 & ((1 << k) - 1)]);
+        }
+
+        let values = (0..8)
+            .map(|i| F::alloc(cs.ns(|| format!("value {}", i)), || Ok(FE::rand(&mut rng))).unwrap())
+            .collect::<Vec<_>>();
+        let num_constraints = cs.num_constraints();
+        let selected = F::multiplex(cs.ns(|| "multiplex"), &bits, &values).unwrap();
+        assert_eq!(
+            cs.num_constraints() - num_constraints,
+            <F as MultiplexerGadget<E>>::cost(3)
+        );
+        assert_eq!(selected.get_value(), values[5].get_value());
+    }
+
     fn random_frobenius_tests<
         FE: Field,
         E: PairingEngine,
@@ -518,4 +648,213 @@ mod test {
         }
         assert!(cs.is_satisfied());
     }
+
+    #[test]
+    fn fp3_fp6_2over3_field_gadgets_test() {
+        use self::fp3_fp6_params::{Fq3, Fq3Gadget, Fq6, Fq6Gadget};
+        use algebra::curves::bls12_381::Bls12_381;
+
+        let mut cs = TestConstraintSystem::<Bls12_381>::new();
+
+        let mut rng = XorShiftRng::from_seed([0x5dbe6259, 0x8d313d76, 0x3237db17, 0xe5bc0653]);
+
+        let a = Fq3Gadget::alloc(&mut cs.ns(|| "generate_a"), || Ok(Fq3::rand(&mut rng))).unwrap();
+        let b = Fq3Gadget::alloc(&mut cs.ns(|| "generate_b"), || Ok(Fq3::rand(&mut rng))).unwrap();
+        field_test(cs.ns(|| "test_fq3"), a, b);
+        random_frobenius_tests::<Fq3, _, Fq3Gadget, _>(cs.ns(|| "test_frob_fq3"), 13);
+        if !cs.is_satisfied() {
+            println!("{:?}", cs.which_is_unsatisfied().unwrap());
+        }
+
+        let c = Fq6Gadget::alloc(&mut cs.ns(|| "generate_c"), || Ok(Fq6::rand(&mut rng))).unwrap();
+        let d = Fq6Gadget::alloc(&mut cs.ns(|| "generate_d"), || Ok(Fq6::rand(&mut rng))).unwrap();
+        field_test(cs.ns(|| "test_fq6"), c, d);
+        random_frobenius_tests::<Fq6, _, Fq6Gadget, _>(cs.ns(|| "test_frob_fq6"), 13);
+        if !cs.is_satisfied() {
+            println!("{:?}", cs.which_is_unsatisfied().unwrap());
+        }
+
+        assert!(cs.is_satisfied());
+    }
+
+    #[test]
+    fn fp6_2over3_cyclotomic_square_test() {
+        use self::fp3_fp6_params::{Fq6, Fq6Gadget};
+        use algebra::curves::bls12_381::Bls12_381;
+
+        let mut cs = TestConstraintSystem::<Bls12_381>::new();
+        let mut rng = XorShiftRng::from_seed([0x5dbe6259, 0x8d313d76, 0x3237db17, 0xe5bc0654]);
+
+        // Map a random element into the cyclotomic subgroup by raising it to
+        // (p^3 - 1) * (p + 1).
+        let a = Fq6::rand(&mut rng);
+        let mut b = a;
+        b.frobenius_map(3);
+        b *= &a.inverse().unwrap();
+        let mut c = b;
+        c.frobenius_map(1);
+        let a = c * &b;
+
+        let a_gadget = Fq6Gadget::alloc(&mut cs.ns(|| "a"), || Ok(a)).unwrap();
+        let num_constraints = cs.num_constraints();
+        let a_square = a_gadget
+            .cyclotomic_square(cs.ns(|| "cyclotomic square"))
+            .unwrap();
+        assert_eq!(cs.num_constraints() - num_constraints, 6);
+        assert_eq!(a_square.get_value().unwrap(), a.square());
+
+        let a_inv = a_gadget.unitary_inverse(cs.ns(|| "unitary inverse")).unwrap();
+        assert_eq!(a_inv.get_value().unwrap(), a.inverse().unwrap());
+
+        let exp = [0x5dbe6259u64, 0x8d313d76];
+        let a_exp = a_gadget
+            .cyclotomic_exp(cs.ns(|| "cyclotomic exp"), &exp)
+            .unwrap();
+        assert_eq!(a_exp.get_value().unwrap(), a.pow(&exp));
+        assert!(cs.is_satisfied());
+    }
+
+    #[test]
+    fn field_gadgets_constraint_costs_test() {
+        use self::fp3_fp6_params::{
+            Fq3 as TestFq3, Fq3Gadget as TestFq3Gadget, Fq6 as TestFq6, Fq6Gadget as TestFq6Gadget,
+        };
+        use crate::fields::bls12_377::{Fq12Gadget, Fq2Gadget, Fq6Gadget, FqGadget};
+        use algebra::{
+            curves::{bls12_381::Bls12_381, sw6::SW6},
+            fields::bls12_377::{Fq, Fq12, Fq2, Fq6},
+        };
+
+        let mut cs = TestConstraintSystem::<SW6>::new();
+        field_constraint_costs::<Fq, _, FqGadget, _>(cs.ns(|| "fq"));
+        field_constraint_costs::<Fq2, _, Fq2Gadget, _>(cs.ns(|| "fq2"));
+        field_constraint_costs::<Fq6, _, Fq6Gadget, _>(cs.ns(|| "fq6"));
+        field_constraint_costs::<Fq12, _, Fq12Gadget, _>(cs.ns(|| "fq12"));
+        assert!(cs.is_satisfied());
+
+        let mut cs = TestConstraintSystem::<Bls12_381>::new();
+        field_constraint_costs::<TestFq3, _, TestFq3Gadget, _>(cs.ns(|| "fq3"));
+        field_constraint_costs::<TestFq6, _, TestFq6Gadget, _>(cs.ns(|| "fq6 2-over-3"));
+        assert!(cs.is_satisfied());
+    }
+
+    // Test-only towers over the scalar field of BLS12-381, so that the Fp3 and
+    // Fp6 (2-over-3) gadgets can be exercised with an existing engine.
+    // Fp3 = Fp[X]/(X^3 - 7) and Fp6 = Fp3[Y]/(Y^2 - X).
+    mod fp3_fp6_params {
+        use crate::fields::{fp3::Fp3Gadget, fp6_2over3::Fp6Gadget};
+        use algebra::{
+            biginteger::BigInteger256 as BigInteger,
+            curves::bls12_381::Bls12_381,
+            fields::{
+                fp3::{Fp3, Fp3Parameters},
+                fp6_2over3::{Fp6, Fp6Parameters},
+                jubjub::fq::Fq,
+            },
+        };
+
+        pub(super) type Fq3 = Fp3<Fq3Parameters>;
+        pub(super) type Fq6 = Fp6<Fq6Parameters>;
+        pub(super) type Fq3Gadget = Fp3Gadget<Fq3Parameters, Bls12_381>;
+        pub(super) type Fq6Gadget = Fp6Gadget<Fq6Parameters, Bls12_381>;
+
+        const FQ_ZERO: Fq = Fq::new(BigInteger([0, 0, 0, 0]));
+        const FQ_ONE: Fq = Fq::new(BigInteger([
+            0x1fffffffe,
+            0x5884b7fa00034802,
+            0x998c4fefecbc4ff5,
+            0x1824b159acc5056f,
+        ]));
+        // Primitive cube root of unity, p^((p - 1) / 3).
+        const OMEGA: Fq = Fq::new(BigInteger([
+            0x92d9090b093011d2,
+            0xfc9cbd719d6aa073,
+            0xc1f14ef0cd65a1a6,
+            0x17f6d35e72fcdeb,
+        ]));
+        const OMEGA_SQUARED: Fq = Fq::new(BigInteger([
+            0x6d26f6f1f6cfee31,
+            0xfe9c2e9762907389,
+            0xd7bc39274f7fe668,
+            0x5a4988c395a8a9ec,
+        ]));
+
+        pub(super) struct Fq3Parameters;
+
+        impl Fp3Parameters for Fq3Parameters {
+            type Fp = Fq;
+
+            // 7
+            const NONRESIDUE: Fq = Fq::new(BigInteger([
+                0xefffffff1,
+                0x17e363d300189c0f,
+                0xff9c57876f8457b0,
+                0x351332208fc5a8c4,
+            ]));
+
+            const TWO_ADICITY: u32 = 32;
+
+            const T_MINUS_ONE_DIV_TWO: &'static [u64] = &[
+                0xfffd89fffffffffe,
+                0x1cd44dfefda16206,
+                0x331b5b4c57915fa,
+                0xaf32071e4e6fac0a,
+                0xe4aad2bed3581fa,
+                0x3e33af62bf71e8dd,
+                0xf17356f3b3a6c26e,
+                0xf56bea3f0a0b859e,
+                0x29ae810e61848c84,
+                0x13f0870fe8d8119e,
+                0x3f6f2f009b3d42b8,
+                0xbe2fa65,
+            ];
+
+            const QUADRATIC_NONRESIDUE_TO_T: (Fq, Fq, Fq) = (
+                Fq::new(BigInteger([
+                    0x9ac057eb73af68d,
+                    0x3abd181507e397e3,
+                    0xd44a20810e593740,
+                    0x48d45cbc99eafd8,
+                ])),
+                FQ_ZERO,
+                FQ_ZERO,
+            );
+
+            const FROBENIUS_COEFF_FP3_C1: [Fq; 3] = [FQ_ONE, OMEGA, OMEGA_SQUARED];
+
+            const FROBENIUS_COEFF_FP3_C2: [Fq; 3] = [FQ_ONE, OMEGA_SQUARED, OMEGA];
+        }
+
+        pub(super) struct Fq6Parameters;
+
+        impl Fp6Parameters for Fq6Parameters {
+            type Fp3Params = Fq3Parameters;
+
+            const NONRESIDUE: Fq3 = Fq3::new(FQ_ZERO, FQ_ONE, FQ_ZERO);
+
+            const FROBENIUS_COEFF_FP6_C1: [Fq; 6] = [
+                FQ_ONE,
+                Fq::new(BigInteger([
+                    0x92d9090d093011d0,
+                    0x5521756b9d6de875,
+                    0x5b7d9ee0ba21f19c,
+                    0x19a41e8f93f4d35b,
+                ])),
+                OMEGA,
+                Fq::new(BigInteger([
+                    0xfffffffd00000003,
+                    0xfb38ec08fffb13fc,
+                    0x99ad88181ce5880f,
+                    0x5bc8f5f97cd877d8,
+                ])),
+                OMEGA_SQUARED,
+                Fq::new(BigInteger([
+                    0x6d26f6f3f6cfee2f,
+                    0x5720e6916293bb8b,
+                    0x714889173c3c365e,
+                    0x726e3a1d426daf5c,
+                ])),
+            ];
+        }
+    }
 }
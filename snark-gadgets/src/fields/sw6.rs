@@ -0,0 +1,9 @@
+use algebra::fields::sw6::{Fq3Parameters, Fq6Parameters};
+
+use super::{fp::FpGadget, fp3::Fp3Gadget, fp6_2over3::Fp6Gadget};
+
+// No engine in this tree has `SW6`'s base field as its scalar field, so these
+// gadgets are generic over the engine `E`, which must satisfy `E::Fr = Fq`.
+pub type FqGadget<E> = FpGadget<E>;
+pub type Fq3Gadget<E> = Fp3Gadget<Fq3Parameters, E>;
+pub type Fq6Gadget<E> = Fp6Gadget<Fq6Parameters, E>;
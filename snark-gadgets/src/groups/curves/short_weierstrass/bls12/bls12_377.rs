@@ -65,6 +65,12 @@ mod test {
             <G1Gadget as CondSelectGadget<SW6>>::cost()
         );
         assert_eq!(add_cost, G1Gadget::cost_of_add());
+
+        let mut gadget_a2 = gadget_a.clone();
+        let num_constraints = cs.num_constraints();
+        gadget_a2.double_in_place(&mut cs.ns(|| "2a")).unwrap();
+        assert_eq!(cs.num_constraints() - num_constraints, G1Gadget::cost_of_double());
+        assert!(cs.is_satisfied());
     }
 
     #[test]
@@ -100,6 +106,12 @@ mod test {
             <G2Gadget as CondSelectGadget<SW6>>::cost()
         );
         assert_eq!(add_cost, G2Gadget::cost_of_add());
+
+        let mut gadget_a2 = gadget_a.clone();
+        let num_constraints = cs.num_constraints();
+        gadget_a2.double_in_place(&mut cs.ns(|| "2a")).unwrap();
+        assert_eq!(cs.num_constraints() - num_constraints, G2Gadget::cost_of_double());
+        assert!(cs.is_satisfied());
     }
 
     #[test]
@@ -0,0 +1,11 @@
+use crate::groups::mnt6::{
+    G1Gadget as MNT6G1Gadget, G1PreparedGadget as MNT6G1PreparedGadget,
+    G2Gadget as MNT6G2Gadget, G2PreparedGadget as MNT6G2PreparedGadget,
+};
+use algebra::curves::{mnt4::MNT4, mnt6::MNT6};
+
+pub type G1Gadget = MNT6G1Gadget<MNT6, MNT4>;
+pub type G2Gadget = MNT6G2Gadget<MNT6, MNT4>;
+
+pub type G1PreparedGadget = MNT6G1PreparedGadget<MNT6, MNT4>;
+pub type G2PreparedGadget = MNT6G2PreparedGadget<MNT6, MNT4>;
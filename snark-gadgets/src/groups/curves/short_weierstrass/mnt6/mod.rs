@@ -0,0 +1,342 @@
+use crate::groups::{curves::short_weierstrass::AffineGadget, GroupGadget};
+use algebra::{
+    curves::models::{mnt6::MNT6Parameters, SWModelParameters},
+    fields::Field,
+    BitIterator, PairingEngine,
+};
+use snark::{ConstraintSystem, SynthesisError};
+
+use crate::{
+    fields::{fp::FpGadget, fp3::Fp3Gadget, FieldGadget},
+    uint8::UInt8,
+    utils::{NEqGadget, ToBytesGadget},
+};
+
+pub mod mnt6_298;
+
+pub type G1Gadget<P, E> = AffineGadget<<P as MNT6Parameters>::G1Parameters, E, FpGadget<E>>;
+pub type G2Gadget<P, E> = AffineGadget<<P as MNT6Parameters>::G2Parameters, E, Fp3G<P, E>>;
+
+type Fp3G<P, E> = Fp3Gadget<<P as MNT6Parameters>::Fp3Params, E>;
+
+#[derive(Derivative)]
+#[derivative(Clone(bound = "Fp3G<P, E>: Clone"), Debug(bound = "Fp3G<P, E>: Debug"))]
+pub struct G1PreparedGadget<P: MNT6Parameters<Fp = E::Fr>, E: PairingEngine> {
+    pub x:       FpGadget<E>,
+    pub y:       FpGadget<E>,
+    pub x_twist: Fp3G<P, E>,
+    pub y_twist: Fp3G<P, E>,
+}
+
+impl<P: MNT6Parameters<Fp = E::Fr>, E: PairingEngine> G1PreparedGadget<P, E> {
+    pub fn from_affine<CS: ConstraintSystem<E>>(
+        mut cs: CS,
+        q: &G1Gadget<P, E>,
+    ) -> Result<Self, SynthesisError> {
+        let x_twist = Self::mul_twist_by_fp(cs.ns(|| "x_twist"), &q.x)?;
+        let y_twist = Self::mul_twist_by_fp(cs.ns(|| "y_twist"), &q.y)?;
+        Ok(Self {
+            x: q.x.clone(),
+            y: q.y.clone(),
+            x_twist,
+            y_twist,
+        })
+    }
+
+    // Computes `TWIST * fe`. Doesn't need any constraints.
+    fn mul_twist_by_fp<CS: ConstraintSystem<E>>(
+        mut cs: CS,
+        fe: &FpGadget<E>,
+    ) -> Result<Fp3G<P, E>, SynthesisError> {
+        let c0 = fe.mul_by_constant(cs.ns(|| "c0"), &P::TWIST.c0)?;
+        let c1 = fe.mul_by_constant(cs.ns(|| "c1"), &P::TWIST.c1)?;
+        let c2 = fe.mul_by_constant(cs.ns(|| "c2"), &P::TWIST.c2)?;
+        Ok(Fp3G::<P, E>::new(c0, c1, c2))
+    }
+}
+
+impl<P: MNT6Parameters<Fp = E::Fr>, E: PairingEngine> ToBytesGadget<E> for G1PreparedGadget<P, E> {
+    #[inline]
+    fn to_bytes<CS: ConstraintSystem<E>>(&self, mut cs: CS) -> Result<Vec<UInt8>, SynthesisError> {
+        let mut bytes = self.x.to_bytes(cs.ns(|| "x"))?;
+        bytes.extend_from_slice(&self.y.to_bytes(cs.ns(|| "y"))?);
+        bytes.extend_from_slice(&self.x_twist.to_bytes(cs.ns(|| "x_twist"))?);
+        bytes.extend_from_slice(&self.y_twist.to_bytes(cs.ns(|| "y_twist"))?);
+        Ok(bytes)
+    }
+
+    fn to_bytes_strict<CS: ConstraintSystem<E>>(
+        &self,
+        cs: CS,
+    ) -> Result<Vec<UInt8>, SynthesisError> {
+        self.to_bytes(cs)
+    }
+}
+
+#[derive(Derivative)]
+#[derivative(Clone(bound = "Fp3G<P, E>: Clone"), Debug(bound = "Fp3G<P, E>: Debug"))]
+pub struct AteDoubleCoefficientsGadget<P: MNT6Parameters<Fp = E::Fr>, E: PairingEngine> {
+    pub c_h:  Fp3G<P, E>,
+    pub c_4c: Fp3G<P, E>,
+    pub c_j:  Fp3G<P, E>,
+    pub c_l:  Fp3G<P, E>,
+}
+
+#[derive(Derivative)]
+#[derivative(Clone(bound = "Fp3G<P, E>: Clone"), Debug(bound = "Fp3G<P, E>: Debug"))]
+pub struct AteAdditionCoefficientsGadget<P: MNT6Parameters<Fp = E::Fr>, E: PairingEngine> {
+    pub c_l1: Fp3G<P, E>,
+    pub c_rz: Fp3G<P, E>,
+}
+
+/// A point of `G2` together with the line-function coefficients of the
+/// (flipped) ate Miller loop, precomputed as in `libsnark`.
+#[derive(Derivative)]
+#[derivative(Clone(bound = "Fp3G<P, E>: Clone"), Debug(bound = "Fp3G<P, E>: Debug"))]
+pub struct G2PreparedGadget<P: MNT6Parameters<Fp = E::Fr>, E: PairingEngine> {
+    pub x:                     Fp3G<P, E>,
+    pub y:                     Fp3G<P, E>,
+    pub x_over_twist:          Fp3G<P, E>,
+    pub y_over_twist:          Fp3G<P, E>,
+    pub double_coefficients:   Vec<AteDoubleCoefficientsGadget<P, E>>,
+    pub addition_coefficients: Vec<AteAdditionCoefficientsGadget<P, E>>,
+}
+
+impl<P: MNT6Parameters<Fp = E::Fr>, E: PairingEngine> ToBytesGadget<E> for G2PreparedGadget<P, E> {
+    #[inline]
+    fn to_bytes<CS: ConstraintSystem<E>>(&self, mut cs: CS) -> Result<Vec<UInt8>, SynthesisError> {
+        let mut bytes = self.x.to_bytes(cs.ns(|| "x"))?;
+        bytes.extend_from_slice(&self.y.to_bytes(cs.ns(|| "y"))?);
+        bytes.extend_from_slice(&self.x_over_twist.to_bytes(cs.ns(|| "x_over_twist"))?);
+        bytes.extend_from_slice(&self.y_over_twist.to_bytes(cs.ns(|| "y_over_twist"))?);
+        for (i, coeffs) in self.double_coefficients.iter().enumerate() {
+            let mut cs = cs.ns(|| format!("Double coefficients {}", i));
+            bytes.extend_from_slice(&coeffs.c_h.to_bytes(cs.ns(|| "c_h"))?);
+            bytes.extend_from_slice(&coeffs.c_4c.to_bytes(cs.ns(|| "c_4c"))?);
+            bytes.extend_from_slice(&coeffs.c_j.to_bytes(cs.ns(|| "c_j"))?);
+            bytes.extend_from_slice(&coeffs.c_l.to_bytes(cs.ns(|| "c_l"))?);
+        }
+        for (i, coeffs) in self.addition_coefficients.iter().enumerate() {
+            let mut cs = cs.ns(|| format!("Addition coefficients {}", i));
+            bytes.extend_from_slice(&coeffs.c_l1.to_bytes(cs.ns(|| "c_l1"))?);
+            bytes.extend_from_slice(&coeffs.c_rz.to_bytes(cs.ns(|| "c_rz"))?);
+        }
+        Ok(bytes)
+    }
+
+    fn to_bytes_strict<CS: ConstraintSystem<E>>(
+        &self,
+        cs: CS,
+    ) -> Result<Vec<UInt8>, SynthesisError> {
+        self.to_bytes(cs)
+    }
+}
+
+// Extended Jacobian coordinates `(X, Y, Z, T = Z^2)` used while precomputing
+// the line-function coefficients.
+struct G2ProjectiveExtendedGadget<P: MNT6Parameters<Fp = E::Fr>, E: PairingEngine> {
+    x: Fp3G<P, E>,
+    y: Fp3G<P, E>,
+    z: Fp3G<P, E>,
+    t: Fp3G<P, E>,
+}
+
+impl<P: MNT6Parameters<Fp = E::Fr>, E: PairingEngine> G2PreparedGadget<P, E> {
+    pub fn from_affine<CS: ConstraintSystem<E>>(
+        mut cs: CS,
+        q: &G2Gadget<P, E>,
+    ) -> Result<Self, SynthesisError> {
+        let zero = G2Gadget::<P, E>::zero(cs.ns(|| "zero"))?;
+        q.enforce_not_equal(cs.ns(|| "enforce not zero"), &zero)?;
+
+        let twist_inv = P::TWIST.inverse().unwrap();
+        let x_over_twist = q.x.mul_by_constant(cs.ns(|| "x_over_twist"), &twist_inv)?;
+        let y_over_twist = q.y.mul_by_constant(cs.ns(|| "y_over_twist"), &twist_inv)?;
+
+        let mut double_coefficients = vec![];
+        let mut addition_coefficients = vec![];
+
+        let mut r = G2ProjectiveExtendedGadget {
+            x: q.x.clone(),
+            y: q.y.clone(),
+            z: Fp3G::<P, E>::one(cs.ns(|| "r.z"))?,
+            t: Fp3G::<P, E>::one(cs.ns(|| "r.t"))?,
+        };
+
+        // Skip leading zeros and the MSB of the loop count.
+        let bits = BitIterator::new(P::ATE_LOOP_COUNT)
+            .skip_while(|b| !b)
+            .skip(1);
+        for (j, bit) in bits.enumerate() {
+            let mut cs = cs.ns(|| format!("Iteration {}", j));
+            let (r2, coeff) = Self::doubling_step(cs.ns(|| "double"), &r)?;
+            double_coefficients.push(coeff);
+            r = r2;
+
+            if bit {
+                let (r2, coeff) = Self::mixed_addition_step(cs.ns(|| "add"), &q.x, &q.y, &r)?;
+                addition_coefficients.push(coeff);
+                r = r2;
+            }
+        }
+
+        if P::ATE_IS_LOOP_COUNT_NEG {
+            let mut cs = cs.ns(|| "Negative loop count");
+            let rz_inv = r.z.inverse(cs.ns(|| "rz_inv"))?;
+            let rz2_inv = rz_inv.square(cs.ns(|| "rz2_inv"))?;
+            let rz3_inv = rz_inv.mul(cs.ns(|| "rz3_inv"), &rz2_inv)?;
+
+            let minus_r_affine_x = r.x.mul(cs.ns(|| "minus_r_affine_x"), &rz2_inv)?;
+            let minus_r_affine_y = r
+                .y
+                .negate(cs.ns(|| "-r.y"))?
+                .mul(cs.ns(|| "minus_r_affine_y"), &rz3_inv)?;
+
+            let (_, coeff) = Self::mixed_addition_step(
+                cs.ns(|| "add"),
+                &minus_r_affine_x,
+                &minus_r_affine_y,
+                &r,
+            )?;
+            addition_coefficients.push(coeff);
+        }
+
+        Ok(Self {
+            x: q.x.clone(),
+            y: q.y.clone(),
+            x_over_twist,
+            y_over_twist,
+            double_coefficients,
+            addition_coefficients,
+        })
+    }
+
+    fn doubling_step<CS: ConstraintSystem<E>>(
+        mut cs: CS,
+        r: &G2ProjectiveExtendedGadget<P, E>,
+    ) -> Result<
+        (
+            G2ProjectiveExtendedGadget<P, E>,
+            AteDoubleCoefficientsGadget<P, E>,
+        ),
+        SynthesisError,
+    > {
+        let a = r.t.square(cs.ns(|| "a"))?;
+        let b = r.x.square(cs.ns(|| "b"))?;
+        let c = r.y.square(cs.ns(|| "c"))?;
+        let d = c.square(cs.ns(|| "d"))?;
+        let e = r
+            .x
+            .add(cs.ns(|| "r.x + c"), &c)?
+            .square(cs.ns(|| "(r.x + c)^2"))?
+            .sub(cs.ns(|| "- b"), &b)?
+            .sub(cs.ns(|| "- d"), &d)?;
+        let coeff_a_times_a = a.mul_by_constant(
+            cs.ns(|| "COEFF_A * a"),
+            &<P::G2Parameters as SWModelParameters>::COEFF_A,
+        )?;
+        let f = b
+            .double(cs.ns(|| "2b"))?
+            .add(cs.ns(|| "3b"), &b)?
+            .add(cs.ns(|| "f"), &coeff_a_times_a)?;
+        let g = f.square(cs.ns(|| "g"))?;
+
+        let d_eight = d
+            .double(cs.ns(|| "2d"))?
+            .double(cs.ns(|| "4d"))?
+            .double(cs.ns(|| "8d"))?;
+
+        let e_double = e.double(cs.ns(|| "2e"))?;
+        let x = g.sub(cs.ns(|| "x"), &e_double.double(cs.ns(|| "4e"))?)?;
+        let y = f
+            .mul(cs.ns(|| "f * (2e - x)"), &e_double.sub(cs.ns(|| "2e - x"), &x)?)?
+            .sub(cs.ns(|| "y"), &d_eight)?;
+        let r_z_square = r.z.square(cs.ns(|| "r.z^2"))?;
+        let z = r
+            .y
+            .add(cs.ns(|| "r.y + r.z"), &r.z)?
+            .square(cs.ns(|| "(r.y + r.z)^2"))?
+            .sub(cs.ns(|| "- c"), &c)?
+            .sub(cs.ns(|| "z"), &r_z_square)?;
+        let t = z.square(cs.ns(|| "t"))?;
+
+        let c_h = z
+            .add(cs.ns(|| "z + r.t"), &r.t)?
+            .square(cs.ns(|| "(z + r.t)^2"))?
+            .sub(cs.ns(|| "- t"), &t)?
+            .sub(cs.ns(|| "c_h"), &a)?;
+        let c_4c = c.double(cs.ns(|| "2c"))?.double(cs.ns(|| "c_4c"))?;
+        let c_j = f
+            .add(cs.ns(|| "f + r.t"), &r.t)?
+            .square(cs.ns(|| "(f + r.t)^2"))?
+            .sub(cs.ns(|| "- g"), &g)?
+            .sub(cs.ns(|| "c_j"), &a)?;
+        let c_l = f
+            .add(cs.ns(|| "f + r.x"), &r.x)?
+            .square(cs.ns(|| "(f + r.x)^2"))?
+            .sub(cs.ns(|| "- g (2)"), &g)?
+            .sub(cs.ns(|| "c_l"), &b)?;
+
+        Ok((
+            G2ProjectiveExtendedGadget { x, y, z, t },
+            AteDoubleCoefficientsGadget { c_h, c_4c, c_j, c_l },
+        ))
+    }
+
+    fn mixed_addition_step<CS: ConstraintSystem<E>>(
+        mut cs: CS,
+        x: &Fp3G<P, E>,
+        y: &Fp3G<P, E>,
+        r: &G2ProjectiveExtendedGadget<P, E>,
+    ) -> Result<
+        (
+            G2ProjectiveExtendedGadget<P, E>,
+            AteAdditionCoefficientsGadget<P, E>,
+        ),
+        SynthesisError,
+    > {
+        let a = y.square(cs.ns(|| "a"))?;
+        let b = r.t.mul(cs.ns(|| "b"), x)?;
+        let d = r
+            .z
+            .add(cs.ns(|| "r.z + y"), y)?
+            .square(cs.ns(|| "(r.z + y)^2"))?
+            .sub(cs.ns(|| "- a"), &a)?
+            .sub(cs.ns(|| "- r.t"), &r.t)?
+            .mul(cs.ns(|| "d"), &r.t)?;
+        let h = b.sub(cs.ns(|| "h"), &r.x)?;
+        let i = h.square(cs.ns(|| "i"))?;
+        let e = i.double(cs.ns(|| "2i"))?.double(cs.ns(|| "e"))?;
+        let j = h.mul(cs.ns(|| "j"), &e)?;
+        let v = r.x.mul(cs.ns(|| "v"), &e)?;
+        let r_y_double = r.y.double(cs.ns(|| "2r.y"))?;
+        let l1 = d.sub(cs.ns(|| "l1"), &r_y_double)?;
+
+        let v_double = v.double(cs.ns(|| "2v"))?;
+        let x = l1
+            .square(cs.ns(|| "l1^2"))?
+            .sub(cs.ns(|| "- j"), &j)?
+            .sub(cs.ns(|| "x"), &v_double)?;
+        let j_r_y_double = j.mul(cs.ns(|| "j * 2r.y"), &r_y_double)?;
+        let y = l1
+            .mul(cs.ns(|| "l1 * (v - x)"), &v.sub(cs.ns(|| "v - x"), &x)?)?
+            .sub(cs.ns(|| "y"), &j_r_y_double)?;
+        let z = r
+            .z
+            .add(cs.ns(|| "r.z + h"), &h)?
+            .square(cs.ns(|| "(r.z + h)^2"))?
+            .sub(cs.ns(|| "- r.t (2)"), &r.t)?
+            .sub(cs.ns(|| "z"), &i)?;
+        let t = z.square(cs.ns(|| "t"))?;
+
+        Ok((
+            G2ProjectiveExtendedGadget {
+                x,
+                y,
+                z: z.clone(),
+                t,
+            },
+            AteAdditionCoefficientsGadget { c_l1: l1, c_rz: z },
+        ))
+    }
+}
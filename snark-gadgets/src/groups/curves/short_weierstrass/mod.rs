@@ -22,6 +22,8 @@ use crate::{
 use algebra::{AffineCurve, Field, ProjectiveCurve};
 
 pub mod bls12;
+pub mod mnt6;
+pub mod projective;
 
 #[derive(Derivative)]
 #[derivative(Debug, Clone)]
@@ -314,11 +316,11 @@ where
     }
 
     fn cost_of_add() -> usize {
-        3 * F::cost_of_mul() + F::cost_of_inv()
+        3 * F::cost_of_mul_equals() + F::cost_of_inv()
     }
 
     fn cost_of_double() -> usize {
-        4 * F::cost_of_mul()
+        2 * F::cost_of_square() + F::cost_of_mul() + F::cost_of_mul_equals()
     }
 }
 
@@ -0,0 +1,874 @@
+use algebra::{
+    curves::{
+        short_weierstrass_jacobian::{GroupAffine as SWAffine, GroupProjective as SWProjective},
+        PairingEngine, SWModelParameters,
+    },
+    AffineCurve, BitIterator, Field, PrimeField, ProjectiveCurve,
+};
+use snark::{ConstraintSystem, SynthesisError};
+use std::{borrow::Borrow, marker::PhantomData, ops::Neg};
+
+use crate::{
+    boolean::Boolean,
+    fields::FieldGadget,
+    groups::GroupGadget,
+    uint8::UInt8,
+    utils::{
+        AllocGadget, CondSelectGadget, ConditionalEqGadget, EqGadget, NEqGadget, ToBitsGadget,
+        ToBytesGadget,
+    },
+    Assignment,
+};
+
+use super::AffineGadget;
+
+/// A short Weierstrass point in homogeneous projective coordinates
+/// `(X : Y : Z)`, representing the affine point `(X/Z, Y/Z)`. The neutral
+/// element is `(0 : 1 : 0)`.
+///
+/// Unlike `AffineGadget`, addition and doubling use the complete formulas of
+/// Renes, Costello and Batina ("Complete addition formulas for prime order
+/// elliptic curves", Algorithms 1 and 3), so they are correct for every pair
+/// of points in the prime order subgroup, including the neutral element,
+/// equal points and inverse points.
+///
+/// The gadget is generic; this crate only instantiates it for the BLS12-377
+/// groups inside SW6 circuits. There are no BLS12-381 or SW6 instantiations,
+/// since no engine in this repository has the BLS12-381 or SW6 base field as
+/// its scalar field.
+#[derive(Derivative)]
+#[derivative(Debug, Clone)]
+#[must_use]
+pub struct ProjectiveGadget<
+    P: SWModelParameters,
+    E: PairingEngine,
+    F: FieldGadget<P::BaseField, E>,
+> {
+    pub x:   F,
+    pub y:   F,
+    pub z:   F,
+    _params: PhantomData<P>,
+    _engine: PhantomData<E>,
+}
+
+impl<P: SWModelParameters, E: PairingEngine, F: FieldGadget<P::BaseField, E>>
+    ProjectiveGadget<P, E, F>
+{
+    pub fn new(x: F, y: F, z: F) -> Self {
+        Self {
+            x,
+            y,
+            z,
+            _params: PhantomData,
+            _engine: PhantomData,
+        }
+    }
+
+    /// Converts `self` into affine coordinates. The neutral element is mapped
+    /// to `(0, 1)`, which is the encoding used by `AffineGadget::zero`.
+    pub fn to_affine<CS: ConstraintSystem<E>>(
+        &self,
+        mut cs: CS,
+    ) -> Result<AffineGadget<P, E, F>, SynthesisError> {
+        let infinity = Boolean::alloc(cs.ns(|| "infinity"), || {
+            Ok(self.z.get_value().get()?.is_zero())
+        })?;
+        let z_inv = F::alloc(cs.ns(|| "z_inv"), || {
+            Ok(self
+                .z
+                .get_value()
+                .get()?
+                .inverse()
+                .unwrap_or(P::BaseField::zero()))
+        })?;
+
+        let zero = F::zero(cs.ns(|| "zero"))?;
+        let one = F::one(cs.ns(|| "one"))?;
+
+        // If Z != 0, then z_inv must be its inverse.
+        // If Z == 0, then the point is the neutral element.
+        let not_infinity =
+            F::conditionally_select(cs.ns(|| "1 - infinity"), &infinity, &zero, &one)?;
+        self.z
+            .mul_equals(cs.ns(|| "check z_inv"), &z_inv, &not_infinity)?;
+        self.z
+            .conditional_enforce_equal(cs.ns(|| "check infinity"), &zero, &infinity)?;
+
+        let x = self.x.mul(cs.ns(|| "X * z_inv"), &z_inv)?;
+        let y = self.y.mul(cs.ns(|| "Y * z_inv"), &z_inv)?;
+        let x = F::conditionally_select(cs.ns(|| "select x"), &infinity, &zero, &x)?;
+        let y = F::conditionally_select(cs.ns(|| "select y"), &infinity, &one, &y)?;
+
+        Ok(AffineGadget::new(x, y))
+    }
+
+    /// Allocates the projective representation of the affine point `(x, y)`,
+    /// or of the neutral element if `infinity` is set. In the latter case
+    /// `(x, y)` must be `(0, 1)`.
+    fn from_affine_coordinates<CS: ConstraintSystem<E>>(
+        mut cs: CS,
+        x: F,
+        y: F,
+        infinity: &Boolean,
+    ) -> Result<Self, SynthesisError> {
+        let b = P::COEFF_B;
+        let a = P::COEFF_A;
+
+        // Check that y^2 = x^3 + ax + b unless `infinity` is set.
+        // We do this by checking that y^2 - b = x * (x^2 + a)
+        let x2 = x.square(&mut cs.ns(|| "x^2"))?;
+        let y2 = y.square(&mut cs.ns(|| "y^2"))?;
+
+        let x2_plus_a = x2.add_constant(cs.ns(|| "x^2 + a"), &a)?;
+        let y2_minus_b = y2.add_constant(cs.ns(|| "y^2 - b"), &b.neg())?;
+        let rhs = x2_plus_a.mul(cs.ns(|| "x * (x^2 + a)"), &x)?;
+        y2_minus_b.conditional_enforce_equal(
+            cs.ns(|| "on curve check"),
+            &rhs,
+            &infinity.not(),
+        )?;
+
+        // Otherwise the point must be encoded as (0, 1).
+        let zero = F::zero(cs.ns(|| "zero"))?;
+        let one = F::one(cs.ns(|| "one"))?;
+        x.conditional_enforce_equal(cs.ns(|| "infinity x"), &zero, infinity)?;
+        y.conditional_enforce_equal(cs.ns(|| "infinity y"), &one, infinity)?;
+
+        let z = F::conditionally_select(cs.ns(|| "z"), infinity, &zero, &one)?;
+
+        Ok(Self::new(x, y, z))
+    }
+
+    /// Computes the second half of the complete addition formula, given
+    ///
+    /// t0 = X1 * X2, t1 = Y1 * Y2, t2 = Z1 * Z2,
+    /// t3 = X1 * Y2 + X2 * Y1, t4 = X1 * Z2 + X2 * Z1, t5 = Y1 * Z2 + Y2 * Z1.
+    fn finish_add<CS: ConstraintSystem<E>>(
+        mut cs: CS,
+        t0: F,
+        t1: F,
+        t2: F,
+        t3: F,
+        t4: F,
+        t5: F,
+    ) -> Result<Self, SynthesisError> {
+        let a = P::COEFF_A;
+        let b3 = P::COEFF_B.double() + &P::COEFF_B;
+
+        // Z3 = a * t4 + 3b * t2
+        let a_t4 = t4.mul_by_constant(cs.ns(|| "a * t4"), &a)?;
+        let z3 = t2
+            .mul_by_constant(cs.ns(|| "3b * t2"), &b3)?
+            .add(cs.ns(|| "a * t4 + 3b * t2"), &a_t4)?;
+        let x3 = t1.sub(cs.ns(|| "t1 - Z3"), &z3)?;
+        let z3 = t1.add(cs.ns(|| "t1 + Z3"), &z3)?;
+        let y3 = x3.mul(cs.ns(|| "X3 * Z3"), &z3)?;
+
+        // t1 = 3 * t0 + a * t2
+        let a_t2 = t2.mul_by_constant(cs.ns(|| "a * t2"), &a)?;
+        let t1 = t0
+            .double(cs.ns(|| "2 * t0"))?
+            .add(cs.ns(|| "3 * t0"), &t0)?
+            .add(cs.ns(|| "3 * t0 + a * t2"), &a_t2)?;
+        // t2 = a * (t0 - a * t2)
+        let t2 = t0
+            .sub(cs.ns(|| "t0 - a * t2"), &a_t2)?
+            .mul_by_constant(cs.ns(|| "a * (t0 - a * t2)"), &a)?;
+        // t4 = 3b * t4 + t2
+        let t4 = t4
+            .mul_by_constant(cs.ns(|| "3b * t4"), &b3)?
+            .add(cs.ns(|| "3b * t4 + t2"), &t2)?;
+
+        // Y3 = Y3 + t1 * t4
+        let t1_t4 = t1.mul(cs.ns(|| "t1 * t4"), &t4)?;
+        let y3 = y3.add(cs.ns(|| "Y3 + t1 * t4"), &t1_t4)?;
+
+        // X3 = t3 * X3 - t5 * t4
+        let t5_t4 = t5.mul(cs.ns(|| "t5 * t4"), &t4)?;
+        let x3 = t3
+            .mul(cs.ns(|| "t3 * X3"), &x3)?
+            .sub(cs.ns(|| "t3 * X3 - t5 * t4"), &t5_t4)?;
+
+        // Z3 = t5 * Z3 + t3 * t1
+        let t3_t1 = t3.mul(cs.ns(|| "t3 * t1"), &t1)?;
+        let z3 = t5
+            .mul(cs.ns(|| "t5 * Z3"), &z3)?
+            .add(cs.ns(|| "t5 * Z3 + t3 * t1"), &t3_t1)?;
+
+        Ok(Self::new(x3, y3, z3))
+    }
+}
+
+impl<P, E, F> PartialEq for ProjectiveGadget<P, E, F>
+where
+    P: SWModelParameters,
+    E: PairingEngine,
+    F: FieldGadget<P::BaseField, E>,
+{
+    fn eq(&self, other: &Self) -> bool {
+        match (self.get_value(), other.get_value()) {
+            (Some(a), Some(b)) => a == b,
+            _ => false,
+        }
+    }
+}
+
+impl<P, E, F> Eq for ProjectiveGadget<P, E, F>
+where
+    P: SWModelParameters,
+    E: PairingEngine,
+    F: FieldGadget<P::BaseField, E>,
+{
+}
+
+impl<P, E, F> GroupGadget<SWProjective<P>, E> for ProjectiveGadget<P, E, F>
+where
+    P: SWModelParameters,
+    E: PairingEngine,
+    F: FieldGadget<P::BaseField, E>,
+{
+    type Value = SWProjective<P>;
+    type Variable = (F::Variable, F::Variable, F::Variable);
+
+    #[inline]
+    fn get_value(&self) -> Option<Self::Value> {
+        match (self.x.get_value(), self.y.get_value(), self.z.get_value()) {
+            (Some(x), Some(y), Some(z)) => match z.inverse() {
+                Some(z_inv) => {
+                    Some(SWAffine::new(x * &z_inv, y * &z_inv, false).into_projective())
+                },
+                None => Some(SWProjective::zero()),
+            },
+            _ => None,
+        }
+    }
+
+    #[inline]
+    fn get_variable(&self) -> Self::Variable {
+        (
+            self.x.get_variable(),
+            self.y.get_variable(),
+            self.z.get_variable(),
+        )
+    }
+
+    #[inline]
+    fn zero<CS: ConstraintSystem<E>>(mut cs: CS) -> Result<Self, SynthesisError> {
+        Ok(Self::new(
+            F::zero(cs.ns(|| "x"))?,
+            F::one(cs.ns(|| "y"))?,
+            F::zero(cs.ns(|| "z"))?,
+        ))
+    }
+
+    /// Complete addition (Algorithm 1 of Renes-Costello-Batina).
+    fn add<CS: ConstraintSystem<E>>(
+        &self,
+        mut cs: CS,
+        other: &Self,
+    ) -> Result<Self, SynthesisError> {
+        let t0 = self.x.mul(cs.ns(|| "X1 * X2"), &other.x)?;
+        let t1 = self.y.mul(cs.ns(|| "Y1 * Y2"), &other.y)?;
+        let t2 = self.z.mul(cs.ns(|| "Z1 * Z2"), &other.z)?;
+
+        // t3 = (X1 + Y1) * (X2 + Y2) - t0 - t1
+        let x1_plus_y1 = self.x.add(cs.ns(|| "X1 + Y1"), &self.y)?;
+        let x2_plus_y2 = other.x.add(cs.ns(|| "X2 + Y2"), &other.y)?;
+        let t3 = x1_plus_y1
+            .mul(cs.ns(|| "(X1 + Y1) * (X2 + Y2)"), &x2_plus_y2)?
+            .sub(cs.ns(|| "t3 - t0"), &t0)?
+            .sub(cs.ns(|| "t3 - t0 - t1"), &t1)?;
+
+        // t4 = (X1 + Z1) * (X2 + Z2) - t0 - t2
+        let x1_plus_z1 = self.x.add(cs.ns(|| "X1 + Z1"), &self.z)?;
+        let x2_plus_z2 = other.x.add(cs.ns(|| "X2 + Z2"), &other.z)?;
+        let t4 = x1_plus_z1
+            .mul(cs.ns(|| "(X1 + Z1) * (X2 + Z2)"), &x2_plus_z2)?
+            .sub(cs.ns(|| "t4 - t0"), &t0)?
+            .sub(cs.ns(|| "t4 - t0 - t2"), &t2)?;
+
+        // t5 = (Y1 + Z1) * (Y2 + Z2) - t1 - t2
+        let y1_plus_z1 = self.y.add(cs.ns(|| "Y1 + Z1"), &self.z)?;
+        let y2_plus_z2 = other.y.add(cs.ns(|| "Y2 + Z2"), &other.z)?;
+        let t5 = y1_plus_z1
+            .mul(cs.ns(|| "(Y1 + Z1) * (Y2 + Z2)"), &y2_plus_z2)?
+            .sub(cs.ns(|| "t5 - t1"), &t1)?
+            .sub(cs.ns(|| "t5 - t1 - t2"), &t2)?;
+
+        Self::finish_add(cs.ns(|| "finish"), t0, t1, t2, t3, t4, t5)
+    }
+
+    /// Complete mixed addition: since `other` is a constant, only the
+    /// products in the second half of the formula require constraints.
+    fn add_constant<CS: ConstraintSystem<E>>(
+        &self,
+        mut cs: CS,
+        other: &SWProjective<P>,
+    ) -> Result<Self, SynthesisError> {
+        if other.is_zero() {
+            return Ok(self.clone());
+        }
+        let other = other.into_affine();
+        let x2 = other.x;
+        let y2 = other.y;
+
+        let t0 = self.x.mul_by_constant(cs.ns(|| "X1 * x2"), &x2)?;
+        let t1 = self.y.mul_by_constant(cs.ns(|| "Y1 * y2"), &y2)?;
+        let t2 = self.z.clone();
+
+        // t3 = (X1 + Y1) * (x2 + y2) - t0 - t1
+        let t3 = self
+            .x
+            .add(cs.ns(|| "X1 + Y1"), &self.y)?
+            .mul_by_constant(cs.ns(|| "(X1 + Y1) * (x2 + y2)"), &(x2 + &y2))?
+            .sub(cs.ns(|| "t3 - t0"), &t0)?
+            .sub(cs.ns(|| "t3 - t0 - t1"), &t1)?;
+
+        // t4 = X1 + x2 * Z1
+        let t4 = self
+            .z
+            .mul_by_constant(cs.ns(|| "x2 * Z1"), &x2)?
+            .add(cs.ns(|| "X1 + x2 * Z1"), &self.x)?;
+
+        // t5 = Y1 + y2 * Z1
+        let t5 = self
+            .z
+            .mul_by_constant(cs.ns(|| "y2 * Z1"), &y2)?
+            .add(cs.ns(|| "Y1 + y2 * Z1"), &self.y)?;
+
+        Self::finish_add(cs.ns(|| "finish"), t0, t1, t2, t3, t4, t5)
+    }
+
+    /// Complete doubling (Algorithm 3 of Renes-Costello-Batina).
+    fn double_in_place<CS: ConstraintSystem<E>>(
+        &mut self,
+        mut cs: CS,
+    ) -> Result<(), SynthesisError> {
+        let a = P::COEFF_A;
+        let b3 = P::COEFF_B.double() + &P::COEFF_B;
+
+        let t0 = self.x.square(cs.ns(|| "X^2"))?;
+        let t1 = self.y.square(cs.ns(|| "Y^2"))?;
+        let t2 = self.z.square(cs.ns(|| "Z^2"))?;
+
+        // t3 = 2 * X * Y
+        let t3 = self
+            .x
+            .mul(cs.ns(|| "X * Y"), &self.y)?
+            .double(cs.ns(|| "2 * X * Y"))?;
+        // Z3 = 2 * X * Z
+        let z3 = self
+            .x
+            .mul(cs.ns(|| "X * Z"), &self.z)?
+            .double(cs.ns(|| "2 * X * Z"))?;
+
+        // Y3 = a * Z3 + 3b * t2
+        let a_z3 = z3.mul_by_constant(cs.ns(|| "a * Z3"), &a)?;
+        let y3 = t2
+            .mul_by_constant(cs.ns(|| "3b * t2"), &b3)?
+            .add(cs.ns(|| "a * Z3 + 3b * t2"), &a_z3)?;
+        let x3 = t1.sub(cs.ns(|| "t1 - Y3"), &y3)?;
+        let y3 = t1
+            .add(cs.ns(|| "t1 + Y3"), &y3)?
+            .mul(cs.ns(|| "X3 * Y3"), &x3)?;
+        let x3 = t3.mul(cs.ns(|| "t3 * X3"), &x3)?;
+
+        let z3 = z3.mul_by_constant(cs.ns(|| "3b * Z3"), &b3)?;
+        let t2 = t2.mul_by_constant(cs.ns(|| "a * t2"), &a)?;
+        // t3 = a * (t0 - t2) + Z3
+        let t3 = t0
+            .sub(cs.ns(|| "t0 - t2"), &t2)?
+            .mul_by_constant(cs.ns(|| "a * (t0 - t2)"), &a)?
+            .add(cs.ns(|| "a * (t0 - t2) + Z3"), &z3)?;
+        // Y3 = Y3 + (3 * t0 + t2) * t3
+        let t0 = t0
+            .double(cs.ns(|| "2 * t0"))?
+            .add(cs.ns(|| "3 * t0"), &t0)?
+            .add(cs.ns(|| "3 * t0 + t2"), &t2)?
+            .mul(cs.ns(|| "(3 * t0 + t2) * t3"), &t3)?;
+        let y3 = y3.add(cs.ns(|| "Y3 + t0"), &t0)?;
+
+        // t2 = 2 * Y * Z
+        let t2 = self
+            .y
+            .mul(cs.ns(|| "Y * Z"), &self.z)?
+            .double(cs.ns(|| "2 * Y * Z"))?;
+        // X3 = X3 - t2 * t3
+        let t2_t3 = t2.mul(cs.ns(|| "t2 * t3"), &t3)?;
+        let x3 = x3.sub(cs.ns(|| "X3 - t2 * t3"), &t2_t3)?;
+        // Z3 = 4 * t2 * t1
+        let z3 = t2
+            .mul(cs.ns(|| "t2 * t1"), &t1)?
+            .double(cs.ns(|| "2 * t2 * t1"))?
+            .double(cs.ns(|| "4 * t2 * t1"))?;
+
+        *self = Self::new(x3, y3, z3);
+        Ok(())
+    }
+
+    fn negate<CS: ConstraintSystem<E>>(&self, mut cs: CS) -> Result<Self, SynthesisError> {
+        Ok(Self::new(
+            self.x.clone(),
+            self.y.negate(cs.ns(|| "negate y"))?,
+            self.z.clone(),
+        ))
+    }
+
+    fn cost_of_add() -> usize {
+        12 * F::cost_of_mul()
+    }
+
+    fn cost_of_double() -> usize {
+        3 * F::cost_of_square() + 8 * F::cost_of_mul()
+    }
+}
+
+impl<P, E, F> CondSelectGadget<E> for ProjectiveGadget<P, E, F>
+where
+    P: SWModelParameters,
+    E: PairingEngine,
+    F: FieldGadget<P::BaseField, E>,
+{
+    #[inline]
+    fn conditionally_select<CS: ConstraintSystem<E>>(
+        mut cs: CS,
+        cond: &Boolean,
+        first: &Self,
+        second: &Self,
+    ) -> Result<Self, SynthesisError> {
+        let x = F::conditionally_select(&mut cs.ns(|| "x"), cond, &first.x, &second.x)?;
+        let y = F::conditionally_select(&mut cs.ns(|| "y"), cond, &first.y, &second.y)?;
+        let z = F::conditionally_select(&mut cs.ns(|| "z"), cond, &first.z, &second.z)?;
+
+        Ok(Self::new(x, y, z))
+    }
+
+    fn cost() -> usize {
+        3 * <F as CondSelectGadget<E>>::cost()
+    }
+}
+
+impl<P, E, F> EqGadget<E> for ProjectiveGadget<P, E, F>
+where
+    P: SWModelParameters,
+    E: PairingEngine,
+    F: FieldGadget<P::BaseField, E>,
+{
+}
+
+impl<P, E, F> ConditionalEqGadget<E> for ProjectiveGadget<P, E, F>
+where
+    P: SWModelParameters,
+    E: PairingEngine,
+    F: FieldGadget<P::BaseField, E>,
+{
+    /// Checks that X1 * Z2 = X2 * Z1 and Y1 * Z2 = Y2 * Z1.
+    #[inline]
+    fn conditional_enforce_equal<CS: ConstraintSystem<E>>(
+        &self,
+        mut cs: CS,
+        other: &Self,
+        condition: &Boolean,
+    ) -> Result<(), SynthesisError> {
+        let x1_z2 = self.x.mul(cs.ns(|| "X1 * Z2"), &other.z)?;
+        let x2_z1 = other.x.mul(cs.ns(|| "X2 * Z1"), &self.z)?;
+        let y1_z2 = self.y.mul(cs.ns(|| "Y1 * Z2"), &other.z)?;
+        let y2_z1 = other.y.mul(cs.ns(|| "Y2 * Z1"), &self.z)?;
+        x1_z2.conditional_enforce_equal(
+            &mut cs.ns(|| "X Coordinate Conditional Equality"),
+            &x2_z1,
+            condition,
+        )?;
+        y1_z2.conditional_enforce_equal(
+            &mut cs.ns(|| "Y Coordinate Conditional Equality"),
+            &y2_z1,
+            condition,
+        )?;
+        Ok(())
+    }
+
+    fn cost() -> usize {
+        4 * F::cost_of_mul() + 2 * <F as ConditionalEqGadget<E>>::cost()
+    }
+}
+
+impl<P, E, F> NEqGadget<E> for ProjectiveGadget<P, E, F>
+where
+    P: SWModelParameters,
+    E: PairingEngine,
+    F: FieldGadget<P::BaseField, E>,
+{
+    #[inline]
+    fn enforce_not_equal<CS: ConstraintSystem<E>>(
+        &self,
+        mut cs: CS,
+        other: &Self,
+    ) -> Result<(), SynthesisError> {
+        let self_affine = self.to_affine(cs.ns(|| "self to affine"))?;
+        let other_affine = other.to_affine(cs.ns(|| "other to affine"))?;
+        self_affine.enforce_not_equal(cs.ns(|| "affine inequality"), &other_affine)
+    }
+
+    fn cost() -> usize {
+        // Each conversion to affine allocates the `infinity` bit, and performs
+        // one multiplication check, two multiplications, three selections and
+        // one conditional equality check.
+        let to_affine_cost = 1
+            + F::cost_of_mul_equals()
+            + 2 * F::cost_of_mul()
+            + 3 * <F as CondSelectGadget<E>>::cost()
+            + <F as ConditionalEqGadget<E>>::cost();
+        2 * to_affine_cost + 2 * <F as NEqGadget<E>>::cost()
+    }
+}
+
+impl<P, E, F> AllocGadget<SWProjective<P>, E> for ProjectiveGadget<P, E, F>
+where
+    P: SWModelParameters,
+    E: PairingEngine,
+    F: FieldGadget<P::BaseField, E>,
+{
+    #[inline]
+    fn alloc<FN, T, CS: ConstraintSystem<E>>(
+        mut cs: CS,
+        value_gen: FN,
+    ) -> Result<Self, SynthesisError>
+    where
+        FN: FnOnce() -> Result<T, SynthesisError>,
+        T: Borrow<SWProjective<P>>,
+    {
+        let (x, y, infinity) = match value_gen() {
+            Ok(ge) => {
+                let ge = ge.borrow().into_affine();
+                (Ok(ge.x), Ok(ge.y), Ok(ge.infinity))
+            },
+            _ => (
+                Err(SynthesisError::AssignmentMissing),
+                Err(SynthesisError::AssignmentMissing),
+                Err(SynthesisError::AssignmentMissing),
+            ),
+        };
+
+        let x = F::alloc(&mut cs.ns(|| "x"), || x)?;
+        let y = F::alloc(&mut cs.ns(|| "y"), || y)?;
+        let infinity = Boolean::alloc(&mut cs.ns(|| "infinity"), || infinity)?;
+
+        Self::from_affine_coordinates(cs.ns(|| "to projective"), x, y, &infinity)
+    }
+
+    #[inline]
+    fn alloc_checked<FN, T, CS: ConstraintSystem<E>>(
+        mut cs: CS,
+        value_gen: FN,
+    ) -> Result<Self, SynthesisError>
+    where
+        FN: FnOnce() -> Result<T, SynthesisError>,
+        T: Borrow<SWProjective<P>>,
+    {
+        let cofactor_weight = BitIterator::new(P::COFACTOR).filter(|b| *b).count();
+        let r_minus_1 = (-P::ScalarField::one()).into_repr();
+        let r_weight = BitIterator::new(&r_minus_1).filter(|b| *b).count();
+
+        // As in `AffineGadget`, we pick the cheaper of the two prime order
+        // checks: either we allocate `ge * cofactor^{-1}` and multiply it by
+        // the cofactor, or we check that `(r - 1) * ge = -ge`. The addition
+        // formulas are complete, so no special care is needed for the
+        // intermediate results.
+        if cofactor_weight < r_weight {
+            let ge = Self::alloc(cs.ns(|| "Alloc checked"), || {
+                value_gen().map(|ge| {
+                    ge.borrow()
+                        .into_affine()
+                        .mul_by_cofactor_inv()
+                        .into_projective()
+                })
+            })?;
+            let mut seen_one = false;
+            let mut result = Self::zero(cs.ns(|| "result"))?;
+            for (i, b) in BitIterator::new(P::COFACTOR).enumerate() {
+                let mut cs = cs.ns(|| format!("Iteration {}", i));
+
+                let old_seen_one = seen_one;
+                if seen_one {
+                    result.double_in_place(cs.ns(|| "Double"))?;
+                } else {
+                    seen_one = b;
+                }
+
+                if b {
+                    result = if old_seen_one {
+                        result.add(cs.ns(|| "Add"), &ge)?
+                    } else {
+                        ge.clone()
+                    };
+                }
+            }
+            Ok(result)
+        } else {
+            let ge = Self::alloc(cs.ns(|| "Alloc checked"), value_gen)?;
+            let mut seen_one = false;
+            let mut result = Self::zero(cs.ns(|| "result"))?;
+            // Returns bits in big-endian order
+            for (i, b) in BitIterator::new(r_minus_1).enumerate() {
+                let mut cs = cs.ns(|| format!("Iteration {}", i));
+
+                let old_seen_one = seen_one;
+                if seen_one {
+                    result.double_in_place(cs.ns(|| "Double"))?;
+                } else {
+                    seen_one = b;
+                }
+
+                if b {
+                    result = if old_seen_one {
+                        result.add(cs.ns(|| "Add"), &ge)?
+                    } else {
+                        ge.clone()
+                    };
+                }
+            }
+            let neg_ge = ge.negate(cs.ns(|| "Negate ge"))?;
+            neg_ge.enforce_equal(cs.ns(|| "Check equals"), &result)?;
+            Ok(ge)
+        }
+    }
+
+    #[inline]
+    fn alloc_input<FN, T, CS: ConstraintSystem<E>>(
+        mut cs: CS,
+        value_gen: FN,
+    ) -> Result<Self, SynthesisError>
+    where
+        FN: FnOnce() -> Result<T, SynthesisError>,
+        T: Borrow<SWProjective<P>>,
+    {
+        let (x, y, infinity) = match value_gen() {
+            Ok(ge) => {
+                let ge = ge.borrow().into_affine();
+                (Ok(ge.x), Ok(ge.y), Ok(ge.infinity))
+            },
+            _ => (
+                Err(SynthesisError::AssignmentMissing),
+                Err(SynthesisError::AssignmentMissing),
+                Err(SynthesisError::AssignmentMissing),
+            ),
+        };
+
+        // Only the affine coordinates are public; `infinity` is determined by
+        // them, since the neutral element must be encoded as (0, 1).
+        let x = F::alloc_input(&mut cs.ns(|| "x"), || x)?;
+        let y = F::alloc_input(&mut cs.ns(|| "y"), || y)?;
+        let infinity = Boolean::alloc(&mut cs.ns(|| "infinity"), || infinity)?;
+
+        Self::from_affine_coordinates(cs.ns(|| "to projective"), x, y, &infinity)
+    }
+}
+
+impl<P, E, F> ToBitsGadget<E> for ProjectiveGadget<P, E, F>
+where
+    P: SWModelParameters,
+    E: PairingEngine,
+    F: FieldGadget<P::BaseField, E>,
+{
+    fn to_bits<CS: ConstraintSystem<E>>(&self, mut cs: CS) -> Result<Vec<Boolean>, SynthesisError> {
+        self.to_affine(cs.ns(|| "to affine"))?
+            .to_bits(cs.ns(|| "affine to bits"))
+    }
+
+    fn to_bits_strict<CS: ConstraintSystem<E>>(
+        &self,
+        mut cs: CS,
+    ) -> Result<Vec<Boolean>, SynthesisError> {
+        self.to_affine(cs.ns(|| "to affine"))?
+            .to_bits_strict(cs.ns(|| "affine to bits"))
+    }
+}
+
+impl<P, E, F> ToBytesGadget<E> for ProjectiveGadget<P, E, F>
+where
+    P: SWModelParameters,
+    E: PairingEngine,
+    F: FieldGadget<P::BaseField, E>,
+{
+    fn to_bytes<CS: ConstraintSystem<E>>(&self, mut cs: CS) -> Result<Vec<UInt8>, SynthesisError> {
+        self.to_affine(cs.ns(|| "to affine"))?
+            .to_bytes(cs.ns(|| "affine to bytes"))
+    }
+
+    fn to_bytes_strict<CS: ConstraintSystem<E>>(
+        &self,
+        mut cs: CS,
+    ) -> Result<Vec<UInt8>, SynthesisError> {
+        self.to_affine(cs.ns(|| "to affine"))?
+            .to_bytes_strict(cs.ns(|| "affine to bytes"))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use rand::{Rand, SeedableRng, XorShiftRng};
+
+    use super::ProjectiveGadget;
+    use crate::{
+        boolean::{AllocatedBit, Boolean},
+        fields::{fp::FpGadget, fp2::Fp2Gadget},
+        groups::{test::group_test, GroupGadget},
+        test_constraint_system::TestConstraintSystem,
+        utils::{AllocGadget, CondSelectGadget, EqGadget},
+    };
+    use algebra::{
+        curves::{
+            bls12_377::{
+                g1::Bls12_377G1Parameters, g2::Bls12_377G2Parameters,
+                G1Projective as G1, G2Projective as G2,
+            },
+            sw6::SW6,
+        },
+        fields::bls12_377::{Fq2Parameters, Fr},
+        AffineCurve, BitIterator, PrimeField, ProjectiveCurve,
+    };
+    use snark::ConstraintSystem;
+
+    type G1Gadget = ProjectiveGadget<Bls12_377G1Parameters, SW6, FpGadget<SW6>>;
+    type G2Gadget = ProjectiveGadget<Bls12_377G2Parameters, SW6, Fp2Gadget<Fq2Parameters, SW6>>;
+
+    #[test]
+    fn projective_g1_constraint_costs() {
+        let mut cs = TestConstraintSystem::<SW6>::new();
+
+        let bit = AllocatedBit::alloc(&mut cs.ns(|| "bool"), || Ok(true))
+            .unwrap()
+            .into();
+
+        let a: G1 = rand::random();
+        let b: G1 = rand::random();
+        let gadget_a = G1Gadget::alloc(&mut cs.ns(|| "a"), || Ok(a)).unwrap();
+        let gadget_b = G1Gadget::alloc(&mut cs.ns(|| "b"), || Ok(b)).unwrap();
+        let alloc_cost = cs.num_constraints();
+        let _ = G1Gadget::conditionally_select(
+            &mut cs.ns(|| "cond_select"),
+            &bit,
+            &gadget_a,
+            &gadget_b,
+        )
+        .unwrap();
+        let cond_select_cost = cs.num_constraints() - alloc_cost;
+
+        let _ = gadget_a.add(&mut cs.ns(|| "ab"), &gadget_b).unwrap();
+        let add_cost = cs.num_constraints() - cond_select_cost - alloc_cost;
+
+        assert!(cs.is_satisfied());
+        assert_eq!(
+            cond_select_cost,
+            <G1Gadget as CondSelectGadget<SW6>>::cost()
+        );
+        assert_eq!(add_cost, G1Gadget::cost_of_add());
+
+        let mut gadget_a2 = gadget_a.clone();
+        let num_constraints = cs.num_constraints();
+        gadget_a2.double_in_place(&mut cs.ns(|| "2a")).unwrap();
+        assert_eq!(cs.num_constraints() - num_constraints, G1Gadget::cost_of_double());
+        assert!(cs.is_satisfied());
+    }
+
+    #[test]
+    fn projective_g1_gadget_test() {
+        let mut rng = XorShiftRng::from_seed([0x5dbe6259, 0x8d313d76, 0x3237db17, 0xe5bc0654]);
+
+        let mut cs = TestConstraintSystem::<SW6>::new();
+
+        let a = G1::rand(&mut rng);
+        let b = G1::rand(&mut rng);
+        let gadget_a = G1Gadget::alloc_checked(&mut cs.ns(|| "a"), || Ok(a)).unwrap();
+        let gadget_b = G1Gadget::alloc_checked(&mut cs.ns(|| "b"), || Ok(b)).unwrap();
+        assert_eq!(gadget_a.get_value().unwrap(), a);
+        assert_eq!(gadget_b.get_value().unwrap(), b);
+        group_test::<_, G1, _, _>(&mut cs.ns(|| "group test"), gadget_a.clone(), gadget_b.clone());
+
+        // Check the neutral element on both sides of an addition.
+        let zero = G1Gadget::alloc(&mut cs.ns(|| "zero"), || Ok(G1::zero())).unwrap();
+        assert_eq!(zero.get_value().unwrap(), G1::zero());
+        let zero_a = zero.add(cs.ns(|| "0 + a"), &gadget_a).unwrap();
+        zero_a
+            .enforce_equal(cs.ns(|| "0 + a == a"), &gadget_a)
+            .unwrap();
+        let mut zero_zero = zero.add(cs.ns(|| "0 + 0"), &zero).unwrap();
+        zero_zero.double_in_place(cs.ns(|| "2 * 0")).unwrap();
+        assert_eq!(zero_zero.get_value().unwrap(), G1::zero());
+
+        // Check inverse points.
+        let neg_a = gadget_a.negate(cs.ns(|| "-a")).unwrap();
+        let a_minus_a = gadget_a.add(cs.ns(|| "a + (-a)"), &neg_a).unwrap();
+        a_minus_a
+            .enforce_equal(cs.ns(|| "a + (-a) == 0"), &zero)
+            .unwrap();
+        let zero_affine = a_minus_a.to_affine(cs.ns(|| "to affine")).unwrap();
+        assert!(zero_affine.get_value().unwrap().is_zero());
+
+        // Check add_constant
+        let ab = gadget_a.add_constant(cs.ns(|| "a + const b"), &b).unwrap();
+        assert_eq!(ab.get_value().unwrap(), a + &b);
+        let a_a = gadget_a.add_constant(cs.ns(|| "a + const a"), &a).unwrap();
+        assert_eq!(a_a.get_value().unwrap(), a.double());
+        let zero_b = zero.add_constant(cs.ns(|| "0 + const b"), &b).unwrap();
+        assert_eq!(zero_b.get_value().unwrap(), b);
+
+        // Check mul_bits starting from the neutral element.
+        let scalar = Fr::rand(&mut rng);
+        let native_result = a.into_affine().mul(scalar);
+
+        let mut scalar: Vec<bool> = BitIterator::new(scalar.into_repr()).collect();
+        // Get the scalar bits into little-endian form.
+        scalar.reverse();
+        let input = Vec::<Boolean>::alloc(cs.ns(|| "Input"), || Ok(scalar)).unwrap();
+        let result = gadget_a
+            .mul_bits(cs.ns(|| "mul_bits"), &zero, input.iter())
+            .unwrap();
+        assert_eq!(result.get_value().unwrap(), native_result);
+
+        if !cs.is_satisfied() {
+            println!("{:?}", cs.which_is_unsatisfied().unwrap());
+        }
+
+        assert!(cs.is_satisfied());
+    }
+
+    #[test]
+    fn projective_g2_gadget_test() {
+        let mut rng = XorShiftRng::from_seed([0x5dbe6259, 0x8d313d76, 0x3237db17, 0xe5bc0654]);
+
+        let mut cs = TestConstraintSystem::<SW6>::new();
+
+        let a = G2::rand(&mut rng);
+        let b = G2::rand(&mut rng);
+        let gadget_a = G2Gadget::alloc(&mut cs.ns(|| "a"), || Ok(a)).unwrap();
+        let gadget_b = G2Gadget::alloc(&mut cs.ns(|| "b"), || Ok(b)).unwrap();
+        group_test::<_, G2, _, _>(&mut cs.ns(|| "group test"), gadget_a.clone(), gadget_b);
+
+        let zero = G2Gadget::zero(cs.ns(|| "zero")).unwrap();
+        let a_zero = gadget_a.add(cs.ns(|| "a + 0"), &zero).unwrap();
+        a_zero
+            .enforce_equal(cs.ns(|| "a + 0 == a"), &gadget_a)
+            .unwrap();
+
+        if !cs.is_satisfied() {
+            println!("{:?}", cs.which_is_unsatisfied().unwrap());
+        }
+
+        assert!(cs.is_satisfied());
+    }
+
+    #[test]
+    fn projective_g1_not_on_curve() {
+        let mut cs = TestConstraintSystem::<SW6>::new();
+
+        let a: G1 = rand::random();
+        let b: G1 = rand::random();
+        let mut not_on_curve = a.into_affine();
+        not_on_curve.y = b.into_affine().y;
+        let _ = G1Gadget::alloc(&mut cs.ns(|| "a"), || Ok(not_on_curve.into_projective()))
+            .unwrap();
+
+        assert!(!cs.is_satisfied());
+    }
+}
@@ -9,7 +9,9 @@ pub type EdwardsSWGadget = AffineGadget<EdwardsParameters, SW6, FqGadget>;
 mod test {
     use super::EdwardsSWGadget as EdwardsG;
     use crate::{
-        groups::curves::twisted_edwards::test::{edwards_constraint_costs, edwards_test},
+        groups::curves::twisted_edwards::test::{
+            edwards_constraint_costs, edwards_fixed_base_test, edwards_test,
+        },
         test_constraint_system::TestConstraintSystem,
     };
     use algebra::curves::{edwards_sw6::EdwardsParameters, sw6::SW6};
@@ -27,4 +29,11 @@ mod test {
         edwards_test::<_, EdwardsParameters, EdwardsG, _>(&mut cs);
         assert!(cs.is_satisfied());
     }
+
+    #[test]
+    fn edwards_fixed_base_gadget_test() {
+        let mut cs = TestConstraintSystem::<SW6>::new();
+        edwards_fixed_base_test::<_, EdwardsParameters, EdwardsG, _>(&mut cs);
+        assert!(cs.is_satisfied());
+    }
 }
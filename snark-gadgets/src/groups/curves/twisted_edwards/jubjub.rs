@@ -9,7 +9,9 @@ pub type JubJubGadget = AffineGadget<JubJubParameters, Bls12_381, FqGadget>;
 mod test {
     use super::JubJubGadget as EdwardsG;
     use crate::{
-        groups::curves::twisted_edwards::test::{edwards_constraint_costs, edwards_test},
+        groups::curves::twisted_edwards::test::{
+            edwards_constraint_costs, edwards_fixed_base_test, edwards_test,
+        },
         test_constraint_system::TestConstraintSystem,
     };
     use algebra::curves::{bls12_381::Bls12_381, jubjub::JubJubParameters as EdwardsParameters};
@@ -27,4 +29,11 @@ mod test {
         edwards_test::<_, EdwardsParameters, EdwardsG, _>(&mut cs);
         assert!(cs.is_satisfied());
     }
+
+    #[test]
+    fn edwards_fixed_base_gadget_test() {
+        let mut cs = TestConstraintSystem::<Bls12_381>::new();
+        edwards_fixed_base_test::<_, EdwardsParameters, EdwardsG, _>(&mut cs);
+        assert!(cs.is_satisfied());
+    }
 }
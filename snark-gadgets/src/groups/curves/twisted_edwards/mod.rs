@@ -21,9 +21,12 @@ use std::{borrow::Borrow, marker::PhantomData};
 pub mod edwards_bls12;
 pub mod edwards_sw6;
 pub mod jubjub;
+mod montgomery;
 #[cfg(test)]
 mod test;
 
+use self::montgomery::MontgomeryAffineGadget;
+
 #[derive(Derivative)]
 #[derivative(Debug, Clone)]
 #[must_use]
@@ -307,11 +310,11 @@ mod affine_impl {
         }
 
         fn cost_of_add() -> usize {
-            4 + 2 * F::cost_of_mul()
+            4 * F::cost_of_mul() + 2 * F::cost_of_mul_equals()
         }
 
         fn cost_of_double() -> usize {
-            4 + F::cost_of_mul()
+            F::cost_of_mul() + 2 * F::cost_of_square() + 2 * F::cost_of_mul_equals()
         }
     }
 
@@ -486,10 +489,14 @@ mod affine_impl {
 
 mod projective_impl {
     use super::*;
-    use crate::Assignment;
+    use crate::{
+        groups::SignedDigitScalarMulGadget,
+        utils::{KBitLookupGadget, ThreeBitCondNegLookupGadget, TwoBitLookupGadget},
+        Assignment,
+    };
     use algebra::{
-        curves::twisted_edwards_extended::GroupProjective as TEProjective, AffineCurve, Field,
-        PrimeField, ProjectiveCurve,
+        curves::twisted_edwards_extended::GroupProjective as TEProjective, AffineCurve, BigInteger,
+        Field, PrimeField, ProjectiveCurve,
     };
     use std::ops::Neg;
 
@@ -721,32 +728,9 @@ mod projective_impl {
                 .map(|(bit, base)| (bit.borrow().clone(), base.clone()))
                 .collect();
             let zero = TEProjective::zero();
-            for (i, bits_base_powers) in scalar_bits_with_base_powers.chunks(2).enumerate() {
+            for (i, bits_base_powers) in scalar_bits_with_base_powers.chunks(3).enumerate() {
                 let mut cs = cs.ns(|| format!("Chunk {}", i));
-                if bits_base_powers.len() == 2 {
-                    let bits = [bits_base_powers[0].0, bits_base_powers[1].0];
-                    let base_powers = [bits_base_powers[0].1, bits_base_powers[1].1];
-
-                    let mut table = [
-                        zero,
-                        base_powers[0],
-                        base_powers[1],
-                        base_powers[0] + &base_powers[1],
-                    ];
-
-                    TEProjective::batch_normalization(&mut table);
-                    let x_s = [table[0].x, table[1].x, table[2].x, table[3].x];
-                    let y_s = [table[0].y, table[1].y, table[2].y, table[3].y];
-
-                    let x: F = F::two_bit_lookup(cs.ns(|| "Lookup x"), &bits, &x_s)?;
-                    let y: F = F::two_bit_lookup(cs.ns(|| "Lookup y"), &bits, &y_s)?;
-                    let adder: Self = Self::new(x, y);
-                    *self = <Self as GroupGadget<TEProjective<P>, E>>::add(
-                        self,
-                        &mut cs.ns(|| "Add"),
-                        &adder,
-                    )?;
-                } else if bits_base_powers.len() == 1 {
+                if bits_base_powers.len() == 1 {
                     let bit = bits_base_powers[0].0;
                     let base_power = bits_base_powers[0].1;
                     let new_encoded =
@@ -757,18 +741,176 @@ mod projective_impl {
                         &new_encoded,
                         &self,
                     )?;
+                } else {
+                    let bits: Vec<_> = bits_base_powers.iter().map(|(bit, _)| *bit).collect();
+
+                    // All subset sums of the base powers, indexed by the
+                    // little-endian bits of the chunk.
+                    let mut table = vec![zero];
+                    for (_, base_power) in bits_base_powers {
+                        let shifted: Vec<_> = table.iter().map(|t| *t + base_power).collect();
+                        table.extend(shifted);
+                    }
+
+                    TEProjective::batch_normalization(&mut table);
+                    let x_s: Vec<_> = table.iter().map(|p| p.x).collect();
+                    let y_s: Vec<_> = table.iter().map(|p| p.y).collect();
+
+                    let x: F = F::k_bit_lookup(cs.ns(|| "Lookup x"), &bits, &x_s)?;
+                    let y: F = F::k_bit_lookup(cs.ns(|| "Lookup y"), &bits, &y_s)?;
+                    let adder: Self = Self::new(x, y);
+                    *self = <Self as GroupGadget<TEProjective<P>, E>>::add(
+                        self,
+                        &mut cs.ns(|| "Add"),
+                        &adder,
+                    )?;
                 }
             }
 
             Ok(())
         }
 
+        fn cost_of_precomputed_base_scalar_mul(num_bits: usize) -> usize {
+            let chunk_cost = |k| {
+                2 * <F as KBitLookupGadget<E>>::cost(k)
+                    + <Self as GroupGadget<TEProjective<P>, E>>::cost_of_add()
+            };
+            let remainder_cost = match num_bits % 3 {
+                0 => 0,
+                1 => {
+                    F::cost_of_mul()
+                        + 2 * F::cost_of_mul_equals()
+                        + <Self as CondSelectGadget<E>>::cost()
+                },
+                _ => chunk_cost(2),
+            };
+            (num_bits / 3) * chunk_cost(3) + remainder_cost
+        }
+
         fn cost_of_add() -> usize {
-            4 + 2 * F::cost_of_mul()
+            4 * F::cost_of_mul() + 2 * F::cost_of_mul_equals()
         }
 
         fn cost_of_double() -> usize {
-            4 + F::cost_of_mul()
+            F::cost_of_mul() + 2 * F::cost_of_square() + 2 * F::cost_of_mul_equals()
+        }
+    }
+
+    /// Checks that `bases[j] = 16^j * bases[0]` for a `bases[0]` of prime order
+    /// `r`, and that `16^{bases.len()} <= (r - 1) / 2`. No partial sum
+    /// `Σ_{j < k} d_j * bases[j]` of nonzero digits `|d_j| <= 4` can then be
+    /// zero or `±d_k * bases[k]`, as these are distinct as integers below
+    /// `r / 2` in absolute value.
+    fn is_signed_digit_base_powers<P: TEModelParameters>(bases: &[TEProjective<P>]) -> bool {
+        let max_num_chunks =
+            (P::ScalarField::modulus_minus_one_div_two().num_bits() as usize - 1) / 4;
+        if bases.len() > max_num_chunks {
+            return false;
+        }
+        if let Some(base) = bases.first() {
+            let base = base.into_affine();
+            if base.is_zero() || !base.is_in_correct_subgroup_assuming_on_curve() {
+                return false;
+            }
+        }
+        bases.windows(2).all(|pair| {
+            let mut next = pair[0];
+            for _ in 0..4 {
+                next.double_in_place();
+            }
+            next == pair[1]
+        })
+    }
+
+    impl<P, E, F> SignedDigitScalarMulGadget<TEProjective<P>, E> for AffineGadget<P, E, F>
+    where
+        P: TEModelParameters,
+        E: PairingEngine,
+        F: FieldGadget<P::BaseField, E>,
+    {
+        fn precomputed_base_3_bit_signed_digit_scalar_mul<CS, J, B>(
+            mut cs: CS,
+            bases: &[B],
+            scalars: &[J],
+        ) -> Result<Self, SynthesisError>
+        where
+            CS: ConstraintSystem<E>,
+            J: Borrow<[Boolean]>,
+            B: Borrow<[TEProjective<P>]>,
+        {
+            let mut result: Option<Self> = None;
+            for (i, (bits, bases)) in scalars.iter().zip(bases).enumerate() {
+                let bits = bits.borrow();
+                let bases = bases.borrow();
+                if bits.len() % 3 != 0 || bases.len() * 3 < bits.len() {
+                    return Err(SynthesisError::Unsatisfiable);
+                }
+                if !is_signed_digit_base_powers::<P>(&bases[..bits.len() / 3]) {
+                    return Err(SynthesisError::Unsatisfiable);
+                }
+
+                // The digits of each scalar are summed in Montgomery form,
+                // where an addition costs 3 constraints instead of 6.
+                let mut acc: Option<MontgomeryAffineGadget<P, E, F>> = None;
+                for (j, (chunk, base)) in bits.chunks(3).zip(bases).enumerate() {
+                    let mut cs = cs.ns(|| format!("Scalar {} chunk {}", i, j));
+
+                    let double = base.double();
+                    let mut x_s = [P::BaseField::zero(); 4];
+                    let mut y_s = [P::BaseField::zero(); 4];
+                    let table = [*base, double, double + base, double.double()];
+                    for (k, point) in table.iter().enumerate() {
+                        let (x, y) = MontgomeryAffineGadget::<P, E, F>::from_edwards_to_coords(
+                            &point.into_affine(),
+                        )?;
+                        x_s[k] = x;
+                        y_s[k] = y;
+                    }
+
+                    // Negating a point negates its y-coordinate in Montgomery
+                    // form, so only the lookup of y depends on the sign bit.
+                    let b0b1 = Boolean::and(cs.ns(|| "b0 AND b1"), &chunk[0], &chunk[1])?;
+                    let x = F::two_bit_lookup(cs.ns(|| "Lookup x"), &chunk[..2], &x_s)?;
+                    let y = F::three_bit_cond_neg_lookup(cs.ns(|| "Lookup y"), chunk, &b0b1, &y_s)?;
+                    let adder = MontgomeryAffineGadget::new(x, y);
+                    acc = Some(match acc {
+                        Some(acc) => acc.add(cs.ns(|| "Add"), &adder)?,
+                        None => adder,
+                    });
+                }
+
+                if let Some(acc) = acc {
+                    let segment = acc.into_edwards(cs.ns(|| format!("Scalar {} to Edwards", i)))?;
+                    result = Some(match result {
+                        Some(result) => <Self as GroupGadget<TEProjective<P>, E>>::add(
+                            &result,
+                            cs.ns(|| format!("Add scalar {}", i)),
+                            &segment,
+                        )?,
+                        None => segment,
+                    });
+                }
+            }
+            match result {
+                Some(result) => Ok(result),
+                None => <Self as GroupGadget<TEProjective<P>, E>>::zero(cs.ns(|| "zero")),
+            }
+        }
+
+        fn cost_of_precomputed_base_3_bit_signed_digit_scalar_mul(num_chunks: &[usize]) -> usize {
+            let chunk_cost = 1
+                + <F as TwoBitLookupGadget<E>>::cost()
+                + <F as ThreeBitCondNegLookupGadget<E>>::cost();
+            let mut cost = 0;
+            let mut num_scalars = 0;
+            for &n in num_chunks.iter().filter(|n| **n > 0) {
+                cost += n * chunk_cost
+                    + (n - 1) * MontgomeryAffineGadget::<P, E, F>::cost_of_add()
+                    + MontgomeryAffineGadget::<P, E, F>::cost_of_into_edwards();
+                num_scalars += 1;
+            }
+            cost + num_scalars.saturating_sub(1)
+                * <Self as GroupGadget<TEProjective<P>, E>>::cost_of_add()
         }
     }
 
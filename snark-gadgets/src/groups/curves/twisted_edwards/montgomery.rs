@@ -0,0 +1,145 @@
+use algebra::{
+    curves::{twisted_edwards_extended::GroupAffine as TEAffine, TEModelParameters},
+    Field, PairingEngine,
+};
+use snark::{ConstraintSystem, SynthesisError};
+
+use crate::{fields::FieldGadget, Assignment};
+
+use super::AffineGadget;
+
+use std::marker::PhantomData;
+
+/// Returns the coefficients `(A, B)` of the Montgomery curve
+/// `B * v^2 = u^3 + A * u^2 + u` that is birationally equivalent to the
+/// twisted Edwards curve of `P`, namely `A = 2 * (a + d) / (a - d)` and
+/// `B = 4 / (a - d)`.
+pub(crate) fn montgomery_coeffs<P: TEModelParameters>() -> (P::BaseField, P::BaseField) {
+    let a_minus_d_inv = (P::COEFF_A - &P::COEFF_D)
+        .inverse()
+        .expect("a != d for a twisted Edwards curve");
+    let coeff_a = (P::COEFF_A + &P::COEFF_D).double() * &a_minus_d_inv;
+    let coeff_b = P::BaseField::one().double().double() * &a_minus_d_inv;
+    (coeff_a, coeff_b)
+}
+
+/// A point on the Montgomery form of the curve of `P`. The point at infinity
+/// and the point of order two `(0, 0)` cannot be represented.
+#[derive(Derivative)]
+#[derivative(Debug, Clone)]
+pub(crate) struct MontgomeryAffineGadget<
+    P: TEModelParameters,
+    E: PairingEngine,
+    F: FieldGadget<P::BaseField, E>,
+> {
+    pub x:   F,
+    pub y:   F,
+    _params: PhantomData<P>,
+    _engine: PhantomData<E>,
+}
+
+impl<P: TEModelParameters, E: PairingEngine, F: FieldGadget<P::BaseField, E>>
+    MontgomeryAffineGadget<P, E, F>
+{
+    pub fn new(x: F, y: F) -> Self {
+        Self {
+            x,
+            y,
+            _params: PhantomData,
+            _engine: PhantomData,
+        }
+    }
+
+    /// Maps the Edwards point `(x, y)` to `((1 + y) / (1 - y), (1 + y) / ((1 -
+    /// y) * x))`. `p` must be neither the identity nor of order two.
+    pub fn from_edwards_to_coords(
+        p: &TEAffine<P>,
+    ) -> Result<(P::BaseField, P::BaseField), SynthesisError> {
+        let one = P::BaseField::one();
+        let u = (one + &p.y) * &(one - &p.y).inverse().get()?;
+        let v = u * &p.x.inverse().get()?;
+        Ok((u, v))
+    }
+
+    /// Maps `self` back to the Edwards point `(u / v, (u - 1) / (u + 1))`.
+    pub fn into_edwards<CS: ConstraintSystem<E>>(
+        &self,
+        mut cs: CS,
+    ) -> Result<AffineGadget<P, E, F>, SynthesisError> {
+        let one = P::BaseField::one();
+
+        let x = F::alloc(cs.ns(|| "x"), || {
+            let u = self.x.get_value().get()?;
+            let v = self.y.get_value().get()?;
+            Ok(u * &v.inverse().get()?)
+        })?;
+        x.mul_equals(cs.ns(|| "check x"), &self.y, &self.x)?;
+
+        let y = F::alloc(cs.ns(|| "y"), || {
+            let u = self.x.get_value().get()?;
+            Ok((u - &one) * &(u + &one).inverse().get()?)
+        })?;
+        let u_plus_one = self.x.add_constant(cs.ns(|| "u + 1"), &one)?;
+        let u_minus_one = self.x.sub_constant(cs.ns(|| "u - 1"), &one)?;
+        y.mul_equals(cs.ns(|| "check y"), &u_plus_one, &u_minus_one)?;
+
+        Ok(AffineGadget::new(x, y))
+    }
+
+    /// Adds `other` to `self` with the incomplete Montgomery addition law,
+    /// which requires `self != ±other`.
+    pub fn add<CS: ConstraintSystem<E>>(
+        &self,
+        mut cs: CS,
+        other: &Self,
+    ) -> Result<Self, SynthesisError> {
+        let (coeff_a, coeff_b) = montgomery_coeffs::<P>();
+
+        // λ = (v2 - v1) / (u2 - u1)
+        let lambda = F::alloc(cs.ns(|| "lambda"), || {
+            let n = other.y.get_value().get()? - &self.y.get_value().get()?;
+            let d = other.x.get_value().get()? - &self.x.get_value().get()?;
+            Ok(n * &d.inverse().get()?)
+        })?;
+        let lambda_n = other.y.sub(cs.ns(|| "v2 - v1"), &self.y)?;
+        let lambda_d = other.x.sub(cs.ns(|| "u2 - u1"), &self.x)?;
+        lambda.mul_equals(cs.ns(|| "check lambda"), &lambda_d, &lambda_n)?;
+
+        // u3 = B * λ^2 - A - u1 - u2
+        let x = F::alloc(cs.ns(|| "u3"), || {
+            let lambda = lambda.get_value().get()?;
+            Ok(coeff_b * &lambda.square()
+                - &coeff_a
+                - &self.x.get_value().get()?
+                - &other.x.get_value().get()?)
+        })?;
+        let x_sum = self
+            .x
+            .add(cs.ns(|| "u1 + u2"), &other.x)?
+            .add(cs.ns(|| "u1 + u2 + u3"), &x)?
+            .add_constant(cs.ns(|| "A + u1 + u2 + u3"), &coeff_a)?;
+        let b_lambda = lambda.mul_by_constant(cs.ns(|| "B * lambda"), &coeff_b)?;
+        b_lambda.mul_equals(cs.ns(|| "check u3"), &lambda, &x_sum)?;
+
+        // v3 = λ * (u1 - u3) - v1
+        let y = F::alloc(cs.ns(|| "v3"), || {
+            let lambda = lambda.get_value().get()?;
+            let x1 = self.x.get_value().get()?;
+            let x3 = x.get_value().get()?;
+            Ok(lambda * &(x1 - &x3) - &self.y.get_value().get()?)
+        })?;
+        let x1_minus_x3 = self.x.sub(cs.ns(|| "u1 - u3"), &x)?;
+        let y3_plus_y1 = y.add(cs.ns(|| "v3 + v1"), &self.y)?;
+        lambda.mul_equals(cs.ns(|| "check v3"), &x1_minus_x3, &y3_plus_y1)?;
+
+        Ok(Self::new(x, y))
+    }
+
+    pub fn cost_of_add() -> usize {
+        3 * F::cost_of_mul_equals()
+    }
+
+    pub fn cost_of_into_edwards() -> usize {
+        2 * F::cost_of_mul_equals()
+    }
+}
@@ -2,12 +2,15 @@ use rand;
 
 use crate::{
     boolean::Boolean,
-    groups::{test::group_test, GroupGadget},
+    groups::{test::group_test, GroupGadget, SignedDigitScalarMulGadget},
     utils::{AllocGadget, CondSelectGadget},
 };
 
 use algebra::{
-    curves::{models::TEModelParameters, twisted_edwards_extended::GroupAffine as TEAffine},
+    curves::{
+        models::TEModelParameters,
+        twisted_edwards_extended::{GroupAffine as TEAffine, GroupProjective as TEProjective},
+    },
     BitIterator, Group, PairingEngine, PrimeField,
 };
 
@@ -72,6 +75,118 @@ where
 
     let _ = gadget_a.add(&mut cs.ns(|| "ab"), &gadget_b).unwrap();
     let add_cost = cs.num_constraints() - cond_select_cost - alloc_cost;
+
+    let mut gadget_a2 = gadget_a.clone();
+    let before_double = cs.num_constraints();
+    gadget_a2.double_in_place(&mut cs.ns(|| "2a")).unwrap();
+    let double_cost = cs.num_constraints() - before_double;
+
     assert_eq!(cond_select_cost, <GG as CondSelectGadget<_>>::cost());
     assert_eq!(add_cost, GG::cost_of_add());
+    assert_eq!(double_cost, GG::cost_of_double());
+}
+
+pub(crate) fn edwards_fixed_base_test<E, P, GG, CS>(cs: &mut CS)
+where
+    E: PairingEngine,
+    P: TEModelParameters,
+    GG: SignedDigitScalarMulGadget<TEProjective<P>, E, Value = TEProjective<P>>,
+    CS: ConstraintSystem<E>,
+{
+    // Scalars whose length is 0, 1 and 2 modulo the lookup chunk size.
+    for num_bits in 9..12 {
+        let mut cs = cs.ns(|| format!("{} bits", num_bits));
+        let bits: Vec<bool> = (0..num_bits).map(|_| rand::random()).collect();
+        let mut base: TEProjective<P> = rand::random();
+        let mut base_powers = vec![];
+        let mut native_result = TEProjective::<P>::zero();
+        for bit in &bits {
+            if *bit {
+                native_result = native_result + &base;
+            }
+            base_powers.push(base);
+            base.double_in_place();
+        }
+
+        let input = Vec::<Boolean>::alloc(cs.ns(|| "Input"), || Ok(bits)).unwrap();
+        let mut result = GG::zero(cs.ns(|| "zero")).unwrap();
+        let num_constraints = cs.num_constraints();
+        result
+            .precomputed_base_scalar_mul(
+                cs.ns(|| "precomputed_base_scalar_mul"),
+                input.iter().zip(&base_powers),
+            )
+            .unwrap();
+        assert_eq!(
+            cs.num_constraints() - num_constraints,
+            GG::cost_of_precomputed_base_scalar_mul(num_bits)
+        );
+        assert_eq!(result.get_value().unwrap(), native_result);
+    }
+
+    // Two scalars of two signed digits each, with bases `16^j * g`.
+    let mut bases = vec![];
+    let mut scalars = vec![];
+    let mut native_result = TEProjective::<P>::zero();
+    for i in 0..2 {
+        let mut digit_bases = vec![];
+        let mut bits = vec![];
+        let mut base: TEProjective<P> = rand::random();
+        for _ in 0..2 {
+            let chunk: [bool; 3] = rand::random();
+            let mut digit = base;
+            for _ in 0..(chunk[0] as usize + 2 * chunk[1] as usize) {
+                digit = digit + &base;
+            }
+            if chunk[2] {
+                digit = -digit;
+            }
+            native_result = native_result + &digit;
+            digit_bases.push(base);
+            bits.extend_from_slice(&chunk);
+            for _ in 0..4 {
+                base.double_in_place();
+            }
+        }
+        let bits = Vec::<Boolean>::alloc(cs.ns(|| format!("Scalar {}", i)), || Ok(bits)).unwrap();
+        bases.push(digit_bases);
+        scalars.push(bits);
+    }
+    let num_constraints = cs.num_constraints();
+    let result = GG::precomputed_base_3_bit_signed_digit_scalar_mul(
+        cs.ns(|| "precomputed_base_3_bit_signed_digit_scalar_mul"),
+        &bases,
+        &scalars,
+    )
+    .unwrap();
+    assert_eq!(
+        cs.num_constraints() - num_constraints,
+        GG::cost_of_precomputed_base_3_bit_signed_digit_scalar_mul(&[2, 2])
+    );
+    assert_eq!(result.get_value().unwrap(), native_result);
+
+    // A scalar must be a whole number of chunks, with a base for each chunk.
+    let too_few_bases = [bases[0][..1].to_vec()];
+    assert!(GG::precomputed_base_3_bit_signed_digit_scalar_mul(
+        cs.ns(|| "too few bases"),
+        &too_few_bases,
+        &scalars[..1],
+    )
+    .is_err());
+    let partial_chunk = [scalars[0][..4].to_vec()];
+    assert!(GG::precomputed_base_3_bit_signed_digit_scalar_mul(
+        cs.ns(|| "partial chunk"),
+        &bases[..1],
+        &partial_chunk,
+    )
+    .is_err());
+
+    // The incomplete additions are only sound for bases `16^j * g`.
+    let unrelated_bases = [vec![bases[0][0], bases[1][1]]];
+    assert!(GG::precomputed_base_3_bit_signed_digit_scalar_mul(
+        cs.ns(|| "unrelated bases"),
+        &unrelated_bases,
+        &scalars[..1],
+    )
+    .is_err());
 }
@@ -10,7 +10,7 @@ use std::{borrow::Borrow, fmt::Debug};
 pub mod curves;
 
 pub use self::curves::{
-    short_weierstrass::bls12,
+    short_weierstrass::{bls12, mnt6},
     twisted_edwards::{edwards_sw6, jubjub},
 };
 
@@ -138,11 +138,45 @@ pub trait GroupGadget<G: Group, E: PairingEngine>:
         Ok(result)
     }
 
+    /// The cost of `precomputed_base_scalar_mul` for a scalar of `num_bits`
+    /// bits.
+    fn cost_of_precomputed_base_scalar_mul(num_bits: usize) -> usize {
+        num_bits * (Self::cost_of_add() + <Self as CondSelectGadget<E>>::cost())
+    }
+
     fn cost_of_add() -> usize;
 
     fn cost_of_double() -> usize;
 }
 
+/// Group gadgets that can multiply precomputed bases by scalars written in
+/// signed 3-bit digits, as the Bowe-Hopwood Pedersen hash does.
+pub trait SignedDigitScalarMulGadget<G: Group, E: PairingEngine>: GroupGadget<G, E> {
+    /// Computes `Σ_i Σ_j d_ij * bases[i][j]`, where `scalars[i]` is split into
+    /// little-endian 3-bit chunks `(s0, s1, s2)`, the `j`-th of which encodes
+    /// the signed digit `d_ij = (1 + s0 + 2 * s1) * (1 - 2 * s2)`.
+    ///
+    /// Implementations may sum the digits of each scalar with an incomplete
+    /// addition law, so they return an error unless `bases[i][j] = 16^j *
+    /// bases[i][0]` for a `bases[i][0]` of prime order `r` and `16^{n_i} <= (r -
+    /// 1) / 2`, where `n_i` is the number of chunks of `scalars[i]`. No partial
+    /// sum `Σ_{j < k} d_ij * bases[i][j]` can then equal `±d_ik * bases[i][k]`
+    /// or zero.
+    fn precomputed_base_3_bit_signed_digit_scalar_mul<CS, J, B>(
+        cs: CS,
+        bases: &[B],
+        scalars: &[J],
+    ) -> Result<Self, SynthesisError>
+    where
+        CS: ConstraintSystem<E>,
+        J: Borrow<[Boolean]>,
+        B: Borrow<[G]>;
+
+    /// The cost of `precomputed_base_3_bit_signed_digit_scalar_mul` for
+    /// scalars of `num_chunks[i]` 3-bit chunks each.
+    fn cost_of_precomputed_base_3_bit_signed_digit_scalar_mul(num_chunks: &[usize]) -> usize;
+}
+
 #[cfg(test)]
 mod test {
     use algebra::PairingEngine;
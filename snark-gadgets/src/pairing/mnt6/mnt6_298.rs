@@ -0,0 +1,3 @@
+use algebra::curves::mnt6::MNT6;
+
+pub type PairingGadget = super::PairingGadget<MNT6>;
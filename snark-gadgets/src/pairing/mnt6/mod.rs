@@ -0,0 +1,216 @@
+use snark::{ConstraintSystem, SynthesisError};
+
+use super::PairingGadget as PG;
+
+use crate::{
+    fields::{fp::FpGadget, fp3::Fp3Gadget, fp6_2over3::Fp6Gadget, FieldGadget},
+    groups::curves::short_weierstrass::mnt6::{
+        AteAdditionCoefficientsGadget, AteDoubleCoefficientsGadget, G1Gadget, G1PreparedGadget,
+        G2Gadget, G2PreparedGadget,
+    },
+};
+use algebra::{
+    curves::{
+        models::mnt6::MNT6Parameters, short_weierstrass_jacobian::GroupProjective as SWProjective,
+    },
+    fields::{fp6_2over3::Fp6, BitIterator},
+    PairingEngine,
+};
+use std::marker::PhantomData;
+
+pub mod mnt6_298;
+
+/// Ate pairing gadget for MNT6-style curves whose G1 and G2 use the Jacobian
+/// curve model.
+///
+/// It is only instantiated for MNT6-298 inside MNT4-298 circuits. SW6 proofs
+/// cannot be verified in a circuit: no engine in this crate has the SW6 base
+/// field as its scalar field.
+pub struct PairingGadget<P: MNT6Parameters>(PhantomData<P>);
+
+type Fp3G<P, E> = Fp3Gadget<<P as MNT6Parameters>::Fp3Params, E>;
+type Fp6G<P, E> = Fp6Gadget<<P as MNT6Parameters>::Fp6Params, E>;
+
+impl<P: MNT6Parameters> PairingGadget<P> {
+    // Evaluate the line function of a doubling step at point p.
+    fn ell_rr_at_p<E: PairingEngine<Fr = P::Fp>, CS: ConstraintSystem<E>>(
+        mut cs: CS,
+        dc: &AteDoubleCoefficientsGadget<P, E>,
+        p: &G1PreparedGadget<P, E>,
+    ) -> Result<Fp6G<P, E>, SynthesisError> {
+        let c_j_x_twist = dc.c_j.mul(cs.ns(|| "c_j * x_twist"), &p.x_twist)?;
+        let c0 = dc
+            .c_l
+            .sub(cs.ns(|| "c_l - c_4c"), &dc.c_4c)?
+            .sub(cs.ns(|| "c0"), &c_j_x_twist)?;
+        let c1 = dc.c_h.mul(cs.ns(|| "c1"), &p.y_twist)?;
+        Ok(Fp6G::<P, E>::new(c0, c1))
+    }
+
+    // Evaluate the line function of an addition step at point p.
+    fn ell_rq_at_p<E: PairingEngine<Fr = P::Fp>, CS: ConstraintSystem<E>>(
+        mut cs: CS,
+        ac: &AteAdditionCoefficientsGadget<P, E>,
+        l1_coeff: &Fp3G<P, E>,
+        p: &G1PreparedGadget<P, E>,
+        q: &G2PreparedGadget<P, E>,
+    ) -> Result<Fp6G<P, E>, SynthesisError> {
+        let c0 = ac.c_rz.mul(cs.ns(|| "c0"), &p.y_twist)?;
+        let l1_coeff_c_l1 = l1_coeff.mul(cs.ns(|| "l1_coeff * c_l1"), &ac.c_l1)?;
+        let c1 = q
+            .y_over_twist
+            .mul(cs.ns(|| "y_over_twist * c_rz"), &ac.c_rz)?
+            .add(cs.ns(|| "add"), &l1_coeff_c_l1)?
+            .negate(cs.ns(|| "c1"))?;
+        Ok(Fp6G::<P, E>::new(c0, c1))
+    }
+
+    // Computes `elt^((q^3 - 1) * (q + 1))`, which lies in the cyclotomic
+    // subgroup.
+    fn final_exponentiation_first_chunk<E: PairingEngine<Fr = P::Fp>, CS: ConstraintSystem<E>>(
+        mut cs: CS,
+        elt: &Fp6G<P, E>,
+        elt_inv: &Fp6G<P, E>,
+    ) -> Result<Fp6G<P, E>, SynthesisError> {
+        // elt_q3 = elt^(q^3)
+        let elt_q3 = elt.frobenius_map(cs.ns(|| "elt^(q^3)"), 3)?;
+        // elt_q3_over_elt = elt^(q^3-1)
+        let elt_q3_over_elt = elt_q3.mul(cs.ns(|| "elt^(q^3 - 1)"), elt_inv)?;
+        // alpha = elt^((q^3-1) * q)
+        let alpha = elt_q3_over_elt.frobenius_map(cs.ns(|| "alpha"), 1)?;
+        // beta = elt^((q^3-1)*(q+1)
+        alpha.mul(cs.ns(|| "beta"), &elt_q3_over_elt)
+    }
+
+    fn final_exponentiation_last_chunk<E: PairingEngine<Fr = P::Fp>, CS: ConstraintSystem<E>>(
+        mut cs: CS,
+        elt: &Fp6G<P, E>,
+        elt_inv: &Fp6G<P, E>,
+    ) -> Result<Fp6G<P, E>, SynthesisError> {
+        let elt_q = elt.frobenius_map(cs.ns(|| "elt^q"), 1)?;
+
+        let w1_part = elt_q.cyclotomic_exp(cs.ns(|| "w1 part"), P::FINAL_EXPONENT_LAST_CHUNK_1)?;
+        let w0_part = if P::FINAL_EXPONENT_LAST_CHUNK_W0_IS_NEG {
+            elt_inv.cyclotomic_exp(
+                cs.ns(|| "w0 part"),
+                P::FINAL_EXPONENT_LAST_CHUNK_ABS_OF_W0,
+            )?
+        } else {
+            elt.cyclotomic_exp(
+                cs.ns(|| "w0 part"),
+                P::FINAL_EXPONENT_LAST_CHUNK_ABS_OF_W0,
+            )?
+        };
+
+        w1_part.mul(cs.ns(|| "w1 part * w0 part"), &w0_part)
+    }
+}
+
+impl<P: MNT6Parameters<Fp = E::Fr>, E: PairingEngine> PG<P, E> for PairingGadget<P>
+where
+    P: PairingEngine<
+        G1Projective = SWProjective<<P as MNT6Parameters>::G1Parameters>,
+        G2Projective = SWProjective<<P as MNT6Parameters>::G2Parameters>,
+        Fqk = Fp6<<P as MNT6Parameters>::Fp6Params>,
+    >,
+{
+    type G1Gadget = G1Gadget<P, E>;
+    type G2Gadget = G2Gadget<P, E>;
+    type G1PreparedGadget = G1PreparedGadget<P, E>;
+    type G2PreparedGadget = G2PreparedGadget<P, E>;
+    type GTGadget = Fp6G<P, E>;
+
+    fn miller_loop<CS: ConstraintSystem<E>>(
+        mut cs: CS,
+        ps: &[Self::G1PreparedGadget],
+        qs: &[Self::G2PreparedGadget],
+    ) -> Result<Self::GTGadget, SynthesisError> {
+        let mut pairs = vec![];
+        for (i, (p, q)) in ps.iter().zip(qs.iter()).enumerate() {
+            let mut cs = cs.ns(|| format!("l1_coeff {}", i));
+            let p_x = Fp3G::<P, E>::new(
+                p.x.clone(),
+                FpGadget::<E>::zero(cs.ns(|| "zero c1"))?,
+                FpGadget::<E>::zero(cs.ns(|| "zero c2"))?,
+            );
+            let l1_coeff = p_x.sub(cs.ns(|| "p.x - q.x_over_twist"), &q.x_over_twist)?;
+            pairs.push((
+                p,
+                q,
+                l1_coeff,
+                q.double_coefficients.iter(),
+                q.addition_coefficients.iter(),
+            ));
+        }
+        let mut f = Self::GTGadget::one(cs.ns(|| "one"))?;
+
+        // The loop is executed for all bits (EXCEPT the MSB itself) of the
+        // ate loop count (skipping leading zeros) in MSB to LSB order.
+        let bits = BitIterator::new(P::ATE_LOOP_COUNT)
+            .skip_while(|b| !b)
+            .skip(1);
+        for (j, bit) in bits.enumerate() {
+            let mut cs = cs.ns(|| format!("Iteration {}", j));
+            f.square_in_place(cs.ns(|| "square"))?;
+
+            for (k, &mut (p, _, _, ref mut dcs, _)) in pairs.iter_mut().enumerate() {
+                let mut cs = cs.ns(|| format!("Double input {}", k));
+                let g_rr_at_p = Self::ell_rr_at_p(cs.ns(|| "line"), dcs.next().unwrap(), p)?;
+                f.mul_in_place(cs.ns(|| "mul"), &g_rr_at_p)?;
+            }
+
+            if bit {
+                for (k, &mut (p, q, ref l1_coeff, _, ref mut acs)) in pairs.iter_mut().enumerate()
+                {
+                    let mut cs = cs.ns(|| format!("Addition input {}", k));
+                    let g_rq_at_p =
+                        Self::ell_rq_at_p(cs.ns(|| "line"), acs.next().unwrap(), l1_coeff, p, q)?;
+                    f.mul_in_place(cs.ns(|| "mul"), &g_rq_at_p)?;
+                }
+            }
+        }
+
+        if P::ATE_IS_LOOP_COUNT_NEG {
+            for (k, &mut (p, q, ref l1_coeff, _, ref mut acs)) in pairs.iter_mut().enumerate() {
+                let mut cs = cs.ns(|| format!("Negate input {}", k));
+                let g_rnegr_at_p =
+                    Self::ell_rq_at_p(cs.ns(|| "line"), acs.next().unwrap(), l1_coeff, p, q)?;
+                f.mul_in_place(cs.ns(|| "mul"), &g_rnegr_at_p)?;
+            }
+            f = f.inverse(cs.ns(|| "f inverse"))?;
+        }
+
+        Ok(f)
+    }
+
+    fn final_exponentiation<CS: ConstraintSystem<E>>(
+        mut cs: CS,
+        f: &Self::GTGadget,
+    ) -> Result<Self::GTGadget, SynthesisError> {
+        let f_inv = f.inverse(cs.ns(|| "f inverse"))?;
+        let f_to_first_chunk =
+            Self::final_exponentiation_first_chunk(cs.ns(|| "first chunk"), f, &f_inv)?;
+        // `f_to_first_chunk` lies in the cyclotomic subgroup, where inversion
+        // is conjugation.
+        let f_inv_to_first_chunk = f_to_first_chunk.unitary_inverse(cs.ns(|| "unitary inverse"))?;
+        Self::final_exponentiation_last_chunk(
+            cs.ns(|| "last chunk"),
+            &f_to_first_chunk,
+            &f_inv_to_first_chunk,
+        )
+    }
+
+    fn prepare_g1<CS: ConstraintSystem<E>>(
+        cs: CS,
+        p: &Self::G1Gadget,
+    ) -> Result<Self::G1PreparedGadget, SynthesisError> {
+        Self::G1PreparedGadget::from_affine(cs, p)
+    }
+
+    fn prepare_g2<CS: ConstraintSystem<E>>(
+        cs: CS,
+        q: &Self::G2Gadget,
+    ) -> Result<Self::G2PreparedGadget, SynthesisError> {
+        Self::G2PreparedGadget::from_affine(cs, q)
+    }
+}
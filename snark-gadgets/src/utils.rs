@@ -168,6 +168,90 @@ where
     fn cost() -> usize;
 }
 
+/// Uses `k` bits to perform a lookup into a table of `2^k` constants.
+/// `bits` is little-endian: `bits[0]` is the LSB of the table index.
+pub trait KBitLookupGadget<E: PairingEngine>
+where
+    Self: Sized,
+{
+    type TableConstant;
+    fn k_bit_lookup<CS: ConstraintSystem<E>>(
+        cs: CS,
+        bits: &[Boolean],
+        constants: &[Self::TableConstant],
+    ) -> Result<Self, SynthesisError>;
+
+    /// The cost of a lookup with `k` non-constant bits.
+    fn cost(k: usize) -> usize;
+}
+
+/// Uses three bits to perform a lookup into a table of four constants, where
+/// the third bit conditionally negates the looked-up value. `b0b1` must be
+/// `bits[0] AND bits[1]`; it is passed in so that it can be shared between
+/// several lookups with the same bits.
+pub trait ThreeBitCondNegLookupGadget<E: PairingEngine>
+where
+    Self: Sized,
+{
+    type TableConstant;
+    fn three_bit_cond_neg_lookup<CS: ConstraintSystem<E>>(
+        cs: CS,
+        bits: &[Boolean],
+        b0b1: &Boolean,
+        constants: &[Self::TableConstant],
+    ) -> Result<Self, SynthesisError>;
+
+    fn cost() -> usize;
+}
+
+/// Uses `k` bits to select one of `2^k` values.
+/// `bits` is little-endian: `bits[0]` is the LSB of the index.
+pub trait MultiplexerGadget<E: PairingEngine>
+where
+    Self: Sized,
+{
+    fn multiplex<CS: ConstraintSystem<E>>(
+        cs: CS,
+        bits: &[Boolean],
+        values: &[Self],
+    ) -> Result<Self, SynthesisError>;
+
+    /// The cost of selecting with `k` non-constant bits.
+    fn cost(k: usize) -> usize;
+}
+
+impl<E: PairingEngine, T: Sized + Clone + CondSelectGadget<E>> MultiplexerGadget<E> for T {
+    fn multiplex<CS: ConstraintSystem<E>>(
+        mut cs: CS,
+        bits: &[Boolean],
+        values: &[Self],
+    ) -> Result<Self, SynthesisError> {
+        assert_eq!(values.len(), 1 << bits.len());
+        let mut layer = values.to_vec();
+        // Each bit halves the number of candidates, starting from the LSB.
+        for (i, bit) in bits.iter().enumerate() {
+            let mut cs = cs.ns(|| format!("Layer {}", i));
+            layer = layer
+                .chunks(2)
+                .enumerate()
+                .map(|(j, pair)| {
+                    Self::conditionally_select(
+                        cs.ns(|| format!("Select {}", j)),
+                        bit,
+                        &pair[1],
+                        &pair[0],
+                    )
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+        }
+        Ok(layer.pop().unwrap())
+    }
+
+    fn cost(k: usize) -> usize {
+        ((1 << k) - 1) * <Self as CondSelectGadget<E>>::cost()
+    }
+}
+
 pub trait OrEqualsGadget<E: PairingEngine>
 where
     Self: Sized,
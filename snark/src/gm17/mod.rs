@@ -1,12 +1,16 @@
-use algebra::{PairingCurve, PairingEngine};
+use algebra::{
+    AffineCurve, FpParameters, PairingCurve, PairingEngine, PrimeField, ProjectiveCurve,
+};
 
 use crate::SynthesisError;
 use std::io::{self, Read, Result as IoResult, Write};
 
-use algebra::bytes::ToBytes;
+use algebra::bytes::{FromBytes, ToBytes};
 
 mod r1cs_to_sap;
 
+pub mod mpc;
+
 mod generator;
 mod prover;
 mod verifier;
@@ -48,16 +52,32 @@ impl<E: PairingEngine> Default for Proof<E> {
     }
 }
 
-impl<E: PairingEngine> Proof<E> {
-    pub fn write<W: Write>(&self, mut _writer: W) -> io::Result<()> {
-        // TODO: implement serialization
-        unimplemented!()
+impl<E: PairingEngine> FromBytes for Proof<E> {
+    /// Proofs come from untrusted provers, so this also rejects points outside
+    /// the prime-order subgroups, which decoding a point alone does not check.
+    #[inline]
+    fn read<R: Read>(mut reader: R) -> io::Result<Self> {
+        let a = E::G1Affine::read(&mut reader)?;
+        let b = E::G2Affine::read(&mut reader)?;
+        let c = E::G1Affine::read(&mut reader)?;
+        if !is_in_prime_order_subgroup(&a)
+            || !is_in_prime_order_subgroup(&b)
+            || !is_in_prime_order_subgroup(&c)
+        {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "proof element is not in the prime-order subgroup",
+            ));
+        }
+        Ok(Self { a, b, c })
     }
+}
 
-    pub fn read<R: Read>(mut _reader: R) -> io::Result<Self> {
-        // TODO: implement serialization
-        unimplemented!()
-    }
+/// Returns whether `point` is killed by the order of the scalar field.
+pub(crate) fn is_in_prime_order_subgroup<G: AffineCurve>(point: &G) -> bool {
+    point
+        .mul(<G::ScalarField as PrimeField>::Params::MODULUS)
+        .is_zero()
 }
 
 #[derive(Clone)]
@@ -77,10 +97,7 @@ impl<E: PairingEngine> ToBytes for VerifyingKey<E> {
         self.h_beta_g2.write(&mut writer)?;
         self.g_gamma_g1.write(&mut writer)?;
         self.h_gamma_g2.write(&mut writer)?;
-        for q in &self.query {
-            q.write(&mut writer)?;
-        }
-        Ok(())
+        write_vec(&self.query, &mut writer)
     }
 }
 
@@ -108,15 +125,22 @@ impl<E: PairingEngine> PartialEq for VerifyingKey<E> {
     }
 }
 
-impl<E: PairingEngine> VerifyingKey<E> {
-    pub fn write<W: Write>(&self, mut _writer: W) -> io::Result<()> {
-        // TODO: implement serialization
-        unimplemented!()
-    }
-
-    pub fn read<R: Read>(mut _reader: R) -> io::Result<Self> {
-        // TODO: implement serialization
-        unimplemented!()
+impl<E: PairingEngine> FromBytes for VerifyingKey<E> {
+    fn read<R: Read>(mut reader: R) -> IoResult<Self> {
+        let h_g2 = E::G2Affine::read(&mut reader)?;
+        let g_alpha_g1 = E::G1Affine::read(&mut reader)?;
+        let h_beta_g2 = E::G2Affine::read(&mut reader)?;
+        let g_gamma_g1 = E::G1Affine::read(&mut reader)?;
+        let h_gamma_g2 = E::G2Affine::read(&mut reader)?;
+        let query = read_vec(&mut reader)?;
+        Ok(Self {
+            h_g2,
+            g_alpha_g1,
+            h_beta_g2,
+            g_gamma_g1,
+            h_gamma_g2,
+            query,
+        })
     }
 }
 
@@ -149,16 +173,56 @@ impl<E: PairingEngine> PartialEq for Parameters<E> {
     }
 }
 
-impl<E: PairingEngine> Parameters<E> {
-    pub fn write<W: Write>(&self, mut _writer: W) -> io::Result<()> {
-        // TODO: implement serialization
-        unimplemented!()
+impl<E: PairingEngine> ToBytes for Parameters<E> {
+    fn write<W: Write>(&self, mut writer: W) -> IoResult<()> {
+        self.vk.write(&mut writer)?;
+        write_vec(&self.a_query, &mut writer)?;
+        write_vec(&self.b_query, &mut writer)?;
+        write_vec(&self.c_query_1, &mut writer)?;
+        write_vec(&self.c_query_2, &mut writer)?;
+        self.g_gamma_z.write(&mut writer)?;
+        self.h_gamma_z.write(&mut writer)?;
+        self.g_ab_gamma_z.write(&mut writer)?;
+        self.g_gamma2_z2.write(&mut writer)?;
+        write_vec(&self.g_gamma2_z_t, &mut writer)
+    }
+}
+
+impl<E: PairingEngine> FromBytes for Parameters<E> {
+    fn read<R: Read>(mut reader: R) -> IoResult<Self> {
+        let vk = VerifyingKey::read(&mut reader)?;
+        Ok(Self {
+            vk,
+            a_query: read_vec(&mut reader)?,
+            b_query: read_vec(&mut reader)?,
+            c_query_1: read_vec(&mut reader)?,
+            c_query_2: read_vec(&mut reader)?,
+            g_gamma_z: E::G1Affine::read(&mut reader)?,
+            h_gamma_z: E::G2Affine::read(&mut reader)?,
+            g_ab_gamma_z: E::G1Affine::read(&mut reader)?,
+            g_gamma2_z2: E::G1Affine::read(&mut reader)?,
+            g_gamma2_z_t: read_vec(&mut reader)?,
+        })
     }
+}
+
+/// Writes `items` prefixed with their number.
+pub(crate) fn write_vec<T: ToBytes, W: Write>(items: &[T], mut writer: W) -> IoResult<()> {
+    (items.len() as u64).write(&mut writer)?;
+    for item in items {
+        item.write(&mut writer)?;
+    }
+    Ok(())
+}
 
-    pub fn read<R: Read>(mut _reader: R, _checked: bool) -> io::Result<Self> {
-        // TODO: implement serialization
-        unimplemented!()
+pub(crate) fn read_vec<T: FromBytes, R: Read>(mut reader: R) -> IoResult<Vec<T>> {
+    let len = u64::read(&mut reader)?;
+    // Do not trust `len` to preallocate: the input may be truncated.
+    let mut items = Vec::new();
+    for _ in 0..len {
+        items.push(T::read(&mut reader)?);
     }
+    Ok(items)
 }
 
 #[derive(Clone)]
@@ -0,0 +1,216 @@
+//! A multi-party computation that generates GM17 parameters without any
+//! single party learning the toxic waste, following the two-phase ceremony
+//! of Bowe, Gabizon and Miers.
+//!
+//! The first phase (`Phase1`) is independent of any circuit: its participants
+//! jointly compute the powers `g^{tau^i}`, `g^{alpha tau^i}`, `g^{beta tau^i}`
+//! and `h^{tau^i}` of secrets `tau`, `alpha` and `beta`. The second phase
+//! (`Phase2`) starts from these powers and a circuit, and its participants
+//! jointly update the secret `gamma` of the resulting parameters. The
+//! parameters are sound as long as one participant of each phase is honest
+//! and destroys their secrets.
+//!
+//! The resulting parameters have the same form as those of
+//! `generate_parameters`, with `g^{gamma}` as the base of the first phase.
+//! The first phase is public, however, so anyone also sees the elements of
+//! the `Accumulator` that GM17 parameters do not contain, such as
+//! `g^{tau^i}` for `i` above the domain size and `g^{alpha tau^i}` and
+//! `g^{beta tau^i}` on their own. Unlike for the analogous ceremony for
+//! Groth16, there is no proof that GM17 remains sound in the presence of
+//! these elements: parameters from this ceremony rely on that assumption,
+//! which `generate_random_parameters` does not need.
+//!
+//! Every contribution carries a `KnowledgeProof` of its secrets that is bound
+//! to the transcript before it, so anyone can check a whole ceremony with
+//! `verify`, and a coordinator can check each new contribution with
+//! `verify_update`.
+
+use algebra::{
+    bytes::{FromBytes, ToBytes},
+    msm::VariableBaseMSM,
+    AffineCurve, Field, PairingCurve, PairingEngine, PrimeField, ProjectiveCurve,
+};
+use blake2::Blake2b;
+use byteorder::{ByteOrder, LittleEndian};
+use digest::Digest;
+use rand::{ChaChaRng, Rng, SeedableRng};
+use std::io::{self, Read, Result as IoResult, Write};
+
+mod phase1;
+mod phase2;
+
+#[cfg(test)]
+mod test;
+
+pub use self::{phase1::*, phase2::*};
+
+/// The version of the encoding of `Phase1` and `Phase2` transcripts.
+pub const TRANSCRIPT_VERSION: u8 = 1;
+
+/// Proves knowledge of the secret `x` by which a participant updated a pair
+/// of elements `(before, after = before^x)`: `s_x_g1 = s_g1^x` for a random
+/// `s_g1`, and `x_g2 = r^x` where `r` is hashed from the transcript so far and
+/// `(s_g1, s_x_g1)`.
+#[derive(Clone)]
+pub struct KnowledgeProof<E: PairingEngine> {
+    pub s_g1:   E::G1Affine,
+    pub s_x_g1: E::G1Affine,
+    pub x_g2:   E::G2Affine,
+}
+
+impl<E: PairingEngine> KnowledgeProof<E> {
+    fn new<R: Rng>(x: E::Fr, digest: &[u8], rng: &mut R) -> Self {
+        let s_g1 = rng.gen::<E::G1Projective>().into_affine();
+        let s_x_g1 = s_g1.mul(x.into_repr()).into_affine();
+        let x_g2 = Self::base(digest, &s_g1, &s_x_g1)
+            .mul(x.into_repr())
+            .into_affine();
+        Self { s_g1, s_x_g1, x_g2 }
+    }
+
+    fn base(digest: &[u8], s_g1: &E::G1Affine, s_x_g1: &E::G1Affine) -> E::G2Affine {
+        let mut input = digest.to_vec();
+        s_g1.write(&mut input)
+            .expect("writing to a vector cannot fail");
+        s_x_g1
+            .write(&mut input)
+            .expect("writing to a vector cannot fail");
+        hash_to_g2::<E>(&input)
+    }
+
+    fn is_valid(&self, digest: &[u8]) -> Option<E::G2Affine> {
+        let r = Self::base(digest, &self.s_g1, &self.s_x_g1);
+        if self.s_g1.is_zero()
+            || self.s_x_g1.is_zero()
+            || !same_ratio::<E>((self.s_g1, self.s_x_g1), (r, self.x_g2))
+        {
+            return None;
+        }
+        Some(r)
+    }
+
+    /// Checks that `self` proves knowledge of the `x` such that
+    /// `after = before^x` in G1.
+    fn verify_g1(&self, digest: &[u8], before: E::G1Affine, after: E::G1Affine) -> bool {
+        match self.is_valid(digest) {
+            Some(r) => same_ratio::<E>((before, after), (r, self.x_g2)),
+            None => false,
+        }
+    }
+
+    /// Checks that `self` proves knowledge of the `x` such that
+    /// `after = before^x` in G2.
+    fn verify_g2(&self, digest: &[u8], before: E::G2Affine, after: E::G2Affine) -> bool {
+        self.is_valid(digest).is_some()
+            && same_ratio::<E>((self.s_g1, self.s_x_g1), (before, after))
+    }
+}
+
+impl<E: PairingEngine> PartialEq for KnowledgeProof<E> {
+    fn eq(&self, other: &Self) -> bool {
+        self.s_g1 == other.s_g1 && self.s_x_g1 == other.s_x_g1 && self.x_g2 == other.x_g2
+    }
+}
+
+impl<E: PairingEngine> ToBytes for KnowledgeProof<E> {
+    fn write<W: Write>(&self, mut writer: W) -> IoResult<()> {
+        self.s_g1.write(&mut writer)?;
+        self.s_x_g1.write(&mut writer)?;
+        self.x_g2.write(&mut writer)
+    }
+}
+
+impl<E: PairingEngine> FromBytes for KnowledgeProof<E> {
+    fn read<R: Read>(mut reader: R) -> IoResult<Self> {
+        Ok(Self {
+            s_g1:   E::G1Affine::read(&mut reader)?,
+            s_x_g1: E::G1Affine::read(&mut reader)?,
+            x_g2:   E::G2Affine::read(&mut reader)?,
+        })
+    }
+}
+
+/// Checks that `g1.1 = g1.0^x` and `g2.1 = g2.0^x` for the same `x`.
+fn same_ratio<E: PairingEngine>(
+    g1: (E::G1Affine, E::G1Affine),
+    g2: (E::G2Affine, E::G2Affine),
+) -> bool {
+    E::pairing(g1.0, g2.1) == E::pairing(g1.1, g2.0)
+}
+
+/// Returns the random linear combinations `(sum r_i v_i, sum r_i w_i)`. They
+/// have the ratio `x` if every `w_i = v_i^x`, and otherwise only with
+/// negligible probability.
+fn merge_pairs<G: PairingCurve, R: Rng>(v: &[G], w: &[G], rng: &mut R) -> (G, G) {
+    assert_eq!(v.len(), w.len());
+    let scalars = (0..v.len())
+        .map(|_| rng.gen::<<G::Engine as PairingEngine>::Fr>().into_repr())
+        .collect::<Vec<_>>();
+    (
+        VariableBaseMSM::multi_scalar_mul(v, &scalars).into_affine(),
+        VariableBaseMSM::multi_scalar_mul(w, &scalars).into_affine(),
+    )
+}
+
+/// Merges the consecutive elements of `powers`, which have the ratio `x` if
+/// `powers` are the successive powers of `x` times a common base.
+fn power_pairs<G: PairingCurve, R: Rng>(powers: &[G], rng: &mut R) -> (G, G) {
+    merge_pairs(&powers[..powers.len() - 1], &powers[1..], rng)
+}
+
+/// Hashes `input` to an element of G2 whose discrete logarithm nobody knows.
+///
+/// Sampling a point with `Rand` would multiply the generator by a scalar that
+/// anyone can recompute from `input`, so only an x-coordinate is derived from
+/// the hash. It is incremented until it is that of a curve point, whose
+/// cofactor is then cleared.
+fn hash_to_g2<E: PairingEngine>(input: &[u8]) -> E::G2Affine {
+    let hash = Blake2b::digest(input);
+    let mut seed = [0u32; 8];
+    LittleEndian::read_u32_into(&hash[..32], &mut seed);
+    let greatest = hash[32] & 1 == 1;
+    let mut x = ChaChaRng::from_seed(&seed).gen::<E::Fqe>();
+    loop {
+        if let Some(point) = E::G2Affine::from_x_coordinate(x, greatest) {
+            let point = point.mul_by_cofactor();
+            if !point.is_zero() {
+                return point;
+            }
+        }
+        x += &E::Fqe::one();
+    }
+}
+
+/// Returns the digest of the transcript after appending `item` to the
+/// transcript with digest `digest`.
+fn chain<T: ToBytes>(digest: &[u8], item: &T) -> Vec<u8> {
+    let mut input = digest.to_vec();
+    item.write(&mut input)
+        .expect("writing to a vector cannot fail");
+    Blake2b::digest(&input).to_vec()
+}
+
+/// Derives a separate digest for each secret of a contribution.
+fn tagged(digest: &[u8], tag: u8) -> Vec<u8> {
+    chain(digest, &tag)
+}
+
+fn nonzero_scalar<F: Field, R: Rng>(rng: &mut R) -> F {
+    loop {
+        let x = rng.gen::<F>();
+        if !x.is_zero() {
+            return x;
+        }
+    }
+}
+
+fn read_version<R: Read>(reader: R) -> IoResult<()> {
+    if u8::read(reader)? == TRANSCRIPT_VERSION {
+        Ok(())
+    } else {
+        Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "unsupported transcript version",
+        ))
+    }
+}
@@ -0,0 +1,327 @@
+use algebra::{
+    bytes::{FromBytes, ToBytes},
+    AffineCurve, Field, PairingEngine, PrimeField, ProjectiveCurve,
+};
+use rand::Rng;
+use rayon::prelude::*;
+use std::io::{Read, Result as IoResult, Write};
+
+use super::{
+    chain, nonzero_scalar, power_pairs, read_version, same_ratio, tagged, KnowledgeProof,
+    TRANSCRIPT_VERSION,
+};
+use crate::gm17::{read_vec, write_vec};
+
+const TAU_TAG: u8 = 0;
+const ALPHA_TAG: u8 = 1;
+const BETA_TAG: u8 = 2;
+
+/// The powers of the secrets `tau`, `alpha` and `beta` computed by the first
+/// phase, over the generators `g` of G1 and `h` of G2. They support circuits
+/// whose SAP domain has at most `max_degree` elements.
+#[derive(Clone)]
+pub struct Accumulator<E: PairingEngine> {
+    /// `g^{tau^i}` for `i` in `0..=2 * max_degree`.
+    pub tau_g1:       Vec<E::G1Affine>,
+    /// `h^{tau^i}` for `i` in `0..=max_degree`.
+    pub tau_g2:       Vec<E::G2Affine>,
+    /// `g^{alpha tau^i}` for `i` in `0..=max_degree`.
+    pub alpha_tau_g1: Vec<E::G1Affine>,
+    /// `g^{beta tau^i}` for `i` in `0..=max_degree`.
+    pub beta_tau_g1:  Vec<E::G1Affine>,
+    /// `h^{beta}`.
+    pub beta_g2:      E::G2Affine,
+}
+
+impl<E: PairingEngine> Accumulator<E> {
+    /// Returns the accumulator of the secrets `tau = alpha = beta = 1`.
+    pub fn new(max_degree: usize) -> Self {
+        assert!(max_degree > 0);
+        let g = E::G1Affine::prime_subgroup_generator();
+        let h = E::G2Affine::prime_subgroup_generator();
+        Self {
+            tau_g1:       vec![g; 2 * max_degree + 1],
+            tau_g2:       vec![h; max_degree + 1],
+            alpha_tau_g1: vec![g; max_degree + 1],
+            beta_tau_g1:  vec![g; max_degree + 1],
+            beta_g2:      h,
+        }
+    }
+
+    pub fn max_degree(&self) -> usize {
+        self.tau_g2.len() - 1
+    }
+
+    /// The elements that each contribution updates by exactly one of its
+    /// secrets, namely `g^{tau}`, `g^{alpha}` and `g^{beta}`.
+    fn heads(&self) -> (E::G1Affine, E::G1Affine, E::G1Affine) {
+        (self.tau_g1[1], self.alpha_tau_g1[0], self.beta_tau_g1[0])
+    }
+
+    /// Checks that the accumulator has the right shape and contains the
+    /// successive powers of some `tau`, `alpha` and `beta`.
+    fn is_consistent<R: Rng>(&self, rng: &mut R) -> bool {
+        if self.tau_g2.len() < 2
+            || self.tau_g1.len() != 2 * self.max_degree() + 1
+            || self.alpha_tau_g1.len() != self.max_degree() + 1
+            || self.beta_tau_g1.len() != self.max_degree() + 1
+        {
+            return false;
+        }
+        if self.tau_g1[0] != E::G1Affine::prime_subgroup_generator()
+            || self.tau_g2[0] != E::G2Affine::prime_subgroup_generator()
+        {
+            return false;
+        }
+
+        let tau_ratio_g1 = (self.tau_g1[0], self.tau_g1[1]);
+        let tau_ratio_g2 = (self.tau_g2[0], self.tau_g2[1]);
+        same_ratio::<E>(power_pairs(&self.tau_g1, rng), tau_ratio_g2)
+            && same_ratio::<E>(tau_ratio_g1, power_pairs(&self.tau_g2, rng))
+            && same_ratio::<E>(power_pairs(&self.alpha_tau_g1, rng), tau_ratio_g2)
+            && same_ratio::<E>(power_pairs(&self.beta_tau_g1, rng), tau_ratio_g2)
+            && same_ratio::<E>(
+                (self.tau_g1[0], self.beta_tau_g1[0]),
+                (self.tau_g2[0], self.beta_g2),
+            )
+    }
+}
+
+impl<E: PairingEngine> PartialEq for Accumulator<E> {
+    fn eq(&self, other: &Self) -> bool {
+        self.tau_g1 == other.tau_g1
+            && self.tau_g2 == other.tau_g2
+            && self.alpha_tau_g1 == other.alpha_tau_g1
+            && self.beta_tau_g1 == other.beta_tau_g1
+            && self.beta_g2 == other.beta_g2
+    }
+}
+
+impl<E: PairingEngine> ToBytes for Accumulator<E> {
+    fn write<W: Write>(&self, mut writer: W) -> IoResult<()> {
+        write_vec(&self.tau_g1, &mut writer)?;
+        write_vec(&self.tau_g2, &mut writer)?;
+        write_vec(&self.alpha_tau_g1, &mut writer)?;
+        write_vec(&self.beta_tau_g1, &mut writer)?;
+        self.beta_g2.write(&mut writer)
+    }
+}
+
+impl<E: PairingEngine> FromBytes for Accumulator<E> {
+    fn read<R: Read>(mut reader: R) -> IoResult<Self> {
+        Ok(Self {
+            tau_g1:       read_vec(&mut reader)?,
+            tau_g2:       read_vec(&mut reader)?,
+            alpha_tau_g1: read_vec(&mut reader)?,
+            beta_tau_g1:  read_vec(&mut reader)?,
+            beta_g2:      E::G2Affine::read(&mut reader)?,
+        })
+    }
+}
+
+/// The public record of a contribution to the first phase: the heads of the
+/// accumulator after it, and proofs of knowledge of its secrets.
+#[derive(Clone)]
+pub struct Phase1Contribution<E: PairingEngine> {
+    pub tau_g1:      E::G1Affine,
+    pub alpha_g1:    E::G1Affine,
+    pub beta_g1:     E::G1Affine,
+    pub tau_proof:   KnowledgeProof<E>,
+    pub alpha_proof: KnowledgeProof<E>,
+    pub beta_proof:  KnowledgeProof<E>,
+}
+
+impl<E: PairingEngine> Phase1Contribution<E> {
+    /// Checks that `self` updates the heads `before` by secrets that its
+    /// author knows.
+    fn verify(&self, digest: &[u8], before: (E::G1Affine, E::G1Affine, E::G1Affine)) -> bool {
+        self.tau_proof
+            .verify_g1(&tagged(digest, TAU_TAG), before.0, self.tau_g1)
+            && self
+                .alpha_proof
+                .verify_g1(&tagged(digest, ALPHA_TAG), before.1, self.alpha_g1)
+            && self
+                .beta_proof
+                .verify_g1(&tagged(digest, BETA_TAG), before.2, self.beta_g1)
+    }
+
+    fn heads(&self) -> (E::G1Affine, E::G1Affine, E::G1Affine) {
+        (self.tau_g1, self.alpha_g1, self.beta_g1)
+    }
+}
+
+impl<E: PairingEngine> PartialEq for Phase1Contribution<E> {
+    fn eq(&self, other: &Self) -> bool {
+        self.tau_g1 == other.tau_g1
+            && self.alpha_g1 == other.alpha_g1
+            && self.beta_g1 == other.beta_g1
+            && self.tau_proof == other.tau_proof
+            && self.alpha_proof == other.alpha_proof
+            && self.beta_proof == other.beta_proof
+    }
+}
+
+impl<E: PairingEngine> ToBytes for Phase1Contribution<E> {
+    fn write<W: Write>(&self, mut writer: W) -> IoResult<()> {
+        self.tau_g1.write(&mut writer)?;
+        self.alpha_g1.write(&mut writer)?;
+        self.beta_g1.write(&mut writer)?;
+        self.tau_proof.write(&mut writer)?;
+        self.alpha_proof.write(&mut writer)?;
+        self.beta_proof.write(&mut writer)
+    }
+}
+
+impl<E: PairingEngine> FromBytes for Phase1Contribution<E> {
+    fn read<R: Read>(mut reader: R) -> IoResult<Self> {
+        Ok(Self {
+            tau_g1:      E::G1Affine::read(&mut reader)?,
+            alpha_g1:    E::G1Affine::read(&mut reader)?,
+            beta_g1:     E::G1Affine::read(&mut reader)?,
+            tau_proof:   KnowledgeProof::read(&mut reader)?,
+            alpha_proof: KnowledgeProof::read(&mut reader)?,
+            beta_proof:  KnowledgeProof::read(&mut reader)?,
+        })
+    }
+}
+
+/// The transcript of the first, circuit-independent phase of the ceremony.
+#[derive(Clone)]
+pub struct Phase1<E: PairingEngine> {
+    pub accumulator:   Accumulator<E>,
+    pub contributions: Vec<Phase1Contribution<E>>,
+}
+
+impl<E: PairingEngine> Phase1<E> {
+    /// Starts a ceremony for circuits whose SAP domain has at most
+    /// `max_degree` elements.
+    pub fn new(max_degree: usize) -> Self {
+        Self {
+            accumulator:   Accumulator::new(max_degree),
+            contributions: vec![],
+        }
+    }
+
+    /// Returns the digest of the transcript, to which the proofs of
+    /// knowledge of the next contribution are bound.
+    pub fn digest(&self) -> Vec<u8> {
+        self.contributions.iter().fold(
+            initial_digest(self.accumulator.max_degree()),
+            |digest, contribution| chain(&digest, contribution),
+        )
+    }
+
+    /// Multiplies the accumulator by fresh secrets sampled from `rng`, and
+    /// records the contribution. The secrets are dropped on return.
+    pub fn contribute<R: Rng>(&mut self, rng: &mut R) {
+        let contribute_time = timer_start!(|| "Phase 1 contribution");
+        let digest = self.digest();
+        let tau = nonzero_scalar::<E::Fr, _>(rng);
+        let alpha = nonzero_scalar::<E::Fr, _>(rng);
+        let beta = nonzero_scalar::<E::Fr, _>(rng);
+
+        let accumulator = &mut self.accumulator;
+        let max_degree = accumulator.max_degree();
+        scale(
+            &mut accumulator.tau_g1,
+            &powers(tau, E::Fr::one(), 2 * max_degree + 1),
+        );
+        scale(
+            &mut accumulator.tau_g2,
+            &powers(tau, E::Fr::one(), max_degree + 1),
+        );
+        scale(
+            &mut accumulator.alpha_tau_g1,
+            &powers(tau, alpha, max_degree + 1),
+        );
+        scale(
+            &mut accumulator.beta_tau_g1,
+            &powers(tau, beta, max_degree + 1),
+        );
+        accumulator.beta_g2 = accumulator.beta_g2.mul(beta.into_repr()).into_affine();
+
+        let (tau_g1, alpha_g1, beta_g1) = accumulator.heads();
+        self.contributions.push(Phase1Contribution {
+            tau_g1,
+            alpha_g1,
+            beta_g1,
+            tau_proof: KnowledgeProof::new(tau, &tagged(&digest, TAU_TAG), rng),
+            alpha_proof: KnowledgeProof::new(alpha, &tagged(&digest, ALPHA_TAG), rng),
+            beta_proof: KnowledgeProof::new(beta, &tagged(&digest, BETA_TAG), rng),
+        });
+        timer_end!(contribute_time);
+    }
+
+    /// Checks every contribution of the transcript, and that the accumulator
+    /// is the result of applying them in order.
+    pub fn verify<R: Rng>(&self, rng: &mut R) -> bool {
+        if !self.accumulator.is_consistent(rng) {
+            return false;
+        }
+
+        let g = E::G1Affine::prime_subgroup_generator();
+        let mut digest = initial_digest(self.accumulator.max_degree());
+        let mut heads = (g, g, g);
+        for contribution in &self.contributions {
+            if !contribution.verify(&digest, heads) {
+                return false;
+            }
+            heads = contribution.heads();
+            digest = chain(&digest, contribution);
+        }
+        heads == self.accumulator.heads()
+    }
+
+    /// Checks that `next` extends the valid transcript `self` by exactly one
+    /// contribution. This is cheaper than `next.verify`.
+    pub fn verify_update<R: Rng>(&self, next: &Self, rng: &mut R) -> bool {
+        let contribution = match next.contributions.split_last() {
+            Some((contribution, previous)) if previous == &self.contributions[..] => contribution,
+            _ => return false,
+        };
+        next.accumulator.is_consistent(rng)
+            && next.accumulator.max_degree() == self.accumulator.max_degree()
+            && contribution.verify(&self.digest(), self.accumulator.heads())
+            && contribution.heads() == next.accumulator.heads()
+    }
+}
+
+impl<E: PairingEngine> ToBytes for Phase1<E> {
+    fn write<W: Write>(&self, mut writer: W) -> IoResult<()> {
+        TRANSCRIPT_VERSION.write(&mut writer)?;
+        self.accumulator.write(&mut writer)?;
+        write_vec(&self.contributions, &mut writer)
+    }
+}
+
+impl<E: PairingEngine> FromBytes for Phase1<E> {
+    fn read<R: Read>(mut reader: R) -> IoResult<Self> {
+        read_version(&mut reader)?;
+        Ok(Self {
+            accumulator:   Accumulator::read(&mut reader)?,
+            contributions: read_vec(&mut reader)?,
+        })
+    }
+}
+
+fn initial_digest(max_degree: usize) -> Vec<u8> {
+    chain(b"GM17 MPC phase 1", &(max_degree as u64))
+}
+
+/// Returns `[scale, scale * x, ..., scale * x^{n - 1}]`.
+fn powers<F: Field>(x: F, scale: F, n: usize) -> Vec<F> {
+    let mut powers = Vec::with_capacity(n);
+    let mut power = scale;
+    for _ in 0..n {
+        powers.push(power);
+        power *= &x;
+    }
+    powers
+}
+
+fn scale<G: AffineCurve>(elements: &mut [G], scalars: &[G::ScalarField]) {
+    elements
+        .par_iter_mut()
+        .zip(scalars)
+        .for_each(|(element, scalar)| *element = element.mul(scalar.into_repr()).into_affine());
+}
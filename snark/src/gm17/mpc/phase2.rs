@@ -0,0 +1,438 @@
+use algebra::{
+    bytes::{FromBytes, ToBytes},
+    fft::{
+        domain::{EvaluationDomain, Point},
+        multicore::Worker,
+    },
+    AffineCurve, Field, PairingEngine, PrimeField, ProjectiveCurve,
+};
+use rand::Rng;
+use rayon::prelude::*;
+use std::{
+    io::{Read, Result as IoResult, Write},
+    iter,
+};
+
+use super::{
+    chain, merge_pairs, nonzero_scalar, read_version, same_ratio, tagged, Accumulator,
+    KnowledgeProof, TRANSCRIPT_VERSION,
+};
+use crate::{
+    gm17::{
+        r1cs_to_sap::R1CStoSAP, read_vec, write_vec, KeypairAssembly, Parameters, VerifyingKey,
+    },
+    Circuit, ConstraintSystem, SynthesisError,
+};
+
+const GAMMA_TAG: u8 = 3;
+
+/// The public record of a contribution to the second phase: the new
+/// `h^{gamma}`, and a proof of knowledge of the secret that updated it.
+#[derive(Clone)]
+pub struct Phase2Contribution<E: PairingEngine> {
+    pub h_gamma_g2:  E::G2Affine,
+    pub gamma_proof: KnowledgeProof<E>,
+}
+
+impl<E: PairingEngine> Phase2Contribution<E> {
+    fn verify(&self, digest: &[u8], before: E::G2Affine) -> bool {
+        self.gamma_proof
+            .verify_g2(&tagged(digest, GAMMA_TAG), before, self.h_gamma_g2)
+    }
+}
+
+impl<E: PairingEngine> PartialEq for Phase2Contribution<E> {
+    fn eq(&self, other: &Self) -> bool {
+        self.h_gamma_g2 == other.h_gamma_g2 && self.gamma_proof == other.gamma_proof
+    }
+}
+
+impl<E: PairingEngine> ToBytes for Phase2Contribution<E> {
+    fn write<W: Write>(&self, mut writer: W) -> IoResult<()> {
+        self.h_gamma_g2.write(&mut writer)?;
+        self.gamma_proof.write(&mut writer)
+    }
+}
+
+impl<E: PairingEngine> FromBytes for Phase2Contribution<E> {
+    fn read<R: Read>(mut reader: R) -> IoResult<Self> {
+        Ok(Self {
+            h_gamma_g2:  E::G2Affine::read(&mut reader)?,
+            gamma_proof: KnowledgeProof::read(&mut reader)?,
+        })
+    }
+}
+
+/// The transcript of the second, circuit-specific phase of the ceremony.
+///
+/// The parameters are those of `generate_parameters` for the generator
+/// `g^{1 / gamma}` of G1, so that `g` itself is `g_gamma_g1`. Contributions
+/// then multiply `gamma` by their secret, which leaves `a_query`, `g_gamma_z`
+/// and `g_ab_gamma_z` unchanged, multiplies `b_query`, `c_query_2`,
+/// `g_gamma2_z_t`, `h_gamma_z`, `g_gamma2_z2` and `h_gamma_g2` by the secret,
+/// and divides `g_alpha_g1` and the `(alpha + beta)` part of `vk.query` and
+/// `c_query_1` by it.
+#[derive(Clone)]
+pub struct Phase2<E: PairingEngine> {
+    pub parameters:    Parameters<E>,
+    /// `g^{C_i(tau)}` for every SAP variable `i`: the parts of `vk.query` and
+    /// `c_query_1`, in this order, that do not depend on `gamma`.
+    pub c_query_base:  Vec<E::G1Affine>,
+    pub contributions: Vec<Phase2Contribution<E>>,
+}
+
+impl<E: PairingEngine> Phase2<E> {
+    /// Starts the second phase for `circuit` from the result of the first
+    /// phase, which should have been checked with `Phase1::verify`.
+    pub fn new<C: Circuit<E>>(
+        accumulator: &Accumulator<E>,
+        circuit: C,
+    ) -> Result<Self, SynthesisError> {
+        let mut assembly = KeypairAssembly {
+            num_inputs:      0,
+            num_aux:         0,
+            num_constraints: 0,
+            at:              vec![],
+            bt:              vec![],
+            ct:              vec![],
+        };
+
+        // Allocate the "one" input variable
+        assembly.alloc_input(|| "", || Ok(E::Fr::one()))?;
+
+        // Synthesize the circuit.
+        let synthesis_time = timer_start!(|| "Circuit synthesis");
+        circuit.synthesize(&mut assembly)?;
+        timer_end!(synthesis_time);
+
+        let reduction_time = timer_start!(|| "R1CS to SAP Instance Map");
+        let sap = R1CStoSAP::instance_map(&assembly)?;
+        timer_end!(reduction_time);
+
+        let m = sap.domain_size;
+        if m > accumulator.max_degree() {
+            return Err(SynthesisError::PolynomialDegreeTooLarge);
+        }
+
+        let lagrange_time = timer_start!(|| "Compute Lagrange bases");
+        // g^{L_j(tau)} and h^{L_j(tau)}
+        let lagrange_g1 = lagrange_basis::<E, E::G1Projective>(&accumulator.tau_g1[..m]);
+        let lagrange_g2 = lagrange_basis::<E, E::G2Projective>(&accumulator.tau_g2[..m]);
+        // g^{(alpha + beta) L_j(tau)}
+        let lagrange_ab_g1 = lagrange_basis::<E, E::G1Projective>(&accumulator.alpha_tau_g1[..m])
+            .into_iter()
+            .zip(lagrange_basis::<E, E::G1Projective>(
+                &accumulator.beta_tau_g1[..m],
+            ))
+            .map(|(alpha, beta)| alpha + &beta)
+            .collect::<Vec<_>>();
+        // g^{Z(tau) L_j(tau)}, where Z(tau) = tau^m - 1
+        let lagrange_z_g1 = lagrange_basis::<E, E::G1Projective>(&accumulator.tau_g1[m..2 * m])
+            .into_iter()
+            .zip(&lagrange_g1)
+            .map(|(shifted, lagrange)| shifted - lagrange)
+            .collect::<Vec<_>>();
+        timer_end!(lagrange_time);
+
+        let queries_time = timer_start!(|| "Compute queries");
+        let a_query = evaluate_all(&sap.a, &lagrange_g1);
+        let b_query = evaluate_all(&sap.a, &lagrange_g2);
+        let c_query_base = evaluate_all(&sap.c, &lagrange_g1);
+        let c_query = c_query_base
+            .par_iter()
+            .zip(evaluate_all(&sap.a, &lagrange_ab_g1))
+            .map(|(c, ab)| *c + &ab)
+            .collect::<Vec<_>>();
+        let c_query_2 = evaluate_all(&sap.a, &lagrange_z_g1)
+            .par_iter()
+            .map(|z| z.double())
+            .collect::<Vec<_>>();
+        timer_end!(queries_time);
+
+        let tau_g1 = |i: usize| accumulator.tau_g1[i].into_projective();
+        let alpha_beta_g1 = |i: usize| {
+            accumulator.alpha_tau_g1[i].into_projective()
+                + &accumulator.beta_tau_g1[i].into_projective()
+        };
+        let g_gamma_z = tau_g1(m) - &tau_g1(0);
+        let h_gamma_z =
+            accumulator.tau_g2[m].into_projective() - &accumulator.tau_g2[0].into_projective();
+        let g_ab_gamma_z = alpha_beta_g1(m) - &alpha_beta_g1(0);
+        let g_gamma2_z2 = tau_g1(2 * m) - &tau_g1(m).double() + &tau_g1(0);
+        let g_gamma2_z_t = (0..m + 1)
+            .map(|i| tau_g1(m + i) - &tau_g1(i))
+            .collect::<Vec<_>>();
+
+        let mut c_query_1 = into_affine(&c_query);
+        let query = c_query_1.drain(..assembly.num_inputs).collect();
+        let vk = VerifyingKey::<E> {
+            h_g2: accumulator.tau_g2[0],
+            g_alpha_g1: accumulator.alpha_tau_g1[0],
+            h_beta_g2: accumulator.beta_g2,
+            g_gamma_g1: accumulator.tau_g1[0],
+            h_gamma_g2: accumulator.tau_g2[0],
+            query,
+        };
+
+        Ok(Self {
+            parameters:    Parameters {
+                vk,
+                a_query: into_affine(&a_query),
+                b_query: into_affine(&b_query),
+                c_query_1,
+                c_query_2: into_affine(&c_query_2),
+                g_gamma_z: g_gamma_z.into_affine(),
+                h_gamma_z: h_gamma_z.into_affine(),
+                g_ab_gamma_z: g_ab_gamma_z.into_affine(),
+                g_gamma2_z2: g_gamma2_z2.into_affine(),
+                g_gamma2_z_t: into_affine(&g_gamma2_z_t),
+            },
+            c_query_base:  into_affine(&c_query_base),
+            contributions: vec![],
+        })
+    }
+
+    /// Returns the digest of the transcript, to which the proof of
+    /// knowledge of the next contribution is bound.
+    pub fn digest(&self) -> Vec<u8> {
+        self.contributions
+            .iter()
+            .fold(self.initial_digest(), |digest, contribution| {
+                chain(&digest, contribution)
+            })
+    }
+
+    /// Multiplies `gamma` by a fresh secret sampled from `rng`, and records
+    /// the contribution. The secret is dropped on return.
+    pub fn contribute<R: Rng>(&mut self, rng: &mut R) {
+        let contribute_time = timer_start!(|| "Phase 2 contribution");
+        let digest = self.digest();
+        let delta = nonzero_scalar::<E::Fr, _>(rng);
+        let delta_inv = delta.inverse().unwrap();
+
+        let parameters = &mut self.parameters;
+        scale_all(&mut parameters.b_query, delta);
+        scale_all(&mut parameters.c_query_2, delta);
+        scale_all(&mut parameters.g_gamma2_z_t, delta);
+        parameters.h_gamma_z = parameters.h_gamma_z.mul(delta.into_repr()).into_affine();
+        parameters.g_gamma2_z2 = parameters.g_gamma2_z2.mul(delta.into_repr()).into_affine();
+        parameters.vk.h_gamma_g2 = parameters
+            .vk
+            .h_gamma_g2
+            .mul(delta.into_repr())
+            .into_affine();
+
+        parameters.vk.g_alpha_g1 = parameters
+            .vk
+            .g_alpha_g1
+            .mul(delta_inv.into_repr())
+            .into_affine();
+        let (query_base, c_query_1_base) = self.c_query_base.split_at(parameters.vk.query.len());
+        scale_offsets::<E>(&mut parameters.vk.query, query_base, delta_inv);
+        scale_offsets::<E>(&mut parameters.c_query_1, c_query_1_base, delta_inv);
+
+        let h_gamma_g2 = parameters.vk.h_gamma_g2;
+        self.contributions.push(Phase2Contribution {
+            h_gamma_g2,
+            gamma_proof: KnowledgeProof::new(delta, &tagged(&digest, GAMMA_TAG), rng),
+        });
+        timer_end!(contribute_time);
+    }
+
+    /// Checks that the transcript starts from `accumulator` and `circuit`,
+    /// that every contribution is valid, and that the parameters are the
+    /// result of applying them in order. The accumulator should have been
+    /// checked with `Phase1::verify`.
+    pub fn verify<C: Circuit<E>, R: Rng>(
+        &self,
+        accumulator: &Accumulator<E>,
+        circuit: C,
+        rng: &mut R,
+    ) -> Result<bool, SynthesisError> {
+        let initial = Self::new(accumulator, circuit)?;
+        if !self.is_rescaling_of(&initial, rng) {
+            return Ok(false);
+        }
+
+        let mut digest = initial.initial_digest();
+        let mut h_gamma_g2 = initial.parameters.vk.h_gamma_g2;
+        for contribution in &self.contributions {
+            if !contribution.verify(&digest, h_gamma_g2) {
+                return Ok(false);
+            }
+            h_gamma_g2 = contribution.h_gamma_g2;
+            digest = chain(&digest, contribution);
+        }
+        Ok(h_gamma_g2 == self.parameters.vk.h_gamma_g2)
+    }
+
+    /// Checks that `next` extends the valid transcript `self` by exactly one
+    /// contribution. Unlike `verify`, this does not need the circuit.
+    pub fn verify_update<R: Rng>(&self, next: &Self, rng: &mut R) -> bool {
+        let contribution = match next.contributions.split_last() {
+            Some((contribution, previous)) if previous == &self.contributions[..] => contribution,
+            _ => return false,
+        };
+        next.is_rescaling_of(self, rng)
+            && contribution.verify(&self.digest(), self.parameters.vk.h_gamma_g2)
+            && contribution.h_gamma_g2 == next.parameters.vk.h_gamma_g2
+    }
+
+    fn initial_digest(&self) -> Vec<u8> {
+        let mut invariants = vec![];
+        self.write_invariants(&mut invariants)
+            .expect("writing to a vector cannot fail");
+        chain(b"GM17 MPC phase 2", &invariants)
+    }
+
+    /// Writes the elements that contributions leave unchanged, which
+    /// identify the circuit and the result of the first phase.
+    fn write_invariants<W: Write>(&self, mut writer: W) -> IoResult<()> {
+        let parameters = &self.parameters;
+        write_vec(&self.c_query_base, &mut writer)?;
+        write_vec(&parameters.a_query, &mut writer)?;
+        parameters.g_gamma_z.write(&mut writer)?;
+        parameters.g_ab_gamma_z.write(&mut writer)?;
+        parameters.vk.h_g2.write(&mut writer)?;
+        parameters.vk.h_beta_g2.write(&mut writer)?;
+        parameters.vk.g_gamma_g1.write(&mut writer)
+    }
+
+    /// Checks that the parameters of `self` are those of `previous` with
+    /// `gamma` multiplied by some secret.
+    fn is_rescaling_of<R: Rng>(&self, previous: &Self, rng: &mut R) -> bool {
+        let (old, new) = (&previous.parameters, &self.parameters);
+        if self.c_query_base != previous.c_query_base
+            || new.vk.query.len() + new.c_query_1.len() != self.c_query_base.len()
+            || new.vk.query.len() != old.vk.query.len()
+            || new.b_query.len() != old.b_query.len()
+            || new.c_query_2.len() != old.c_query_2.len()
+            || new.g_gamma2_z_t.len() != old.g_gamma2_z_t.len()
+        {
+            return false;
+        }
+        if new.a_query != old.a_query
+            || new.g_gamma_z != old.g_gamma_z
+            || new.g_ab_gamma_z != old.g_ab_gamma_z
+            || new.vk.h_g2 != old.vk.h_g2
+            || new.vk.h_beta_g2 != old.vk.h_beta_g2
+            || new.vk.g_gamma_g1 != old.vk.g_gamma_g1
+        {
+            return false;
+        }
+        if new.g_gamma2_z2.is_zero() || new.vk.h_gamma_g2.is_zero() {
+            return false;
+        }
+
+        // Elements multiplied by the secret.
+        let up_g1 = |p: &Parameters<E>| {
+            iter::once(p.g_gamma2_z2)
+                .chain(p.g_gamma2_z_t.iter().cloned())
+                .chain(p.c_query_2.iter().cloned())
+                .collect::<Vec<_>>()
+        };
+        let up_g2 = |p: &Parameters<E>| {
+            iter::once(p.h_gamma_z)
+                .chain(p.b_query.iter().cloned())
+                .collect::<Vec<_>>()
+        };
+        // Elements divided by the secret.
+        let down_g1 = |p: &Parameters<E>| {
+            let offsets =
+                p.vk.query
+                    .iter()
+                    .chain(&p.c_query_1)
+                    .zip(&self.c_query_base)
+                    .map(|(element, base)| element.into_projective() - &base.into_projective())
+                    .collect::<Vec<_>>();
+            iter::once(p.vk.g_alpha_g1)
+                .chain(into_affine(&offsets))
+                .collect::<Vec<_>>()
+        };
+
+        let gamma_ratio = (old.vk.h_gamma_g2, new.vk.h_gamma_g2);
+        let inverse_ratio = (new.vk.h_gamma_g2, old.vk.h_gamma_g2);
+        same_ratio::<E>(merge_pairs(&up_g1(old), &up_g1(new), rng), gamma_ratio)
+            && same_ratio::<E>(
+                merge_pairs(&down_g1(old), &down_g1(new), rng),
+                inverse_ratio,
+            )
+            && same_ratio::<E>(
+                (old.g_gamma2_z2, new.g_gamma2_z2),
+                merge_pairs(&up_g2(old), &up_g2(new), rng),
+            )
+    }
+}
+
+impl<E: PairingEngine> ToBytes for Phase2<E> {
+    fn write<W: Write>(&self, mut writer: W) -> IoResult<()> {
+        TRANSCRIPT_VERSION.write(&mut writer)?;
+        self.parameters.write(&mut writer)?;
+        write_vec(&self.c_query_base, &mut writer)?;
+        write_vec(&self.contributions, &mut writer)
+    }
+}
+
+impl<E: PairingEngine> FromBytes for Phase2<E> {
+    fn read<R: Read>(mut reader: R) -> IoResult<Self> {
+        read_version(&mut reader)?;
+        Ok(Self {
+            parameters:    Parameters::read(&mut reader)?,
+            c_query_base:  read_vec(&mut reader)?,
+            contributions: read_vec(&mut reader)?,
+        })
+    }
+}
+
+/// Returns the Lagrange basis `g^{L_j(tau)}` of the domain of size
+/// `powers.len()`, given the powers `g^{tau^j}`.
+fn lagrange_basis<E, G>(powers: &[G::Affine]) -> Vec<G>
+where
+    E: PairingEngine,
+    G: ProjectiveCurve<ScalarField = E::Fr>,
+{
+    let powers = powers.iter().map(|p| Point(p.into_projective())).collect();
+    let mut domain = EvaluationDomain::<E, Point<G>>::from_coeffs(powers)
+        .expect("the domain size is a supported power of two");
+    domain.ifft(&Worker::new());
+    domain.into_coeffs().into_iter().map(|p| p.0).collect()
+}
+
+/// Evaluates every polynomial of `polys` over the Lagrange basis `basis`.
+fn evaluate_all<G: ProjectiveCurve>(polys: &[Vec<(G::ScalarField, usize)>], basis: &[G]) -> Vec<G> {
+    polys
+        .par_iter()
+        .map(|terms| {
+            let mut acc = G::zero();
+            for &(coeff, j) in terms {
+                let mut term = basis[j];
+                term.mul_assign(coeff.into_repr());
+                acc += &term;
+            }
+            acc
+        })
+        .collect()
+}
+
+fn into_affine<G: ProjectiveCurve>(elements: &[G]) -> Vec<G::Affine> {
+    elements.par_iter().map(|g| g.into_affine()).collect()
+}
+
+fn scale_all<G: AffineCurve>(elements: &mut [G], x: G::ScalarField) {
+    elements
+        .par_iter_mut()
+        .for_each(|element| *element = element.mul(x.into_repr()).into_affine());
+}
+
+/// Replaces every `element` by `base + (element - base) * x`.
+fn scale_offsets<E: PairingEngine>(elements: &mut [E::G1Affine], bases: &[E::G1Affine], x: E::Fr) {
+    elements
+        .par_iter_mut()
+        .zip(bases)
+        .for_each(|(element, base)| {
+            let mut offset = element.into_projective() - &base.into_projective();
+            offset.mul_assign(x.into_repr());
+            offset.add_assign_mixed(base);
+            *element = offset.into_affine();
+        });
+}
@@ -0,0 +1,227 @@
+use super::{hash_to_g2, Phase1, Phase2};
+use crate::{
+    gm17::{
+        create_random_proof, prepare_verifying_key, r1cs_to_sap::R1CStoSAP, verify_proof,
+        KeypairAssembly,
+    },
+    Circuit, ConstraintSystem, SynthesisError,
+};
+
+use algebra::{
+    curves::bls12_377::Bls12_377,
+    fft::domain::{EvaluationDomain, Scalar},
+    fields::bls12_377::Fr,
+    to_bytes, AffineCurve, Field, FpParameters, FromBytes, PairingEngine, PrimeField,
+    ProjectiveCurve, ToBytes,
+};
+use blake2::Blake2b;
+use byteorder::{ByteOrder, LittleEndian};
+use digest::Digest;
+use rand::{thread_rng, ChaChaRng, Rand, Rng, SeedableRng};
+use std::ops::MulAssign;
+
+struct MySillyCircuit<E: PairingEngine> {
+    a: Option<E::Fr>,
+    b: Option<E::Fr>,
+}
+
+impl<E: PairingEngine> Circuit<E> for MySillyCircuit<E> {
+    fn synthesize<CS: ConstraintSystem<E>>(self, cs: &mut CS) -> Result<(), SynthesisError> {
+        let a = cs.alloc(|| "a", || self.a.ok_or(SynthesisError::AssignmentMissing))?;
+        let b = cs.alloc(|| "b", || self.b.ok_or(SynthesisError::AssignmentMissing))?;
+        let c = cs.alloc_input(
+            || "c",
+            || {
+                let mut a = self.a.ok_or(SynthesisError::AssignmentMissing)?;
+                let b = self.b.ok_or(SynthesisError::AssignmentMissing)?;
+
+                a.mul_assign(&b);
+                Ok(a)
+            },
+        )?;
+        let d = cs.alloc(
+            || "d",
+            || {
+                let mut a = self.a.ok_or(SynthesisError::AssignmentMissing)?;
+                a.square_in_place();
+                Ok(a)
+            },
+        )?;
+
+        cs.enforce(|| "a*b=c", |lc| lc + a, |lc| lc + b, |lc| lc + c);
+        cs.enforce(|| "a*a=d", |lc| lc + a, |lc| lc + a, |lc| lc + d);
+
+        Ok(())
+    }
+}
+
+fn empty_circuit() -> MySillyCircuit<Bls12_377> {
+    MySillyCircuit { a: None, b: None }
+}
+
+#[test]
+fn instance_map_matches_evaluation() {
+    let rng = &mut thread_rng();
+
+    let mut assembly = KeypairAssembly::<Bls12_377> {
+        num_inputs:      0,
+        num_aux:         0,
+        num_constraints: 0,
+        at:              vec![],
+        bt:              vec![],
+        ct:              vec![],
+    };
+    assembly.alloc_input(|| "", || Ok(Fr::one())).unwrap();
+    empty_circuit().synthesize(&mut assembly).unwrap();
+
+    let t = Fr::rand(rng);
+    let (a, c, _, num_variables, m) =
+        R1CStoSAP::instance_map_with_evaluation(&assembly, &t).unwrap();
+    let sap = R1CStoSAP::instance_map(&assembly).unwrap();
+    assert_eq!(sap.num_variables, num_variables);
+    assert_eq!(sap.domain_size, m);
+
+    let u = EvaluationDomain::<Bls12_377, _>::from_coeffs(vec![Scalar(Fr::zero()); m])
+        .unwrap()
+        .evaluate_all_lagrange_coefficients(&t);
+    let evaluate = |terms: &[(Fr, usize)]| {
+        terms
+            .iter()
+            .fold(Fr::zero(), |acc, (coeff, j)| acc + &(u[*j] * coeff))
+    };
+    assert!(sap.a.iter().map(|terms| evaluate(terms)).eq(a));
+    assert!(sap.c.iter().map(|terms| evaluate(terms)).eq(c));
+}
+
+#[test]
+fn ceremony() {
+    let rng = &mut thread_rng();
+
+    let mut phase1 = Phase1::<Bls12_377>::new(8);
+    for _ in 0..3 {
+        let previous = phase1.clone();
+        phase1.contribute(rng);
+        assert!(previous.verify_update(&phase1, rng));
+    }
+    assert!(phase1.verify(rng));
+
+    let phase1_bytes = to_bytes![phase1].unwrap();
+    let phase1 = Phase1::<Bls12_377>::read(&phase1_bytes[..]).unwrap();
+    assert!(phase1.verify(rng));
+
+    let mut phase2 = Phase2::new(&phase1.accumulator, empty_circuit()).unwrap();
+    for _ in 0..3 {
+        let previous = phase2.clone();
+        phase2.contribute(rng);
+        assert!(previous.verify_update(&phase2, rng));
+    }
+    assert!(phase2
+        .verify(&phase1.accumulator, empty_circuit(), rng)
+        .unwrap());
+
+    let phase2_bytes = to_bytes![phase2].unwrap();
+    let phase2 = Phase2::<Bls12_377>::read(&phase2_bytes[..]).unwrap();
+    assert!(phase2
+        .verify(&phase1.accumulator, empty_circuit(), rng)
+        .unwrap());
+
+    let params = phase2.parameters;
+    let pvk = prepare_verifying_key::<Bls12_377>(&params.vk);
+    for _ in 0..10 {
+        let a = Fr::rand(rng);
+        let b = Fr::rand(rng);
+        let mut c = a;
+        c.mul_assign(&b);
+
+        let proof = create_random_proof(
+            MySillyCircuit {
+                a: Some(a),
+                b: Some(b),
+            },
+            &params,
+            rng,
+        )
+        .unwrap();
+
+        assert!(verify_proof(&pvk, &proof, &[c]).unwrap());
+        assert!(!verify_proof(&pvk, &proof, &[a]).unwrap());
+    }
+}
+
+#[test]
+fn invalid_transcripts_are_rejected() {
+    let rng = &mut thread_rng();
+
+    let mut phase1 = Phase1::<Bls12_377>::new(8);
+    phase1.contribute(rng);
+    phase1.contribute(rng);
+
+    let mut tampered = phase1.clone();
+    tampered.accumulator.tau_g1[3] = tampered.accumulator.tau_g1[4];
+    assert!(!tampered.verify(rng));
+
+    let mut tampered = phase1.clone();
+    tampered.contributions.remove(0);
+    assert!(!tampered.verify(rng));
+
+    let mut tampered = phase1.clone();
+    tampered.contributions.swap(0, 1);
+    assert!(!tampered.verify(rng));
+
+    let mut phase1_bytes = to_bytes![phase1].unwrap();
+    assert!(Phase1::<Bls12_377>::read(&phase1_bytes[..phase1_bytes.len() - 1]).is_err());
+    phase1_bytes[0] += 1;
+    assert!(Phase1::<Bls12_377>::read(&phase1_bytes[..]).is_err());
+
+    // The domain of the circuit is larger than the accumulator supports.
+    assert!(Phase2::new(&Phase1::<Bls12_377>::new(4).accumulator, empty_circuit()).is_err());
+
+    let mut phase2 = Phase2::new(&phase1.accumulator, empty_circuit()).unwrap();
+    phase2.contribute(rng);
+    let previous = phase2.clone();
+    phase2.contribute(rng);
+
+    let mut tampered = phase2.clone();
+    tampered.parameters.c_query_2[1] = tampered.parameters.c_query_2[0];
+    assert!(!previous.verify_update(&tampered, rng));
+    assert!(!tampered
+        .verify(&phase1.accumulator, empty_circuit(), rng)
+        .unwrap());
+
+    let mut tampered = phase2.clone();
+    tampered.parameters.vk.g_alpha_g1 = previous.parameters.vk.g_alpha_g1;
+    assert!(!previous.verify_update(&tampered, rng));
+
+    let mut tampered = phase2.clone();
+    tampered.contributions[1].gamma_proof = tampered.contributions[0].gamma_proof.clone();
+    assert!(!previous.verify_update(&tampered, rng));
+    assert!(!tampered
+        .verify(&phase1.accumulator, empty_circuit(), rng)
+        .unwrap());
+
+    // The transcript must start from the same accumulator.
+    let mut other = Phase1::<Bls12_377>::new(8);
+    other.contribute(rng);
+    assert!(!phase2
+        .verify(&other.accumulator, empty_circuit(), rng)
+        .unwrap());
+}
+
+#[test]
+fn hash_to_g2_hides_the_discrete_log() {
+    type G2 = <Bls12_377 as PairingEngine>::G2Affine;
+    let input = b"hash to G2";
+    let base = hash_to_g2::<Bls12_377>(input);
+    assert!(!base.is_zero());
+    assert!(base.mul(<Fr as PrimeField>::Params::MODULUS).is_zero());
+    assert!(base == hash_to_g2::<Bls12_377>(input));
+
+    // The base is not the generator times a scalar drawn from the seed.
+    let hash = Blake2b::digest(input);
+    let mut seed = [0u32; 8];
+    LittleEndian::read_u32_into(&hash[..32], &mut seed);
+    let rng_scalar = ChaChaRng::from_seed(&seed).gen::<Fr>();
+    assert!(base != G2::prime_subgroup_generator().mul(rng_scalar).into_affine());
+    let rng_point = ChaChaRng::from_seed(&seed).gen::<<Bls12_377 as PairingEngine>::G2Projective>();
+    assert!(base != rng_point.into_affine());
+}
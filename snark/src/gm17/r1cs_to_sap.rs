@@ -16,6 +16,16 @@ use std::ops::{AddAssign, SubAssign};
 
 pub(crate) struct R1CStoSAP;
 
+/// The SAP polynomials of a circuit, in the Lagrange basis of the evaluation
+/// domain: the `A` polynomial of the `i`-th variable is the sum of
+/// `coeff * L_j` over the `(coeff, j)` in `a[i]`, and likewise for `C`.
+pub(crate) struct SAPInstance<E: PairingEngine> {
+    pub(crate) domain_size:   usize,
+    pub(crate) num_variables: usize,
+    pub(crate) a:             Vec<Vec<(E::Fr, usize)>>,
+    pub(crate) c:             Vec<Vec<(E::Fr, usize)>>,
+}
+
 impl R1CStoSAP {
     #[inline]
     pub(crate) fn instance_map_with_evaluation<E: PairingEngine>(
@@ -106,6 +116,78 @@ impl R1CStoSAP {
         Ok((a, c, zt, sap_num_variables, m))
     }
 
+    /// Like `instance_map_with_evaluation`, but keeps the polynomials
+    /// symbolic instead of evaluating them at a known point.
+    pub(crate) fn instance_map<E: PairingEngine>(
+        assembly: &KeypairAssembly<E>,
+    ) -> Result<SAPInstance<E>, SynthesisError> {
+        let domain = vec![
+            Scalar::<E>(E::Fr::zero());
+            2 * assembly.num_constraints + 2 * (assembly.num_inputs - 1) + 1
+        ];
+        let domain = EvaluationDomain::<E, _>::from_coeffs(domain)
+            .ok_or(SynthesisError::PolynomialDegreeTooLarge)?;
+
+        let sap_num_variables =
+            2 * (assembly.num_inputs - 1) + assembly.num_aux + assembly.num_constraints;
+        let extra_var_offset = (assembly.num_inputs - 1) + assembly.num_aux + 1;
+        let extra_constr_offset = 2 * assembly.num_constraints;
+        let extra_var_offset2 =
+            (assembly.num_inputs - 1) + assembly.num_aux + assembly.num_constraints;
+
+        let index = |var: Index| match var {
+            Index::Input(i) => i,
+            Index::Aux(i) => assembly.num_inputs + i,
+        };
+        let one = E::Fr::one();
+
+        let mut a = vec![vec![]; sap_num_variables + 1];
+        let mut c = vec![vec![]; sap_num_variables + 1];
+
+        for i in 0..assembly.num_constraints {
+            for &(coeff, var) in assembly.at[i].iter() {
+                a[index(var)].push((coeff, 2 * i));
+                a[index(var)].push((coeff, 2 * i + 1));
+            }
+
+            for &(coeff, var) in assembly.bt[i].iter() {
+                a[index(var)].push((coeff, 2 * i));
+                a[index(var)].push((-coeff, 2 * i + 1));
+            }
+
+            for &(coeff, var) in assembly.ct[i].iter() {
+                c[index(var)].push((coeff.double().double(), 2 * i));
+            }
+            c[extra_var_offset + i].push((one, 2 * i));
+            c[extra_var_offset + i].push((one, 2 * i + 1));
+        }
+
+        a[0].push((one, extra_constr_offset));
+        c[0].push((one, extra_constr_offset));
+
+        for i in 1..(assembly.num_inputs - 1) + 1 {
+            // First extra constraint
+            let j = extra_constr_offset + 2 * i - 1;
+            a[i].push((one, j));
+            a[0].push((one, j));
+            c[i].push((one.double().double(), j));
+            c[extra_var_offset2 + i].push((one, j));
+
+            // Second extra constraint
+            let j = extra_constr_offset + 2 * i;
+            a[i].push((one, j));
+            a[0].push((-one, j));
+            c[extra_var_offset2 + i].push((one, j));
+        }
+
+        Ok(SAPInstance {
+            domain_size: domain.m(),
+            num_variables: sap_num_variables,
+            a,
+            c,
+        })
+    }
+
     #[inline]
     pub(crate) fn witness_map<E: PairingEngine>(
         prover: &ProvingAssignment<E>,
@@ -2,13 +2,18 @@ mod bls12_377 {
     use crate::{
         gm17::{
             create_random_proof, generate_random_parameters, prepare_verifying_key, verify_proof,
+            Parameters, Proof, VerifyingKey,
         },
         Circuit, ConstraintSystem, SynthesisError,
     };
 
-    use algebra::{curves::bls12_377::Bls12_377, fields::bls12_377::Fr, PairingEngine};
+    use algebra::{
+        curves::bls12_377::{Bls12_377, G1Affine, G2Affine},
+        fields::bls12_377::{Fq, Fr},
+        to_bytes, AffineCurve, Field, FromBytes, PairingEngine, SquareRootField, ToBytes,
+    };
     use rand::{thread_rng, Rand};
-    use std::ops::MulAssign;
+    use std::{io::ErrorKind, ops::MulAssign};
 
     #[test]
     fn prove_and_verify() {
@@ -49,6 +54,19 @@ mod bls12_377 {
 
         let pvk = prepare_verifying_key::<Bls12_377>(&params.vk);
 
+        let params_bytes = to_bytes![params].unwrap();
+        assert!(Parameters::<Bls12_377>::read(&params_bytes[..]).unwrap() == params);
+        assert!(Parameters::<Bls12_377>::read(&params_bytes[..params_bytes.len() - 1]).is_err());
+        let vk_bytes = to_bytes![params.vk].unwrap();
+        assert!(VerifyingKey::<Bls12_377>::read(&vk_bytes[..]).unwrap() == params.vk);
+        assert!(VerifyingKey::<Bls12_377>::read(&vk_bytes[..vk_bytes.len() - 1]).is_err());
+        // The query length is encoded, so reading stops at the end of the key.
+        let mut vk_and_more_bytes = vk_bytes.clone();
+        vk_and_more_bytes.extend_from_slice(&[0u8; 8]);
+        let mut reader = &vk_and_more_bytes[..];
+        assert!(VerifyingKey::<Bls12_377>::read(&mut reader).unwrap() == params.vk);
+        assert_eq!(reader, &[0u8; 8][..]);
+
         for _ in 0..100 {
             let a = Fr::rand(rng);
             let b = Fr::rand(rng);
@@ -69,6 +87,31 @@ mod bls12_377 {
             assert!(!verify_proof(&pvk, &proof, &[a]).unwrap());
         }
     }
+
+    #[test]
+    fn proof_outside_subgroup() {
+        // The smallest x for which (x, y) is on y^2 = x^3 + 1. The cofactor of
+        // G1 is large, so this point is not in the prime-order subgroup.
+        let mut x = Fq::one();
+        let y = loop {
+            if let Some(y) = (x.square() * &x + &Fq::one()).sqrt() {
+                break y;
+            }
+            x += &Fq::one();
+        };
+        let point = G1Affine::new(x, y, false);
+        assert!(point.is_on_curve());
+        assert!(!point.is_in_correct_subgroup_assuming_on_curve());
+
+        let proof = Proof::<Bls12_377> {
+            a: point,
+            b: G2Affine::prime_subgroup_generator(),
+            c: G1Affine::prime_subgroup_generator(),
+        };
+        let proof_bytes = to_bytes![proof].unwrap();
+        let error = Proof::<Bls12_377>::read(&proof_bytes[..]).unwrap_err();
+        assert_eq!(error.kind(), ErrorKind::InvalidData);
+    }
 }
 
 mod sw6 {